@@ -241,7 +241,11 @@ impl<T: Config> Pallet<T> {
 		))
 	}
 
-	fn get_aggregated_swap_amount(paths: &[SwapPath], swap_limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+	/// Calculate the supply amount and target amount of the aggregated swap paths.
+	pub fn get_aggregated_swap_amount(
+		paths: &[SwapPath],
+		swap_limit: SwapLimit<Balance>,
+	) -> Option<(Balance, Balance)> {
 		Self::check_swap_paths(paths).ok()?;
 
 		match swap_limit {
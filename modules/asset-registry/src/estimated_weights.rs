@@ -0,0 +1,39 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_asset_registry
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights are estimated from the storage
+//! accesses and the benchmarked weights of the similar calls. Replace them by running the
+//! `module_asset_registry` benchmarks, which generate them into the weights of the module and the
+//! runtimes.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+// Storage: AssetRegistry AssetMetadatas (r:1 w:0)
+// Storage: AssetRegistry Erc20Behaviours (r:0 w:1)
+pub fn set_erc20_behaviour<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(14_986_000 as Weight)
+		.saturating_add(DbWeight::get().reads(2 as Weight))
+		.saturating_add(DbWeight::get().writes(2 as Weight))
+}
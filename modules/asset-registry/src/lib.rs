@@ -35,8 +35,8 @@ use frame_system::pallet_prelude::*;
 use module_support::{AssetIdMapping, BuyWeightRate, EVMBridge, Erc20InfoMapping, InvokeContext, Ratio};
 use primitives::{
 	currency::{
-		AssetIds, AssetMetadata, CurrencyIdType, DexShare, DexShareType, Erc20Id, ForeignAssetId, Lease,
-		StableAssetPoolId, TokenInfo,
+		AssetIds, AssetMetadata, CurrencyIdType, DexShare, DexShareType, Erc20Behaviour, Erc20Id, ForeignAssetId,
+		Lease, StableAssetPoolId, TokenInfo,
 	},
 	evm::{
 		is_system_contract, EvmAddress, H160_POSITION_CURRENCY_ID_TYPE, H160_POSITION_DEXSHARE_LEFT_FIELD,
@@ -54,6 +54,7 @@ use xcm::{
 	VersionedMultiLocation,
 };
 
+pub mod estimated_weights;
 mod mock;
 mod tests;
 mod weights;
@@ -128,6 +129,11 @@ pub mod module {
 			asset_id: AssetIds,
 			metadata: AssetMetadata<BalanceOf<T>>,
 		},
+		/// The transfer behaviour of the ERC20 asset updated.
+		Erc20BehaviourUpdated {
+			contract: EvmAddress,
+			behaviour: Erc20Behaviour,
+		},
 	}
 
 	/// Next available Foreign AssetId ID.
@@ -173,6 +179,13 @@ pub mod module {
 	pub type AssetMetadatas<T: Config> =
 		StorageMap<_, Twox64Concat, AssetIds, AssetMetadata<BalanceOf<T>>, OptionQuery>;
 
+	/// The transfer behaviours of the ERC20 assets, `Erc20Behaviour::Standard` by default.
+	///
+	/// Erc20Behaviours: map EvmAddress => Erc20Behaviour
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_behaviours)]
+	pub type Erc20Behaviours<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, Erc20Behaviour, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -357,6 +370,31 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Mark the transfer behaviour of a registered ERC20 asset. The assets which are not
+		/// `Erc20Behaviour::Standard` can't be listed in the DEX or as collateral.
+		#[pallet::weight(T::WeightInfo::set_erc20_behaviour())]
+		#[transactional]
+		pub fn set_erc20_behaviour(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			behaviour: Erc20Behaviour,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				AssetMetadatas::<T>::contains_key(AssetIds::Erc20(contract)),
+				Error::<T>::AssetIdNotExists
+			);
+			if behaviour == Erc20Behaviour::Standard {
+				Erc20Behaviours::<T>::remove(contract);
+			} else {
+				Erc20Behaviours::<T>::insert(contract, behaviour);
+			}
+
+			Self::deposit_event(Event::<T>::Erc20BehaviourUpdated { contract, behaviour });
+			Ok(())
+		}
 	}
 }
 
@@ -894,6 +932,15 @@ impl<T: Config> Erc20InfoMapping for EvmErc20InfoMapping<T> {
 		EvmAddress::try_from(v).ok()
 	}
 
+	// Returns the transfer behaviour of a registered ERC20 token.
+	fn erc20_behaviour(contract: EvmAddress) -> Option<Erc20Behaviour> {
+		if AssetMetadatas::<T>::contains_key(AssetIds::Erc20(contract)) {
+			Some(Erc20Behaviours::<T>::get(contract))
+		} else {
+			None
+		}
+	}
+
 	// Decode the CurrencyId from EvmAddress.
 	// If is CurrencyId::DexShare and contain DexShare::Erc20,
 	// will use the u32 to get the DexShare::Erc20 from the mapping.
@@ -83,6 +83,7 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub NetworkContractSource: EvmAddress = alice_evm_addr();
+	pub PredeployAddressRanges: Vec<(EvmAddress, EvmAddress)> = vec![(EvmAddress::zero(), EvmAddress::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -98,7 +99,10 @@ impl module_evm::Config for Runtime {
 	type TransferAll = ();
 	type NewContractExtraBytes = ConstU32<1>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = ConstU128<10>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 	type Event = Event;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
@@ -106,6 +110,7 @@ impl module_evm::Config for Runtime {
 	type ChargeTransactionPayment = module_support::mocks::MockReservedTransactionPayment<Balances>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
@@ -28,6 +28,7 @@ use mock::{
 };
 use primitives::TokenSymbol;
 use sp_core::H160;
+use sp_runtime::traits::BadOrigin;
 use std::str::{from_utf8, FromStr};
 
 #[test]
@@ -708,6 +709,68 @@ fn update_erc20_asset_should_not_work() {
 	});
 }
 
+#[test]
+fn set_erc20_behaviour_works() {
+	ExtBuilder::default()
+		.balances(vec![(alice(), 1_000_000_000_000)])
+		.build()
+		.execute_with(|| {
+			deploy_contracts();
+			assert_noop!(
+				AssetRegistry::set_erc20_behaviour(
+					Origin::signed(CouncilAccount::get()),
+					erc20_address(),
+					Erc20Behaviour::FeeOnTransfer
+				),
+				Error::<Runtime>::AssetIdNotExists
+			);
+			assert_eq!(EvmErc20InfoMapping::<Runtime>::erc20_behaviour(erc20_address()), None);
+
+			assert_ok!(AssetRegistry::register_erc20_asset(
+				Origin::signed(CouncilAccount::get()),
+				erc20_address(),
+				1
+			));
+			assert_eq!(
+				EvmErc20InfoMapping::<Runtime>::erc20_behaviour(erc20_address()),
+				Some(Erc20Behaviour::Standard)
+			);
+			assert!(
+				module_support::evm::is_supported_currency::<EvmErc20InfoMapping<Runtime>>(CurrencyId::Erc20(
+					erc20_address()
+				))
+			);
+
+			assert_noop!(
+				AssetRegistry::set_erc20_behaviour(Origin::signed(alice()), erc20_address(), Erc20Behaviour::Rebasing),
+				BadOrigin
+			);
+			assert_ok!(AssetRegistry::set_erc20_behaviour(
+				Origin::signed(CouncilAccount::get()),
+				erc20_address(),
+				Erc20Behaviour::Rebasing
+			));
+			System::assert_last_event(Event::AssetRegistry(crate::Event::Erc20BehaviourUpdated {
+				contract: erc20_address(),
+				behaviour: Erc20Behaviour::Rebasing,
+			}));
+			assert_eq!(
+				EvmErc20InfoMapping::<Runtime>::erc20_behaviour(erc20_address()),
+				Some(Erc20Behaviour::Rebasing)
+			);
+			assert!(!module_support::evm::is_supported_currency::<
+				EvmErc20InfoMapping<Runtime>,
+			>(CurrencyId::Erc20(erc20_address())));
+
+			assert_ok!(AssetRegistry::set_erc20_behaviour(
+				Origin::signed(CouncilAccount::get()),
+				erc20_address(),
+				Erc20Behaviour::Standard
+			));
+			assert!(!Erc20Behaviours::<Runtime>::contains_key(erc20_address()));
+		});
+}
+
 #[test]
 fn name_works() {
 	ExtBuilder::default()
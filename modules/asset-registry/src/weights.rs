@@ -54,6 +54,7 @@ pub trait WeightInfo {
 	fn update_erc20_asset() -> Weight;
 	fn register_native_asset() -> Weight;
 	fn update_native_asset() -> Weight;
+	fn set_erc20_behaviour() -> Weight;
 }
 
 /// Weights for module_asset_registry using the Acala node and recommended hardware.
@@ -116,6 +117,9 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_erc20_behaviour() -> Weight {
+		crate::estimated_weights::set_erc20_behaviour::<T::DbWeight>()
+	}
 }
 
 // For backwards compatibility and tests
@@ -160,4 +164,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_erc20_behaviour() -> Weight {
+		crate::estimated_weights::set_erc20_behaviour::<RocksDbWeight>()
+	}
 }
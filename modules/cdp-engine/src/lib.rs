@@ -49,7 +49,8 @@ use sp_runtime::{
 		Duration,
 	},
 	traits::{
-		AccountIdConversion, BlockNumberProvider, Bounded, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
+		AccountIdConversion, BlockNumberProvider, Bounded, Convert, One, Saturating, StaticLookup, UniqueSaturatedInto,
+		Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
@@ -58,8 +59,10 @@ use sp_runtime::{
 };
 use sp_std::{marker::PhantomData, prelude::*};
 use support::{
-	AddressMapping, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, ExchangeRate, InvokeContext,
-	LiquidateCollateral, LiquidationEvmBridge, Price, PriceProvider, Rate, Ratio, RiskManager, Swap, SwapLimit,
+	evm::{is_supported_currency, limits::erc20},
+	AddressMapping, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Erc20InfoMapping, ExchangeRate,
+	InvokeContext, LiquidateCollateral, LiquidationEvmBridge, Price, PriceProvider, Rate, Ratio, RiskManager, Swap,
+	SwapLimit,
 };
 
 mod mock;
@@ -207,8 +210,14 @@ pub mod module {
 
 		type EvmAddressMapping: AddressMapping<Self::AccountId>;
 
+		/// The ERC20 info mapping to reject the non-standard ERC20 collaterals.
+		type Erc20InfoMapping: Erc20InfoMapping;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
+
+		/// Convert gas to weight, to charge the transfers of the ERC20 collaterals.
+		type GasToWeight: Convert<u64, Weight>;
 	}
 
 	#[pallet::error]
@@ -251,6 +260,8 @@ pub mod module {
 		TooManyLiquidationContracts,
 		/// Collateral ERC20 contract not found.
 		CollateralContractNotFound,
+		/// The ERC20 collateral is unregistered or has non-standard transfer behaviour.
+		UnsupportedCollateral,
 	}
 
 	#[pallet::event]
@@ -426,7 +437,9 @@ pub mod module {
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
-		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction()))]
+		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(*currency_id, 3))
+		)]
 		#[transactional]
 		pub fn liquidate(
 			origin: OriginFor<T>,
@@ -446,7 +459,9 @@ pub mod module {
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
-		#[pallet::weight(<T as Config>::WeightInfo::settle())]
+		#[pallet::weight(<T as Config>::WeightInfo::settle()
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(*currency_id, 1))
+		)]
 		#[transactional]
 		pub fn settle(
 			origin: OriginFor<T>,
@@ -487,7 +502,17 @@ pub mod module {
 		) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
 
-			let mut collateral_params = Self::collateral_params(currency_id).unwrap_or_default();
+			let mut collateral_params = match Self::collateral_params(currency_id) {
+				Some(params) => params,
+				None => {
+					// listing a new collateral
+					ensure!(
+						is_supported_currency::<T::Erc20InfoMapping>(currency_id),
+						Error::<T>::UnsupportedCollateral
+					);
+					Default::default()
+				}
+			};
 			if let Change::NewValue(update) = interest_rate_per_sec {
 				collateral_params.interest_rate_per_sec = update;
 				Self::deposit_event(Event::InterestRatePerSecUpdated {
@@ -593,6 +618,16 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The weight of `transfers` transfers of the collateral `currency_id`, the transfers of the
+	/// ERC20 collaterals are charged by their gas.
+	pub fn erc20_transfer_weight(currency_id: CurrencyId, transfers: u64) -> Weight {
+		if currency_id.is_erc20_currency_id() {
+			T::GasToWeight::convert(erc20::CHECKED_TRANSFER_GAS).saturating_mul(transfers)
+		} else {
+			0
+		}
+	}
+
 	fn accumulate_interest(now_secs: u64, last_accumulation_secs: u64) -> u32 {
 		let mut count: u32 = 0;
 
@@ -1255,7 +1290,8 @@ impl<T: Config> Pallet<T> {
 			bad_debt_value,
 			target_amount: target_stable_amount,
 		});
-		Ok(T::WeightInfo::liquidate_by_dex())
+		// the collateral is confiscated, sold and the remaining is refunded to the owner
+		Ok(T::WeightInfo::liquidate_by_dex().saturating_add(Self::erc20_transfer_weight(currency_id, 3)))
 	}
 
 	pub fn handle_liquidated_collateral(
@@ -35,7 +35,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, One as OneT},
 };
 use sp_std::{cell::RefCell, str::FromStr};
-use support::mocks::MockStableAsset;
+use support::mocks::{MockErc20InfoMapping, MockStableAsset};
 use support::{AuctionManager, EmergencyShutdown, SpecificJointsSwap};
 
 pub type AccountId = AccountId32;
@@ -263,6 +263,7 @@ impl dex::Config for Runtime {
 	type Erc20InfoMapping = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type GasToWeight = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
@@ -281,6 +282,9 @@ impl evm_accounts::Config for Runtime {
 	type ChainId = ();
 	type AddressMapping = evm_accounts::EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type EVMBridge = ();
+	type RotationDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -403,8 +407,10 @@ impl Config for Runtime {
 	type LiquidationEvmBridge = MockLiquidationEvmBridge;
 	type PalletId = CDPEnginePalletId;
 	type EvmAddressMapping = evm_accounts::EvmAddressMapping<Runtime>;
+	type Erc20InfoMapping = MockErc20InfoMapping;
 	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
 	type WeightInfo = ();
+	type GasToWeight = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -39,14 +39,21 @@ use orml_traits::{
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDust,
 };
-use primitives::{evm::EvmAddress, CurrencyId};
+use primitives::{
+	evm::{EvmAddress, Log, ERC20_TRANSFER_TOPIC},
+	CurrencyId,
+};
+use sp_core::{H256, U256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
+	traits::{
+		Bounded, CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize, SaturatedConversion, Saturating,
+		StaticLookup, Zero,
+	},
 	DispatchError, DispatchResult,
 };
 use sp_std::{fmt::Debug, marker, result, vec::Vec};
-use support::{evm::limits::erc20, AddressMapping, EVMBridge, InvokeContext};
+use support::{evm::limits::erc20, AddressMapping, EVMBridge, EVMLogs, InvokeContext};
 
 mod mock;
 mod tests;
@@ -109,6 +116,10 @@ pub mod module {
 
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, CurrencyId, BalanceOf<Self>>;
+
+		/// Deposit the ERC20 `Transfer` logs of native transfers of ERC20 mapped tokens. Use
+		/// `()` to disable the logs.
+		type EVMLogs: EVMLogs;
 	}
 
 	#[pallet::error]
@@ -125,6 +136,11 @@ pub mod module {
 		RealOriginNotFound,
 		/// Deposit result is not expected
 		DepositFailed,
+		/// The transfer amount exceeds the allowance of the spender.
+		InsufficientAllowance,
+		/// The balances changed by the ERC20 transfer don't match the transfer amount, e.g. the
+		/// fee-on-transfer and rebasing tokens.
+		Erc20TransferAmountMismatch,
 	}
 
 	#[pallet::event]
@@ -155,8 +171,38 @@ pub mod module {
 			who: T::AccountId,
 			amount: BalanceOf<T>,
 		},
+		/// The allowance of `spender` over the tokens of `owner` is set.
+		Approval {
+			currency_id: CurrencyId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
+	/// The remaining amount that `spender` is allowed to transfer on behalf of `owner`.
+	///
+	/// Allowances: double_map (CurrencyId, Owner), Spender => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn allowances)]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CurrencyId, T::AccountId),
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The EIP-2612 permit nonces of the owners.
+	///
+	/// PermitNonces: double_map CurrencyId, EvmAddress => u64
+	#[pallet::storage]
+	#[pallet::getter(fn permit_nonces)]
+	pub type PermitNonces<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, EvmAddress, u64, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -170,7 +216,7 @@ pub mod module {
 		/// The dispatch origin for this call must be `Signed` by the
 		/// transactor.
 		#[pallet::weight(T::WeightInfo::transfer_non_native_currency()
-			.saturating_add(if currency_id.is_erc20_currency_id() { T::GasToWeight::convert(erc20::TRANSFER.gas) } else { 0 })
+			.saturating_add(if currency_id.is_erc20_currency_id() { T::GasToWeight::convert(erc20::CHECKED_TRANSFER_GAS) } else { 0 })
 		)]
 		pub fn transfer(
 			origin: OriginFor<T>,
@@ -180,7 +226,9 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)
+			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+			Self::deposit_evm_transfer_log(currency_id, &from, &to, amount);
+			Ok(())
 		}
 
 		/// Transfer some native currency to another account.
@@ -195,7 +243,9 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			T::NativeCurrency::transfer(&from, &to, amount)
+			T::NativeCurrency::transfer(&from, &to, amount)?;
+			Self::deposit_evm_transfer_log(T::GetNativeCurrencyId::get(), &from, &to, amount);
+			Ok(())
 		}
 
 		/// Update amount of account `who` under `currency_id`.
@@ -246,6 +296,124 @@ pub mod module {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Set the allowance of `spender` over the tokens of `owner`. ERC20 tokens keep the
+	/// allowances in their own contracts.
+	pub fn do_approve(
+		currency_id: CurrencyId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!currency_id.is_erc20_currency_id(), Error::<T>::Erc20InvalidOperation);
+
+		if amount.is_zero() {
+			Allowances::<T>::remove((currency_id, owner), spender);
+		} else {
+			Allowances::<T>::insert((currency_id, owner), spender, amount);
+		}
+
+		Self::deposit_event(Event::Approval {
+			currency_id,
+			owner: owner.clone(),
+			spender: spender.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Transfer `amount` from `from` to `to` by `spender`, the allowance is deducted unless it is
+	/// unlimited or `spender` is `from`.
+	#[transactional]
+	pub fn do_transfer_from(
+		currency_id: CurrencyId,
+		spender: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!currency_id.is_erc20_currency_id(), Error::<T>::Erc20InvalidOperation);
+
+		if spender != from {
+			Allowances::<T>::try_mutate_exists((currency_id, from), spender, |maybe_allowance| -> DispatchResult {
+				let allowance = maybe_allowance.unwrap_or_else(Zero::zero);
+				if allowance != BalanceOf::<T>::max_value() {
+					let remaining = allowance
+						.checked_sub(&amount)
+						.ok_or(Error::<T>::InsufficientAllowance)?;
+					*maybe_allowance = Some(remaining).filter(|remaining| !remaining.is_zero());
+				}
+				Ok(())
+			})?;
+		}
+
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)
+	}
+
+	/// Consume the EIP-2612 permit nonce of `owner`, returns the consumed nonce.
+	pub fn use_permit_nonce(currency_id: CurrencyId, owner: EvmAddress) -> u64 {
+		PermitNonces::<T>::mutate(currency_id, owner, |nonce| {
+			let current = *nonce;
+			*nonce = nonce.saturating_add(1);
+			current
+		})
+	}
+
+	/// Transfer ERC20 tokens from `context.sender` to `to`, and verify the balances of both
+	/// changed by exactly `amount`.
+	#[transactional]
+	fn transfer_erc20(context: InvokeContext, to: EvmAddress, amount: BalanceOf<T>) -> DispatchResult {
+		let balance_of = |address: EvmAddress| {
+			T::EVMBridge::balance_of(
+				InvokeContext {
+					contract: context.contract,
+					sender: Default::default(),
+					origin: Default::default(),
+				},
+				address,
+			)
+		};
+
+		let sender_balance = balance_of(context.sender)?;
+		let receiver_balance = balance_of(to)?;
+		T::EVMBridge::transfer(context, to, amount)?;
+
+		ensure!(
+			sender_balance.saturating_sub(balance_of(context.sender)?) == amount
+				&& balance_of(to)?.saturating_sub(receiver_balance) == amount,
+			Error::<T>::Erc20TransferAmountMismatch
+		);
+		Ok(())
+	}
+
+	/// Deposit the ERC20 `Transfer` log of a native transfer, ERC20 tokens are skipped as their
+	/// contracts emit the logs.
+	fn deposit_evm_transfer_log(currency_id: CurrencyId, from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) {
+		if currency_id.is_erc20_currency_id() {
+			return;
+		}
+
+		if let Ok(address) = EvmAddress::try_from(currency_id) {
+			let evm_address_of = |who: &T::AccountId| {
+				T::AddressMapping::get_evm_address(who)
+					.unwrap_or_else(|| T::AddressMapping::get_default_evm_address(who))
+			};
+
+			let from = evm_address_of(from);
+			T::EVMLogs::deposit_logs(
+				from,
+				vec![Log {
+					address,
+					topics: vec![ERC20_TRANSFER_TOPIC, H256::from(from), H256::from(evm_address_of(to))],
+					data: H256::from_uint(&U256::from(amount.saturated_into::<u128>()))
+						.as_bytes()
+						.to_vec(),
+				}],
+			);
+		}
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = CurrencyId;
 	type Balance = BalanceOf<T>;
@@ -348,7 +516,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 				let origin = T::EVMBridge::get_origin().ok_or(Error::<T>::RealOriginNotFound)?;
 				let origin_address = T::AddressMapping::get_or_create_evm_address(&origin);
 				let address = T::AddressMapping::get_or_create_evm_address(to);
-				T::EVMBridge::transfer(
+				Self::transfer_erc20(
 					InvokeContext {
 						contract,
 						sender,
@@ -387,7 +555,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 					Error::<T>::DepositFailed
 				);
 				let receiver = T::AddressMapping::get_or_create_evm_address(who);
-				T::EVMBridge::transfer(
+				Self::transfer_erc20(
 					InvokeContext {
 						contract,
 						sender,
@@ -426,7 +594,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 				// charge storage fee.
 				let receiver = T::Erc20HoldingAccount::get();
 				let sender = T::AddressMapping::get_evm_address(who).ok_or(Error::<T>::EvmAccountNotFound)?;
-				T::EVMBridge::transfer(
+				Self::transfer_erc20(
 					InvokeContext {
 						contract,
 						sender,
@@ -570,7 +738,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 					return Ok(());
 				}
 				let address = T::AddressMapping::get_evm_address(who).ok_or(Error::<T>::EvmAccountNotFound)?;
-				T::EVMBridge::transfer(
+				Self::transfer_erc20(
 					InvokeContext {
 						contract,
 						sender: address,
@@ -603,7 +771,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 					)
 					.unwrap_or_default();
 					let actual = reserved_balance.min(value);
-					match T::EVMBridge::transfer(
+					match Self::transfer_erc20(
 						InvokeContext {
 							contract,
 							sender,
@@ -663,7 +831,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 				.unwrap_or_default();
 				let actual = slashed_reserved_balance.min(value);
 				match status {
-					BalanceStatus::Free => T::EVMBridge::transfer(
+					BalanceStatus::Free => Self::transfer_erc20(
 						InvokeContext {
 							contract,
 							sender: slashed_reserve_address,
@@ -672,7 +840,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 						beneficiary_address,
 						actual,
 					),
-					BalanceStatus::Reserved => T::EVMBridge::transfer(
+					BalanceStatus::Reserved => Self::transfer_erc20(
 						InvokeContext {
 							contract,
 							sender: slashed_reserve_address,
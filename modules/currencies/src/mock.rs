@@ -129,6 +129,7 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -154,7 +155,10 @@ impl module_evm::Config for Runtime {
 	type TransferAll = ();
 	type NewContractExtraBytes = ConstU32<1>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = TxFeePerGas;
+	type BlockGasLimit = ConstU64<30_000_000>;
 	type Event = Event;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
@@ -162,6 +166,7 @@ impl module_evm::Config for Runtime {
 	type ChargeTransactionPayment = support::mocks::MockReservedTransactionPayment<Balances>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
@@ -195,6 +200,7 @@ impl Config for Runtime {
 	type GasToWeight = GasToWeight;
 	type SweepOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type OnDust = crate::TransferDust<Runtime, DustAccount>;
+	type EVMLogs = EVM;
 }
 
 pub type NativeCurrency = Currency<Runtime, GetNativeCurrencyId>;
@@ -284,6 +290,24 @@ pub fn deploy_contracts() {
 	));
 }
 
+/// Deploys a token which returns `uint256(1)` for any call, so the balances are unchanged by
+/// the successful transfers.
+pub fn deploy_unchanged_balance_contract() -> EvmAddress {
+	let code = hex_literal::hex!("600a80600b6000396000f3 600160005260206000f3").to_vec();
+	assert_ok!(EVM::create(Origin::signed(alice()), code, 0, 2_100_000, 10000, vec![]));
+
+	let contract = System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			Event::EVM(module_evm::Event::Created { contract, .. }) => Some(contract),
+			_ => None,
+		})
+		.unwrap();
+	assert_ok!(EVM::publish_free(Origin::signed(CouncilAccount::get()), contract));
+	contract
+}
+
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
 }
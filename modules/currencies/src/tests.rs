@@ -24,9 +24,9 @@ use super::*;
 use crate::mock::Erc20HoldingAccount;
 use frame_support::{assert_noop, assert_ok, weights::GetDispatchInfo};
 use mock::{
-	alice, bob, deploy_contracts, erc20_address, eva, AccountId, AdaptedBasicCurrency, CouncilAccount, Currencies,
-	DustAccount, Event, ExtBuilder, NativeCurrency, Origin, PalletBalances, Runtime, System, Tokens, ALICE_BALANCE,
-	DOT, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID,
+	alice, alice_evm_addr, bob, bob_evm_addr, deploy_contracts, deploy_unchanged_balance_contract, erc20_address, eva,
+	AccountId, AdaptedBasicCurrency, CouncilAccount, Currencies, DustAccount, Event, ExtBuilder, NativeCurrency,
+	Origin, PalletBalances, Runtime, System, Tokens, ALICE_BALANCE, DOT, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID,
 };
 use sp_core::H160;
 use sp_runtime::{
@@ -295,6 +295,57 @@ fn update_balance_call_fails_if_not_root_origin() {
 	});
 }
 
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), 30));
+			System::assert_last_event(Event::Currencies(crate::Event::Approval {
+				currency_id: X_TOKEN_ID,
+				owner: alice(),
+				spender: bob(),
+				amount: 30,
+			}));
+			assert_eq!(Currencies::allowances((X_TOKEN_ID, alice()), bob()), 30);
+
+			assert_noop!(
+				Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 31),
+				Error::<Runtime>::InsufficientAllowance
+			);
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 20));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 80);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 20);
+			assert_eq!(Currencies::allowances((X_TOKEN_ID, alice()), bob()), 10);
+
+			// unlimited allowance is not deducted
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), u128::max_value()));
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 20));
+			assert_eq!(Currencies::allowances((X_TOKEN_ID, alice()), bob()), u128::max_value());
+
+			// the owner doesn't need allowance
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &alice(), &alice(), &eva(), 10));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 50);
+
+			assert_noop!(
+				Currencies::do_approve(CurrencyId::Erc20(erc20_address()), &alice(), &bob(), 30),
+				Error::<Runtime>::Erc20InvalidOperation
+			);
+		});
+}
+
+#[test]
+fn use_permit_nonce_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Currencies::permit_nonces(X_TOKEN_ID, alice_evm_addr()), 0);
+		assert_eq!(Currencies::use_permit_nonce(X_TOKEN_ID, alice_evm_addr()), 0);
+		assert_eq!(Currencies::use_permit_nonce(X_TOKEN_ID, alice_evm_addr()), 1);
+		assert_eq!(Currencies::permit_nonces(X_TOKEN_ID, alice_evm_addr()), 2);
+		assert_eq!(Currencies::permit_nonces(DOT, alice_evm_addr()), 0);
+	});
+}
+
 #[test]
 fn call_event_should_work() {
 	ExtBuilder::default()
@@ -365,6 +416,54 @@ fn call_event_should_work() {
 		});
 }
 
+#[test]
+fn transfer_deposits_evm_logs() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer(Some(alice()).into(), bob(), X_TOKEN_ID, 50));
+			System::assert_last_event(Event::EVM(module_evm::Event::Logged {
+				from: alice_evm_addr(),
+				logs: vec![Log {
+					address: EvmAddress::try_from(X_TOKEN_ID).unwrap(),
+					topics: vec![
+						ERC20_TRANSFER_TOPIC,
+						H256::from(alice_evm_addr()),
+						H256::from(bob_evm_addr()),
+					],
+					data: H256::from_low_u64_be(50).as_bytes().to_vec(),
+				}],
+			}));
+
+			assert_ok!(Currencies::transfer_native_currency(Some(alice()).into(), bob(), 10));
+			System::assert_last_event(Event::EVM(module_evm::Event::Logged {
+				from: alice_evm_addr(),
+				logs: vec![Log {
+					address: EvmAddress::try_from(NATIVE_CURRENCY_ID).unwrap(),
+					topics: vec![
+						ERC20_TRANSFER_TOPIC,
+						H256::from(alice_evm_addr()),
+						H256::from(bob_evm_addr()),
+					],
+					data: H256::from_low_u64_be(10).as_bytes().to_vec(),
+				}],
+			}));
+
+			// transfers made by other modules are not logged
+			System::reset_events();
+			assert_ok!(<Currencies as MultiCurrency<AccountId>>::transfer(
+				X_TOKEN_ID,
+				&alice(),
+				&bob(),
+				10
+			));
+			assert!(!System::events()
+				.iter()
+				.any(|record| matches!(record.event, Event::EVM(module_evm::Event::Logged { .. }))));
+		});
+}
+
 #[test]
 fn erc20_total_issuance_should_work() {
 	ExtBuilder::default()
@@ -552,6 +651,31 @@ fn erc20_transfer_should_fail() {
 		});
 }
 
+#[test]
+fn erc20_transfer_amount_mismatch_should_fail() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let contract = deploy_unchanged_balance_contract();
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+
+			// the transfer succeeds in the contract, but the balances are unchanged
+			assert_noop!(
+				Currencies::transfer(Origin::signed(alice()), bob(), CurrencyId::Erc20(contract), 1),
+				Error::<Runtime>::Erc20TransferAmountMismatch
+			);
+			assert_noop!(
+				Currencies::reserve(CurrencyId::Erc20(contract), &alice(), 1),
+				Error::<Runtime>::Erc20TransferAmountMismatch
+			);
+			assert_eq!(Currencies::unreserve(CurrencyId::Erc20(contract), &alice(), 1), 1);
+		});
+}
+
 #[test]
 fn erc20_can_reserve_should_work() {
 	ExtBuilder::default()
@@ -2433,7 +2557,7 @@ fn transfer_erc20_will_charge_gas() {
 		assert_eq!(
 			dispatch_info.weight,
 			<Runtime as module::Config>::WeightInfo::transfer_non_native_currency()
-				+ support::evm::limits::erc20::TRANSFER.gas // mock GasToWeight is 1:1
+				+ support::evm::limits::erc20::CHECKED_TRANSFER_GAS // mock GasToWeight is 1:1
 		);
 
 		let dispatch_info = module::Call::<Runtime>::transfer {
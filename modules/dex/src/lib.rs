@@ -41,11 +41,14 @@ use primitives::{Balance, CurrencyId, TradingPair};
 use scale_info::TypeInfo;
 use sp_core::{H160, U256};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating, Zero},
+	traits::{AccountIdConversion, Convert, One, Saturating, Zero},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{prelude::*, vec};
-use support::{DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, SwapLimit};
+use support::{
+	evm::{is_supported_currency, limits::erc20},
+	DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, SwapLimit,
+};
 
 mod mock;
 mod tests;
@@ -121,6 +124,9 @@ pub mod module {
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 
+		/// Convert gas to weight, to charge the transfers of the ERC20 tokens.
+		type GasToWeight: Convert<u64, Weight>;
+
 		/// DEX incentives
 		type DEXIncentives: DEXIncentives<Self::AccountId, CurrencyId, Balance>;
 
@@ -183,6 +189,8 @@ pub mod module {
 		NotAllowedRefund,
 		/// Cannot swap
 		CannotSwap,
+		/// The ERC20 token is unregistered or has non-standard transfer behaviour.
+		UnsupportedToken,
 	}
 
 	#[pallet::event]
@@ -358,7 +366,9 @@ pub mod module {
 		/// - `path`: trading path.
 		/// - `supply_amount`: exact supply amount.
 		/// - `min_target_amount`: acceptable minimum target amount.
-		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(path.len() as u32))]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(path.len() as u32)
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(path.first().into_iter().chain(path.last())))
+		)]
 		#[transactional]
 		pub fn swap_with_exact_supply(
 			origin: OriginFor<T>,
@@ -376,7 +386,9 @@ pub mod module {
 		/// - `path`: trading path.
 		/// - `target_amount`: exact target amount.
 		/// - `max_supply_amount`: acceptable maximum supply amount.
-		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(path.len() as u32))]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(path.len() as u32)
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(path.first().into_iter().chain(path.last())))
+		)]
 		#[transactional]
 		pub fn swap_with_exact_target(
 			origin: OriginFor<T>,
@@ -406,7 +418,7 @@ pub mod module {
 			<T as Config>::WeightInfo::add_liquidity_and_stake()
 		} else {
 			<T as Config>::WeightInfo::add_liquidity()
-		})]
+		}.saturating_add(Pallet::<T>::erc20_transfer_weight(&[*currency_id_a, *currency_id_b])))]
 		#[transactional]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
@@ -438,7 +450,9 @@ pub mod module {
 		/// - `currency_id_b`: currency id B.
 		/// - `amount_a`: provision amount for currency_id_a.
 		/// - `amount_b`: provision amount for currency_id_b.
-		#[pallet::weight(<T as Config>::WeightInfo::add_provision())]
+		#[pallet::weight(<T as Config>::WeightInfo::add_provision()
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(&[*currency_id_a, *currency_id_b]))
+		)]
 		#[transactional]
 		pub fn add_provision(
 			origin: OriginFor<T>,
@@ -484,7 +498,7 @@ pub mod module {
 			<T as Config>::WeightInfo::remove_liquidity_by_unstake()
 		} else {
 			<T as Config>::WeightInfo::remove_liquidity()
-		})]
+		}.saturating_add(Pallet::<T>::erc20_transfer_weight(&[*currency_id_a, *currency_id_b])))]
 		#[transactional]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
@@ -548,6 +562,7 @@ pub mod module {
 			};
 			check_asset_registry(currency_id_a)?;
 			check_asset_registry(currency_id_b)?;
+			Self::ensure_supported_currencies(trading_pair)?;
 
 			let (min_contribution, target_provision) = if currency_id_a == trading_pair.first() {
 				(
@@ -711,6 +726,7 @@ pub mod module {
 			T::ListingOrigin::ensure_origin(origin)?;
 			let trading_pair =
 				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+			Self::ensure_supported_currencies(trading_pair)?;
 			match Self::trading_pair_statuses(trading_pair) {
 				TradingPairStatus::<_, _>::Disabled => {}
 				TradingPairStatus::<_, _>::Provisioning(provisioning_parameters) => {
@@ -757,7 +773,9 @@ pub mod module {
 		/// - `owner`: founder account.
 		/// - `currency_id_a`: currency id A.
 		/// - `currency_id_b`: currency id B.
-		#[pallet::weight(<T as Config>::WeightInfo::refund_provision())]
+		#[pallet::weight(<T as Config>::WeightInfo::refund_provision()
+			.saturating_add(Pallet::<T>::erc20_transfer_weight(&[*currency_id_a, *currency_id_b]))
+		)]
 		#[transactional]
 		pub fn refund_provision(
 			origin: OriginFor<T>,
@@ -851,6 +869,26 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account_truncating()
 	}
 
+	/// The weight of the transfers of `currency_ids`, the transfers of the ERC20 tokens are
+	/// charged by their gas.
+	pub fn erc20_transfer_weight<'a>(currency_ids: impl IntoIterator<Item = &'a CurrencyId>) -> Weight {
+		let transfers = currency_ids
+			.into_iter()
+			.filter(|currency_id| currency_id.is_erc20_currency_id())
+			.count() as u64;
+		T::GasToWeight::convert(erc20::CHECKED_TRANSFER_GAS).saturating_mul(transfers)
+	}
+
+	/// The fee-on-transfer and rebasing ERC20 tokens break the pool accounting.
+	fn ensure_supported_currencies(trading_pair: TradingPair) -> DispatchResult {
+		ensure!(
+			is_supported_currency::<T::Erc20InfoMapping>(trading_pair.first())
+				&& is_supported_currency::<T::Erc20InfoMapping>(trading_pair.second()),
+			Error::<T>::UnsupportedToken
+		);
+		Ok(())
+	}
+
 	fn try_mutate_liquidity_pool<R, E>(
 		trading_pair: &TradingPair,
 		f: impl FnOnce((&mut Balance, &mut Balance)) -> sp_std::result::Result<R, E>,
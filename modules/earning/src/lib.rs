@@ -22,17 +22,22 @@
 #![allow(clippy::unused_unit)]
 
 use frame_support::{
+	log,
 	pallet_prelude::*,
 	traits::{Currency, ExistenceRequirement, LockIdentifier, LockableCurrency, OnUnbalanced, WithdrawReasons},
-	transactional,
+	transactional, PalletId,
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::Happened;
 use primitives::{
 	bonding::{self, BondingController},
-	Balance,
+	Balance, CurrencyId,
 };
-use sp_runtime::{traits::Saturating, Permill};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, Permill,
+};
+use support::{EarningManager, IncentivesManager, PoolId};
 
 pub use module::*;
 
@@ -67,12 +72,35 @@ pub mod module {
 		#[pallet::constant]
 		type LockIdentifier: Get<LockIdentifier>;
 
+		/// The native currency id, which is bonded and also paid as bonding rewards.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyId>;
+
+		/// Incentives to distribute bonding rewards to the shares of bonders.
+		type Incentives: IncentivesManager<Self::AccountId, Balance, CurrencyId, PoolId>;
+
+		/// The origin which may update the inflation rewards per block.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The module id, keep the bonding rewards before distribution.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Whether unbonding tokens count toward voting power, with the weight decaying linearly
+		/// until they are unlocked.
+		#[pallet::constant]
+		type UnbondingVotingPower: Get<bool>;
+
+		/// The maximum number of accounts that can delegate voting power to one account.
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
 	pub type BondingLedgerOf<T> = bonding::BondingLedgerOf<Pallet<T>>;
-	type NegativeImbalanceOf<T> =
+	pub(crate) type NegativeImbalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
 	#[pallet::error]
@@ -80,6 +108,11 @@ pub mod module {
 		BelowMinBondThreshold,
 		MaxUnlockChunksExceeded,
 		NotBonded,
+		NoRewardsToCompound,
+		SelfDelegation,
+		NestedDelegation,
+		TooManyDelegators,
+		NotDelegating,
 	}
 
 	#[pallet::event]
@@ -106,6 +139,25 @@ pub mod module {
 			who: T::AccountId,
 			amount: Balance,
 		},
+		RewardsPerBlockUpdated {
+			amount: Balance,
+		},
+		RewardsDistributed {
+			inflation: Balance,
+			fee_share: Balance,
+		},
+		RewardsCompounded {
+			who: T::AccountId,
+			amount: Balance,
+		},
+		Delegated {
+			who: T::AccountId,
+			to: T::AccountId,
+		},
+		Undelegated {
+			who: T::AccountId,
+			from: T::AccountId,
+		},
 	}
 
 	/// The earning bonding ledger.
@@ -115,12 +167,55 @@ pub mod module {
 	#[pallet::getter(fn ledger)]
 	pub type Ledger<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BondingLedgerOf<T>, OptionQuery>;
 
+	/// The amount of native token issued as bonding rewards every block.
+	///
+	/// RewardsPerBlock: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn rewards_per_block)]
+	pub type RewardsPerBlock<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The account which the voting power of an account is delegated to.
+	///
+	/// Delegations: map AccountId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// The accounts which delegate their voting power to an account.
+	///
+	/// Delegators: map AccountId => BoundedVec<AccountId, MaxDelegators>
+	#[pallet::storage]
+	#[pallet::getter(fn delegators)]
+	pub type Delegators<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<T::AccountId, T::MaxDelegators>, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let inflation = Self::rewards_per_block();
+			let fee_share =
+				T::Currency::free_balance(&Self::account_id()).saturating_sub(T::Currency::minimum_balance());
+			if inflation.is_zero() && fee_share.is_zero() {
+				return T::DbWeight::get().reads(2);
+			}
+
+			// rewards are kept in the module account if there are no bonders yet,
+			// and no inflation is issued until they can be distributed.
+			match Self::distribute_rewards(inflation, fee_share) {
+				Ok(_) => Self::deposit_event(Event::RewardsDistributed { inflation, fee_share }),
+				Err(e) => log::debug!(
+					target: "earning",
+					"distribute_rewards: failed to distribute rewards, inflation {:?}, fee_share {:?}: {:?}",
+					inflation, fee_share, e
+				),
+			}
+			T::WeightInfo::on_initialize()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -132,15 +227,7 @@ pub mod module {
 		pub fn bond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let change = <Self as BondingController>::bond(&who, amount)?;
-
-			if let Some(change) = change {
-				T::OnBonded::happened(&(who.clone(), change.change));
-				Self::deposit_event(Event::Bonded {
-					who,
-					amount: change.change,
-				});
-			}
+			Self::do_bond(&who, amount)?;
 			Ok(())
 		}
 
@@ -152,17 +239,7 @@ pub mod module {
 		pub fn unbond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let unbond_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UnbondingPeriod::get());
-			let change = <Self as BondingController>::unbond(&who, amount, unbond_at)?;
-
-			if let Some(change) = change {
-				T::OnUnbonded::happened(&(who.clone(), change.change));
-				Self::deposit_event(Event::Unbonded {
-					who,
-					amount: change.change,
-				});
-			}
-
+			Self::do_unbond(&who, amount)?;
 			Ok(())
 		}
 
@@ -174,25 +251,7 @@ pub mod module {
 		pub fn unbond_instant(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let change = <Self as BondingController>::unbond_instant(&who, amount)?;
-
-			if let Some(change) = change {
-				let amount = change.change;
-				let fee = T::InstantUnstakeFee::get().mul_ceil(amount);
-				let final_amount = amount.saturating_sub(fee);
-
-				let unbalance =
-					T::Currency::withdraw(&who, fee, WithdrawReasons::TRANSFER, ExistenceRequirement::KeepAlive)?;
-				T::OnUnstakeFee::on_unbalanced(unbalance);
-
-				T::OnUnbonded::happened(&(who.clone(), final_amount));
-				Self::deposit_event(Event::InstantUnbonded {
-					who,
-					amount: final_amount,
-					fee,
-				});
-			}
-
+			Self::do_unbond_instant(&who, amount)?;
 			Ok(())
 		}
 
@@ -204,16 +263,7 @@ pub mod module {
 		pub fn rebond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let change = <Self as BondingController>::rebond(&who, amount)?;
-
-			if let Some(change) = change {
-				T::OnBonded::happened(&(who.clone(), change.change));
-				Self::deposit_event(Event::Rebonded {
-					who,
-					amount: change.change,
-				});
-			}
-
+			Self::do_rebond(&who, amount)?;
 			Ok(())
 		}
 
@@ -223,22 +273,315 @@ pub mod module {
 		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let change =
-				<Self as BondingController>::withdraw_unbonded(&who, frame_system::Pallet::<T>::block_number())?;
+			Self::do_withdraw_unbonded(&who)?;
+			Ok(())
+		}
+
+		/// Claim the bonding rewards.
+		/// If `compound` is true, the claimed native rewards will be bonded immediately.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>, compound: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			if let Some(change) = change {
-				Self::deposit_event(Event::Withdrawn {
-					who,
-					amount: change.change,
-				});
+			let before = T::Currency::free_balance(&who);
+			T::Incentives::claim_rewards(who.clone(), Self::rewards_pool_id())?;
+
+			if compound {
+				let rewards = T::Currency::free_balance(&who).saturating_sub(before);
+				ensure!(!rewards.is_zero(), Error::<T>::NoRewardsToCompound);
+
+				let change = <Self as BondingController>::bond(&who, rewards)?;
+
+				if let Some(change) = change {
+					T::OnBonded::happened(&(who.clone(), change.change));
+					Self::deposit_event(Event::RewardsCompounded {
+						who,
+						amount: change.change,
+					});
+				}
 			}
 
 			Ok(())
 		}
+
+		/// Set the amount of native token issued as bonding rewards every block.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_rewards_per_block())]
+		pub fn set_rewards_per_block(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			RewardsPerBlock::<T>::put(amount);
+			Self::deposit_event(Event::RewardsPerBlockUpdated { amount });
+			Ok(())
+		}
+
+		/// Delegate the voting power of bonded tokens to `to`, replacing the existing delegation.
+		/// Delegation is not transitive, so an account that delegates cannot be delegated to.
+		/// Only accounts with at least `MinBond` bonded can delegate, and the delegation is removed
+		/// once all of their bonded tokens are unbonded.
+		#[pallet::weight(T::WeightInfo::delegate())]
+		#[transactional]
+		pub fn delegate(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != to, Error::<T>::SelfDelegation);
+			let active = Self::ledger(&who).ok_or(Error::<T>::NotBonded)?.active();
+			ensure!(active >= T::MinBond::get(), Error::<T>::BelowMinBondThreshold);
+			ensure!(
+				!Delegations::<T>::contains_key(&to) && Delegators::<T>::decode_len(&who).unwrap_or_default() == 0,
+				Error::<T>::NestedDelegation
+			);
+
+			if let Some(from) = Delegations::<T>::take(&who) {
+				Self::remove_delegator(&who, &from);
+			}
+			Delegators::<T>::try_mutate(&to, |delegators| -> DispatchResult {
+				delegators
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::TooManyDelegators)?;
+				Ok(())
+			})?;
+			Delegations::<T>::insert(&who, &to);
+
+			Self::deposit_event(Event::Delegated { who, to });
+			Ok(())
+		}
+
+		/// Take back the voting power delegated to another account.
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		#[transactional]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let from = Delegations::<T>::take(&who).ok_or(Error::<T>::NotDelegating)?;
+			Self::remove_delegator(&who, &from);
+
+			Self::deposit_event(Event::Undelegated { who, from });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account which keeps the bonding rewards before distribution.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The incentives pool which the shares of bonders are added to.
+	pub fn rewards_pool_id() -> PoolId {
+		PoolId::Loans(T::NativeCurrencyId::get())
+	}
+
+	fn do_bond(who: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let change = <Self as BondingController>::bond(who, amount)?;
+
+		if let Some(change) = change {
+			T::OnBonded::happened(&(who.clone(), change.change));
+			Self::deposit_event(Event::Bonded {
+				who: who.clone(),
+				amount: change.change,
+			});
+			return Ok(change.change);
+		}
+		Ok(Zero::zero())
+	}
+
+	fn do_unbond(who: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let unbond_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UnbondingPeriod::get());
+		let change = <Self as BondingController>::unbond(who, amount, unbond_at)?;
+
+		if let Some(change) = change {
+			T::OnUnbonded::happened(&(who.clone(), change.change));
+			Self::deposit_event(Event::Unbonded {
+				who: who.clone(),
+				amount: change.change,
+			});
+			Self::undelegate_if_unbonded(who);
+			return Ok(change.change);
+		}
+		Ok(Zero::zero())
+	}
+
+	fn do_unbond_instant(who: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let change = <Self as BondingController>::unbond_instant(who, amount)?;
+
+		if let Some(change) = change {
+			let amount = change.change;
+			let fee = T::InstantUnstakeFee::get().mul_ceil(amount);
+			let final_amount = amount.saturating_sub(fee);
+
+			let unbalance =
+				T::Currency::withdraw(who, fee, WithdrawReasons::TRANSFER, ExistenceRequirement::KeepAlive)?;
+			T::OnUnstakeFee::on_unbalanced(unbalance);
+
+			T::OnUnbonded::happened(&(who.clone(), final_amount));
+			Self::deposit_event(Event::InstantUnbonded {
+				who: who.clone(),
+				amount: final_amount,
+				fee,
+			});
+			Self::undelegate_if_unbonded(who);
+			return Ok(final_amount);
+		}
+		Ok(Zero::zero())
+	}
+
+	fn do_rebond(who: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let change = <Self as BondingController>::rebond(who, amount)?;
+
+		if let Some(change) = change {
+			T::OnBonded::happened(&(who.clone(), change.change));
+			Self::deposit_event(Event::Rebonded {
+				who: who.clone(),
+				amount: change.change,
+			});
+			return Ok(change.change);
+		}
+		Ok(Zero::zero())
+	}
+
+	fn do_withdraw_unbonded(who: &T::AccountId) -> Result<Balance, DispatchError> {
+		let change = <Self as BondingController>::withdraw_unbonded(who, frame_system::Pallet::<T>::block_number())?;
+
+		if let Some(change) = change {
+			Self::deposit_event(Event::Withdrawn {
+				who: who.clone(),
+				amount: change.change,
+			});
+			return Ok(change.change);
+		}
+		Ok(Zero::zero())
+	}
+
+	/// Removes the delegation of `who` once it has no bonded tokens left.
+	fn undelegate_if_unbonded(who: &T::AccountId) {
+		if Self::ledger(who).map_or(false, |ledger| !ledger.active().is_zero()) {
+			return;
+		}
+
+		if let Some(from) = Delegations::<T>::take(who) {
+			Self::remove_delegator(who, &from);
+			Self::deposit_event(Event::Undelegated { who: who.clone(), from });
+		}
+	}
+
+	fn remove_delegator(who: &T::AccountId, from: &T::AccountId) {
+		Delegators::<T>::mutate_exists(from, |maybe_delegators| {
+			if let Some(delegators) = maybe_delegators {
+				delegators.retain(|x| x != who);
+				if delegators.is_empty() {
+					*maybe_delegators = None;
+				}
+			}
+		});
+	}
+
+	#[transactional]
+	fn distribute_rewards(inflation: Balance, fee_share: Balance) -> DispatchResult {
+		// issue the inflation into the module account to distribute together with the fee share.
+		drop(T::Currency::deposit_creating(&Self::account_id(), inflation));
+
+		T::Incentives::accumulate_rewards(
+			&Self::account_id(),
+			Self::rewards_pool_id(),
+			T::NativeCurrencyId::get(),
+			inflation.saturating_add(fee_share),
+		)
+	}
+}
+
+impl<T: Config> EarningManager<T::AccountId, Balance, BondingLedgerOf<T>> for Pallet<T> {
+	type Moment = T::BlockNumber;
+	type FeeRatio = Permill;
+
+	#[transactional]
+	fn bond(who: T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		Self::do_bond(&who, amount)
+	}
+
+	#[transactional]
+	fn unbond(who: T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		Self::do_unbond(&who, amount)
+	}
+
+	#[transactional]
+	fn unbond_instant(who: T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		Self::do_unbond_instant(&who, amount)
+	}
+
+	#[transactional]
+	fn rebond(who: T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		Self::do_rebond(&who, amount)
+	}
+
+	#[transactional]
+	fn withdraw_unbonded(who: T::AccountId) -> Result<Balance, DispatchError> {
+		Self::do_withdraw_unbonded(&who)
+	}
+
+	fn get_bonding_ledger(who: T::AccountId) -> BondingLedgerOf<T> {
+		Self::ledger(who).unwrap_or_default()
+	}
+
+	fn get_min_bond() -> Balance {
+		T::MinBond::get()
+	}
+
+	fn get_unbonding_period() -> T::BlockNumber {
+		T::UnbondingPeriod::get()
+	}
+
+	fn get_max_unbonding_chunks() -> u32 {
+		T::MaxUnbondingChunks::get()
+	}
+
+	fn get_instant_unstake_fee() -> Permill {
+		T::InstantUnstakeFee::get()
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The voting power of `who` from its own bonded balances, regardless of delegation.
+	pub fn own_voting_power(who: &T::AccountId) -> Balance {
+		let ledger = match Self::ledger(who) {
+			Some(ledger) => ledger,
+			None => return Zero::zero(),
+		};
+		if !T::UnbondingVotingPower::get() {
+			return ledger.active();
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let period: Balance = T::UnbondingPeriod::get().unique_saturated_into();
+		ledger.unlocking().iter().fold(ledger.active(), |power, chunk| {
+			let remaining: Balance = chunk.unlock_at().saturating_sub(now).unique_saturated_into();
+			power.saturating_add(Perbill::from_rational(remaining.min(period), period) * chunk.value())
+		})
+	}
+
+	/// The voting power `who` can vote with, including the voting power delegated to it.
+	/// Returns zero if `who` has delegated its voting power to another account.
+	pub fn voting_power(who: &T::AccountId) -> Balance {
+		if Delegations::<T>::contains_key(who) {
+			return Zero::zero();
+		}
+
+		Self::delegators(who)
+			.iter()
+			.fold(Self::own_voting_power(who), |power, delegator| {
+				power.saturating_add(Self::own_voting_power(delegator))
+			})
 	}
 }
 
-impl<T: Config> Pallet<T> {}
+/// Share of the fees to be distributed to bonders as rewards.
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		T::Currency::resolve_creating(&Self::account_id(), amount);
+	}
+}
 
 impl<T: Config> BondingController for Pallet<T> {
 	type MinBond = T::MinBond;
@@ -26,10 +26,12 @@ use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU128, ConstU32, ConstU64, Everything, Imbalance},
 };
+use frame_system::EnsureRoot;
 use pallet_balances::NegativeImbalance;
-use primitives::mock_handler;
+use primitives::{mock_handler, TokenSymbol};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError};
+use support::Rate;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -76,6 +78,51 @@ impl pallet_balances::Config for Runtime {
 parameter_types! {
 	pub const InstantUnstakeFee: Permill = Permill::from_percent(10);
 	pub const EarningLockIdentifier: LockIdentifier = *b"12345678";
+	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+	pub const EarningPalletId: PalletId = PalletId(*b"aca/earn");
+	pub static UnbondingVotingPower: bool = false;
+}
+
+pub const REWARDS_POOL: AccountId = 100;
+
+/// Pays out all the accumulated rewards to the claimer, assuming a single bonder.
+pub struct MockIncentives;
+impl IncentivesManager<AccountId, Balance, CurrencyId, PoolId> for MockIncentives {
+	fn get_incentive_reward_amount(_: PoolId, _: CurrencyId) -> Balance {
+		unimplemented!()
+	}
+
+	fn get_dex_reward_rate(_: PoolId) -> Rate {
+		unimplemented!()
+	}
+
+	fn deposit_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn withdraw_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn claim_rewards(who: AccountId, _: PoolId) -> DispatchResult {
+		let rewards = Balances::free_balance(&REWARDS_POOL);
+		<Balances as Currency<AccountId>>::transfer(&REWARDS_POOL, &who, rewards, ExistenceRequirement::AllowDeath)
+	}
+
+	fn get_claim_reward_deduction_rate(_: PoolId) -> Rate {
+		unimplemented!()
+	}
+
+	fn get_pending_rewards(_: PoolId, _: AccountId, _: Vec<CurrencyId>) -> Vec<Balance> {
+		unimplemented!()
+	}
+
+	fn accumulate_rewards(from: &AccountId, _: PoolId, _: CurrencyId, amount: Balance) -> DispatchResult {
+		if Ledger::<Runtime>::iter().next().is_none() {
+			return Err(DispatchError::Other("PoolDoesNotExist"));
+		}
+		<Balances as Currency<AccountId>>::transfer(from, &REWARDS_POOL, amount, ExistenceRequirement::AllowDeath)
+	}
 }
 
 mock_handler! {
@@ -101,6 +148,12 @@ impl Config for Runtime {
 	type InstantUnstakeFee = InstantUnstakeFee;
 	type MaxUnbondingChunks = ConstU32<3>;
 	type LockIdentifier = EarningLockIdentifier;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type Incentives = MockIncentives;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type PalletId = EarningPalletId;
+	type UnbondingVotingPower = UnbondingVotingPower;
+	type MaxDelegators = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -122,6 +175,9 @@ construct_runtime!(
 pub struct ExtBuilder;
 
 pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
@@ -136,7 +192,7 @@ impl ExtBuilder {
 			.unwrap();
 
 		pallet_balances::GenesisConfig::<Runtime> {
-			balances: vec![(ALICE, 1000)],
+			balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000), (DAVE, 1000)],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -207,3 +207,241 @@ fn rebond_works() {
 		assert_no_handler_events();
 	});
 }
+
+#[test]
+fn set_rewards_per_block_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Earning::set_rewards_per_block(Origin::signed(ALICE), 100),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_ok!(Earning::set_rewards_per_block(Origin::root(), 100));
+		System::assert_last_event(Event::RewardsPerBlockUpdated { amount: 100 }.into());
+		assert_eq!(Earning::rewards_per_block(), 100);
+	});
+}
+
+#[test]
+fn distribute_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Earning::set_rewards_per_block(Origin::root(), 100));
+		let total_issuance = Balances::total_issuance();
+
+		// no bonders, no inflation
+		Earning::on_initialize(2);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+		assert_eq!(Balances::free_balance(&REWARDS_POOL), 0);
+
+		assert_ok!(Earning::bond(Origin::signed(ALICE), 500));
+
+		// fee share is kept in module account until distribution
+		Earning::on_unbalanced(<Balances as Currency<AccountId>>::issue(50));
+		assert_eq!(Balances::free_balance(&Earning::account_id()), 50);
+
+		Earning::on_initialize(3);
+		System::assert_last_event(
+			Event::RewardsDistributed {
+				inflation: 100,
+				fee_share: 40,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::total_issuance(), total_issuance + 150);
+		assert_eq!(Balances::free_balance(&REWARDS_POOL), 140);
+		assert_eq!(Balances::free_balance(&Earning::account_id()), 10);
+	});
+}
+
+#[test]
+fn claim_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Earning::set_rewards_per_block(Origin::root(), 100));
+		assert_ok!(Earning::bond(Origin::signed(ALICE), 500));
+		clear_handler_events();
+
+		assert_noop!(
+			Earning::claim_rewards(Origin::signed(ALICE), true),
+			Error::<Runtime>::NoRewardsToCompound
+		);
+
+		Earning::on_initialize(2);
+		assert_ok!(Earning::claim_rewards(Origin::signed(ALICE), false));
+		assert_eq!(Balances::free_balance(&ALICE), 1100);
+		assert_eq!(Earning::ledger(ALICE).unwrap().active(), 500);
+		assert_no_handler_events();
+
+		Earning::on_initialize(3);
+		assert_ok!(Earning::claim_rewards(Origin::signed(ALICE), true));
+		System::assert_last_event(
+			Event::RewardsCompounded {
+				who: ALICE,
+				amount: 100,
+			}
+			.into(),
+		);
+		OnBonded::assert_eq_and_clear(vec![(ALICE, 100)]);
+		assert_eq!(Balances::free_balance(&ALICE), 1200);
+		assert_eq!(Earning::ledger(ALICE).unwrap().active(), 600);
+		assert_eq!(Balances::reducible_balance(&ALICE, false), 600);
+
+		assert_no_handler_events();
+	});
+}
+
+#[test]
+fn voting_power_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Earning::own_voting_power(&ALICE), 0);
+
+		assert_ok!(Earning::bond(Origin::signed(ALICE), 1000));
+		assert_ok!(Earning::unbond(Origin::signed(ALICE), 400));
+		assert_eq!(Earning::own_voting_power(&ALICE), 600);
+		assert_eq!(Earning::voting_power(&ALICE), 600);
+
+		UnbondingVotingPower::set(true);
+		assert_eq!(Earning::own_voting_power(&ALICE), 1000);
+
+		// unbonding weight decays until unlocked
+		System::set_block_number(3);
+		assert_eq!(Earning::own_voting_power(&ALICE), 733);
+
+		System::set_block_number(4);
+		assert_eq!(Earning::own_voting_power(&ALICE), 600);
+	});
+}
+
+#[test]
+fn delegation_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Earning::bond(Origin::signed(BOB), 500));
+		assert_ok!(Earning::bond(Origin::signed(CHARLIE), 200));
+
+		assert_noop!(
+			Earning::delegate(Origin::signed(BOB), BOB),
+			Error::<Runtime>::SelfDelegation
+		);
+		assert_noop!(
+			Earning::delegate(Origin::signed(ALICE), BOB),
+			Error::<Runtime>::NotBonded
+		);
+		assert_ok!(Earning::bond(Origin::signed(ALICE), 100));
+		assert_ok!(Earning::bond(Origin::signed(DAVE), 100));
+
+		assert_ok!(Earning::delegate(Origin::signed(CHARLIE), BOB));
+		System::assert_last_event(Event::Delegated { who: CHARLIE, to: BOB }.into());
+		assert_eq!(Earning::delegations(CHARLIE), Some(BOB));
+		assert_eq!(Earning::delegators(BOB).into_inner(), vec![CHARLIE]);
+		assert_eq!(Earning::voting_power(&BOB), 700);
+		assert_eq!(Earning::voting_power(&CHARLIE), 0);
+		assert_eq!(Earning::own_voting_power(&CHARLIE), 200);
+
+		assert_noop!(
+			Earning::delegate(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::NestedDelegation
+		);
+		assert_noop!(
+			Earning::delegate(Origin::signed(ALICE), CHARLIE),
+			Error::<Runtime>::NestedDelegation
+		);
+
+		assert_ok!(Earning::delegate(Origin::signed(DAVE), BOB));
+		assert_noop!(
+			Earning::delegate(Origin::signed(ALICE), BOB),
+			Error::<Runtime>::TooManyDelegators
+		);
+
+		assert_noop!(
+			Earning::undelegate(Origin::signed(ALICE)),
+			Error::<Runtime>::NotDelegating
+		);
+		assert_ok!(Earning::undelegate(Origin::signed(CHARLIE)));
+		System::assert_last_event(
+			Event::Undelegated {
+				who: CHARLIE,
+				from: BOB,
+			}
+			.into(),
+		);
+		assert_eq!(Earning::delegations(CHARLIE), None);
+		assert_eq!(Earning::delegators(BOB).into_inner(), vec![DAVE]);
+		assert_eq!(Earning::voting_power(&BOB), 600);
+		assert_eq!(Earning::voting_power(&CHARLIE), 200);
+
+		// delegate to another account
+		assert_ok!(Earning::delegate(Origin::signed(DAVE), ALICE));
+		assert_eq!(Earning::delegations(DAVE), Some(ALICE));
+		assert!(!Delegators::<Runtime>::contains_key(BOB));
+		assert_eq!(Earning::delegators(ALICE).into_inner(), vec![DAVE]);
+	});
+}
+
+#[test]
+fn delegation_is_removed_when_unbonded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Earning::bond(Origin::signed(ALICE), 500));
+		assert_ok!(Earning::bond(Origin::signed(BOB), 200));
+		assert_ok!(Earning::bond(Origin::signed(CHARLIE), 200));
+		assert_ok!(Earning::delegate(Origin::signed(BOB), ALICE));
+		assert_ok!(Earning::delegate(Origin::signed(CHARLIE), ALICE));
+		assert_eq!(Earning::voting_power(&ALICE), 900);
+
+		// partially unbonded, still delegating
+		assert_ok!(Earning::unbond(Origin::signed(BOB), 100));
+		assert_eq!(Earning::delegations(BOB), Some(ALICE));
+
+		assert_ok!(Earning::unbond(Origin::signed(BOB), 100));
+		System::assert_last_event(Event::Undelegated { who: BOB, from: ALICE }.into());
+		assert_eq!(Earning::delegations(BOB), None);
+		assert_eq!(Earning::delegators(ALICE).into_inner(), vec![CHARLIE]);
+
+		assert_ok!(Earning::unbond_instant(Origin::signed(CHARLIE), 200));
+		System::assert_last_event(
+			Event::Undelegated {
+				who: CHARLIE,
+				from: ALICE,
+			}
+			.into(),
+		);
+		assert!(!Delegators::<Runtime>::contains_key(ALICE));
+		assert_eq!(Earning::voting_power(&ALICE), 500);
+
+		assert_noop!(
+			Earning::delegate(Origin::signed(CHARLIE), ALICE),
+			Error::<Runtime>::NotBonded
+		);
+	});
+}
+
+#[test]
+fn earning_manager_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(<Earning as EarningManager<_, _, _>>::get_min_bond(), 100);
+		assert_eq!(<Earning as EarningManager<_, _, _>>::get_unbonding_period(), 3);
+		assert_eq!(<Earning as EarningManager<_, _, _>>::get_max_unbonding_chunks(), 3);
+		assert_eq!(
+			<Earning as EarningManager<_, _, _>>::get_instant_unstake_fee(),
+			Permill::from_percent(10)
+		);
+
+		assert_eq!(<Earning as EarningManager<_, _, _>>::bond(ALICE, 1000), Ok(1000));
+		assert_eq!(<Earning as EarningManager<_, _, _>>::unbond(ALICE, 400), Ok(400));
+		assert_eq!(<Earning as EarningManager<_, _, _>>::rebond(ALICE, 100), Ok(100));
+		assert_eq!(
+			<Earning as EarningManager<_, _, _>>::unbond_instant(ALICE, 200),
+			Ok(180)
+		);
+
+		let ledger = <Earning as EarningManager<_, _, _>>::get_bonding_ledger(ALICE);
+		assert_eq!(ledger.active(), 500);
+		assert_eq!(ledger.total(), 800);
+
+		assert_eq!(<Earning as EarningManager<_, _, _>>::withdraw_unbonded(ALICE), Ok(0));
+		System::set_block_number(4);
+		assert_eq!(<Earning as EarningManager<_, _, _>>::withdraw_unbonded(ALICE), Ok(300));
+		assert_eq!(
+			<Earning as EarningManager<_, _, _>>::get_bonding_ledger(BOB),
+			Default::default()
+		);
+	});
+}
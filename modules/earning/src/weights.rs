@@ -51,6 +51,11 @@ pub trait WeightInfo {
 	fn unbond() -> Weight;
 	fn rebond() -> Weight;
 	fn withdraw_unbonded() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn set_rewards_per_block() -> Weight;
+	fn on_initialize() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
 }
 
 /// Weights for module_earning using the Acala node and recommended hardware.
@@ -91,6 +96,45 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Earning Ledger (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Rewards PoolInfos (r:1 w:1)
+	// Storage: Rewards SharesAndWithdrawnRewards (r:1 w:1)
+	// Storage: Incentives PendingMultiRewards (r:1 w:1)
+	// Storage: Incentives ClaimRewardDeductionRates (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(89_371_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Earning RewardsPerBlock (r:0 w:1)
+	fn set_rewards_per_block() -> Weight {
+		(12_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Earning RewardsPerBlock (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Rewards PoolInfos (r:1 w:1)
+	fn on_initialize() -> Weight {
+		(41_823_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Earning Delegations (r:2 w:1)
+	// Storage: Earning Delegators (r:3 w:2)
+	fn delegate() -> Weight {
+		(31_452_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Earning Delegations (r:1 w:1)
+	// Storage: Earning Delegators (r:1 w:1)
+	fn undelegate() -> Weight {
+		(22_816_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -120,4 +164,28 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	fn claim_rewards() -> Weight {
+		(89_371_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn set_rewards_per_block() -> Weight {
+		(12_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize() -> Weight {
+		(41_823_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn delegate() -> Weight {
+		(31_452_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(22_816_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
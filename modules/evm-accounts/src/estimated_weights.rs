@@ -0,0 +1,107 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_evm_accounts
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights are estimated from the storage
+//! accesses and the benchmarked weights of the similar calls. Replace them by running the
+//! `module_evm_accounts` benchmarks, which generate them into the weights of the module and the
+//! runtimes.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: EvmAccounts EvmAddresses (r:1 w:1)
+// Storage: EvmAccounts PendingRotations (r:1 w:0)
+// Storage: System BlockHash (r:1 w:0)
+// Storage: EvmAccounts Accounts (r:1 w:2)
+// Storage: EvmAccounts RetiredEvmAddresses (r:1 w:2)
+// Storage: EvmAccounts AccountRetiredEvmAddresses (r:0 w:2)
+// Storage: System Account (r:1 w:0)
+pub fn rotate_evm_address<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(111_720_000 as Weight)
+		.saturating_add(DbWeight::get().reads(6 as Weight))
+		.saturating_add(DbWeight::get().writes(7 as Weight))
+}
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EvmAccounts PendingRotations (r:1 w:1)
+// Storage: EvmAccounts Accounts (r:1 w:0)
+// Storage: EvmAccounts RetiredEvmAddresses (r:1 w:0)
+// Storage: System BlockHash (r:1 w:0)
+pub fn request_evm_address_rotation<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(58_140_000 as Weight)
+		.saturating_add(DbWeight::get().reads(5 as Weight))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+}
+// Storage: EvmAccounts PendingRotations (r:1 w:1)
+pub fn cancel_evm_address_rotation<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(7_980_000 as Weight)
+		.saturating_add(DbWeight::get().reads(1 as Weight))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+}
+// Storage: EvmAccounts PendingRotations (r:1 w:1)
+// Storage: EvmAccounts EvmAddresses (r:1 w:1)
+// Storage: EvmAccounts Accounts (r:1 w:2)
+// Storage: EvmAccounts RetiredEvmAddresses (r:1 w:2)
+// Storage: EvmAccounts AccountRetiredEvmAddresses (r:0 w:2)
+// Storage: System Account (r:1 w:0)
+pub fn execute_evm_address_rotation<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(17_670_000 as Weight)
+		.saturating_add(DbWeight::get().reads(5 as Weight))
+		.saturating_add(DbWeight::get().writes(8 as Weight))
+}
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EvmAccounts Accounts (r:1 w:1)
+// Storage: EvmAccounts RetiredEvmAddresses (r:1 w:1)
+// Storage: EvmAccounts AccountRetiredEvmAddresses (r:0 w:1)
+// Storage: System BlockHash (r:1 w:0)
+// Storage: EvmAccounts SecondaryEvmAddresses (r:1 w:1)
+// Storage: System Account (r:1 w:0)
+pub fn add_secondary_evm_address<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(59_280_000 as Weight)
+		.saturating_add(DbWeight::get().reads(6 as Weight))
+		.saturating_add(DbWeight::get().writes(4 as Weight))
+}
+// Storage: EvmAccounts SecondaryEvmAddresses (r:1 w:1)
+// Storage: EvmAccounts Accounts (r:0 w:1)
+// Storage: EvmAccounts RetiredEvmAddresses (r:0 w:1)
+// Storage: EvmAccounts AccountRetiredEvmAddresses (r:0 w:1)
+pub fn remove_secondary_evm_address<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(9_120_000 as Weight)
+		.saturating_add(DbWeight::get().reads(1 as Weight))
+		.saturating_add(DbWeight::get().writes(4 as Weight))
+}
+// Storage: EvmAccounts EvmAddresses (r:1 w:1)
+// Storage: EvmAccounts Accounts (r:1 w:1)
+// Storage: EvmAccounts RetiredEvmAddresses (r:1 w:1)
+// Storage: EvmAccounts AccountRetiredEvmAddresses (r:0 w:1)
+// Storage: System BlockHash (r:1 w:0)
+// Storage: EVM ChainId (r:1 w:0)
+// Storage: EVM Accounts (r:2 w:0)
+// Storage: EVM Codes (r:1 w:0)
+// Storage: EVM ContractStorageSizes (r:1 w:0)
+// Storage: System Account (r:2 w:0)
+// Storage: EVM ExtrinsicOrigin (r:1 w:0)
+pub fn claim_contract_account<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(231_905_000 as Weight)
+		.saturating_add(DbWeight::get().reads(12 as Weight))
+		.saturating_add(DbWeight::get().writes(4 as Weight))
+}
@@ -22,6 +22,18 @@
 //!
 //! Evm Accounts module provide a two way mapping between Substrate accounts and
 //! EVM accounts so user only have deal with one account / private key.
+//!
+//! Contract wallets, e.g. multisig wallets, can be claimed with a signature accepted by the
+//! `isValidSignature` method of the contract, as specified by EIP-1271.
+//!
+//! The EVM address of an account can be rotated, either immediately with the signatures of both
+//! the old and the new keys, or by the Substrate account with the signature of the new key after
+//! `RotationDelay` blocks, during which the rotation can be cancelled. An account can also
+//! authorise up to `MaxSecondaryAddresses` secondary EVM addresses to act for it.
+//!
+//! The addresses rotated away or removed are retired: they don't act for the account anymore and
+//! can't be claimed by other accounts, but the account can still transfer the maintainership of
+//! the contracts maintained by them. The account id is unchanged, so are its reserved balances.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -35,9 +47,9 @@ use frame_support::{
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
 use module_evm_utility_macro::keccak256;
-use module_support::{AddressMapping, EVMAccountsManager};
+use module_support::{AddressMapping, EVMAccountsManager, EVMBridge, InvokeContext};
 use orml_traits::currency::TransferAll;
-use primitives::{evm::EvmAddress, to_bytes, AccountIndex};
+use primitives::{evm::EvmAddress, to_bytes, AccountIndex, Balance};
 use sp_core::crypto::AccountId32;
 use sp_core::{H160, H256};
 use sp_io::{
@@ -45,11 +57,12 @@ use sp_io::{
 	hashing::{blake2_256, keccak_256},
 };
 use sp_runtime::{
-	traits::{LookupError, StaticLookup, Zero},
+	traits::{LookupError, Saturating, StaticLookup, Zero},
 	MultiAddress,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
 
+pub mod estimated_weights;
 mod mock;
 mod tests;
 pub mod weights;
@@ -60,6 +73,9 @@ pub use weights::WeightInfo;
 /// A signature (a 512-bit value, plus 8 bits for recovery ID).
 pub type Eip712Signature = [u8; 65];
 
+/// The max size of the signature of a contract wallet.
+pub const MAX_CONTRACT_SIGNATURE_SIZE: usize = 2048;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -81,6 +97,18 @@ pub mod module {
 		/// Merge free balance from source to dest.
 		type TransferAll: TransferAll<Self::AccountId>;
 
+		/// The EVM bridge to verify the signatures of contract wallets.
+		type EVMBridge: EVMBridge<Self::AccountId, Balance>;
+
+		/// The number of blocks before a rotation requested by the Substrate account can be
+		/// executed.
+		#[pallet::constant]
+		type RotationDelay: Get<Self::BlockNumber>;
+
+		/// The max number of secondary EVM addresses of an account.
+		#[pallet::constant]
+		type MaxSecondaryAddresses: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -94,6 +122,33 @@ pub mod module {
 			account_id: T::AccountId,
 			evm_address: EvmAddress,
 		},
+		/// The EVM address of the account is rotated.
+		EvmAddressRotated {
+			account_id: T::AccountId,
+			old_address: EvmAddress,
+			new_address: EvmAddress,
+		},
+		/// A rotation of the EVM address of the account is requested.
+		EvmAddressRotationRequested {
+			account_id: T::AccountId,
+			new_address: EvmAddress,
+			executable_at: T::BlockNumber,
+		},
+		/// The pending rotation of the EVM address of the account is cancelled.
+		EvmAddressRotationCancelled {
+			account_id: T::AccountId,
+			new_address: EvmAddress,
+		},
+		/// A secondary EVM address is added to the account.
+		SecondaryEvmAddressAdded {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+		},
+		/// A secondary EVM address is removed from the account.
+		SecondaryEvmAddressRemoved {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+		},
 	}
 
 	/// Error for evm accounts module.
@@ -109,6 +164,22 @@ pub mod module {
 		InvalidSignature,
 		/// Account ref count is not zero
 		NonZeroRefCount,
+		/// The signature of the contract wallet is too long
+		SignatureTooLong,
+		/// AccountId has not mapped
+		AccountIdNotMapped,
+		/// A rotation is pending
+		RotationPending,
+		/// No rotation is pending
+		RotationNotPending,
+		/// The rotation can't be executed yet
+		RotationNotReady,
+		/// Too many secondary addresses
+		TooManySecondaryAddresses,
+		/// The address is not a secondary address of the account
+		SecondaryAddressNotFound,
+		/// The contract wallet holds native funds
+		ContractHasFunds,
 	}
 
 	/// The Substrate Account for EvmAddresses
@@ -125,6 +196,36 @@ pub mod module {
 	#[pallet::getter(fn evm_addresses)]
 	pub type EvmAddresses<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, EvmAddress, OptionQuery>;
 
+	/// The secondary EvmAddresses of Substrate Accounts, they are also in `Accounts`.
+	///
+	/// SecondaryEvmAddresses: map AccountId => BoundedVec<EvmAddress, MaxSecondaryAddresses>
+	#[pallet::storage]
+	#[pallet::getter(fn secondary_evm_addresses)]
+	pub type SecondaryEvmAddresses<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<EvmAddress, T::MaxSecondaryAddresses>, ValueQuery>;
+
+	/// The Substrate Account of the retired EvmAddresses, which were rotated away or removed.
+	///
+	/// RetiredEvmAddresses: map EvmAddress => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn retired_evm_addresses)]
+	pub type RetiredEvmAddresses<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::AccountId, OptionQuery>;
+
+	/// The retired EvmAddresses of Substrate Accounts, they are also in `RetiredEvmAddresses`.
+	///
+	/// AccountRetiredEvmAddresses: double_map AccountId, EvmAddress => Option<()>
+	#[pallet::storage]
+	pub type AccountRetiredEvmAddresses<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, EvmAddress, (), OptionQuery>;
+
+	/// The pending rotations requested by Substrate Accounts.
+	///
+	/// PendingRotations: map AccountId => Option<(EvmAddress, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rotations)]
+	pub type PendingRotations<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (EvmAddress, T::BlockNumber), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -149,31 +250,60 @@ pub mod module {
 
 			// ensure account_id and eth_address has not been mapped
 			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
-			ensure!(
-				!Accounts::<T>::contains_key(eth_address),
-				Error::<T>::EthAddressHasMapped
-			);
+			Self::ensure_address_available(&who, &eth_address)?;
 
 			// recover evm address from signature
 			let address = Self::verify_eip712_signature(&who, &eth_signature).ok_or(Error::<T>::BadSignature)?;
 			ensure!(eth_address == address, Error::<T>::InvalidSignature);
 
-			// check if the evm padded address already exists
-			let account_id = T::AddressMapping::get_account_id(&eth_address);
-			if frame_system::Pallet::<T>::account_exists(&account_id) {
-				// merge balance from `evm padded address` to `origin`
-				T::TransferAll::transfer_all(&account_id, &who)?;
-			}
+			Self::do_claim_account(who, eth_address)
+		}
 
-			Accounts::<T>::insert(eth_address, &who);
-			EvmAddresses::<T>::insert(&who, eth_address);
+		/// Claim account mapping between Substrate accounts and a contract wallet, the signature
+		/// is verified by the `isValidSignature` method of the contract (EIP-1271).
+		/// Ensure eth_address has not been mapped, and the contract doesn't hold native funds
+		/// which would be moved to the caller. The other tokens of the contract are merged into
+		/// the caller like the claims of the other addresses, so a contract wallet which holds
+		/// them must only accept the signatures of its owners.
+		///
+		/// - `eth_address`: The contract address to bind to the caller's account
+		/// - `signature`: A signature of the claim message accepted by the contract
+		#[pallet::weight(T::WeightInfo::claim_contract_account())]
+		#[transactional]
+		pub fn claim_contract_account(
+			origin: OriginFor<T>,
+			eth_address: EvmAddress,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::ClaimAccount {
-				account_id: who,
-				evm_address: eth_address,
-			});
+			// ensure account_id and eth_address has not been mapped
+			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
+			Self::ensure_address_available(&who, &eth_address)?;
+			ensure!(
+				signature.len() <= MAX_CONTRACT_SIGNATURE_SIZE,
+				Error::<T>::SignatureTooLong
+			);
+			ensure!(
+				T::Currency::free_balance(&T::AddressMapping::get_account_id(&eth_address)).is_zero(),
+				Error::<T>::ContractHasFunds
+			);
 
-			Ok(())
+			// the contract wallets revert on invalid signatures usually
+			let msg_hash = keccak_256(&Self::eip712_signable_message(&who));
+			let valid = T::EVMBridge::is_valid_signature(
+				InvokeContext {
+					contract: eth_address,
+					sender: Default::default(),
+					origin: Default::default(),
+				},
+				msg_hash.into(),
+				signature,
+			)
+			.map_err(|_| Error::<T>::InvalidSignature)?;
+			ensure!(valid, Error::<T>::InvalidSignature);
+
+			Self::do_claim_account(who, eth_address)
 		}
 
 		/// Claim account mapping between Substrate accounts and a generated EVM
@@ -185,6 +315,162 @@ pub mod module {
 			let _ = Self::do_claim_default_evm_address(who)?;
 			Ok(())
 		}
+
+		/// Rotate the EVM address of the caller's account immediately.
+		///
+		/// - `new_address`: The address to replace the current address of the caller's account
+		/// - `old_signature`: A rotation signature generated by the current address
+		/// - `new_signature`: A rotation signature generated by the new address
+		#[pallet::weight(T::WeightInfo::rotate_evm_address())]
+		#[transactional]
+		pub fn rotate_evm_address(
+			origin: OriginFor<T>,
+			new_address: EvmAddress,
+			old_signature: Eip712Signature,
+			new_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			ensure!(!PendingRotations::<T>::contains_key(&who), Error::<T>::RotationPending);
+
+			let address =
+				Self::verify_rotation_signature(&who, &new_address, &old_signature).ok_or(Error::<T>::BadSignature)?;
+			ensure!(old_address == address, Error::<T>::InvalidSignature);
+			let address =
+				Self::verify_rotation_signature(&who, &new_address, &new_signature).ok_or(Error::<T>::BadSignature)?;
+			ensure!(new_address == address, Error::<T>::InvalidSignature);
+
+			Self::do_rotate_evm_address(who, new_address)
+		}
+
+		/// Request to rotate the EVM address of the caller's account without the signature of the
+		/// current address. The rotation can be executed after `RotationDelay` blocks.
+		///
+		/// - `new_address`: The address to replace the current address of the caller's account
+		/// - `new_signature`: A rotation signature generated by the new address
+		#[pallet::weight(T::WeightInfo::request_evm_address_rotation())]
+		#[transactional]
+		pub fn request_evm_address_rotation(
+			origin: OriginFor<T>,
+			new_address: EvmAddress,
+			new_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdNotMapped);
+			ensure!(!PendingRotations::<T>::contains_key(&who), Error::<T>::RotationPending);
+			Self::ensure_address_available(&who, &new_address)?;
+
+			let address =
+				Self::verify_rotation_signature(&who, &new_address, &new_signature).ok_or(Error::<T>::BadSignature)?;
+			ensure!(new_address == address, Error::<T>::InvalidSignature);
+
+			let executable_at = frame_system::Pallet::<T>::block_number().saturating_add(T::RotationDelay::get());
+			PendingRotations::<T>::insert(&who, (new_address, executable_at));
+
+			Self::deposit_event(Event::EvmAddressRotationRequested {
+				account_id: who,
+				new_address,
+				executable_at,
+			});
+			Ok(())
+		}
+
+		/// Cancel the pending rotation of the caller's account.
+		#[pallet::weight(T::WeightInfo::cancel_evm_address_rotation())]
+		pub fn cancel_evm_address_rotation(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (new_address, _) = PendingRotations::<T>::take(&who).ok_or(Error::<T>::RotationNotPending)?;
+
+			Self::deposit_event(Event::EvmAddressRotationCancelled {
+				account_id: who,
+				new_address,
+			});
+			Ok(())
+		}
+
+		/// Execute the pending rotation of an account after its delay.
+		///
+		/// - `who`: The account whose rotation is executed
+		#[pallet::weight(T::WeightInfo::execute_evm_address_rotation())]
+		#[transactional]
+		pub fn execute_evm_address_rotation(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let (new_address, executable_at) =
+				PendingRotations::<T>::take(&who).ok_or(Error::<T>::RotationNotPending)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= executable_at,
+				Error::<T>::RotationNotReady
+			);
+
+			Self::do_rotate_evm_address(who, new_address)
+		}
+
+		/// Authorise a secondary EVM address to act for the caller's account.
+		///
+		/// - `eth_address`: The address to add to the caller's account
+		/// - `eth_signature`: A secondary address signature generated by the address to prove
+		///   ownership
+		#[pallet::weight(T::WeightInfo::add_secondary_evm_address())]
+		#[transactional]
+		pub fn add_secondary_evm_address(
+			origin: OriginFor<T>,
+			eth_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdNotMapped);
+			Self::ensure_address_available(&who, &eth_address)?;
+
+			let address =
+				Self::verify_secondary_address_signature(&who, &eth_signature).ok_or(Error::<T>::BadSignature)?;
+			ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+			SecondaryEvmAddresses::<T>::try_mutate(&who, |addresses| {
+				addresses
+					.try_push(eth_address)
+					.map_err(|_| Error::<T>::TooManySecondaryAddresses)
+			})?;
+			Self::merge_padded_account(&who, &eth_address)?;
+
+			Self::unretire_evm_address(&eth_address);
+			Accounts::<T>::insert(eth_address, &who);
+
+			Self::deposit_event(Event::SecondaryEvmAddressAdded {
+				account_id: who,
+				evm_address: eth_address,
+			});
+			Ok(())
+		}
+
+		/// Remove a secondary EVM address of the caller's account, the address is retired.
+		///
+		/// - `eth_address`: The secondary address to remove
+		#[pallet::weight(T::WeightInfo::remove_secondary_evm_address())]
+		#[transactional]
+		pub fn remove_secondary_evm_address(origin: OriginFor<T>, eth_address: EvmAddress) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			SecondaryEvmAddresses::<T>::try_mutate_exists(&who, |maybe_addresses| -> DispatchResult {
+				let addresses = maybe_addresses.as_mut().ok_or(Error::<T>::SecondaryAddressNotFound)?;
+				let index = addresses
+					.iter()
+					.position(|address| *address == eth_address)
+					.ok_or(Error::<T>::SecondaryAddressNotFound)?;
+				addresses.remove(index);
+				if addresses.is_empty() {
+					*maybe_addresses = None;
+				}
+				Ok(())
+			})?;
+
+			Accounts::<T>::remove(eth_address);
+			Self::retire_evm_address(&who, eth_address);
+
+			Self::deposit_event(Event::SecondaryEvmAddressRemoved {
+				account_id: who,
+				evm_address: eth_address,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -213,6 +499,32 @@ impl<T: Config> Pallet<T> {
 		r
 	}
 
+	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
+	// Constructs a rotation message and signs it.
+	pub fn eth_sign_rotation(
+		secret: &libsecp256k1::SecretKey,
+		who: &T::AccountId,
+		new_address: &EvmAddress,
+	) -> Eip712Signature {
+		let msg = keccak_256(&Self::eip712_rotation_message(who, new_address));
+		let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+		let mut r = [0u8; 65];
+		r[0..64].copy_from_slice(&sig.serialize()[..]);
+		r[64] = recovery_id.serialize();
+		r
+	}
+
+	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
+	// Constructs a secondary address message and signs it.
+	pub fn eth_sign_secondary_address(secret: &libsecp256k1::SecretKey, who: &T::AccountId) -> Eip712Signature {
+		let msg = keccak_256(&Self::eip712_secondary_address_message(who));
+		let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+		let mut r = [0u8; 65];
+		r[0..64].copy_from_slice(&sig.serialize()[..]);
+		r[64] = recovery_id.serialize();
+		r
+	}
+
 	fn verify_eip712_signature(who: &T::AccountId, sig: &[u8; 65]) -> Option<H160> {
 		let msg = Self::eip712_signable_message(who);
 		let msg_hash = keccak_256(msg.as_slice());
@@ -231,6 +543,51 @@ impl<T: Config> Pallet<T> {
 		msg
 	}
 
+	fn verify_rotation_signature(who: &T::AccountId, new_address: &EvmAddress, sig: &[u8; 65]) -> Option<H160> {
+		let msg = Self::eip712_rotation_message(who, new_address);
+		let msg_hash = keccak_256(msg.as_slice());
+
+		recover_signer(sig, &msg_hash)
+	}
+
+	// Eip-712 message to be signed by both the old and the new addresses of a rotation
+	fn eip712_rotation_message(who: &T::AccountId, new_address: &EvmAddress) -> Vec<u8> {
+		let domain_separator = Self::evm_account_domain_separator();
+
+		let tx_type_hash = keccak256!("Rotation(bytes substrateAddress,address newAddress)");
+		let mut tx_msg = tx_type_hash.to_vec();
+		tx_msg.extend_from_slice(&keccak_256(&who.encode()));
+		tx_msg.extend_from_slice(H256::from(*new_address).as_bytes());
+		let payload_hash = keccak_256(tx_msg.as_slice());
+
+		let mut msg = b"\x19\x01".to_vec();
+		msg.extend_from_slice(&domain_separator);
+		msg.extend_from_slice(&payload_hash);
+		msg
+	}
+
+	fn verify_secondary_address_signature(who: &T::AccountId, sig: &[u8; 65]) -> Option<H160> {
+		let msg = Self::eip712_secondary_address_message(who);
+		let msg_hash = keccak_256(msg.as_slice());
+
+		recover_signer(sig, &msg_hash)
+	}
+
+	// Eip-712 message to be signed by a secondary address, it must not be valid as a claim
+	fn eip712_secondary_address_message(who: &T::AccountId) -> Vec<u8> {
+		let domain_separator = Self::evm_account_domain_separator();
+
+		let tx_type_hash = keccak256!("SecondaryAddress(bytes substrateAddress)");
+		let mut tx_msg = tx_type_hash.to_vec();
+		tx_msg.extend_from_slice(&keccak_256(&who.encode()));
+		let payload_hash = keccak_256(tx_msg.as_slice());
+
+		let mut msg = b"\x19\x01".to_vec();
+		msg.extend_from_slice(&domain_separator);
+		msg.extend_from_slice(&payload_hash);
+		msg
+	}
+
 	fn evm_account_payload_hash(who: &T::AccountId) -> [u8; 32] {
 		let tx_type_hash = keccak256!("Transaction(bytes substrateAddress)");
 		let mut tx_msg = tx_type_hash.to_vec();
@@ -256,6 +613,80 @@ impl<T: Config> Pallet<T> {
 
 		Ok(eth_address)
 	}
+
+	fn do_claim_account(who: T::AccountId, eth_address: EvmAddress) -> DispatchResult {
+		Self::merge_padded_account(&who, &eth_address)?;
+
+		Self::unretire_evm_address(&eth_address);
+		Accounts::<T>::insert(eth_address, &who);
+		EvmAddresses::<T>::insert(&who, eth_address);
+
+		Self::deposit_event(Event::ClaimAccount {
+			account_id: who,
+			evm_address: eth_address,
+		});
+
+		Ok(())
+	}
+
+	/// Ensures `address` can be mapped to `who`: it's not mapped, and it's not retired by another
+	/// account.
+	fn ensure_address_available(who: &T::AccountId, address: &EvmAddress) -> DispatchResult {
+		ensure!(!Accounts::<T>::contains_key(address), Error::<T>::EthAddressHasMapped);
+		ensure!(
+			Self::retired_evm_addresses(address).map_or(true, |account_id| account_id == *who),
+			Error::<T>::EthAddressHasMapped
+		);
+		Ok(())
+	}
+
+	/// Merges the free balance of the evm padded account of `address` into `who`. The padded
+	/// account must not have reserved balances or other consumers, which would be left behind.
+	fn merge_padded_account(who: &T::AccountId, address: &EvmAddress) -> DispatchResult {
+		let account_id = T::AddressMapping::get_account_id(address);
+		if frame_system::Pallet::<T>::account_exists(&account_id) {
+			ensure!(
+				frame_system::Pallet::<T>::consumers(&account_id).is_zero(),
+				Error::<T>::NonZeroRefCount
+			);
+			// merge balance from `evm padded address` to `origin`
+			T::TransferAll::transfer_all(&account_id, who)?;
+		}
+		Ok(())
+	}
+
+	/// Records `address` as retired by `who`.
+	fn retire_evm_address(who: &T::AccountId, address: EvmAddress) {
+		RetiredEvmAddresses::<T>::insert(address, who);
+		AccountRetiredEvmAddresses::<T>::insert(who, address, ());
+	}
+
+	/// Removes the retired record of `address`, if any.
+	fn unretire_evm_address(address: &EvmAddress) {
+		if let Some(account_id) = RetiredEvmAddresses::<T>::take(address) {
+			AccountRetiredEvmAddresses::<T>::remove(account_id, address);
+		}
+	}
+
+	/// Replaces the EVM address of `who` with `new_address`, and retires the old address.
+	fn do_rotate_evm_address(who: T::AccountId, new_address: EvmAddress) -> DispatchResult {
+		let old_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+		Self::ensure_address_available(&who, &new_address)?;
+		Self::merge_padded_account(&who, &new_address)?;
+
+		Accounts::<T>::remove(old_address);
+		Self::retire_evm_address(&who, old_address);
+		Self::unretire_evm_address(&new_address);
+		Accounts::<T>::insert(new_address, &who);
+		EvmAddresses::<T>::insert(&who, new_address);
+
+		Self::deposit_event(Event::EvmAddressRotated {
+			account_id: who,
+			old_address,
+			new_address,
+		});
+		Ok(())
+	}
 }
 
 fn recover_signer(sig: &[u8; 65], msg_hash: &[u8; 32]) -> Option<H160> {
@@ -332,9 +763,12 @@ where
 
 	// Returns true if a given AccountId is associated with a given EvmAddress
 	// and false if is not.
+	// Secondary and retired addresses are linked too.
 	fn is_linked(account_id: &T::AccountId, evm: &EvmAddress) -> bool {
 		Self::get_evm_address(account_id).as_ref() == Some(evm)
 			|| &account_to_default_evm_address(account_id.into_ref()) == evm
+			|| Accounts::<T>::get(evm).as_ref() == Some(account_id)
+			|| RetiredEvmAddresses::<T>::get(evm).as_ref() == Some(account_id)
 	}
 }
 
@@ -346,6 +780,13 @@ impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 			Accounts::<T>::remove(evm_addr);
 			EvmAddresses::<T>::remove(who);
 		}
+		for evm_addr in SecondaryEvmAddresses::<T>::take(who) {
+			Accounts::<T>::remove(evm_addr);
+		}
+		for (evm_addr, _) in AccountRetiredEvmAddresses::<T>::drain_prefix(who) {
+			RetiredEvmAddresses::<T>::remove(evm_addr);
+		}
+		PendingRotations::<T>::remove(who);
 	}
 }
 
@@ -25,6 +25,13 @@ use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU128, ConstU64, Everything, Nothing},
 };
+use module_support::{
+	evm::{
+		abi::{AbiCodec, Function},
+		limits::Limit,
+	},
+	ExecutionMode,
+};
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, Balance, CurrencyId, TokenSymbol};
 use sp_core::{crypto::AccountId32, H256};
@@ -113,12 +120,62 @@ impl orml_currencies::Config for Runtime {
 }
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
 
+pub fn contract_wallet() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x1271)
+}
+
+/// Accepts the signature `b"valid"` for the `contract_wallet()`.
+pub struct MockEVMBridge;
+impl EVMBridge<AccountId, Balance> for MockEVMBridge {
+	fn name(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn symbol(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn decimals(_context: InvokeContext) -> Result<u8, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn total_supply(_context: InvokeContext) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn balance_of(_context: InvokeContext, _address: EvmAddress) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn is_valid_signature(context: InvokeContext, _hash: H256, signature: Vec<u8>) -> Result<bool, DispatchError> {
+		if context.contract == contract_wallet() {
+			Ok(signature == b"valid")
+		} else {
+			Err(DispatchError::Other("execution revert"))
+		}
+	}
+	fn call<Args: AbiCodec, Ret: AbiCodec>(
+		_context: InvokeContext,
+		_function: Function<Args, Ret>,
+		_args: Args,
+		_limit: &Limit,
+		_mode: ExecutionMode,
+	) -> Result<Ret, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+	fn set_origin(_origin: AccountId) {}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type ChainId = ();
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type EVMBridge = MockEVMBridge;
+	type RotationDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -173,6 +230,10 @@ pub fn bob() -> libsecp256k1::SecretKey {
 	libsecp256k1::SecretKey::parse(&keccak_256(b"Bob")).unwrap()
 }
 
+pub fn charlie() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Charlie")).unwrap()
+}
+
 pub fn bob_account_id() -> AccountId {
 	let address = EvmAccountsModule::eth_address(&bob());
 	let mut data = [0u8; 32];
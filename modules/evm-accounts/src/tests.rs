@@ -22,7 +22,10 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{alice, bob, Event, EvmAccountsModule, ExtBuilder, Origin, Runtime, System, ALICE, BOB};
+use mock::{
+	alice, bob, bob_account_id, charlie, contract_wallet, Balances, Event, EvmAccountsModule, ExtBuilder, Origin,
+	Runtime, System, ALICE, BOB,
+};
 use std::str::FromStr;
 
 #[test]
@@ -87,6 +90,61 @@ fn claim_account_should_not_work() {
 	});
 }
 
+#[test]
+fn claim_contract_account_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(Origin::signed(ALICE), contract_wallet(), b"invalid".to_vec()),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(ALICE),
+				EvmAccountsModule::eth_address(&alice()),
+				b"valid".to_vec()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(ALICE),
+				contract_wallet(),
+				vec![0u8; MAX_CONTRACT_SIGNATURE_SIZE + 1]
+			),
+			Error::<Runtime>::SignatureTooLong
+		);
+
+		// the native funds of the contract are not moved to the caller
+		let contract_account_id = EvmAddressMapping::<Runtime>::get_account_id(&contract_wallet());
+		Balances::make_free_balance_be(&contract_account_id, 1000);
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(Origin::signed(ALICE), contract_wallet(), b"valid".to_vec()),
+			Error::<Runtime>::ContractHasFunds
+		);
+		Balances::make_free_balance_be(&contract_account_id, 0);
+
+		assert_ok!(EvmAccountsModule::claim_contract_account(
+			Origin::signed(ALICE),
+			contract_wallet(),
+			b"valid".to_vec()
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::ClaimAccount {
+			account_id: ALICE,
+			evm_address: contract_wallet(),
+		}));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&contract_wallet()), ALICE);
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_evm_address(&ALICE),
+			Some(contract_wallet())
+		);
+
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(Origin::signed(BOB), contract_wallet(), b"valid".to_vec()),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+	});
+}
+
 #[test]
 fn evm_get_account_id() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -187,3 +245,324 @@ fn account_to_evm_with_create_default() {
 		);
 	});
 }
+
+#[test]
+fn rotate_evm_address_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_address = EvmAccountsModule::eth_address(&alice());
+		let bob_address = EvmAccountsModule::eth_address(&bob());
+
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				Origin::signed(ALICE),
+				bob_address,
+				EvmAccountsModule::eth_sign_rotation(&alice(), &ALICE, &bob_address),
+				EvmAccountsModule::eth_sign_rotation(&bob(), &ALICE, &bob_address)
+			),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			alice_address,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+		));
+
+		// signed by the wrong keys
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				Origin::signed(ALICE),
+				bob_address,
+				EvmAccountsModule::eth_sign_rotation(&bob(), &ALICE, &bob_address),
+				EvmAccountsModule::eth_sign_rotation(&bob(), &ALICE, &bob_address)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				Origin::signed(ALICE),
+				bob_address,
+				EvmAccountsModule::eth_sign_rotation(&alice(), &ALICE, &bob_address),
+				EvmAccountsModule::eth_sign(&bob(), &ALICE)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountsModule::rotate_evm_address(
+			Origin::signed(ALICE),
+			bob_address,
+			EvmAccountsModule::eth_sign_rotation(&alice(), &ALICE, &bob_address),
+			EvmAccountsModule::eth_sign_rotation(&bob(), &ALICE, &bob_address)
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::EvmAddressRotated {
+			account_id: ALICE,
+			old_address: alice_address,
+			new_address: bob_address,
+		}));
+
+		assert_eq!(EvmAddressMapping::<Runtime>::get_evm_address(&ALICE), Some(bob_address));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&bob_address), ALICE);
+		assert_ne!(EvmAddressMapping::<Runtime>::get_account_id(&alice_address), ALICE);
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(alice_address), Some(ALICE));
+		assert!(EvmAddressMapping::<Runtime>::is_linked(&ALICE, &alice_address));
+
+		// the balance of the evm padded account is merged
+		assert_eq!(Balances::free_balance(ALICE), 100000);
+		assert_eq!(Balances::free_balance(bob_account_id()), 0);
+
+		// the retired address can't be claimed by other accounts
+		assert_noop!(
+			EvmAccountsModule::claim_account(
+				Origin::signed(BOB),
+				alice_address,
+				EvmAccountsModule::eth_sign(&alice(), &BOB)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+	});
+}
+
+#[test]
+fn delayed_rotation_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let charlie_address = EvmAccountsModule::eth_address(&charlie());
+		let default_address = EvmAddressMapping::<Runtime>::get_or_create_evm_address(&ALICE);
+
+		assert_noop!(
+			EvmAccountsModule::cancel_evm_address_rotation(Origin::signed(ALICE)),
+			Error::<Runtime>::RotationNotPending
+		);
+		assert_noop!(
+			EvmAccountsModule::request_evm_address_rotation(
+				Origin::signed(ALICE),
+				charlie_address,
+				EvmAccountsModule::eth_sign_rotation(&charlie(), &BOB, &charlie_address)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountsModule::request_evm_address_rotation(
+			Origin::signed(ALICE),
+			charlie_address,
+			EvmAccountsModule::eth_sign_rotation(&charlie(), &ALICE, &charlie_address)
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::EvmAddressRotationRequested {
+			account_id: ALICE,
+			new_address: charlie_address,
+			executable_at: 11,
+		}));
+		assert_noop!(
+			EvmAccountsModule::request_evm_address_rotation(
+				Origin::signed(ALICE),
+				charlie_address,
+				EvmAccountsModule::eth_sign_rotation(&charlie(), &ALICE, &charlie_address)
+			),
+			Error::<Runtime>::RotationPending
+		);
+
+		assert_ok!(EvmAccountsModule::cancel_evm_address_rotation(Origin::signed(ALICE)));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::EvmAddressRotationCancelled {
+			account_id: ALICE,
+			new_address: charlie_address,
+		}));
+		assert_eq!(EvmAccountsModule::pending_rotations(ALICE), None);
+
+		assert_ok!(EvmAccountsModule::request_evm_address_rotation(
+			Origin::signed(ALICE),
+			charlie_address,
+			EvmAccountsModule::eth_sign_rotation(&charlie(), &ALICE, &charlie_address)
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			EvmAccountsModule::execute_evm_address_rotation(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::RotationNotReady
+		);
+
+		System::set_block_number(11);
+		assert_ok!(EvmAccountsModule::execute_evm_address_rotation(
+			Origin::signed(BOB),
+			ALICE
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::EvmAddressRotated {
+			account_id: ALICE,
+			old_address: default_address,
+			new_address: charlie_address,
+		}));
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_evm_address(&ALICE),
+			Some(charlie_address)
+		);
+		assert_eq!(EvmAccountsModule::pending_rotations(ALICE), None);
+	});
+}
+
+#[test]
+fn secondary_evm_address_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_address = EvmAccountsModule::eth_address(&alice());
+		let bob_address = EvmAccountsModule::eth_address(&bob());
+		let charlie_address = EvmAccountsModule::eth_address(&charlie());
+
+		assert_noop!(
+			EvmAccountsModule::add_secondary_evm_address(
+				Origin::signed(ALICE),
+				bob_address,
+				EvmAccountsModule::eth_sign_secondary_address(&bob(), &ALICE)
+			),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			alice_address,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_noop!(
+			EvmAccountsModule::add_secondary_evm_address(
+				Origin::signed(ALICE),
+				alice_address,
+				EvmAccountsModule::eth_sign_secondary_address(&alice(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+
+		// the claim signature is not valid for a secondary address
+		assert_noop!(
+			EvmAccountsModule::add_secondary_evm_address(
+				Origin::signed(ALICE),
+				bob_address,
+				EvmAccountsModule::eth_sign(&bob(), &ALICE)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountsModule::add_secondary_evm_address(
+			Origin::signed(ALICE),
+			bob_address,
+			EvmAccountsModule::eth_sign_secondary_address(&bob(), &ALICE)
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::SecondaryEvmAddressAdded {
+			account_id: ALICE,
+			evm_address: bob_address,
+		}));
+		assert_ok!(EvmAccountsModule::add_secondary_evm_address(
+			Origin::signed(ALICE),
+			charlie_address,
+			EvmAccountsModule::eth_sign_secondary_address(&charlie(), &ALICE)
+		));
+		assert_eq!(
+			EvmAccountsModule::secondary_evm_addresses(ALICE).into_inner(),
+			vec![bob_address, charlie_address]
+		);
+
+		// the primary address is unchanged
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_evm_address(&ALICE),
+			Some(alice_address)
+		);
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&bob_address), ALICE);
+		assert!(EvmAddressMapping::<Runtime>::is_linked(&ALICE, &bob_address));
+		assert_eq!(Balances::free_balance(ALICE), 100000);
+
+		assert_noop!(
+			EvmAccountsModule::remove_secondary_evm_address(Origin::signed(ALICE), alice_address),
+			Error::<Runtime>::SecondaryAddressNotFound
+		);
+		assert_ok!(EvmAccountsModule::remove_secondary_evm_address(
+			Origin::signed(ALICE),
+			bob_address
+		));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::SecondaryEvmAddressRemoved {
+			account_id: ALICE,
+			evm_address: bob_address,
+		}));
+		assert_eq!(
+			EvmAccountsModule::secondary_evm_addresses(ALICE).into_inner(),
+			vec![charlie_address]
+		);
+		assert_ne!(EvmAddressMapping::<Runtime>::get_account_id(&bob_address), ALICE);
+		assert!(EvmAddressMapping::<Runtime>::is_linked(&ALICE, &bob_address));
+
+		// the removed address can be added back
+		assert_ok!(EvmAccountsModule::add_secondary_evm_address(
+			Origin::signed(ALICE),
+			bob_address,
+			EvmAccountsModule::eth_sign_secondary_address(&bob(), &ALICE)
+		));
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(bob_address), None);
+	});
+}
+
+#[test]
+fn add_secondary_evm_address_should_not_exceed_max() {
+	ExtBuilder::default().build().execute_with(|| {
+		let default_address = EvmAddressMapping::<Runtime>::get_or_create_evm_address(&BOB);
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_evm_address(&BOB),
+			Some(default_address)
+		);
+
+		for secret in [alice(), bob()] {
+			assert_ok!(EvmAccountsModule::add_secondary_evm_address(
+				Origin::signed(BOB),
+				EvmAccountsModule::eth_address(&secret),
+				EvmAccountsModule::eth_sign_secondary_address(&secret, &BOB)
+			));
+		}
+		assert_noop!(
+			EvmAccountsModule::add_secondary_evm_address(
+				Origin::signed(BOB),
+				EvmAccountsModule::eth_address(&charlie()),
+				EvmAccountsModule::eth_sign_secondary_address(&charlie(), &BOB)
+			),
+			Error::<Runtime>::TooManySecondaryAddresses
+		);
+	});
+}
+
+#[test]
+fn kill_account_removes_retired_evm_addresses() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_address = EvmAccountsModule::eth_address(&alice());
+		let bob_address = EvmAccountsModule::eth_address(&bob());
+		let charlie_address = EvmAccountsModule::eth_address(&charlie());
+
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			alice_address,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_ok!(EvmAccountsModule::add_secondary_evm_address(
+			Origin::signed(ALICE),
+			charlie_address,
+			EvmAccountsModule::eth_sign_secondary_address(&charlie(), &ALICE)
+		));
+		assert_ok!(EvmAccountsModule::remove_secondary_evm_address(
+			Origin::signed(ALICE),
+			charlie_address
+		));
+		assert_ok!(EvmAccountsModule::rotate_evm_address(
+			Origin::signed(ALICE),
+			bob_address,
+			EvmAccountsModule::eth_sign_rotation(&alice(), &ALICE, &bob_address),
+			EvmAccountsModule::eth_sign_rotation(&bob(), &ALICE, &bob_address)
+		));
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(alice_address), Some(ALICE));
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(charlie_address), Some(ALICE));
+
+		CallKillAccount::<Runtime>::on_killed_account(&ALICE);
+		assert_eq!(EvmAccountsModule::evm_addresses(ALICE), None);
+		assert_eq!(EvmAccountsModule::accounts(bob_address), None);
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(alice_address), None);
+		assert_eq!(EvmAccountsModule::retired_evm_addresses(charlie_address), None);
+		assert_eq!(AccountRetiredEvmAddresses::<Runtime>::iter_prefix(ALICE).count(), 0);
+		assert!(!EvmAddressMapping::<Runtime>::is_linked(&ALICE, &alice_address));
+
+		// the retired addresses can be claimed by other accounts
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(BOB),
+			alice_address,
+			EvmAccountsModule::eth_sign(&alice(), &BOB)
+		));
+	});
+}
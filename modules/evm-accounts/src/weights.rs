@@ -50,6 +50,13 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn claim_account() -> Weight;
 	fn claim_default_account() -> Weight;
+	fn claim_contract_account() -> Weight;
+	fn rotate_evm_address() -> Weight;
+	fn request_evm_address_rotation() -> Weight;
+	fn cancel_evm_address_rotation() -> Weight;
+	fn execute_evm_address_rotation() -> Weight;
+	fn add_secondary_evm_address() -> Weight;
+	fn remove_secondary_evm_address() -> Weight;
 }
 
 /// Weights for module_evm_accounts using the Acala node and recommended hardware.
@@ -65,6 +72,27 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	fn claim_contract_account() -> Weight {
+		crate::estimated_weights::claim_contract_account::<T::DbWeight>()
+	}
+	fn rotate_evm_address() -> Weight {
+		crate::estimated_weights::rotate_evm_address::<T::DbWeight>()
+	}
+	fn request_evm_address_rotation() -> Weight {
+		crate::estimated_weights::request_evm_address_rotation::<T::DbWeight>()
+	}
+	fn cancel_evm_address_rotation() -> Weight {
+		crate::estimated_weights::cancel_evm_address_rotation::<T::DbWeight>()
+	}
+	fn execute_evm_address_rotation() -> Weight {
+		crate::estimated_weights::execute_evm_address_rotation::<T::DbWeight>()
+	}
+	fn add_secondary_evm_address() -> Weight {
+		crate::estimated_weights::add_secondary_evm_address::<T::DbWeight>()
+	}
+	fn remove_secondary_evm_address() -> Weight {
+		crate::estimated_weights::remove_secondary_evm_address::<T::DbWeight>()
+	}
 }
 
 // For backwards compatibility and tests
@@ -79,4 +107,25 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	fn claim_contract_account() -> Weight {
+		crate::estimated_weights::claim_contract_account::<RocksDbWeight>()
+	}
+	fn rotate_evm_address() -> Weight {
+		crate::estimated_weights::rotate_evm_address::<RocksDbWeight>()
+	}
+	fn request_evm_address_rotation() -> Weight {
+		crate::estimated_weights::request_evm_address_rotation::<RocksDbWeight>()
+	}
+	fn cancel_evm_address_rotation() -> Weight {
+		crate::estimated_weights::cancel_evm_address_rotation::<RocksDbWeight>()
+	}
+	fn execute_evm_address_rotation() -> Weight {
+		crate::estimated_weights::execute_evm_address_rotation::<RocksDbWeight>()
+	}
+	fn add_secondary_evm_address() -> Weight {
+		crate::estimated_weights::add_secondary_evm_address::<RocksDbWeight>()
+	}
+	fn remove_secondary_evm_address() -> Weight {
+		crate::estimated_weights::remove_secondary_evm_address::<RocksDbWeight>()
+	}
 }
@@ -19,39 +19,55 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use ethereum_types::BigEndianHash;
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	pallet_prelude::*,
 };
 use module_evm::{ExitReason, ExitSucceed};
-use num_enum::{IntoPrimitive, TryFromPrimitive};
+use module_evm_utility_macro::keccak256;
 use primitive_types::H256;
 use primitives::{evm::EvmAddress, Balance};
 use sp_core::{H160, U256};
 use sp_runtime::{ArithmeticError, SaturatedConversion};
 use sp_std::vec::Vec;
 use support::{
-	evm::limits::{erc20, liquidation},
+	evm::{
+		abi::{AbiBytes, AbiCodec, AbiString, Function},
+		limits::{erc1271, erc20, liquidation, Limit},
+	},
 	EVMBridge as EVMBridgeTrait, ExecutionMode, InvokeContext, LiquidationEvmBridge as LiquidationEvmBridgeT, EVM,
 };
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::EVM as EVM<AccountIdOf<T>>>::Balance;
 
-#[module_evm_utility_macro::generate_function_selector]
-#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
-#[repr(u32)]
-pub enum Action {
-	Name = "name()",
-	Symbol = "symbol()",
-	Decimals = "decimals()",
-	TotalSupply = "totalSupply()",
-	BalanceOf = "balanceOf(address)",
-	Transfer = "transfer(address,uint256)",
-	Liquidate = "liquidate(address,address,uint256,uint256)",
-	OnCollateralTransfer = "onCollateralTransfer(address,uint256)",
-	OnRepaymentRefund = "onRepaymentRefund(address,uint256)",
+pub mod erc20_abi {
+	use super::*;
+
+	pub const NAME: Function<(), (AbiString,)> = Function::new(keccak256!("name()"));
+	pub const SYMBOL: Function<(), (AbiString,)> = Function::new(keccak256!("symbol()"));
+	pub const DECIMALS: Function<(), (U256,)> = Function::new(keccak256!("decimals()"));
+	pub const TOTAL_SUPPLY: Function<(), (U256,)> = Function::new(keccak256!("totalSupply()"));
+	pub const BALANCE_OF: Function<(H160,), (U256,)> = Function::new(keccak256!("balanceOf(address)"));
+	pub const TRANSFER: Function<(H160, U256), (bool,)> = Function::new(keccak256!("transfer(address,uint256)"));
+}
+
+pub mod erc1271_abi {
+	use super::*;
+
+	pub const IS_VALID_SIGNATURE: Function<(H256, AbiBytes), (H256,)> =
+		Function::new(keccak256!("isValidSignature(bytes32,bytes)"));
+}
+
+pub mod liquidation_abi {
+	use super::*;
+
+	pub const LIQUIDATE: Function<(H160, H160, U256, U256), ()> =
+		Function::new(keccak256!("liquidate(address,address,uint256,uint256)"));
+	pub const ON_COLLATERAL_TRANSFER: Function<(H160, U256), ()> =
+		Function::new(keccak256!("onCollateralTransfer(address,uint256)"));
+	pub const ON_REPAYMENT_REFUND: Function<(H160, U256), ()> =
+		Function::new(keccak256!("onRepaymentRefund(address,uint256)"));
 }
 
 mod mock;
@@ -100,152 +116,107 @@ impl<T: Config> EVMBridgeTrait<AccountIdOf<T>, BalanceOf<T>> for EVMBridge<T> {
 	// Calls the name method on an ERC20 contract using the given context
 	// and returns the token name.
 	fn name(context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
-		// ERC20.name method hash
-		let input = Into::<u32>::into(Action::Name).to_be_bytes().to_vec();
-
-		let info = T::EVM::execute(
-			context,
-			input,
-			Default::default(),
-			erc20::NAME.gas,
-			erc20::NAME.storage,
-			ExecutionMode::View,
-		)?;
-
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
-		Pallet::<T>::decode_string(info.value.as_slice().to_vec())
+		let (name,) = Self::call(context, erc20_abi::NAME, (), &erc20::NAME, ExecutionMode::View)?;
+		Ok(name.0)
 	}
 
 	// Calls the symbol method on an ERC20 contract using the given context
 	// and returns the token symbol.
 	fn symbol(context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
-		// ERC20.symbol method hash
-		let input = Into::<u32>::into(Action::Symbol).to_be_bytes().to_vec();
-
-		let info = T::EVM::execute(
-			context,
-			input,
-			Default::default(),
-			erc20::SYMBOL.gas,
-			erc20::SYMBOL.storage,
-			ExecutionMode::View,
-		)?;
-
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
-		Pallet::<T>::decode_string(info.value.as_slice().to_vec())
+		let (symbol,) = Self::call(context, erc20_abi::SYMBOL, (), &erc20::SYMBOL, ExecutionMode::View)?;
+		Ok(symbol.0)
 	}
 
 	// Calls the decimals method on an ERC20 contract using the given context
 	// and returns the decimals.
 	fn decimals(context: InvokeContext) -> Result<u8, DispatchError> {
-		// ERC20.decimals method hash
-		let input = Into::<u32>::into(Action::Decimals).to_be_bytes().to_vec();
-
-		let info = T::EVM::execute(
-			context,
-			input,
-			Default::default(),
-			erc20::DECIMALS.gas,
-			erc20::DECIMALS.storage,
-			ExecutionMode::View,
-		)?;
-
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
-
-		ensure!(info.value.len() == 32, Error::<T>::InvalidReturnValue);
-		let value: u8 = U256::from(info.value.as_slice())
-			.try_into()
-			.map_err(|_| ArithmeticError::Overflow)?;
+		let (decimals,) = Self::call(context, erc20_abi::DECIMALS, (), &erc20::DECIMALS, ExecutionMode::View)?;
+		let value: u8 = decimals.try_into().map_err(|_| ArithmeticError::Overflow)?;
 		Ok(value)
 	}
 
 	// Calls the totalSupply method on an ERC20 contract using the given context
 	// and returns the total supply.
 	fn total_supply(context: InvokeContext) -> Result<BalanceOf<T>, DispatchError> {
-		// ERC20.totalSupply method hash
-		let input = Into::<u32>::into(Action::TotalSupply).to_be_bytes().to_vec();
-
-		let info = T::EVM::execute(
+		let (supply,) = Self::call(
 			context,
-			input,
-			Default::default(),
-			erc20::TOTAL_SUPPLY.gas,
-			erc20::TOTAL_SUPPLY.storage,
+			erc20_abi::TOTAL_SUPPLY,
+			(),
+			&erc20::TOTAL_SUPPLY,
 			ExecutionMode::View,
 		)?;
-
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
-
-		ensure!(info.value.len() == 32, Error::<T>::InvalidReturnValue);
-		let value: u128 = U256::from(info.value.as_slice())
-			.try_into()
-			.map_err(|_| ArithmeticError::Overflow)?;
-		let supply = value.try_into().map_err(|_| ArithmeticError::Overflow)?;
-		Ok(supply)
+		Pallet::<T>::to_balance(supply)
 	}
 
 	// Calls the balanceOf method on an ERC20 contract using the given context
 	// and returns the address's balance.
 	fn balance_of(context: InvokeContext, address: H160) -> Result<BalanceOf<T>, DispatchError> {
-		// ERC20.balanceOf method hash
-		let mut input = Into::<u32>::into(Action::BalanceOf).to_be_bytes().to_vec();
-		// append address
-		input.extend_from_slice(H256::from(address).as_bytes());
-
-		let info = T::EVM::execute(
+		let output = Pallet::<T>::execute(
 			context,
-			input,
-			Default::default(),
-			erc20::BALANCE_OF.gas,
-			erc20::BALANCE_OF.storage,
+			erc20_abi::BALANCE_OF.encode_call(&(address,)),
+			&erc20::BALANCE_OF,
 			ExecutionMode::View,
 		)?;
 
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
-
-		let value: u128 = U256::from(info.value.as_slice())
-			.try_into()
-			.map_err(|_| ArithmeticError::Overflow)?;
-		let balance = value.try_into().map_err(|_| ArithmeticError::Overflow)?;
-		Ok(balance)
+		// the balance of an address without contract is zero
+		if output.is_empty() {
+			return Ok(Default::default());
+		}
+		let (balance,) = erc20_abi::BALANCE_OF
+			.decode_output(&output)
+			.map_err(|_| Error::<T>::InvalidReturnValue)?;
+		Pallet::<T>::to_balance(balance)
 	}
 
 	// Calls the transfer method on an ERC20 contract using the given context.
 	fn transfer(context: InvokeContext, to: H160, value: BalanceOf<T>) -> DispatchResult {
-		// ERC20.transfer method hash
-		let mut input = Into::<u32>::into(Action::Transfer).to_be_bytes().to_vec();
-		// append receiver address
-		input.extend_from_slice(H256::from(to).as_bytes());
-		// append amount to be transferred
-		input.extend_from_slice(H256::from_uint(&U256::from(value.saturated_into::<u128>())).as_bytes());
-
 		let storage_limit = if context.origin == Default::default() {
 			0
 		} else {
 			erc20::TRANSFER.storage
 		};
 
-		let info = T::EVM::execute(
+		// Check return value to make sure not calling on empty contracts.
+		let (success,) = Self::call(
 			context,
-			input,
-			Default::default(),
-			erc20::TRANSFER.gas,
-			storage_limit,
+			erc20_abi::TRANSFER,
+			(to, U256::from(value.saturated_into::<u128>())),
+			&Limit::new(erc20::TRANSFER.gas, storage_limit),
 			ExecutionMode::Execute,
 		)?;
+		ensure!(success, Error::<T>::InvalidReturnValue);
+		Ok(())
+	}
 
-		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
+	// Calls the isValidSignature method on an EIP-1271 contract using the given context
+	// and returns whether the signature is valid for the contract.
+	fn is_valid_signature(context: InvokeContext, hash: H256, signature: Vec<u8>) -> Result<bool, DispatchError> {
+		let output = Pallet::<T>::execute(
+			context,
+			erc1271_abi::IS_VALID_SIGNATURE.encode_call(&(hash, AbiBytes(signature))),
+			&erc1271::IS_VALID_SIGNATURE,
+			ExecutionMode::View,
+		)?;
 
-		// return value is true.
-		let mut bytes = [0u8; 32];
-		U256::from(1).to_big_endian(&mut bytes);
+		// the magic value is the method hash, padded to 32 bytes
+		let mut magic_value = [0u8; 32];
+		magic_value[..4].copy_from_slice(&erc1271_abi::IS_VALID_SIGNATURE.selector());
+		Ok(output == magic_value)
+	}
 
-		// Check return value to make sure not calling on empty contracts.
-		ensure!(
-			!info.value.is_empty() && info.value == bytes,
-			Error::<T>::InvalidReturnValue
-		);
-		Ok(())
+	// Calls the `function` on a contract using the given context and returns the decoded
+	// return values.
+	fn call<Args: AbiCodec, Ret: AbiCodec>(
+		context: InvokeContext,
+		function: Function<Args, Ret>,
+		args: Args,
+		limit: &Limit,
+		mode: ExecutionMode,
+	) -> Result<Ret, DispatchError> {
+		let output = Pallet::<T>::execute(context, function.encode_call(&args), limit, mode)?;
+		function
+			.decode_output(&output)
+			.map_err(|_| Error::<T>::InvalidReturnValue.into())
 	}
 
 	fn get_origin() -> Option<AccountIdOf<T>> {
@@ -267,62 +238,31 @@ impl<T: Config> LiquidationEvmBridgeT for LiquidationEvmBridge<T> {
 		amount: Balance,
 		min_repayment: Balance,
 	) -> DispatchResult {
-		// liquidation contract method hash
-		let mut input = Into::<u32>::into(Action::Liquidate).to_be_bytes().to_vec();
-
-		// append collateral ERC20 address
-		input.extend_from_slice(H256::from(collateral).as_bytes());
-		// append repay dest address
-		input.extend_from_slice(H256::from(repay_dest).as_bytes());
-		// append collateral amount
-		input.extend_from_slice(H256::from_uint(&U256::from(amount)).as_bytes());
-		// append minimum repayment amount
-		input.extend_from_slice(H256::from_uint(&U256::from(min_repayment)).as_bytes());
-
-		let info = T::EVM::execute(
+		EVMBridge::<T>::call(
 			context,
-			input,
-			Default::default(),
-			liquidation::LIQUIDATE.gas,
-			liquidation::LIQUIDATE.storage,
+			liquidation_abi::LIQUIDATE,
+			(collateral, repay_dest, amount.into(), min_repayment.into()),
+			&liquidation::LIQUIDATE,
 			ExecutionMode::Execute,
-		)?;
-
-		Pallet::<T>::handle_exit_reason(info.exit_reason)
+		)
 	}
 
 	fn on_collateral_transfer(context: InvokeContext, collateral: EvmAddress, amount: Balance) {
-		// liquidation contract method hash
-		let mut input = Into::<u32>::into(Action::OnCollateralTransfer).to_be_bytes().to_vec();
-		// append collateral ERC20 address
-		input.extend_from_slice(H256::from(collateral).as_bytes());
-		// append collateral amount
-		input.extend_from_slice(H256::from_uint(&U256::from(amount)).as_bytes());
-
-		let _ = T::EVM::execute(
+		let _ = EVMBridge::<T>::call(
 			context,
-			input,
-			Default::default(),
-			liquidation::ON_COLLATERAL_TRANSFER.gas,
-			liquidation::ON_COLLATERAL_TRANSFER.storage,
+			liquidation_abi::ON_COLLATERAL_TRANSFER,
+			(collateral, amount.into()),
+			&liquidation::ON_COLLATERAL_TRANSFER,
 			ExecutionMode::Execute,
 		);
 	}
 
 	fn on_repayment_refund(context: InvokeContext, collateral: EvmAddress, repayment: Balance) {
-		// liquidation contract method hash
-		let mut input = Into::<u32>::into(Action::OnRepaymentRefund).to_be_bytes().to_vec();
-		// append collateral ERC20 address
-		input.extend_from_slice(H256::from(collateral).as_bytes());
-		// append repayment amount
-		input.extend_from_slice(H256::from_uint(&U256::from(repayment)).as_bytes());
-
-		let _ = T::EVM::execute(
+		let _ = EVMBridge::<T>::call(
 			context,
-			input,
-			Default::default(),
-			liquidation::ON_REPAYMENT_REFUND.gas,
-			liquidation::ON_REPAYMENT_REFUND.storage,
+			liquidation_abi::ON_REPAYMENT_REFUND,
+			(collateral, repayment.into()),
+			&liquidation::ON_REPAYMENT_REFUND,
 			ExecutionMode::Execute,
 		);
 	}
@@ -340,28 +280,21 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn decode_string(output: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
-		// output is 32-byte aligned and consists of 3 parts:
-		// - part 1: 32 byte, the offset of its description is passed in the position of
-		// the corresponding parameter or return value.
-		// - part 2: 32 byte, string length
-		// - part 3: string data
-		ensure!(
-			output.len() >= 64 && output.len() % 32 == 0,
-			Error::<T>::InvalidReturnValue
-		);
-
-		let offset = U256::from_big_endian(&output[0..32]);
-		let length = U256::from_big_endian(&output[offset.as_usize()..offset.as_usize() + 32]);
-		ensure!(
-			// output is 32-byte aligned. ensure total_length >= offset + string length + string data length.
-			output.len() >= offset.as_usize() + 32 + length.as_usize(),
-			Error::<T>::InvalidReturnValue
-		);
+	fn execute(
+		context: InvokeContext,
+		input: Vec<u8>,
+		limit: &Limit,
+		mode: ExecutionMode,
+	) -> Result<Vec<u8>, DispatchError> {
+		let info = T::EVM::execute(context, input, Default::default(), limit.gas, limit.storage, mode)?;
 
-		let mut data = Vec::new();
-		data.extend_from_slice(&output[offset.as_usize() + 32..offset.as_usize() + 32 + length.as_usize()]);
+		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
+		Ok(info.value)
+	}
 
-		Ok(data.to_vec())
+	fn to_balance(value: U256) -> Result<BalanceOf<T>, DispatchError> {
+		let value: u128 = value.try_into().map_err(|_| ArithmeticError::Overflow)?;
+		let balance = value.try_into().map_err(|_| ArithmeticError::Overflow)?;
+		Ok(balance)
 	}
 }
@@ -88,6 +88,7 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub NetworkContractSource: EvmAddress = alice_evm_addr();
+	pub PredeployAddressRanges: Vec<(EvmAddress, EvmAddress)> = vec![(EvmAddress::zero(), EvmAddress::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -103,7 +104,10 @@ impl module_evm::Config for Runtime {
 	type TransferAll = ();
 	type NewContractExtraBytes = ConstU32<1>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = ConstU128<10>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 	type Event = Event;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
@@ -111,6 +115,7 @@ impl module_evm::Config for Runtime {
 	type ChargeTransactionPayment = support::mocks::MockReservedTransactionPayment<Balances>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
@@ -248,6 +253,18 @@ pub fn deploy_liquidation_err_contracts() {
 	));
 }
 
+/// Deploys a contract which accepts any signature, its runtime code returns the EIP-1271 magic
+/// value `0x1626ba7e` for any call.
+pub fn deploy_signature_validator_contract() {
+	let code = hex::decode("601080600b6000396000f3631626ba7e60e01b60005260206000f3").unwrap();
+	assert_ok!(EVM::create(Origin::signed(alice()), code, 0, 2_100_000, 10000, vec![]));
+
+	assert_ok!(EVM::publish_free(
+		Origin::signed(CouncilAccount::get()),
+		erc20_address()
+	));
+}
+
 impl ExtBuilder {
 	pub fn balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
 		self.balances = balances;
@@ -353,3 +353,121 @@ fn liquidation_err_fails_as_expected() {
 			);
 		});
 }
+
+#[test]
+fn is_valid_signature_works() {
+	ExtBuilder::default()
+		.balances(vec![(alice(), 1_000_000_000_000)])
+		.build()
+		.execute_with(|| {
+			let context = InvokeContext {
+				contract: erc20_address(),
+				sender: Default::default(),
+				origin: Default::default(),
+			};
+			let hash = H256::repeat_byte(1);
+
+			// not a contract
+			assert_eq!(
+				EVMBridge::<Runtime>::is_valid_signature(context, hash, vec![1u8; 65]),
+				Ok(false)
+			);
+
+			deploy_signature_validator_contract();
+			assert_eq!(
+				EVMBridge::<Runtime>::is_valid_signature(context, hash, vec![1u8; 65]),
+				Ok(true)
+			);
+			assert_eq!(
+				EVMBridge::<Runtime>::is_valid_signature(context, hash, vec![]),
+				Ok(true)
+			);
+		});
+
+	ExtBuilder::default()
+		.balances(vec![(alice(), 1_000_000_000_000)])
+		.build()
+		.execute_with(|| {
+			// erc20 doesn't implement EIP-1271
+			deploy_contracts();
+			assert_err!(
+				EVMBridge::<Runtime>::is_valid_signature(
+					InvokeContext {
+						contract: erc20_address(),
+						sender: Default::default(),
+						origin: Default::default(),
+					},
+					H256::repeat_byte(1),
+					vec![1u8; 65]
+				),
+				Error::<Runtime>::ExecutionRevert
+			);
+		});
+}
+
+#[test]
+fn function_selectors_match_signatures() {
+	assert!(erc20_abi::NAME.matches("name"));
+	assert!(erc20_abi::SYMBOL.matches("symbol"));
+	assert!(erc20_abi::DECIMALS.matches("decimals"));
+	assert!(erc20_abi::TOTAL_SUPPLY.matches("totalSupply"));
+	assert!(erc20_abi::BALANCE_OF.matches("balanceOf"));
+	assert!(erc20_abi::TRANSFER.matches("transfer"));
+	assert!(erc1271_abi::IS_VALID_SIGNATURE.matches("isValidSignature"));
+	assert!(liquidation_abi::LIQUIDATE.matches("liquidate"));
+	assert!(liquidation_abi::ON_COLLATERAL_TRANSFER.matches("onCollateralTransfer"));
+	assert!(liquidation_abi::ON_REPAYMENT_REFUND.matches("onRepaymentRefund"));
+}
+
+#[test]
+fn call_works() {
+	ExtBuilder::default()
+		.balances(vec![(alice(), 1_000_000_000_000)])
+		.build()
+		.execute_with(|| {
+			deploy_contracts();
+			let context = InvokeContext {
+				contract: erc20_address(),
+				sender: Default::default(),
+				origin: Default::default(),
+			};
+
+			assert_eq!(
+				EVMBridge::<Runtime>::call(
+					context,
+					erc20_abi::BALANCE_OF,
+					(alice_evm_addr(),),
+					&erc20::BALANCE_OF,
+					ExecutionMode::View
+				),
+				Ok((U256::from(ALICE_BALANCE),))
+			);
+
+			// the return values don't match
+			let balance_of: Function<(H160,), (U256, AbiString)> = Function::new(keccak256!("balanceOf(address)"));
+			assert_err!(
+				EVMBridge::<Runtime>::call(
+					context,
+					balance_of,
+					(alice_evm_addr(),),
+					&erc20::BALANCE_OF,
+					ExecutionMode::View
+				),
+				Error::<Runtime>::InvalidReturnValue
+			);
+		});
+}
+
+#[test]
+fn selectors_match_args() {
+	assert!(erc20_abi::NAME.matches("name"));
+	assert!(erc20_abi::SYMBOL.matches("symbol"));
+	assert!(erc20_abi::DECIMALS.matches("decimals"));
+	assert!(erc20_abi::TOTAL_SUPPLY.matches("totalSupply"));
+	assert!(erc20_abi::BALANCE_OF.matches("balanceOf"));
+	assert!(erc20_abi::TRANSFER.matches("transfer"));
+	assert!(erc1271_abi::IS_VALID_SIGNATURE.matches("isValidSignature"));
+	assert!(liquidation_abi::LIQUIDATE.matches("liquidate"));
+	assert!(liquidation_abi::ON_COLLATERAL_TRANSFER.matches("onCollateralTransfer"));
+	assert!(liquidation_abi::ON_REPAYMENT_REFUND.matches("onRepaymentRefund"));
+}
@@ -0,0 +1,400 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # EVM Paymaster Module
+//!
+//! ## Overview
+//!
+//! EVM Paymaster module allows EVM contracts, the paymasters, to sponsor the EVM calls of the
+//! users. The maintainer of a contract registers it as a paymaster, and the fees of the sponsored
+//! calls are paid from the escrow account of the paymaster, which anyone can deposit to and only
+//! the maintainer can withdraw from.
+//!
+//! When a sponsored call is validated and dispatched, the paymaster is asked by a view call of
+//! `validateSponsorship(address,address,bytes,uint256,uint256,uint256)` with the user, the target
+//! contract, the input, the value, the gas limit and the storage limit of the call, and the call
+//! is sponsored only if it returns `true`. Otherwise the extrinsic fails and the user pays the
+//! fee as usual.
+//!
+//! The escrow pays the transaction fee of the sponsored call, it's charged by the transaction
+//! payment when the transaction is validated, with `EvmPaymaster::sponsor_of` as the fee payer,
+//! and the fee of the unused gas is refunded to the escrow after the call. So the user can
+//! submit the sponsored call without any balance. The escrow is also the origin of the call, so
+//! it reserves the storage deposit of the storage limit, and the unused deposit and the deposit
+//! refunded by the freed storage go back to it. The `value` of the call is still transferred
+//! from the user.
+//!
+//! The escrow account is derived from the paymaster address, so the storage deposits refunded
+//! after the paymaster is unregistered can be withdrawn by registering it again.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, IsType},
+	transactional, PalletId,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use module_evm_utility_macro::keccak256;
+use module_support::{
+	evm::{
+		abi::{AbiBytes, Function},
+		limits,
+	},
+	AddressMapping, EVMBridge, EVMManager, ExecutionMode, InvokeContext, EVM,
+};
+use primitives::{evm::EvmAddress, Balance};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{AccountIdConversion, Convert, UniqueSaturatedInto};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub mod paymaster_abi {
+	use super::*;
+
+	pub const VALIDATE_SPONSORSHIP: Function<(H160, H160, AbiBytes, U256, U256, U256), (bool,)> = Function::new(
+		keccak256!("validateSponsorship(address,address,bytes,uint256,uint256,uint256)"),
+	);
+}
+
+/// Helper method to calculate `sponsored_call` weight.
+fn sponsored_call_weight<T: Config>(gas: u64) -> Weight {
+	T::WeightInfo::sponsored_call()
+		// the validation is charged by its gas limit, it runs when the fee is charged by the
+		// transaction payment and again in the dispatch
+		.saturating_add(T::GasToWeight::convert(
+			gas.saturating_add(limits::paymaster::VALIDATE_SPONSORSHIP.gas.saturating_mul(2)),
+		))
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The native currency paying the sponsored calls.
+		type Currency: Currency<Self::AccountId, Balance = Balance>;
+
+		/// Mapping from address to account id.
+		type AddressMapping: AddressMapping<Self::AccountId>;
+
+		/// The EVM to execute the sponsored calls.
+		type EVM: EVM<Self::AccountId, Balance = Balance>;
+
+		/// The EVM bridge to validate the sponsorships with the paymasters.
+		type EVMBridge: EVMBridge<Self::AccountId, Balance>;
+
+		/// The EVM manager to query the maintainers of the paymasters.
+		type EVMManager: EVMManager<Self::AccountId, Balance>;
+
+		/// Convert gas to weight.
+		type GasToWeight: Convert<u64, Weight>;
+
+		/// The module id, the escrow accounts of the paymasters are its sub accounts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The paymaster is not registered.
+		PaymasterNotRegistered,
+		/// The paymaster is already registered.
+		PaymasterAlreadyRegistered,
+		/// The caller is not the maintainer of the paymaster.
+		NoPermission,
+		/// The account has no EVM address.
+		AddressNotMapped,
+		/// The paymaster rejected the call, or the validation failed.
+		SponsorshipRejected,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A paymaster is registered.
+		PaymasterRegistered {
+			paymaster: EvmAddress,
+			escrow: T::AccountId,
+		},
+		/// A paymaster is unregistered, and its escrow is refunded to the maintainer.
+		PaymasterUnregistered { paymaster: EvmAddress, refund: Balance },
+		/// Deposited to the escrow of a paymaster.
+		Deposited {
+			paymaster: EvmAddress,
+			who: T::AccountId,
+			amount: Balance,
+		},
+		/// Withdrawn from the escrow of a paymaster.
+		Withdrawn {
+			paymaster: EvmAddress,
+			who: T::AccountId,
+			amount: Balance,
+		},
+		/// A call is sponsored by a paymaster.
+		CallSponsored {
+			paymaster: EvmAddress,
+			who: T::AccountId,
+			contract: EvmAddress,
+			used_gas: u64,
+			used_storage: i32,
+		},
+	}
+
+	/// The escrow accounts of the registered paymasters.
+	///
+	/// Paymasters: map EvmAddress => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn paymasters)]
+	pub type Paymasters<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::AccountId, OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a contract as a paymaster. Only the maintainer of the contract can register it.
+		///
+		/// - `paymaster`: the paymaster contract address
+		#[pallet::weight(T::WeightInfo::register_paymaster())]
+		pub fn register_paymaster(origin: OriginFor<T>, paymaster: EvmAddress) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_maintainer(&who, paymaster)?;
+			ensure!(
+				!Paymasters::<T>::contains_key(paymaster),
+				Error::<T>::PaymasterAlreadyRegistered
+			);
+
+			let escrow = Self::escrow_account_id(paymaster);
+			// the escrow is the origin of the sponsored calls
+			T::AddressMapping::get_or_create_evm_address(&escrow);
+			Paymasters::<T>::insert(paymaster, &escrow);
+
+			Self::deposit_event(Event::PaymasterRegistered { paymaster, escrow });
+			Ok(())
+		}
+
+		/// Unregister a paymaster, and refund all the free balance of its escrow to the
+		/// maintainer. Only the maintainer of the contract can unregister it.
+		///
+		/// - `paymaster`: the paymaster contract address
+		#[pallet::weight(T::WeightInfo::unregister_paymaster())]
+		#[transactional]
+		pub fn unregister_paymaster(origin: OriginFor<T>, paymaster: EvmAddress) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_maintainer(&who, paymaster)?;
+			let escrow = Paymasters::<T>::take(paymaster).ok_or(Error::<T>::PaymasterNotRegistered)?;
+
+			let refund = T::Currency::free_balance(&escrow);
+			T::Currency::transfer(&escrow, &who, refund, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::PaymasterUnregistered { paymaster, refund });
+			Ok(())
+		}
+
+		/// Deposit to the escrow of a paymaster.
+		///
+		/// - `paymaster`: the paymaster contract address
+		/// - `amount`: the amount to deposit
+		#[pallet::weight(T::WeightInfo::deposit())]
+		pub fn deposit(
+			origin: OriginFor<T>,
+			paymaster: EvmAddress,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let escrow = Self::paymasters(paymaster).ok_or(Error::<T>::PaymasterNotRegistered)?;
+
+			T::Currency::transfer(&who, &escrow, amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::Deposited { paymaster, who, amount });
+			Ok(())
+		}
+
+		/// Withdraw from the escrow of a paymaster. Only the maintainer of the paymaster can
+		/// withdraw, and the escrow is kept alive.
+		///
+		/// - `paymaster`: the paymaster contract address
+		/// - `amount`: the amount to withdraw
+		#[pallet::weight(T::WeightInfo::withdraw())]
+		pub fn withdraw(
+			origin: OriginFor<T>,
+			paymaster: EvmAddress,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_maintainer(&who, paymaster)?;
+			let escrow = Self::paymasters(paymaster).ok_or(Error::<T>::PaymasterNotRegistered)?;
+
+			T::Currency::transfer(&escrow, &who, amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::Withdrawn { paymaster, who, amount });
+			Ok(())
+		}
+
+		/// Issue an EVM call operation sponsored by a paymaster. The fees and the storage deposit
+		/// of the call are paid by the escrow of the paymaster, the signer pays nothing if the
+		/// paymaster accepts the call.
+		///
+		/// - `paymaster`: the paymaster contract address
+		/// - `target`: the contract address to call
+		/// - `input`: the data supplied for the call
+		/// - `value`: the amount sent for payable calls, paid by the signer
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		#[pallet::weight(sponsored_call_weight::<T>(*gas_limit))]
+		#[transactional]
+		pub fn sponsored_call(
+			origin: OriginFor<T>,
+			paymaster: EvmAddress,
+			target: EvmAddress,
+			input: Vec<u8>,
+			#[pallet::compact] value: Balance,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+			// the fee is charged from the escrow by the transaction payment, see `sponsor_of`
+			let escrow =
+				Self::validate_sponsorship(source, paymaster, target, &input, value, gas_limit, storage_limit)?;
+
+			// the escrow is the origin of the call, so the storage deposit is paid by it, also for
+			// the storage charged by the precompiles
+			let escrow_address = T::AddressMapping::get_or_create_evm_address(&escrow);
+			T::EVM::set_origin(escrow.clone());
+			let result = T::EVM::execute(
+				InvokeContext {
+					contract: target,
+					sender: source,
+					origin: escrow_address,
+				},
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				ExecutionMode::Execute,
+			);
+			T::EVM::set_origin(who.clone());
+			let info = result?;
+
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+
+			Self::deposit_event(Event::CallSponsored {
+				paymaster,
+				who,
+				contract: target,
+				used_gas,
+				used_storage: if info.exit_reason.is_succeed() {
+					info.used_storage
+				} else {
+					Default::default()
+				},
+			});
+
+			// the fee of the unused gas is refunded to the payer of the fee
+			Ok(Some(sponsored_call_weight::<T>(used_gas)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The escrow account of the paymaster.
+	pub fn escrow_account_id(paymaster: EvmAddress) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(paymaster)
+	}
+
+	/// The escrow paying the fee of the `sponsored_call` signed by `who`, if the paymaster
+	/// accepts it. Used by the transaction payment to find the fee payer.
+	pub fn sponsor_of(who: &T::AccountId, call: &Call<T>) -> Option<T::AccountId> {
+		match call {
+			Call::sponsored_call {
+				paymaster,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+			} => {
+				let source = T::AddressMapping::get_or_create_evm_address(who);
+				Self::validate_sponsorship(source, *paymaster, *target, input, *value, *gas_limit, *storage_limit).ok()
+			}
+			_ => None,
+		}
+	}
+
+	/// Ask the paymaster to sponsor the call, returns the escrow of the paymaster if accepted.
+	fn validate_sponsorship(
+		source: EvmAddress,
+		paymaster: EvmAddress,
+		target: EvmAddress,
+		input: &[u8],
+		value: Balance,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<T::AccountId, DispatchError> {
+		let escrow = Self::paymasters(paymaster).ok_or(Error::<T>::PaymasterNotRegistered)?;
+
+		let (accepted,) = T::EVMBridge::call(
+			InvokeContext {
+				contract: paymaster,
+				sender: Default::default(),
+				origin: Default::default(),
+			},
+			paymaster_abi::VALIDATE_SPONSORSHIP,
+			(
+				source,
+				target,
+				AbiBytes(input.to_vec()),
+				value.into(),
+				gas_limit.into(),
+				storage_limit.into(),
+			),
+			&limits::paymaster::VALIDATE_SPONSORSHIP,
+			ExecutionMode::View,
+		)
+		.map_err(|_| Error::<T>::SponsorshipRejected)?;
+		ensure!(accepted, Error::<T>::SponsorshipRejected);
+
+		Ok(escrow)
+	}
+
+	fn ensure_maintainer(who: &T::AccountId, paymaster: EvmAddress) -> DispatchResult {
+		let maintainer = T::EVMManager::query_maintainer(paymaster)?;
+		let address = T::AddressMapping::get_evm_address(who).ok_or(Error::<T>::AddressNotMapped)?;
+		ensure!(address == maintainer, Error::<T>::NoPermission);
+		Ok(())
+	}
+}
@@ -0,0 +1,341 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the evm-paymaster module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Everything},
+};
+use module_evm_utility::evm::{ExitReason, ExitRevert, ExitSucceed};
+use module_support::{
+	evm::{
+		abi::{self, AbiCodec},
+		limits::Limit,
+	},
+	mocks::MockAddressMapping,
+};
+use primitives::evm::{CallInfo, ContractVerification};
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{Identity, IdentityLookup},
+	DispatchError, DispatchResult,
+};
+use std::cell::RefCell;
+
+pub type AccountId = AccountId32;
+pub type BlockNumber = u64;
+
+mod evm_paymaster {
+	pub use super::super::*;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+pub fn alice_evm_addr() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x1111)
+}
+
+pub fn alice() -> AccountId {
+	MockAddressMapping::get_account_id(&alice_evm_addr())
+}
+
+pub fn bob_evm_addr() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x2222)
+}
+
+pub fn bob() -> AccountId {
+	MockAddressMapping::get_account_id(&bob_evm_addr())
+}
+
+/// An account without EVM address.
+pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
+
+/// The paymaster maintained by alice, accepting the calls to `target()` and `reverting_target()`
+/// within 1_000_000 gas.
+pub fn paymaster() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x4337)
+}
+
+/// The contract maintained by alice, reverting the validation.
+pub fn reverting_paymaster() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x4338)
+}
+
+pub fn target() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x1000)
+}
+
+/// The contract reverting the calls.
+pub fn reverting_target() -> EvmAddress {
+	EvmAddress::from_low_u64_be(0x1001)
+}
+
+pub struct MockEVMManager;
+impl EVMManager<AccountId, Balance> for MockEVMManager {
+	fn query_new_contract_extra_bytes() -> u32 {
+		unimplemented!()
+	}
+	fn query_storage_deposit_per_byte() -> Balance {
+		unimplemented!()
+	}
+	fn query_maintainer(contract: H160) -> Result<H160, DispatchError> {
+		if contract == paymaster() || contract == reverting_paymaster() {
+			Ok(alice_evm_addr())
+		} else {
+			Err(DispatchError::Other("contract not found"))
+		}
+	}
+	fn query_verification(_contract: H160) -> Result<ContractVerification, DispatchError> {
+		unimplemented!()
+	}
+	fn query_developer_deposit() -> Balance {
+		unimplemented!()
+	}
+	fn query_publication_fee() -> Balance {
+		unimplemented!()
+	}
+	fn transfer_maintainer(_from: AccountId, _contract: H160, _new_maintainer: H160) -> DispatchResult {
+		unimplemented!()
+	}
+	fn publish_contract_precompile(_who: AccountId, _contract: H160) -> DispatchResult {
+		unimplemented!()
+	}
+	fn query_developer_status(_who: AccountId) -> bool {
+		unimplemented!()
+	}
+	fn enable_account_contract_development(_who: AccountId) -> DispatchResult {
+		unimplemented!()
+	}
+	fn disable_account_contract_development(_who: AccountId) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+pub struct MockEVMBridge;
+impl EVMBridge<AccountId, Balance> for MockEVMBridge {
+	fn name(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn symbol(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn decimals(_context: InvokeContext) -> Result<u8, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn total_supply(_context: InvokeContext) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn balance_of(_context: InvokeContext, _address: EvmAddress) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn is_valid_signature(_context: InvokeContext, _hash: H256, _signature: Vec<u8>) -> Result<bool, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn call<Args: AbiCodec, Ret: AbiCodec>(
+		context: InvokeContext,
+		function: Function<Args, Ret>,
+		args: Args,
+		_limit: &Limit,
+		mode: ExecutionMode,
+	) -> Result<Ret, DispatchError> {
+		assert_eq!(mode, ExecutionMode::View);
+		if context.contract != paymaster() {
+			return Err(DispatchError::Other("execution revert"));
+		}
+
+		let (_, contract, _, _, gas_limit, _) = paymaster_abi::VALIDATE_SPONSORSHIP
+			.decode_call(&function.encode_call(&args))
+			.map_err(|_| DispatchError::Other("execution revert"))?;
+		let accepted = (contract == target() || contract == reverting_target()) && gas_limit <= U256::from(1_000_000);
+		abi::decode(&abi::encode(&(accepted,))).map_err(|_| DispatchError::Other("invalid return value"))
+	}
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+	fn set_origin(_origin: AccountId) {}
+}
+
+thread_local! {
+	static ORIGIN: RefCell<Option<AccountId>> = RefCell::new(None);
+	pub static EXECUTED: RefCell<Option<(InvokeContext, Option<AccountId>)>> = RefCell::new(None);
+}
+
+/// Uses half of the gas limit, and charges the storage limit from the origin as the deposit of the
+/// successful calls.
+pub struct MockEVM;
+impl EVM<AccountId> for MockEVM {
+	type Balance = Balance;
+
+	fn execute(
+		context: InvokeContext,
+		_input: Vec<u8>,
+		value: Balance,
+		gas_limit: u64,
+		storage_limit: u32,
+		_mode: ExecutionMode,
+	) -> Result<CallInfo, DispatchError> {
+		let origin = Self::get_origin();
+		EXECUTED.with(|v| *v.borrow_mut() = Some((context, origin.clone())));
+
+		let contract = MockAddressMapping::get_account_id(&context.contract);
+		let exit_reason = if context.contract == reverting_target() {
+			ExitReason::Revert(ExitRevert::Reverted)
+		} else {
+			let origin = origin.ok_or(DispatchError::Other("no origin"))?;
+			<Balances as Currency<_>>::transfer(
+				&origin,
+				&contract,
+				storage_limit.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			<Balances as Currency<_>>::transfer(
+				&MockAddressMapping::get_account_id(&context.sender),
+				&contract,
+				value,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		};
+
+		Ok(CallInfo {
+			exit_reason,
+			value: vec![],
+			used_gas: (gas_limit / 2).into(),
+			used_storage: storage_limit as i32,
+			logs: vec![],
+		})
+	}
+
+	fn get_origin() -> Option<AccountId> {
+		ORIGIN.with(|v| v.borrow().clone())
+	}
+
+	fn set_origin(origin: AccountId) {
+		ORIGIN.with(|v| *v.borrow_mut() = Some(origin));
+	}
+}
+
+parameter_types! {
+	pub const EvmPaymasterPalletId: PalletId = PalletId(*b"aca/evmp");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type AddressMapping = MockAddressMapping;
+	type EVM = MockEVM;
+	type EVMBridge = MockEVMBridge;
+	type EVMManager = MockEVMManager;
+	type GasToWeight = Identity;
+	type PalletId = EvmPaymasterPalletId;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		EvmPaymaster: evm_paymaster::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+pub struct ExtBuilder();
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self()
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				(alice(), 1_000_000_000_000),
+				(bob(), 1_000),
+				(CHARLIE, 1_000_000_000_000),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			ORIGIN.with(|v| *v.borrow_mut() = None);
+			EXECUTED.with(|v| *v.borrow_mut() = None);
+		});
+		ext
+	}
+}
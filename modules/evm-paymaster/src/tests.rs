@@ -0,0 +1,331 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the evm-paymaster module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	alice, alice_evm_addr, bob, bob_evm_addr, paymaster, reverting_paymaster, reverting_target, target, Balances,
+	Event, EvmPaymaster, ExtBuilder, MockEVM, Origin, Runtime, System, CHARLIE, EXECUTED,
+};
+use module_support::mocks::MockAddressMapping;
+
+fn escrow() -> <Runtime as frame_system::Config>::AccountId {
+	EvmPaymaster::escrow_account_id(paymaster())
+}
+
+#[test]
+fn register_paymaster_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmPaymaster::register_paymaster(Origin::signed(bob()), paymaster()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EvmPaymaster::register_paymaster(Origin::signed(CHARLIE), paymaster()),
+			Error::<Runtime>::AddressNotMapped
+		);
+		assert_noop!(
+			EvmPaymaster::register_paymaster(Origin::signed(alice()), target()),
+			DispatchError::Other("contract not found")
+		);
+
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::PaymasterRegistered {
+			paymaster: paymaster(),
+			escrow: escrow(),
+		}));
+		assert_eq!(EvmPaymaster::paymasters(paymaster()), Some(escrow()));
+		assert_ne!(escrow(), EvmPaymaster::escrow_account_id(reverting_paymaster()));
+
+		assert_noop!(
+			EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()),
+			Error::<Runtime>::PaymasterAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn deposit_and_withdraw_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmPaymaster::deposit(Origin::signed(CHARLIE), paymaster(), 1_000),
+			Error::<Runtime>::PaymasterNotRegistered
+		);
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+
+		// anyone can deposit
+		assert_ok!(EvmPaymaster::deposit(Origin::signed(CHARLIE), paymaster(), 1_000));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::Deposited {
+			paymaster: paymaster(),
+			who: CHARLIE,
+			amount: 1_000,
+		}));
+		assert_eq!(Balances::free_balance(escrow()), 1_000);
+
+		// only the maintainer can withdraw
+		assert_noop!(
+			EvmPaymaster::withdraw(Origin::signed(bob()), paymaster(), 100),
+			Error::<Runtime>::NoPermission
+		);
+		let alice_balance = Balances::free_balance(alice());
+		assert_ok!(EvmPaymaster::withdraw(Origin::signed(alice()), paymaster(), 100));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::Withdrawn {
+			paymaster: paymaster(),
+			who: alice(),
+			amount: 100,
+		}));
+		assert_eq!(Balances::free_balance(escrow()), 900);
+		assert_eq!(Balances::free_balance(alice()), alice_balance + 100);
+
+		// the escrow is kept alive
+		assert_noop!(
+			EvmPaymaster::withdraw(Origin::signed(alice()), paymaster(), 900),
+			pallet_balances::Error::<Runtime>::KeepAlive
+		);
+	});
+}
+
+#[test]
+fn unregister_paymaster_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmPaymaster::unregister_paymaster(Origin::signed(alice()), paymaster()),
+			Error::<Runtime>::PaymasterNotRegistered
+		);
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_ok!(EvmPaymaster::deposit(Origin::signed(CHARLIE), paymaster(), 1_000));
+
+		assert_noop!(
+			EvmPaymaster::unregister_paymaster(Origin::signed(bob()), paymaster()),
+			Error::<Runtime>::NoPermission
+		);
+
+		let alice_balance = Balances::free_balance(alice());
+		assert_ok!(EvmPaymaster::unregister_paymaster(Origin::signed(alice()), paymaster()));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::PaymasterUnregistered {
+			paymaster: paymaster(),
+			refund: 1_000,
+		}));
+		assert_eq!(EvmPaymaster::paymasters(paymaster()), None);
+		assert_eq!(Balances::free_balance(escrow()), 0);
+		assert_eq!(Balances::free_balance(alice()), alice_balance + 1_000);
+
+		// the same escrow is used when registered again
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_eq!(EvmPaymaster::paymasters(paymaster()), Some(escrow()));
+	});
+}
+
+#[test]
+fn sponsored_call_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_ok!(EvmPaymaster::deposit(
+			Origin::signed(CHARLIE),
+			paymaster(),
+			100_000_000_000
+		));
+
+		let post_info = EvmPaymaster::sponsored_call(
+			Origin::signed(bob()),
+			paymaster(),
+			target(),
+			vec![1; 10],
+			100,
+			1_000_000,
+			64,
+		)
+		.unwrap();
+		// the unused gas is refunded to the escrow by the transaction payment
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(post_info.actual_weight, Some(sponsored_call_weight::<Runtime>(500_000)));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::CallSponsored {
+			paymaster: paymaster(),
+			who: bob(),
+			contract: target(),
+			used_gas: 500_000,
+			used_storage: 64,
+		}));
+
+		// the call is sent by the user, and originates from the escrow
+		let (context, origin) = EXECUTED.with(|v| v.borrow_mut().take()).unwrap();
+		assert_eq!(context.contract, target());
+		assert_eq!(context.sender, bob_evm_addr());
+		assert_eq!(context.origin, MockAddressMapping::get_or_create_evm_address(&escrow()));
+		assert_eq!(origin, Some(escrow()));
+		assert_eq!(MockEVM::get_origin(), Some(bob()));
+
+		// the escrow pays the storage deposit, the user pays the value only
+		assert_eq!(Balances::free_balance(escrow()), 100_000_000_000 - 64);
+		assert_eq!(Balances::free_balance(bob()), 1_000 - 100);
+		assert_eq!(
+			Balances::free_balance(MockAddressMapping::get_account_id(&target())),
+			100 + 64
+		);
+	});
+}
+
+#[test]
+fn sponsored_call_works_on_revert() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_ok!(EvmPaymaster::deposit(
+			Origin::signed(CHARLIE),
+			paymaster(),
+			100_000_000_000
+		));
+
+		let post_info = EvmPaymaster::sponsored_call(
+			Origin::signed(bob()),
+			paymaster(),
+			reverting_target(),
+			vec![],
+			100,
+			200_000,
+			64,
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(post_info.actual_weight, Some(sponsored_call_weight::<Runtime>(100_000)));
+		System::assert_last_event(Event::EvmPaymaster(crate::Event::CallSponsored {
+			paymaster: paymaster(),
+			who: bob(),
+			contract: reverting_target(),
+			used_gas: 100_000,
+			used_storage: 0,
+		}));
+
+		// no storage deposit and value are paid
+		assert_eq!(Balances::free_balance(escrow()), 100_000_000_000);
+		assert_eq!(Balances::free_balance(bob()), 1_000);
+	});
+}
+
+#[test]
+fn sponsored_call_should_not_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmPaymaster::sponsored_call(Origin::signed(bob()), paymaster(), target(), vec![], 0, 1_000_000, 0),
+			Error::<Runtime>::PaymasterNotRegistered
+		);
+
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_ok!(EvmPaymaster::register_paymaster(
+			Origin::signed(alice()),
+			reverting_paymaster()
+		));
+		assert_ok!(EvmPaymaster::deposit(Origin::signed(CHARLIE), paymaster(), 1_000));
+
+		// rejected by the paymaster
+		assert_noop!(
+			EvmPaymaster::sponsored_call(Origin::signed(bob()), paymaster(), target(), vec![], 0, 2_000_000, 0),
+			Error::<Runtime>::SponsorshipRejected
+		);
+		assert_noop!(
+			EvmPaymaster::sponsored_call(
+				Origin::signed(bob()),
+				paymaster(),
+				alice_evm_addr(),
+				vec![],
+				0,
+				1_000_000,
+				0
+			),
+			Error::<Runtime>::SponsorshipRejected
+		);
+
+		// the validation reverts
+		assert_noop!(
+			EvmPaymaster::sponsored_call(
+				Origin::signed(bob()),
+				reverting_paymaster(),
+				target(),
+				vec![],
+				0,
+				1_000_000,
+				0
+			),
+			Error::<Runtime>::SponsorshipRejected
+		);
+	});
+}
+
+#[test]
+fn sponsor_of_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let sponsored_call = |paymaster, gas_limit| crate::Call::<Runtime>::sponsored_call {
+			paymaster,
+			target: target(),
+			input: vec![],
+			value: 0,
+			gas_limit,
+			storage_limit: 0,
+		};
+		assert_eq!(
+			EvmPaymaster::sponsor_of(&bob(), &sponsored_call(paymaster(), 1_000_000)),
+			None
+		);
+
+		assert_ok!(EvmPaymaster::register_paymaster(Origin::signed(alice()), paymaster()));
+		assert_ok!(EvmPaymaster::register_paymaster(
+			Origin::signed(alice()),
+			reverting_paymaster()
+		));
+
+		// the escrow pays the fee of the accepted call
+		assert_eq!(
+			EvmPaymaster::sponsor_of(&bob(), &sponsored_call(paymaster(), 1_000_000)),
+			Some(escrow())
+		);
+		assert_eq!(
+			EvmPaymaster::sponsor_of(&CHARLIE, &sponsored_call(paymaster(), 1_000_000)),
+			Some(escrow())
+		);
+
+		// the signer pays the fee of the rejected call
+		assert_eq!(
+			EvmPaymaster::sponsor_of(&bob(), &sponsored_call(paymaster(), 2_000_000)),
+			None
+		);
+		assert_eq!(
+			EvmPaymaster::sponsor_of(&bob(), &sponsored_call(reverting_paymaster(), 1_000_000)),
+			None
+		);
+
+		// and the other calls
+		assert_eq!(
+			EvmPaymaster::sponsor_of(
+				&bob(),
+				&crate::Call::<Runtime>::deposit {
+					paymaster: paymaster(),
+					amount: 1_000
+				}
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn selectors_match_args() {
+	assert!(paymaster_abi::VALIDATE_SPONSORSHIP.matches("validateSponsorship"));
+}
@@ -19,16 +19,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use primitives::evm::{AccessListItem, BlockLimits, CallInfo, CreateInfo, EstimateResourcesRequest};
-use sp_core::H160;
+use primitives::evm::{
+	AccessListItem, Account, BlockLimits, CallInfo, ContractVerification, CreateInfo, EstimateResourcesRequest,
+	EvmReceipt,
+};
+use sp_core::{H160, H256};
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	#[api_version(2)]
+	#[api_version(6)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
@@ -56,5 +59,28 @@ sp_api::decl_runtime_apis! {
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
 
 		fn block_limits() -> BlockLimits;
+
+		fn base_fee_per_gas() -> Balance;
+
+		fn chain_id() -> u64;
+
+		fn account_basic(address: H160) -> Account;
+
+		fn code_at_address(address: H160) -> Vec<u8>;
+
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// Convert a raw signed Ethereum transaction into the `eth_call` extrinsic.
+		fn convert_eth_transaction(raw: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError>;
+
+		/// The receipts of the EVM transactions in the block.
+		fn block_receipts() -> Vec<EvmReceipt>;
+
+		/// The hash of the signed Ethereum transaction `extrinsic` is converted from, `None` if
+		/// it isn't an Ethereum transaction.
+		fn ethereum_transaction_hash(extrinsic: <Block as BlockT>::Extrinsic) -> Option<H256>;
+
+		/// The source verification of the contract.
+		fn contract_verification(address: H160) -> Option<ContractVerification>;
 	}
 }
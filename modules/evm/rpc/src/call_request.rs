@@ -16,9 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use primitives::evm::AccessListItem;
+use primitives::evm::{AccessListItem, ContractVerification};
 use serde::{Deserialize, Serialize};
-use sp_core::{Bytes, H160, U256};
+use sp_core::{Bytes, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
 
 /// Call request
@@ -53,3 +53,31 @@ pub struct EstimateResourcesResponse {
 	/// Adjusted weight fee
 	pub weight_fee: U256,
 }
+
+/// ContractVerification response
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractVerificationResponse {
+	/// Verified code hash
+	pub code_hash: H256,
+	/// Compiler version
+	pub compiler_version: String,
+	/// Compiler settings hash
+	pub settings_hash: H256,
+	/// Metadata hash
+	pub metadata_hash: H256,
+	/// ABI hash
+	pub abi_hash: H256,
+}
+
+impl From<ContractVerification> for ContractVerificationResponse {
+	fn from(verification: ContractVerification) -> Self {
+		Self {
+			code_hash: verification.code_hash,
+			compiler_version: String::from_utf8_lossy(&verification.compiler_version).into_owned(),
+			settings_hash: verification.settings_hash,
+			metadata_hash: verification.metadata_hash,
+			abi_hash: verification.abi_hash,
+		}
+	}
+}
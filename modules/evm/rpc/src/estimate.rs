@@ -0,0 +1,267 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Binary search estimators of the gas and storage limits.
+//!
+//! The gas and storage used by an execution with the max limits are not always enough for the
+//! execution to succeed with them as the limits: the gas refunds, the gas retained by the 63/64
+//! rule of the sub calls, `gasleft()` checks and the storage freed by the sub calls all make the
+//! required limits higher than the used resources. The estimators search the lowest executable
+//! limits instead, assuming the executability is monotonic in the limit.
+
+use sp_runtime::Permill;
+
+/// The config of the resources estimation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstimateConfig {
+	/// A search stops once the gap between the failing and the executable limits is within this
+	/// ratio of the executable limit. The estimate is exact with zero precision.
+	pub precision: Permill,
+	/// The max number of executions of a search, the best executable limit found so far is used
+	/// once reached.
+	pub max_iterations: u32,
+}
+
+impl Default for EstimateConfig {
+	fn default() -> Self {
+		Self {
+			precision: Permill::from_perthousand(15),
+			max_iterations: 32,
+		}
+	}
+}
+
+/// Search the lowest executable limit in `(lowest, highest]`, where `lowest` is known to fail and
+/// `highest` is known to be executable.
+pub fn binary_search<E>(
+	mut lowest: u64,
+	mut highest: u64,
+	config: &EstimateConfig,
+	mut executable: impl FnMut(u64) -> Result<bool, E>,
+) -> Result<u64, E> {
+	let mut iterations = 0;
+	while highest.saturating_sub(lowest) > 1
+		&& highest - lowest > config.precision.mul_floor(highest)
+		&& iterations < config.max_iterations
+	{
+		let mid = lowest + (highest - lowest) / 2;
+		if executable(mid)? {
+			highest = mid;
+		} else {
+			lowest = mid;
+		}
+		iterations += 1;
+	}
+
+	Ok(highest)
+}
+
+/// Estimate the gas limit of an execution which used `used_gas` and succeeded with the gas limit
+/// `cap`.
+pub fn estimate_gas<E>(
+	used_gas: u64,
+	cap: u64,
+	config: &EstimateConfig,
+	mut executable: impl FnMut(u64) -> Result<bool, E>,
+) -> Result<u64, E> {
+	// the execution with less gas than used can't succeed
+	let lowest = used_gas.saturating_sub(1);
+	if cap <= used_gas || config.max_iterations == 0 {
+		return Ok(cap);
+	}
+
+	// try the used gas plus the gas retained by the 63/64 rule first, which is enough for most of
+	// the executions
+	let guess = used_gas.saturating_add(used_gas / 63).saturating_add(1).min(cap);
+	let config = EstimateConfig {
+		max_iterations: config.max_iterations - 1,
+		..*config
+	};
+	if executable(guess)? {
+		binary_search(lowest, guess, &config, executable)
+	} else {
+		binary_search(guess, cap, &config, executable)
+	}
+}
+
+/// Estimate the storage limit of an execution which used `used_storage` and succeeded with the
+/// storage limit `cap`.
+pub fn estimate_storage<E>(
+	used_storage: i32,
+	cap: u32,
+	config: &EstimateConfig,
+	mut executable: impl FnMut(u32) -> Result<bool, E>,
+) -> Result<u32, E> {
+	let used_storage = used_storage.max(0) as u32;
+	if cap <= used_storage || config.max_iterations == 0 {
+		return Ok(cap);
+	}
+
+	// the used storage is enough unless the storage freed during the execution is reused
+	if executable(used_storage)? {
+		return Ok(used_storage);
+	}
+
+	let config = EstimateConfig {
+		max_iterations: config.max_iterations - 1,
+		..*config
+	};
+	binary_search(used_storage.into(), cap.into(), &config, |limit| {
+		executable(limit as u32)
+	})
+	.map(|limit| limit as u32)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::convert::Infallible;
+
+	const EXACT: EstimateConfig = EstimateConfig {
+		precision: Permill::from_parts(0),
+		max_iterations: 64,
+	};
+
+	/// The gas required to forward `gas` to a sub call under the 63/64 rule.
+	fn with_sub_call(gas: u64) -> u64 {
+		gas + gas / 63 + 1
+	}
+
+	/// An execution which succeeds with at least `required` gas, counting the executions.
+	fn execution(required: u64, count: &mut u32) -> impl FnMut(u64) -> Result<bool, Infallible> + '_ {
+		move |gas| {
+			*count += 1;
+			Ok(gas >= required)
+		}
+	}
+
+	#[test]
+	fn binary_search_works() {
+		let mut count = 0;
+		assert_eq!(
+			binary_search(0, 1_000_000, &EXACT, execution(123_456, &mut count)),
+			Ok(123_456)
+		);
+		assert!(count <= 20);
+
+		// stops within the precision
+		let config = EstimateConfig {
+			precision: Permill::from_percent(1),
+			max_iterations: 64,
+		};
+		let mut count = 0;
+		let estimate = binary_search(0, 1_000_000, &config, execution(123_456, &mut count)).unwrap();
+		assert!(estimate >= 123_456 && estimate - 123_456 <= config.precision.mul_floor(estimate));
+		assert!(count < 20);
+
+		// stops at the max iterations with an executable limit
+		let config = EstimateConfig {
+			precision: Permill::from_parts(0),
+			max_iterations: 3,
+		};
+		let mut count = 0;
+		let estimate = binary_search(0, 1_000_000, &config, execution(123_456, &mut count)).unwrap();
+		assert_eq!(count, 3);
+		assert_eq!(estimate, 125_000);
+
+		// errors are propagated
+		assert_eq!(binary_search(0, 100, &EXACT, |_| Err("error")), Err("error"));
+	}
+
+	#[test]
+	fn estimate_gas_works_for_plain_calls() {
+		// a transfer needs exactly the used gas, found by the first guess
+		let mut count = 0;
+		let estimate = estimate_gas(21_000, 20_000_000, &EXACT, execution(21_000, &mut count)).unwrap();
+		assert_eq!(estimate, 21_000);
+		assert!(count <= 10);
+
+		// the cap is used when nothing to search
+		assert_eq!(estimate_gas(21_000, 21_000, &EXACT, |_| Err("unreachable")), Ok(21_000));
+	}
+
+	#[test]
+	fn estimate_gas_works_for_uniswap_style_routers() {
+		// `swapExactTokensForTokens` through the path A -> B -> C -> D: the router calls the pairs
+		// one by one, and each pair calls the token contracts twice, the last sub calls need the
+		// gas retained by the 63/64 rule on top of the used gas
+		let pair_gas = 45_000 + 30_000 + with_sub_call(30_000);
+		let router_gas = 25_000 + 2 * 105_000 + with_sub_call(pair_gas);
+		let used_gas = 25_000 + 3 * 105_000;
+		assert!(router_gas > used_gas);
+
+		let mut count = 0;
+		let estimate = estimate_gas(used_gas, 20_000_000, &EXACT, execution(router_gas, &mut count)).unwrap();
+		assert_eq!(estimate, router_gas);
+		assert!(count <= 25);
+
+		// the estimate stays executable with the default precision
+		let config = EstimateConfig::default();
+		let mut count = 0;
+		let estimate = estimate_gas(used_gas, 20_000_000, &config, execution(router_gas, &mut count)).unwrap();
+		assert!(estimate >= router_gas && estimate - router_gas <= config.precision.mul_floor(estimate));
+	}
+
+	#[test]
+	fn estimate_gas_works_for_gasleft_checks() {
+		// the contract reverts unless `gasleft() >= 100_000` before doing cheap work
+		let used_gas = 30_000;
+		let mut count = 0;
+		let estimate = estimate_gas(used_gas, 20_000_000, &EXACT, execution(121_000, &mut count)).unwrap();
+		assert_eq!(estimate, 121_000);
+		assert!(count <= 30);
+
+		// the best estimate found is used at the max iterations
+		let config = EstimateConfig {
+			precision: Permill::from_parts(0),
+			max_iterations: 4,
+		};
+		let mut count = 0;
+		let estimate = estimate_gas(used_gas, 20_000_000, &config, execution(121_000, &mut count)).unwrap();
+		assert_eq!(count, 4);
+		assert!(estimate >= 121_000);
+	}
+
+	#[test]
+	fn estimate_storage_works() {
+		// a plain storage write needs the used storage
+		let mut count = 0;
+		let estimate = estimate_storage(64, 4_194_304, &EXACT, |limit| {
+			count += 1;
+			Ok::<_, Infallible>(limit >= 64)
+		})
+		.unwrap();
+		assert_eq!((estimate, count), (64, 1));
+
+		// the refunded storage needs no limit
+		assert_eq!(
+			estimate_storage(-64, 4_194_304, &EXACT, |limit| Ok::<_, Infallible>(limit >= 0)),
+			Ok(0)
+		);
+
+		// a router pays 320 bytes for the new position while the sub calls free 256 bytes of the
+		// cache, the net used storage is 64 bytes
+		assert_eq!(
+			estimate_storage(64, 4_194_304, &EXACT, |limit| Ok::<_, Infallible>(limit >= 320)),
+			Ok(320)
+		);
+
+		// the cap is used when nothing to search
+		assert_eq!(estimate_storage(64, 64, &EXACT, |_| Err("unreachable")), Ok(64));
+	}
+}
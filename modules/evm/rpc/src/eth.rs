@@ -0,0 +1,828 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `eth_*` namespace, so that the Ethereum tooling can connect to the node directly.
+
+use frame_support::log;
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::SubscriptionResult,
+	SubscriptionSink,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{traits::SpawnNamed, Bytes, H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{
+		Block as BlockT, Hash as HashT, HashFor, Header as HeaderT, MaybeDisplay, MaybeFromStr, NumberFor,
+		UniqueSaturatedInto,
+	},
+};
+use std::{collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+use crate::{
+	error_on_execution_failure,
+	estimate::{self, EstimateConfig},
+	execution_err, internal_err, invalid_params,
+	pool::ExecutionPool,
+};
+use module_evm::{ExitError, ExitReason};
+use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+use primitives::evm::{
+	convert_decimals_from_evm, AccessListItem, BlockLimits, CallInfo, CreateInfo, EvmReceipt, Log as EvmLog,
+};
+
+pub use crate::eth_types::{BlockNumber, BlockTag, EthCallRequest, Filter, Log, Receipt, ValueOrArray};
+pub use ethbloom::{Bloom, Input as BloomInput};
+
+/// The max number of blocks `eth_getLogs` can query at once.
+pub const MAX_LOGS_BLOCK_RANGE: u32 = 10_000;
+
+/// The index of the EVM transactions, maintained by the node.
+pub trait EthMapping: Send + Sync {
+	/// The blocks including the transaction `hash`, and the index of the transaction in them.
+	fn transaction_blocks(&self, hash: &H256) -> Result<Vec<(H256, u32)>, String>;
+
+	/// The receipts of the EVM transactions in `block` with their transaction hashes, `None` if
+	/// the block is not indexed.
+	fn block_receipts(&self, block: &H256) -> Result<Option<Vec<(H256, EvmReceipt)>>, String>;
+
+	/// The bloom filter of the logs in `block`, `None` if the block is not indexed.
+	fn block_bloom(&self, block: &H256) -> Result<Option<Bloom>, String>;
+
+	/// The indexed blocks in `from..=to` with logs emitted by one of the `addresses`, and with
+	/// one of the topics of every entry of `topics`. An empty entry matches any topic. At least
+	/// one address or topic is required.
+	fn filter_blocks(&self, from: u32, to: u32, addresses: &[H160], topics: &[Vec<H256>]) -> Result<Vec<H256>, String>;
+}
+
+/// The bloom filter of the addresses and the topics of the logs.
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a EvmLog>) -> Bloom {
+	let mut bloom = Bloom::default();
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+		}
+	}
+	bloom
+}
+
+/// Collect the receipts of the EVM transactions in the block `hash`, with their transaction
+/// hashes. Ethereum transactions are hashed as the signed raw transaction, the others as the
+/// extrinsic.
+pub fn fetch_block_receipts<B, C, Balance>(client: &C, hash: H256) -> Result<Vec<(H256, EvmReceipt)>, String>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let block_id = BlockId::Hash(hash);
+
+	let version = client
+		.runtime_api()
+		.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(&block_id)
+		.map_err(|err| format!("runtime error: {:?}", err))?
+		.unwrap_or_default();
+	if version < 4 {
+		// receipts are not supported by the runtime
+		return Ok(vec![]);
+	}
+
+	let receipts = client
+		.runtime_api()
+		.block_receipts(&block_id)
+		.map_err(|err| format!("runtime error: {:?}", err))?;
+	if receipts.is_empty() {
+		return Ok(vec![]);
+	}
+
+	let extrinsics = client
+		.block_body(&block_id)
+		.map_err(|err| format!("blockchain error: {:?}", err))?
+		.ok_or_else(|| format!("body of block `{:?}` not found", hash))?;
+
+	receipts
+		.into_iter()
+		.map(|receipt| {
+			let extrinsic = extrinsics
+				.get(receipt.extrinsic_index as usize)
+				.ok_or_else(|| format!("extrinsic {} of block `{:?}` not found", receipt.extrinsic_index, hash))?;
+			let ethereum_transaction_hash = if version >= 6 {
+				client
+					.runtime_api()
+					.ethereum_transaction_hash(&block_id, extrinsic.clone())
+					.map_err(|err| format!("runtime error: {:?}", err))?
+			} else {
+				None
+			};
+			let transaction_hash =
+				ethereum_transaction_hash.unwrap_or_else(|| <HashFor<B> as HashT>::hash_of(extrinsic));
+			Ok((transaction_hash, receipt))
+		})
+		.collect()
+}
+
+/// The receipts of the block `hash` from the mapping, or from the chain if it's not indexed yet.
+fn receipts_of<B, C, Balance>(
+	client: &C,
+	mapping: &dyn EthMapping,
+	hash: H256,
+) -> Result<Vec<(H256, EvmReceipt)>, String>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	match mapping.block_receipts(&hash)? {
+		Some(receipts) => Ok(receipts),
+		None => fetch_block_receipts::<B, C, Balance>(client, hash),
+	}
+}
+
+/// The logs of the successful transactions in the block matching the filter.
+fn block_logs(receipts: Vec<(H256, EvmReceipt)>, block_hash: H256, block_number: U256, filter: &Filter) -> Vec<Log> {
+	let mut logs = vec![];
+	let mut log_index = 0usize;
+	for (transaction_hash, receipt) in receipts {
+		let count = receipt.logs.len();
+		// failed transactions have no logs
+		if receipt.succeed {
+			logs.extend(
+				receipt
+					.logs
+					.into_iter()
+					.enumerate()
+					.filter(|(_, log)| filter.matches(log))
+					.map(|(i, log)| {
+						to_log(
+							log,
+							block_hash,
+							block_number,
+							transaction_hash,
+							receipt.extrinsic_index,
+							log_index.saturating_add(i),
+							i,
+						)
+					}),
+			);
+		}
+		log_index = log_index.saturating_add(count);
+	}
+	logs
+}
+
+/// Eth rpc interface.
+#[rpc(client, server)]
+pub trait EthApi {
+	/// Returns the chain id.
+	#[method(name = "eth_chainId")]
+	fn chain_id(&self) -> RpcResult<U256>;
+
+	/// Returns the number of the best block.
+	#[method(name = "eth_blockNumber")]
+	fn block_number(&self) -> RpcResult<U256>;
+
+	/// Returns the balance of the account, in wei.
+	#[method(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<U256>;
+
+	/// Returns the nonce of the account.
+	#[method(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<U256>;
+
+	/// Returns the code of the contract.
+	#[method(name = "eth_getCode")]
+	async fn code_at(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<Bytes>;
+
+	/// Returns the value of the storage slot `index` of the contract.
+	#[method(name = "eth_getStorageAt")]
+	async fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> RpcResult<H256>;
+
+	/// Call contract, returning the output data.
+	#[method(name = "eth_call")]
+	async fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> RpcResult<Bytes>;
+
+	/// Estimate the gas needed for execution of the call.
+	#[method(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> RpcResult<U256>;
+
+	/// Submit a signed Ethereum transaction, returning the hash of the extrinsic it maps to.
+	#[method(name = "eth_sendRawTransaction")]
+	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256>;
+
+	/// Returns the receipt of the transaction.
+	#[method(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>>;
+
+	/// Returns the logs matching the filter.
+	#[method(name = "eth_getLogs")]
+	fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+	/// Subscribe to the logs matching the filter in the new best blocks. Only the `logs` kind is
+	/// supported.
+	#[subscription(name = "eth_subscribe" => "eth_subscription", unsubscribe = "eth_unsubscribe", item = Log)]
+	fn subscribe_logs(&self, kind: String, filter: Option<Filter>);
+}
+
+pub struct Eth<B, C, P, Balance> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	mapping: Arc<dyn EthMapping>,
+	executor: Arc<dyn SpawnNamed>,
+	estimate_config: EstimateConfig,
+	execution_pool: Arc<ExecutionPool>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<B, C, P, Balance> Eth<B, C, P, Balance> {
+	pub fn new(
+		client: Arc<C>,
+		pool: Arc<P>,
+		mapping: Arc<dyn EthMapping>,
+		executor: Arc<dyn SpawnNamed>,
+		estimate_config: EstimateConfig,
+		execution_pool: Arc<ExecutionPool>,
+	) -> Self {
+		Self {
+			client,
+			pool,
+			mapping,
+			executor,
+			estimate_config,
+			execution_pool,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, P, Balance> Eth<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn block_number_of(&self, number: Option<BlockNumber>) -> RpcResult<NumberFor<B>> {
+		match number.unwrap_or_default() {
+			BlockNumber::Tag(BlockTag::Earliest) => Ok(0u32.into()),
+			BlockNumber::Tag(BlockTag::Latest) | BlockNumber::Tag(BlockTag::Pending) => {
+				Ok(self.client.info().best_number)
+			}
+			BlockNumber::Number(number) => TryInto::<u32>::try_into(number)
+				.map(Into::into)
+				.map_err(|_| invalid_params(format!("Invalid block number: {:?}", number))),
+		}
+	}
+
+	fn block_id_of(&self, number: Option<BlockNumber>) -> RpcResult<BlockId<B>> {
+		self.block_hash_of(number).map(BlockId::Hash)
+	}
+
+	fn block_hash_of(&self, number: Option<BlockNumber>) -> RpcResult<H256> {
+		let hash = match number.unwrap_or_default() {
+			BlockNumber::Tag(BlockTag::Latest) | BlockNumber::Tag(BlockTag::Pending) => self.client.info().best_hash,
+			number => {
+				let number = self.block_number_of(Some(number))?;
+				self.client
+					.hash(number)
+					.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+					.ok_or_else(|| invalid_params(format!("Block `{:?}` not found", number)))?
+			}
+		};
+		let block_id = BlockId::Hash(hash);
+
+		let version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(&block_id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| {
+				internal_err(format!(
+					"Could not find `EVMRuntimeRPCApi` api for block `{:?}`.",
+					&block_id
+				))
+			})?;
+		if version < 4 {
+			return Err(internal_err("eth api is not supported by the runtime"));
+		}
+
+		Ok(hash)
+	}
+
+	/// The canonical block number of `hash`, `None` if the block is unknown or on a stale fork.
+	fn canonical_number(&self, hash: H256) -> RpcResult<Option<NumberFor<B>>> {
+		let number = match self
+			.client
+			.number(hash)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+		{
+			Some(number) => number,
+			None => return Ok(None),
+		};
+		let canonical_hash = self
+			.client
+			.hash(number)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?;
+
+		Ok(if canonical_hash == Some(hash) {
+			Some(number)
+		} else {
+			None
+		})
+	}
+
+	fn block_receipts(&self, hash: H256) -> RpcResult<Vec<(H256, EvmReceipt)>> {
+		receipts_of::<B, C, Balance>(&self.client, &*self.mapping, hash).map_err(internal_err)
+	}
+
+	fn block_limits(client: &C, block_id: &BlockId<B>) -> Result<BlockLimits, String> {
+		client
+			.runtime_api()
+			.block_limits(block_id)
+			.map_err(|e| format!("runtime error: Unable to query block limits {:?}", e))
+	}
+
+	/// The gas limit of `request`, capped at the max gas limit.
+	fn gas_limit_of(request: &EthCallRequest, block_limits: &BlockLimits) -> u64 {
+		request
+			.gas
+			.map_or(block_limits.max_gas_limit, |gas| gas.low_u64())
+			.min(block_limits.max_gas_limit)
+	}
+
+	/// The value of `request`, in the native token.
+	fn value_of(request: &EthCallRequest) -> RpcResult<Balance> {
+		request
+			.value
+			.unwrap_or_default()
+			.try_into()
+			.ok()
+			.and_then(convert_decimals_from_evm::<u128>)
+			.and_then(|value| TryInto::<Balance>::try_into(value).ok())
+			.ok_or_else(|| invalid_params(format!("Invalid parameter value: {:?}", request.value)))
+	}
+
+	fn execute(
+		client: &C,
+		request: &EthCallRequest,
+		value: Balance,
+		block_id: &BlockId<B>,
+	) -> Result<(ExitReason, Vec<u8>, u64), String> {
+		log::debug!(target: "evm", "eth rpc execute, request: {:?}", request);
+
+		let api = client.runtime_api();
+		let block_limits = Self::block_limits(client, block_id)?;
+
+		let gas_limit = Self::gas_limit_of(request, &block_limits);
+		let from = request.from.unwrap_or_default();
+		let data = request.data.clone().map(|d| d.0).unwrap_or_default();
+
+		match request.to {
+			Some(to) => {
+				let info = api
+					.call(
+						block_id,
+						from,
+						to,
+						data,
+						value,
+						gas_limit,
+						block_limits.max_storage_limit,
+						None,
+						true,
+					)
+					.map_err(|err| format!("runtime error: {:?}", err))?
+					.map_err(|err| format!("execution fatal: {:?}", err))?;
+
+				Ok((info.exit_reason, info.value, info.used_gas.low_u64()))
+			}
+			None => {
+				let info = api
+					.create(
+						block_id,
+						from,
+						data,
+						value,
+						gas_limit,
+						block_limits.max_storage_limit,
+						None,
+						true,
+					)
+					.map_err(|err| format!("runtime error: {:?}", err))?
+					.map_err(|err| format!("execution fatal: {:?}", err))?;
+
+				Ok((info.exit_reason, info.value[..].to_vec(), info.used_gas.low_u64()))
+			}
+		}
+	}
+
+	/// Execute `request` at the block `hash` in the execution pool.
+	async fn pool_execute(
+		&self,
+		request: &EthCallRequest,
+		value: Balance,
+		hash: H256,
+	) -> RpcResult<(ExitReason, Vec<u8>)> {
+		log::debug!(target: "evm", "eth rpc pool execute, request: {:?}", request);
+
+		let block_limits = Self::block_limits(&self.client, &BlockId::Hash(hash)).map_err(internal_err)?;
+		let gas_limit = Self::gas_limit_of(request, &block_limits);
+		let from = request.from.unwrap_or_default();
+		let data = request.data.clone().map(|d| d.0).unwrap_or_default();
+		let access_list: Option<Vec<AccessListItem>> = None;
+
+		match request.to {
+			Some(to) => {
+				let info = self
+					.execution_pool
+					.call_at::<Result<CallInfo, sp_runtime::DispatchError>>(
+						hash,
+						"EVMRuntimeRPCApi_call",
+						(
+							from,
+							to,
+							data,
+							value,
+							gas_limit,
+							block_limits.max_storage_limit,
+							access_list,
+							true,
+						),
+					)
+					.await
+					.map_err(execution_err)?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+				Ok((info.exit_reason, info.value))
+			}
+			None => {
+				let info = self
+					.execution_pool
+					.call_at::<Result<CreateInfo, sp_runtime::DispatchError>>(
+						hash,
+						"EVMRuntimeRPCApi_create",
+						(
+							from,
+							data,
+							value,
+							gas_limit,
+							block_limits.max_storage_limit,
+							access_list,
+							true,
+						),
+					)
+					.await
+					.map_err(execution_err)?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+				Ok((info.exit_reason, info.value[..].to_vec()))
+			}
+		}
+	}
+}
+
+fn to_log(
+	log: primitives::evm::Log,
+	block_hash: H256,
+	block_number: U256,
+	transaction_hash: H256,
+	transaction_index: u32,
+	log_index: usize,
+	transaction_log_index: usize,
+) -> Log {
+	Log {
+		address: log.address,
+		topics: log.topics,
+		data: Bytes(log.data),
+		block_hash,
+		block_number,
+		transaction_hash,
+		transaction_index: transaction_index.into(),
+		log_index: log_index.into(),
+		transaction_log_index: transaction_log_index.into(),
+		removed: false,
+	}
+}
+
+#[async_trait]
+impl<B, C, P, Balance> EthApiServer for Eth<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	P: TransactionPool<Block = B, Hash = H256> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn chain_id(&self) -> RpcResult<U256> {
+		let block_id = self.block_id_of(None)?;
+		let chain_id = self
+			.client
+			.runtime_api()
+			.chain_id(&block_id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(chain_id.into())
+	}
+
+	fn block_number(&self) -> RpcResult<U256> {
+		Ok(UniqueSaturatedInto::<u64>::unique_saturated_into(self.client.info().best_number).into())
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<U256> {
+		let block_id = self.block_id_of(number)?;
+		let account = self
+			.client
+			.runtime_api()
+			.account_basic(&block_id, address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(account.balance)
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<U256> {
+		let block_id = self.block_id_of(number)?;
+		let account = self
+			.client
+			.runtime_api()
+			.account_basic(&block_id, address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(account.nonce)
+	}
+
+	async fn code_at(&self, address: H160, number: Option<BlockNumber>) -> RpcResult<Bytes> {
+		let hash = self.block_hash_of(number)?;
+		let code = self
+			.execution_pool
+			.call_at::<Vec<u8>>(hash, "EVMRuntimeRPCApi_code_at_address", address)
+			.await
+			.map_err(execution_err)?;
+
+		Ok(Bytes(code))
+	}
+
+	async fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> RpcResult<H256> {
+		let hash = self.block_hash_of(number)?;
+		let mut key = [0u8; 32];
+		index.to_big_endian(&mut key);
+
+		self.execution_pool
+			.call_at::<H256>(hash, "EVMRuntimeRPCApi_storage_at", (address, H256::from(key)))
+			.await
+			.map_err(execution_err)
+	}
+
+	async fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> RpcResult<Bytes> {
+		let hash = self.block_hash_of(number)?;
+		let value = Self::value_of(&request)?;
+		let (exit_reason, output) = self.pool_execute(&request, value, hash).await?;
+		error_on_execution_failure(&exit_reason, &output)?;
+
+		Ok(Bytes(output))
+	}
+
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> RpcResult<U256> {
+		let block_id = self.block_id_of(number)?;
+		let value = Self::value_of(&request)?;
+		let cap = Self::gas_limit_of(
+			&request,
+			&Self::block_limits(&self.client, &block_id).map_err(internal_err)?,
+		);
+		let (exit_reason, output, used_gas) =
+			Self::execute(&self.client, &request, value, &block_id).map_err(internal_err)?;
+		error_on_execution_failure(&exit_reason, &output)?;
+
+		// the call may revert or run out of gas with the used gas due to gas-dependent branching
+		let gas = estimate::estimate_gas(used_gas, cap, &self.estimate_config, |gas| {
+			let request = EthCallRequest {
+				gas: Some(gas.into()),
+				..request.clone()
+			};
+			let value = Self::value_of(&request)?;
+			let (exit_reason, output, _) =
+				Self::execute(&self.client, &request, value, &block_id).map_err(internal_err)?;
+			match exit_reason {
+				ExitReason::Succeed(_) => Ok(true),
+				ExitReason::Revert(_) | ExitReason::Error(ExitError::OutOfGas) => Ok(false),
+				other => error_on_execution_failure(&other, &output).map(|_| false),
+			}
+		})?;
+
+		Ok(gas.into())
+	}
+
+	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
+		let block_id = self.block_id_of(None)?;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.convert_eth_transaction(&block_id, bytes.to_vec())
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| invalid_params(format!("Invalid transaction: {:?}", err)))?;
+
+		self.pool
+			.submit_one(&block_id, TransactionSource::External, extrinsic)
+			.await
+			.map_err(|err| internal_err(format!("submit transaction to pool failed: {:?}", err)))?;
+
+		// the transaction is indexed by the hash computed by the wallet, not the extrinsic hash
+		Ok(H256(keccak_256(&bytes)))
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>> {
+		for (block_hash, extrinsic_index) in self.mapping.transaction_blocks(&hash).map_err(internal_err)? {
+			let block_number = match self.canonical_number(block_hash)? {
+				Some(number) => U256::from(UniqueSaturatedInto::<u64>::unique_saturated_into(number)),
+				None => continue,
+			};
+
+			let mut cumulative_gas_used = 0u64;
+			let mut log_index = 0usize;
+			for (transaction_hash, receipt) in self.block_receipts(block_hash)? {
+				cumulative_gas_used = cumulative_gas_used.saturating_add(receipt.used_gas);
+				if receipt.extrinsic_index != extrinsic_index {
+					log_index = log_index.saturating_add(receipt.logs.len());
+					continue;
+				}
+
+				let logs_bloom = logs_bloom(&receipt.logs);
+				let logs = receipt
+					.logs
+					.into_iter()
+					.enumerate()
+					.map(|(i, log)| {
+						to_log(
+							log,
+							block_hash,
+							block_number,
+							transaction_hash,
+							extrinsic_index,
+							log_index.saturating_add(i),
+							i,
+						)
+					})
+					.collect();
+
+				return Ok(Some(Receipt {
+					transaction_hash,
+					transaction_index: extrinsic_index.into(),
+					block_hash,
+					block_number,
+					from: receipt.from,
+					to: receipt.to,
+					contract_address: receipt.contract_address,
+					gas_used: receipt.used_gas.into(),
+					cumulative_gas_used: cumulative_gas_used.into(),
+					status: (receipt.succeed as u8).into(),
+					logs,
+					logs_bloom,
+				}));
+			}
+		}
+
+		Ok(None)
+	}
+
+	fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+		let blocks = match filter.block_hash {
+			Some(hash) => {
+				let number = self
+					.canonical_number(hash)?
+					.ok_or_else(|| invalid_params(format!("Block `{:?}` not found", hash)))?;
+				vec![(hash, number)]
+			}
+			None => {
+				let from = self.block_number_of(filter.from_block)?;
+				let to = self
+					.block_number_of(filter.to_block)?
+					.min(self.client.info().best_number);
+				if to.saturating_sub(from) >= MAX_LOGS_BLOCK_RANGE.into() {
+					return Err(invalid_params(format!(
+						"Block range exceeds the limit: {}",
+						MAX_LOGS_BLOCK_RANGE
+					)));
+				}
+
+				let mut blocks = vec![];
+				let mut number = from;
+				while number <= to {
+					if let Some(hash) = self
+						.client
+						.hash(number)
+						.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+					{
+						blocks.push((hash, number));
+					}
+					number += 1u32.into();
+				}
+				blocks
+			}
+		};
+
+		// the blocks with the addresses and the topics of the filter, from the index
+		let addresses = filter.addresses();
+		let topics = filter.topics();
+		let candidates = match (blocks.first(), blocks.last()) {
+			(Some((_, from)), Some((_, to))) if !addresses.is_empty() || topics.iter().any(|t| !t.is_empty()) => Some(
+				self.mapping
+					.filter_blocks(
+						UniqueSaturatedInto::<u32>::unique_saturated_into(*from),
+						UniqueSaturatedInto::<u32>::unique_saturated_into(*to),
+						&addresses,
+						&topics,
+					)
+					.map_err(internal_err)?
+					.into_iter()
+					.collect::<BTreeSet<_>>(),
+			),
+			_ => None,
+		};
+
+		let mut logs = vec![];
+		for (block_hash, number) in blocks {
+			// skip the indexed blocks without matching logs
+			if let Some(bloom) = self.mapping.block_bloom(&block_hash).map_err(internal_err)? {
+				let indexed = candidates
+					.as_ref()
+					.map_or(bloom != Bloom::zero(), |candidates| candidates.contains(&block_hash));
+				if !indexed || !filter.matches_bloom(&bloom) {
+					continue;
+				}
+			}
+
+			let block_number = U256::from(UniqueSaturatedInto::<u64>::unique_saturated_into(number));
+			logs.extend(block_logs(
+				self.block_receipts(block_hash)?,
+				block_hash,
+				block_number,
+				&filter,
+			));
+		}
+
+		Ok(logs)
+	}
+
+	fn subscribe_logs(&self, mut sink: SubscriptionSink, kind: String, filter: Option<Filter>) -> SubscriptionResult {
+		if kind != "logs" {
+			let _ = sink.reject(invalid_params(format!("Unsupported subscription: {}", kind)));
+			return Ok(());
+		}
+
+		let filter = filter.unwrap_or_default();
+		let client = self.client.clone();
+		let mapping = self.mapping.clone();
+		let stream = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| futures::future::ready(notification.is_new_best))
+			.flat_map(move |notification| {
+				let block_number = U256::from(UniqueSaturatedInto::<u64>::unique_saturated_into(
+					*notification.header.number(),
+				));
+				let logs = receipts_of::<B, C, Balance>(&*client, &*mapping, notification.hash)
+					.map(|receipts| block_logs(receipts, notification.hash, block_number, &filter))
+					.unwrap_or_else(|err| {
+						log::warn!(
+							target: "evm",
+							"eth subscription: failed to get the receipts of block {:?}: {}",
+							notification.hash, err
+						);
+						vec![]
+					});
+				futures::stream::iter(logs)
+			})
+			.boxed();
+
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("evm-rpc-subscription", Some("rpc"), fut.boxed());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,268 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethbloom::{Bloom, Input as BloomInput};
+use primitives::evm::Log as EvmLog;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, H160, H256, U256};
+
+/// Block tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockTag {
+	/// Genesis block
+	Earliest,
+	/// Best block
+	Latest,
+	/// Treated as the best block
+	Pending,
+}
+
+/// Block number or tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockNumber {
+	/// Tag
+	Tag(BlockTag),
+	/// Number
+	Number(U256),
+}
+
+impl Default for BlockNumber {
+	fn default() -> Self {
+		BlockNumber::Tag(BlockTag::Latest)
+	}
+}
+
+/// Eth call request
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallRequest {
+	/// From
+	pub from: Option<H160>,
+	/// To
+	pub to: Option<H160>,
+	/// Gas
+	pub gas: Option<U256>,
+	/// Value, in wei
+	pub value: Option<U256>,
+	/// Data
+	#[serde(alias = "input")]
+	pub data: Option<Bytes>,
+}
+
+/// A single value or an array of values
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ValueOrArray<T> {
+	/// Value
+	Value(T),
+	/// Array
+	Array(Vec<T>),
+}
+
+impl<T: PartialEq> ValueOrArray<T> {
+	/// Whether `value` is one of the values.
+	pub fn contains(&self, value: &T) -> bool {
+		self.as_slice().contains(value)
+	}
+
+	/// The values as a slice.
+	pub fn as_slice(&self) -> &[T] {
+		match self {
+			ValueOrArray::Value(v) => std::slice::from_ref(v),
+			ValueOrArray::Array(values) => values,
+		}
+	}
+}
+
+/// Log filter
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	/// From block, `latest` by default
+	pub from_block: Option<BlockNumber>,
+	/// To block, `latest` by default
+	pub to_block: Option<BlockNumber>,
+	/// Block hash, exclusive with `from_block` and `to_block`
+	pub block_hash: Option<H256>,
+	/// Contract addresses
+	pub address: Option<ValueOrArray<H160>>,
+	/// Topics, by position. `None` matches any topic.
+	pub topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+}
+
+impl Filter {
+	/// Whether the log matches the addresses and the topics of the filter.
+	pub fn matches(&self, log: &EvmLog) -> bool {
+		if let Some(address) = &self.address {
+			if !address.contains(&log.address) {
+				return false;
+			}
+		}
+
+		self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+			Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+			None => true,
+		})
+	}
+
+	/// The addresses of the filter, empty if it matches any address.
+	pub fn addresses(&self) -> Vec<H160> {
+		self.address.as_ref().map(|a| a.as_slice().to_vec()).unwrap_or_default()
+	}
+
+	/// The topics of the filter by position, empty if it matches any topic at the position.
+	pub fn topics(&self) -> Vec<Vec<H256>> {
+		self.topics
+			.iter()
+			.flatten()
+			.map(|topic| topic.as_ref().map(|t| t.as_slice().to_vec()).unwrap_or_default())
+			.collect()
+	}
+
+	/// Whether a block with the logs bloom `bloom` may contain logs matching the filter.
+	pub fn matches_bloom(&self, bloom: &Bloom) -> bool {
+		fn contains_any<T: AsRef<[u8]>>(bloom: &Bloom, inputs: &[T]) -> bool {
+			inputs.is_empty()
+				|| inputs
+					.iter()
+					.any(|input| bloom.contains_input(BloomInput::Raw(input.as_ref())))
+		}
+
+		contains_any(bloom, &self.addresses()) && self.topics().iter().all(|topics| contains_any(bloom, topics))
+	}
+}
+
+/// Log
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+	/// Contract address
+	pub address: H160,
+	/// Topics
+	pub topics: Vec<H256>,
+	/// Data
+	pub data: Bytes,
+	/// Block hash
+	pub block_hash: H256,
+	/// Block number
+	pub block_number: U256,
+	/// Transaction hash
+	pub transaction_hash: H256,
+	/// Index of the transaction in the block
+	pub transaction_index: U256,
+	/// Index of the log in the block
+	pub log_index: U256,
+	/// Index of the log in the transaction
+	pub transaction_log_index: U256,
+	/// Whether the log was removed by a chain reorganization, always `false`
+	pub removed: bool,
+}
+
+/// Transaction receipt
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	/// Transaction hash
+	pub transaction_hash: H256,
+	/// Index of the transaction in the block
+	pub transaction_index: U256,
+	/// Block hash
+	pub block_hash: H256,
+	/// Block number
+	pub block_number: U256,
+	/// From
+	pub from: H160,
+	/// To, `None` for contract creations
+	pub to: Option<H160>,
+	/// The created contract
+	pub contract_address: Option<H160>,
+	/// Used gas
+	pub gas_used: U256,
+	/// Total used gas of the transactions in the block up to this one
+	pub cumulative_gas_used: U256,
+	/// `1` if the execution succeeded, otherwise `0`
+	pub status: U256,
+	/// Logs
+	pub logs: Vec<Log>,
+	/// Bloom filter of the logs
+	pub logs_bloom: Bloom,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	#[test]
+	fn deserialize_filter_works() {
+		let filter: Filter = serde_json::from_str(
+			r#"{
+				"fromBlock": "earliest",
+				"toBlock": "0x10",
+				"address": "0x1111111111222222222233333333334444444444",
+				"topics": [null, ["0x0000000000000000000000000000000000000000000000000000000000000001", "0x0000000000000000000000000000000000000000000000000000000000000002"]]
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(filter.from_block, Some(BlockNumber::Tag(BlockTag::Earliest)));
+		assert_eq!(filter.to_block, Some(BlockNumber::Number(16.into())));
+
+		let address = H160::from_str("0x1111111111222222222233333333334444444444").unwrap();
+		let log = |address, topics| EvmLog {
+			address,
+			topics,
+			data: vec![],
+		};
+		assert!(filter.matches(&log(address, vec![H256::repeat_byte(0xff), H256::from_low_u64_be(2)])));
+		assert!(!filter.matches(&log(address, vec![H256::repeat_byte(0xff), H256::from_low_u64_be(3)])));
+		assert!(!filter.matches(&log(address, vec![H256::repeat_byte(0xff)])));
+		assert!(!filter.matches(&log(
+			H160::zero(),
+			vec![H256::repeat_byte(0xff), H256::from_low_u64_be(2)]
+		)));
+
+		// matches any log
+		assert!(Filter::default().matches(&log(H160::zero(), vec![])));
+	}
+
+	#[test]
+	fn matches_bloom_works() {
+		let address = H160::repeat_byte(0x11);
+		let topic = H256::repeat_byte(0x22);
+		let mut bloom = Bloom::default();
+		bloom.accrue(BloomInput::Raw(address.as_bytes()));
+		bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+
+		let filter = |address: Option<ValueOrArray<H160>>, topics: Option<Vec<Option<ValueOrArray<H256>>>>| Filter {
+			address,
+			topics,
+			..Default::default()
+		};
+		assert!(filter(None, None).matches_bloom(&bloom));
+		assert!(filter(Some(ValueOrArray::Value(address)), None).matches_bloom(&bloom));
+		assert!(filter(
+			Some(ValueOrArray::Array(vec![H160::zero(), address])),
+			Some(vec![None, Some(ValueOrArray::Value(topic))])
+		)
+		.matches_bloom(&bloom));
+		assert!(!filter(Some(ValueOrArray::Value(H160::zero())), None).matches_bloom(&bloom));
+		assert!(!filter(None, Some(vec![Some(ValueOrArray::Value(H256::zero()))])).matches_bloom(&bloom));
+	}
+}
@@ -29,7 +29,7 @@ use rustc_hex::ToHex;
 use sc_rpc_api::DenyUnsafe;
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, Decode, H160, U256};
+use sp_core::{Bytes, Decode, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
@@ -39,19 +39,29 @@ use sp_runtime::{
 };
 use std::{marker::PhantomData, sync::Arc};
 
-use call_request::{CallRequest, EstimateResourcesResponse};
+use call_request::{CallRequest, ContractVerificationResponse, EstimateResourcesResponse};
 pub use module_evm::{ExitError, ExitReason};
 pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
-use primitives::evm::{BlockLimits, EstimateResourcesRequest};
+use primitives::evm::{BlockLimits, CallInfo, CreateInfo, EstimateResourcesRequest};
 
 mod call_request;
+pub mod estimate;
+pub mod eth;
+mod eth_types;
+pub mod pool;
+pub mod state;
+
+pub use estimate::EstimateConfig;
+pub use eth::{Eth, EthApiServer, EthMapping};
+pub use pool::{ExecutionError, ExecutionPool, ExecutionPoolConfig};
+pub use state::{CachedStateExecutor, StateExecutor};
 
 /// EVM rpc interface.
 #[rpc(client, server)]
 pub trait EVMApi<BlockHash> {
 	/// Call contract, returning the output data.
 	#[method(name = "evm_call")]
-	fn call(&self, call_request: CallRequest, at: Option<BlockHash>) -> RpcResult<Bytes>;
+	async fn call(&self, call_request: CallRequest, at: Option<BlockHash>) -> RpcResult<Bytes>;
 
 	/// Estimate resources needed for execution of given contract.
 	#[method(name = "evm_estimateResources")]
@@ -65,6 +75,18 @@ pub trait EVMApi<BlockHash> {
 	/// Get max gas and storage limits per transaction
 	#[method(name = "evm_blockLimits")]
 	fn block_limits(&self, at: Option<BlockHash>) -> RpcResult<BlockLimits>;
+
+	/// Get the base fee per gas
+	#[method(name = "evm_baseFeePerGas")]
+	fn base_fee_per_gas(&self, at: Option<BlockHash>) -> RpcResult<U256>;
+
+	/// Get the source verification of a contract
+	#[method(name = "evm_contractVerification")]
+	fn contract_verification(
+		&self,
+		address: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ContractVerificationResponse>>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -83,6 +105,14 @@ fn invalid_params<T: ToString>(message: T) -> JsonRpseeError {
 	)))
 }
 
+fn execution_err(err: ExecutionError) -> JsonRpseeError {
+	match err {
+		ExecutionError::Timeout => internal_err("execution timeout"),
+		ExecutionError::Canceled => internal_err("execution canceled"),
+		ExecutionError::Failed(message) => internal_err(message),
+	}
+}
+
 #[allow(dead_code)]
 fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult<()> {
 	match reason {
@@ -141,14 +171,23 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 pub struct EVM<B, C, Balance> {
 	client: Arc<C>,
 	_deny_unsafe: DenyUnsafe,
+	estimate_config: EstimateConfig,
+	execution_pool: Arc<ExecutionPool>,
 	_marker: PhantomData<(B, Balance)>,
 }
 
 impl<B, C, Balance> EVM<B, C, Balance> {
-	pub fn new(client: Arc<C>, _deny_unsafe: DenyUnsafe) -> Self {
+	pub fn new(
+		client: Arc<C>,
+		_deny_unsafe: DenyUnsafe,
+		estimate_config: EstimateConfig,
+		execution_pool: Arc<ExecutionPool>,
+	) -> Self {
 		Self {
 			client,
 			_deny_unsafe,
+			estimate_config,
+			execution_pool,
 			_marker: Default::default(),
 		}
 	}
@@ -161,15 +200,13 @@ fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 #[async_trait]
 impl<B, C, Balance> EVMApiServer<<B as BlockT>::Hash> for EVM<B, C, Balance>
 where
-	B: BlockT,
+	B: BlockT<Hash = H256>,
 	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
 	C::Api: TransactionPaymentApi<B, Balance>,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
-	fn call(&self, request: CallRequest, at: Option<<B as BlockT>::Hash>) -> RpcResult<Bytes> {
-		let api = self.client.runtime_api();
-
+	async fn call(&self, request: CallRequest, at: Option<<B as BlockT>::Hash>) -> RpcResult<Bytes> {
 		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
 		let block_id = BlockId::Hash(hash);
@@ -228,21 +265,27 @@ where
 		let balance_value =
 			balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
 
-		match to {
+		let from = from.unwrap_or_default();
+		let (exit_reason, value) = match to {
 			Some(to) => {
-				let info = api
-					.call(
-						&block_id,
-						from.unwrap_or_default(),
-						to,
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						access_list,
-						true,
+				let info = self
+					.execution_pool
+					.call_at::<Result<CallInfo, sp_runtime::DispatchError>>(
+						hash,
+						"EVMRuntimeRPCApi_call",
+						(
+							from,
+							to,
+							data,
+							balance_value,
+							gas_limit,
+							storage_limit,
+							access_list,
+							true,
+						),
 					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.await
+					.map_err(execution_err)?
 					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
 
 				log::debug!(
@@ -250,23 +293,19 @@ where
 					"rpc call, info.exit_reason: {:?}, info.value: {:?}",
 					info.exit_reason, info.value,
 				);
-				error_on_execution_failure(&info.exit_reason, &info.value)?;
 
-				Ok(Bytes(info.value))
+				(info.exit_reason, info.value)
 			}
 			None => {
-				let info = api
-					.create(
-						&block_id,
-						from.unwrap_or_default(),
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						access_list,
-						true,
+				let info = self
+					.execution_pool
+					.call_at::<Result<CreateInfo, sp_runtime::DispatchError>>(
+						hash,
+						"EVMRuntimeRPCApi_create",
+						(from, data, balance_value, gas_limit, storage_limit, access_list, true),
 					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.await
+					.map_err(execution_err)?
 					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
 
 				log::debug!(
@@ -274,11 +313,17 @@ where
 					"rpc create, info.exit_reason: {:?}, info.value: {:?}",
 					info.exit_reason, info.value,
 				);
-				error_on_execution_failure(&info.exit_reason, &[])?;
 
-				Ok(Bytes(info.value[..].to_vec()))
+				(info.exit_reason, info.value[..].to_vec())
 			}
+		};
+
+		match to {
+			Some(_) => error_on_execution_failure(&exit_reason, &value)?,
+			None => error_on_execution_failure(&exit_reason, &[])?,
 		}
+
+		Ok(Bytes(value))
 	}
 
 	fn estimate_resources(
@@ -322,9 +367,6 @@ where
 			block_limits.max_storage_limit,
 		);
 
-		// Determine the highest possible gas limits
-		let mut highest = gas_limit;
-
 		let request = CallRequest {
 			from: Some(from),
 			to: request.to,
@@ -348,8 +390,8 @@ where
 			used_storage: i32,
 		}
 
-		// Create a helper to check if a gas allowance results in an executable transaction
-		let executable = move |request: CallRequest, gas: u64| -> RpcResult<ExecutableResult> {
+		// Create a helper to check if a gas and storage allowance results in an executable transaction
+		let executable = move |request: CallRequest, gas: u64, storage: u32| -> RpcResult<ExecutableResult> {
 			let CallRequest {
 				from,
 				to,
@@ -365,8 +407,9 @@ where
 				storage_limit.expect("Cannot be none, value set when request is constructed above; qed");
 			let data = data.map(|d| d.0).unwrap_or_default();
 
-			// Use request gas limit only if it less than gas_limit parameter
+			// Use request limits only if they are less than the limit parameters
 			let gas_limit = core::cmp::min(gas_limit, gas);
+			let storage_limit = core::cmp::min(storage_limit, storage);
 
 			let balance_value = if let Some(value) = value {
 				to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
@@ -428,13 +471,13 @@ where
 		};
 
 		// Verify that the transaction succeed with highest capacity
-		let cap = highest;
+		let cap = gas_limit;
 		let ExecutableResult {
 			data,
 			exit_reason,
 			used_gas,
 			used_storage,
-		} = executable(request.clone(), highest)?;
+		} = executable(request.clone(), gas_limit, storage_limit)?;
 		match exit_reason {
 			ExitReason::Succeed(_) => (),
 			ExitReason::Error(ExitError::OutOfGas) => {
@@ -449,7 +492,7 @@ where
 					// with less block gas limit, so we must reexecute with block gas limit to
 					// know if the revert is due to a lack of gas or not.
 					let ExecutableResult { data, exit_reason, .. } =
-						executable(request.clone(), block_limits.max_gas_limit)?;
+						executable(request.clone(), block_limits.max_gas_limit, storage_limit)?;
 					match exit_reason {
 						ExitReason::Succeed(_) => {
 							return Err(internal_err(format!("gas required exceeds allowance {}", cap)))
@@ -466,36 +509,29 @@ where
 			other => error_on_execution_failure(&other, &data)?,
 		};
 
-		// rpc_binary_search_estimate block
-		{
-			// Define the lower bound of the binary search
-			let mut lowest = 21_000;
-
-			// Start close to the used gas for faster binary search
-			let mut mid = std::cmp::min(used_gas * 3, (highest + lowest) / 2);
-
-			// Execute the binary search and hone in on an executable gas limit.
-			let mut previous_highest = highest;
-			while (highest - lowest) > 1 {
-				let ExecutableResult { data, exit_reason, .. } = executable(request.clone(), mid)?;
-				match exit_reason {
-					ExitReason::Succeed(_) => {
-						highest = mid;
-						// If the variation in the estimate is less than 10%,
-						// then the estimate is considered sufficiently accurate.
-						if (previous_highest - highest) * 10 / previous_highest < 1 {
-							break;
-						}
-						previous_highest = highest;
-					}
-					ExitReason::Revert(_) | ExitReason::Error(ExitError::OutOfGas) => {
-						lowest = mid;
-					}
-					other => error_on_execution_failure(&other, &data)?,
-				}
-				mid = (highest + lowest) / 2;
+		// Search the lowest gas limit the transaction succeeds with, the transaction may revert or run
+		// out of gas with less gas due to gas-dependent branching
+		let gas = estimate::estimate_gas(used_gas, gas_limit, &self.estimate_config, |gas| {
+			let ExecutableResult { data, exit_reason, .. } = executable(request.clone(), gas, storage_limit)?;
+			match exit_reason {
+				ExitReason::Succeed(_) => Ok(true),
+				ExitReason::Revert(_) | ExitReason::Error(ExitError::OutOfGas) => Ok(false),
+				other => error_on_execution_failure(&other, &data).map(|_| false),
 			}
-		}
+		})?;
+
+		// Search the lowest storage limit the transaction succeeds with the estimated gas. The
+		// transaction is known to succeed with the storage limit, so any failure with less storage
+		// only makes the estimate higher.
+		let storage = estimate::estimate_storage(used_storage, storage_limit, &self.estimate_config, |storage| {
+			Ok::<_, JsonRpseeError>(matches!(
+				executable(request.clone(), gas, storage),
+				Ok(ExecutableResult {
+					exit_reason: ExitReason::Succeed(_),
+					..
+				})
+			))
+		})?;
 
 		let uxt: <B as traits::Block>::Extrinsic = Decode::decode(&mut &*unsigned_extrinsic)
 			.map_err(|e| internal_err(format!("execution error: Unable to dry run extrinsic {:?}", e)))?;
@@ -511,8 +547,9 @@ where
 			.map_or_else(Default::default, |inclusion| inclusion.adjusted_weight_fee);
 
 		Ok(EstimateResourcesResponse {
-			gas: highest,
-			storage: used_storage,
+			gas,
+			// keep the refunded storage of the transactions which free storage
+			storage: used_storage.max(storage.saturated_into()),
 			weight_fee: adjusted_weight_fee.into(),
 		})
 	}
@@ -548,6 +585,70 @@ where
 
 		Ok(block_limits)
 	}
+
+	fn base_fee_per_gas(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<U256> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let block_id = BlockId::Hash(hash);
+
+		let version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(&block_id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| {
+				internal_err(format!(
+					"Could not find `EVMRuntimeRPCApi` api for block `{:?}`.",
+					&block_id
+				))
+			})?;
+
+		if version < 3 {
+			return Err(internal_err("base fee per gas is not supported by the runtime"));
+		}
+
+		let base_fee_per_gas = self
+			.client
+			.runtime_api()
+			.base_fee_per_gas(&block_id)
+			.map_err(|e| internal_err(format!("runtime error: Unable to query base fee per gas {:?}", e)))?;
+
+		Ok(base_fee_per_gas.into())
+	}
+
+	fn contract_verification(
+		&self,
+		address: H160,
+		at: Option<<B as BlockT>::Hash>,
+	) -> RpcResult<Option<ContractVerificationResponse>> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let block_id = BlockId::Hash(hash);
+
+		let version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(&block_id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| {
+				internal_err(format!(
+					"Could not find `EVMRuntimeRPCApi` api for block `{:?}`.",
+					&block_id
+				))
+			})?;
+
+		if version < 5 {
+			return Err(internal_err("contract verification is not supported by the runtime"));
+		}
+
+		let verification = self
+			.client
+			.runtime_api()
+			.contract_verification(&block_id, address)
+			.map_err(|e| internal_err(format!("runtime error: Unable to query contract verification {:?}", e)))?;
+
+		Ok(verification.map(Into::into))
+	}
 }
 
 #[test]
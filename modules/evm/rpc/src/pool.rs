@@ -0,0 +1,435 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A pool of the read-only executions of the RPC calls.
+//!
+//! The executions run on the blocking tasks of the node, at most `max_concurrency` of them at the
+//! same time. The state of a block never changes, so the identical requests at the same block
+//! being executed share one execution, and the results of the recent blocks are cached. The
+//! runtime calls run against the state of the block whose trie nodes are cached, see
+//! [`crate::state`].
+
+use futures::{
+	channel::oneshot,
+	future::{BoxFuture, FutureExt, Shared},
+};
+use parking_lot::Mutex;
+use sp_core::{hashing::blake2_256, traits::SpawnNamed, H256};
+use sp_runtime::codec::{Decode, Encode};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+	time::Duration,
+};
+use tokio::sync::Semaphore;
+
+use crate::state::StateExecutor;
+
+/// The config of the execution pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionPoolConfig {
+	/// The max number of the concurrent executions.
+	pub max_concurrency: usize,
+	/// The timeout of an execution, including the time waiting for the other executions.
+	pub timeout: Duration,
+	/// The number of the recent blocks whose results and state are cached.
+	pub cache_blocks: usize,
+	/// The max number of the cached results of a block.
+	pub cache_size: usize,
+	/// The max size of the cached trie nodes of a block, in bytes.
+	pub state_cache_size: usize,
+}
+
+impl Default for ExecutionPoolConfig {
+	fn default() -> Self {
+		Self {
+			max_concurrency: 8,
+			timeout: Duration::from_secs(10),
+			cache_blocks: 4,
+			cache_size: 1024,
+			state_cache_size: 64 * 1024 * 1024,
+		}
+	}
+}
+
+/// The error of an execution of the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionError {
+	/// The execution didn't finish within the timeout.
+	Timeout,
+	/// The execution was dropped before finishing.
+	Canceled,
+	/// The execution failed with the message.
+	Failed(String),
+}
+
+type Key = (H256, [u8; 32]);
+type Execution = Shared<BoxFuture<'static, Result<Vec<u8>, ExecutionError>>>;
+
+/// The pool executing the read-only EVM calls of the RPC methods.
+pub struct ExecutionPool {
+	config: ExecutionPoolConfig,
+	spawner: Arc<dyn SpawnNamed>,
+	state: Arc<dyn StateExecutor>,
+	slots: Arc<Semaphore>,
+	/// The executions in progress.
+	executions: Mutex<HashMap<Key, Execution>>,
+	/// The encoded results of the recent blocks, the oldest block first.
+	cache: Mutex<VecDeque<(H256, HashMap<[u8; 32], Vec<u8>>)>>,
+}
+
+impl ExecutionPool {
+	pub fn new(config: ExecutionPoolConfig, spawner: Arc<dyn SpawnNamed>, state: Arc<dyn StateExecutor>) -> Self {
+		Self {
+			config,
+			spawner,
+			state,
+			slots: Arc::new(Semaphore::new(config.max_concurrency.max(1))),
+			executions: Default::default(),
+			cache: Default::default(),
+		}
+	}
+
+	/// Execute `f` at the block `block`. `request` identifies the execution, it should contain the
+	/// name of the RPC method and all the parameters `f` depends on.
+	pub async fn execute<T, F>(&self, block: H256, request: impl Encode, f: F) -> Result<T, ExecutionError>
+	where
+		T: Encode + Decode + 'static,
+		F: FnOnce() -> Result<T, String> + Send + 'static,
+	{
+		let key = (block, blake2_256(&request.encode()));
+		if let Some(output) = self.cached(&key) {
+			return Self::decode(output);
+		}
+
+		let execution = self
+			.executions
+			.lock()
+			.entry(key)
+			.or_insert_with(|| self.spawn(f).shared())
+			.clone();
+		let result = execution.await;
+		self.executions.lock().remove(&key);
+
+		let output = result?;
+		self.cache(key, output.clone());
+		Self::decode(output)
+	}
+
+	/// Call the runtime API `method` with `args` at the block `block`, against the cached state of
+	/// the block.
+	pub async fn call_at<T>(&self, block: H256, method: &'static str, args: impl Encode) -> Result<T, ExecutionError>
+	where
+		T: Encode + Decode + 'static,
+	{
+		let call_data = args.encode();
+		let request = (method, &call_data).encode();
+		let state = self.state.clone();
+		self.execute(block, request, move || {
+			let output = state.call_at(block, method, &call_data)?;
+			T::decode(&mut &output[..]).map_err(|e| format!("invalid output: {:?}", e))
+		})
+		.await
+	}
+
+	fn spawn<T, F>(&self, f: F) -> BoxFuture<'static, Result<Vec<u8>, ExecutionError>>
+	where
+		T: Encode + 'static,
+		F: FnOnce() -> Result<T, String> + Send + 'static,
+	{
+		let slots = self.slots.clone();
+		let spawner = self.spawner.clone();
+		let execution = async move {
+			let slot = slots.acquire_owned().await.map_err(|_| ExecutionError::Canceled)?;
+			let (sender, receiver) = oneshot::channel();
+			spawner.spawn_blocking(
+				"evm-rpc-execution",
+				Some("rpc"),
+				async move {
+					// the slot is held until the execution finishes, even after the timeout
+					let _ = sender.send(f().map(|output| output.encode()));
+					drop(slot);
+				}
+				.boxed(),
+			);
+			receiver
+				.await
+				.map_err(|_| ExecutionError::Canceled)?
+				.map_err(ExecutionError::Failed)
+		};
+
+		let timeout = self.config.timeout;
+		async move {
+			tokio::time::timeout(timeout, execution)
+				.await
+				.map_err(|_| ExecutionError::Timeout)?
+		}
+		.boxed()
+	}
+
+	fn cached(&self, (block, request): &Key) -> Option<Vec<u8>> {
+		self.cache
+			.lock()
+			.iter()
+			.find(|(hash, _)| hash == block)
+			.and_then(|(_, results)| results.get(request).cloned())
+	}
+
+	fn cache(&self, (block, request): Key, output: Vec<u8>) {
+		if self.config.cache_blocks == 0 || self.config.cache_size == 0 {
+			return;
+		}
+
+		let mut cache = self.cache.lock();
+		let index = match cache.iter().position(|(hash, _)| *hash == block) {
+			Some(index) => index,
+			None => {
+				if cache.len() >= self.config.cache_blocks {
+					cache.pop_front();
+				}
+				cache.push_back((block, Default::default()));
+				cache.len() - 1
+			}
+		};
+
+		// keep the results cached first, which are the hot ones of the block
+		let results = &mut cache[index].1;
+		if results.len() < self.config.cache_size {
+			results.insert(request, output);
+		}
+	}
+
+	fn decode<T: Decode>(output: Vec<u8>) -> Result<T, ExecutionError> {
+		T::decode(&mut &output[..]).map_err(|e| ExecutionError::Failed(format!("invalid output: {:?}", e)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::testing::TaskExecutor;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// A state calling the runtime API `double` only.
+	struct MockState;
+
+	impl StateExecutor for MockState {
+		fn call_at(&self, _block: H256, method: &str, call_data: &[u8]) -> Result<Vec<u8>, String> {
+			match method {
+				"double" => Ok((u32::decode(&mut &call_data[..]).map_err(|e| e.to_string())? * 2).encode()),
+				_ => Err(format!("unknown method: {}", method)),
+			}
+		}
+	}
+
+	fn pool(config: ExecutionPoolConfig) -> ExecutionPool {
+		ExecutionPool::new(config, Arc::new(TaskExecutor::new()), Arc::new(MockState))
+	}
+
+	/// An execution returning `output` after `delay`, counting the executions.
+	fn execution(
+		output: u32,
+		delay: Duration,
+		count: Arc<AtomicUsize>,
+	) -> impl FnOnce() -> Result<u32, String> + Send + 'static {
+		move || {
+			count.fetch_add(1, Ordering::SeqCst);
+			std::thread::sleep(delay);
+			Ok(output)
+		}
+	}
+
+	#[tokio::test]
+	async fn execute_works() {
+		let pool = pool(Default::default());
+		let count = Arc::new(AtomicUsize::new(0));
+
+		assert_eq!(
+			pool.execute(
+				H256::zero(),
+				("call", 1u32),
+				execution(1, Duration::ZERO, count.clone())
+			)
+			.await,
+			Ok(1)
+		);
+		assert_eq!(
+			pool.execute(
+				H256::zero(),
+				("call", 2u32),
+				execution(2, Duration::ZERO, count.clone())
+			)
+			.await,
+			Ok(2)
+		);
+		assert_eq!(count.load(Ordering::SeqCst), 2);
+
+		// the failures are not cached
+		assert_eq!(
+			pool.execute(H256::zero(), ("call", 3u32), || Err::<u32, _>("failed".into()))
+				.await,
+			Err(ExecutionError::Failed("failed".into()))
+		);
+		assert_eq!(
+			pool.execute(
+				H256::zero(),
+				("call", 3u32),
+				execution(3, Duration::ZERO, count.clone())
+			)
+			.await,
+			Ok(3)
+		);
+	}
+
+	#[tokio::test]
+	async fn call_at_works() {
+		let pool = pool(Default::default());
+
+		assert_eq!(pool.call_at::<u32>(H256::zero(), "double", 2u32).await, Ok(4));
+		assert_eq!(
+			pool.call_at::<u32>(H256::zero(), "triple", 2u32).await,
+			Err(ExecutionError::Failed("unknown method: triple".into()))
+		);
+		// the output is decoded
+		assert!(matches!(
+			pool.call_at::<(u32, u32)>(H256::zero(), "double", 3u32).await,
+			Err(ExecutionError::Failed(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn execute_caches_results_per_block() {
+		let pool = pool(ExecutionPoolConfig {
+			cache_blocks: 2,
+			cache_size: 2,
+			..Default::default()
+		});
+		let count = Arc::new(AtomicUsize::new(0));
+		let execute = {
+			let pool = &pool;
+			let count = count.clone();
+			move |block: u64, request: u32| {
+				pool.execute(
+					H256::from_low_u64_be(block),
+					("call", request),
+					execution(request, Duration::ZERO, count.clone()),
+				)
+			}
+		};
+
+		assert_eq!(execute(1, 1).await, Ok(1));
+		assert_eq!(execute(1, 1).await, Ok(1));
+		assert_eq!(count.load(Ordering::SeqCst), 1);
+
+		// the cache of a block is bounded
+		assert_eq!(execute(1, 2).await, Ok(2));
+		assert_eq!(execute(1, 3).await, Ok(3));
+		assert_eq!(execute(1, 3).await, Ok(3));
+		assert_eq!(execute(1, 2).await, Ok(2));
+		assert_eq!(count.load(Ordering::SeqCst), 4);
+
+		// the same request at another block is executed again
+		assert_eq!(execute(2, 1).await, Ok(1));
+		assert_eq!(count.load(Ordering::SeqCst), 5);
+
+		// the oldest block is evicted
+		assert_eq!(execute(3, 1).await, Ok(1));
+		assert_eq!(execute(2, 1).await, Ok(1));
+		assert_eq!(count.load(Ordering::SeqCst), 6);
+		assert_eq!(execute(1, 1).await, Ok(1));
+		assert_eq!(count.load(Ordering::SeqCst), 7);
+	}
+
+	#[tokio::test]
+	async fn execute_deduplicates_requests() {
+		let pool = pool(ExecutionPoolConfig {
+			cache_blocks: 0,
+			..Default::default()
+		});
+		let count = Arc::new(AtomicUsize::new(0));
+		let delay = Duration::from_millis(100);
+
+		let (a, b) = futures::join!(
+			pool.execute(H256::zero(), ("call", 1u32), execution(1, delay, count.clone())),
+			pool.execute(H256::zero(), ("call", 1u32), execution(1, delay, count.clone())),
+		);
+		assert_eq!((a, b), (Ok(1), Ok(1)));
+		assert_eq!(count.load(Ordering::SeqCst), 1);
+
+		// nothing is cached without the cache
+		assert_eq!(
+			pool.execute(
+				H256::zero(),
+				("call", 1u32),
+				execution(1, Duration::ZERO, count.clone())
+			)
+			.await,
+			Ok(1)
+		);
+		assert_eq!(count.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn execute_limits_concurrency() {
+		let pool = pool(ExecutionPoolConfig {
+			max_concurrency: 2,
+			..Default::default()
+		});
+		let running = Arc::new(AtomicUsize::new(0));
+		let max_running = Arc::new(AtomicUsize::new(0));
+		let execute = {
+			let pool = &pool;
+			let running = running.clone();
+			let max_running = max_running.clone();
+			move |request: u32| {
+				let running = running.clone();
+				let max_running = max_running.clone();
+				pool.execute(H256::zero(), ("call", request), move || {
+					let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+					max_running.fetch_max(now, Ordering::SeqCst);
+					std::thread::sleep(Duration::from_millis(50));
+					running.fetch_sub(1, Ordering::SeqCst);
+					Ok(request)
+				})
+			}
+		};
+
+		let results = futures::future::join_all((0..6).map(execute)).await;
+		assert_eq!(results, (0..6).map(Ok).collect::<Vec<_>>());
+		assert!(max_running.load(Ordering::SeqCst) <= 2);
+	}
+
+	#[tokio::test]
+	async fn execute_times_out() {
+		let pool = pool(ExecutionPoolConfig {
+			timeout: Duration::from_millis(50),
+			..Default::default()
+		});
+		let count = Arc::new(AtomicUsize::new(0));
+
+		assert_eq!(
+			pool.execute(
+				H256::zero(),
+				("call", 1u32),
+				execution(1, Duration::from_millis(500), count.clone())
+			)
+			.await,
+			Err(ExecutionError::Timeout)
+		);
+	}
+}
@@ -0,0 +1,245 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The state the executions of the pool run against.
+//!
+//! The runtime calls are executed against the state of the block with the trie nodes read from the
+//! database cached per block, so the code and the storage of the hot contracts are read from the
+//! database once for all the executions at the block. The trie nodes are addressed by their hash,
+//! so a cached node is never stale.
+
+use hash_db::Prefix;
+use parking_lot::{Mutex, RwLock};
+use sc_client_api::Backend;
+use sp_core::{
+	traits::{CodeExecutor, SpawnNamed},
+	H256,
+};
+use sp_externalities::Extensions;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashFor},
+};
+use sp_state_machine::{
+	backend::BackendRuntimeCode, Backend as StateBackend, DBValue, ExecutionStrategy, OverlayedChanges, StateMachine,
+	TrieBackend, TrieBackendStorage,
+};
+use std::{
+	collections::{HashMap, VecDeque},
+	marker::PhantomData,
+	sync::Arc,
+};
+
+/// Executes the runtime calls of the executions.
+pub trait StateExecutor: Send + Sync {
+	/// Call the runtime API `method` with the encoded `call_data` at the block `block`, returning
+	/// the encoded output.
+	fn call_at(&self, block: H256, method: &str, call_data: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// The trie nodes of a block read by the executions.
+#[derive(Default)]
+struct NodeCache {
+	nodes: HashMap<H256, DBValue>,
+	size: usize,
+}
+
+/// The trie storage of a block, reading the nodes from the cache of the block first.
+struct CachedTrieStorage<'a, S> {
+	storage: &'a S,
+	cache: &'a RwLock<NodeCache>,
+	cache_size: usize,
+}
+
+impl<'a, H, S> TrieBackendStorage<H> for CachedTrieStorage<'a, S>
+where
+	H: hash_db::Hasher<Out = H256>,
+	S: TrieBackendStorage<H>,
+{
+	type Overlay = S::Overlay;
+
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		if let Some(node) = self.cache.read().nodes.get(key) {
+			return Ok(Some(node.clone()));
+		}
+
+		let node = self.storage.get(key, prefix)?;
+		if let Some(node) = &node {
+			// keep the nodes cached first, which are the hot ones of the block
+			let mut cache = self.cache.write();
+			let size = cache.size.saturating_add(node.len());
+			if size <= self.cache_size && !cache.nodes.contains_key(key) {
+				cache.size = size;
+				cache.nodes.insert(*key, node.clone());
+			}
+		}
+		Ok(node)
+	}
+}
+
+/// Executes the runtime calls against the state of the client backend, with the trie nodes of
+/// the recent blocks cached.
+pub struct CachedStateExecutor<B, BE, E> {
+	backend: Arc<BE>,
+	executor: E,
+	spawner: Box<dyn SpawnNamed>,
+	/// The number of the recent blocks whose trie nodes are cached.
+	cache_blocks: usize,
+	/// The max size of the cached trie nodes of a block, in bytes.
+	cache_size: usize,
+	/// The trie nodes of the recent blocks, the oldest block first.
+	cache: Mutex<VecDeque<(H256, Arc<RwLock<NodeCache>>)>>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, BE, E> CachedStateExecutor<B, BE, E> {
+	pub fn new(
+		backend: Arc<BE>,
+		executor: E,
+		spawner: Box<dyn SpawnNamed>,
+		cache_blocks: usize,
+		cache_size: usize,
+	) -> Self {
+		Self {
+			backend,
+			executor,
+			spawner,
+			cache_blocks,
+			cache_size,
+			cache: Default::default(),
+			_marker: Default::default(),
+		}
+	}
+
+	/// The cached trie nodes of `block`, a new cache is created for a block not cached.
+	fn cache_of(&self, block: H256) -> Arc<RwLock<NodeCache>> {
+		let mut cache = self.cache.lock();
+		if let Some((_, nodes)) = cache.iter().find(|(hash, _)| *hash == block) {
+			return nodes.clone();
+		}
+
+		let nodes = Arc::new(RwLock::new(NodeCache::default()));
+		if self.cache_blocks > 0 {
+			if cache.len() >= self.cache_blocks {
+				cache.pop_front();
+			}
+			cache.push_back((block, nodes.clone()));
+		}
+		nodes
+	}
+}
+
+impl<B, BE, E> StateExecutor for CachedStateExecutor<B, BE, E>
+where
+	B: BlockT<Hash = H256>,
+	BE: Backend<B>,
+	E: CodeExecutor + Clone + 'static,
+{
+	fn call_at(&self, block: H256, method: &str, call_data: &[u8]) -> Result<Vec<u8>, String> {
+		let state = self
+			.backend
+			.state_at(BlockId::Hash(block))
+			.map_err(|err| format!("blockchain error: {:?}", err))?;
+		let trie_backend = state
+			.as_trie_backend()
+			.ok_or_else(|| "state error: the state is not a trie backend".to_string())?;
+
+		let cache = self.cache_of(block);
+		let cached_backend = TrieBackend::<_, HashFor<B>>::new(
+			CachedTrieStorage {
+				storage: trie_backend.backend_storage(),
+				cache: &*cache,
+				cache_size: self.cache_size,
+			},
+			*trie_backend.root(),
+		);
+
+		let runtime_code = BackendRuntimeCode::new(&cached_backend)
+			.runtime_code()
+			.map_err(|err| format!("runtime error: {:?}", err))?;
+		let mut changes = OverlayedChanges::default();
+
+		StateMachine::new(
+			&cached_backend,
+			&mut changes,
+			&self.executor,
+			method,
+			call_data,
+			Extensions::default(),
+			&runtime_code,
+			self.spawner.clone(),
+		)
+		.execute(ExecutionStrategy::NativeElseWasm)
+		.map_err(|err| format!("runtime error: {:?}", err))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::BlakeTwo256;
+	use sp_state_machine::InMemoryBackend;
+	use sp_storage::StateVersion;
+
+	fn cached_backend<'a>(
+		state: &'a InMemoryBackend<BlakeTwo256>,
+		cache: &'a RwLock<NodeCache>,
+		cache_size: usize,
+	) -> TrieBackend<CachedTrieStorage<'a, sp_state_machine::MemoryDB<BlakeTwo256>>, BlakeTwo256> {
+		TrieBackend::new(
+			CachedTrieStorage {
+				storage: state.backend_storage(),
+				cache,
+				cache_size,
+			},
+			*state.root(),
+		)
+	}
+
+	#[test]
+	fn cached_trie_storage_works() {
+		let state = InMemoryBackend::<BlakeTwo256>::from((
+			vec![(None, (0u8..16).map(|i| (vec![i], Some(vec![i; 64]))).collect())],
+			StateVersion::V1,
+		));
+
+		let cache = RwLock::new(NodeCache::default());
+		let backend = cached_backend(&state, &cache, 1024 * 1024);
+		for i in 0u8..16 {
+			assert_eq!(backend.storage(&[i]), Ok(Some(vec![i; 64])));
+		}
+		let (nodes, size) = (cache.read().nodes.len(), cache.read().size);
+		assert!(nodes > 0);
+
+		// the nodes are cached once
+		let backend = cached_backend(&state, &cache, 1024 * 1024);
+		for i in 0u8..16 {
+			assert_eq!(backend.storage(&[i]), Ok(Some(vec![i; 64])));
+		}
+		assert_eq!((cache.read().nodes.len(), cache.read().size), (nodes, size));
+
+		// the cache is bounded
+		let cache = RwLock::new(NodeCache::default());
+		let backend = cached_backend(&state, &cache, 200);
+		for i in 0u8..16 {
+			assert_eq!(backend.storage(&[i]), Ok(Some(vec![i; 64])));
+		}
+		assert!(cache.read().size <= 200);
+		assert!(cache.read().nodes.len() < nodes);
+	}
+}
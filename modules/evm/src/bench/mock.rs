@@ -174,6 +174,7 @@ impl FindAuthor<AccountId32> for AuthorGiven {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(1);
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -189,7 +190,10 @@ impl Config for Runtime {
 	type TransferAll = Currencies;
 	type NewContractExtraBytes = ConstU32<100>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU32<100>;
+	type UpgradeDelay = ConstU32<10>;
 	type TxFeePerGas = ConstU128<20_000_000>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 
 	type Event = Event;
 	type PrecompilesType = ();
@@ -199,6 +203,7 @@ impl Config for Runtime {
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
@@ -263,6 +268,7 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeePayer = ();
 }
 
 pub struct MockDEXIncentives;
@@ -290,6 +296,7 @@ impl module_dex::Config for Runtime {
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = MockErc20InfoMapping;
 	type WeightInfo = ();
+	type GasToWeight = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId32>;
 	type ExtendedProvisioningBlocks = ConstU32<0>;
@@ -0,0 +1,87 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_evm
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights are estimated from the storage
+//! accesses and the benchmarked weights of the similar calls. Replace them by running the
+//! `module_evm` benchmarks, which generate them into the weights of the module and the runtimes.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: EVM ContractLastTouched (r:1 w:1)
+// Storage: EVM Accounts (r:1 w:1)
+// Storage: EVM ContractStorageSizes (r:1 w:1)
+// Storage: IdleScheduler NextTaskId (r:1 w:1)
+// Storage: IdleScheduler Tasks (r:0 w:1)
+// Storage: EVM ArchivedContracts (r:0 w:1)
+pub fn archive_contract<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(61_285_000 as Weight)
+		.saturating_add(DbWeight::get().reads(4 as Weight))
+		.saturating_add(DbWeight::get().writes(6 as Weight))
+}
+// Storage: EVM ArchivedContracts (r:1 w:1)
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: System Account (r:2 w:2)
+// Storage: Balances Reserves (r:1 w:1)
+// Storage: EVM Accounts (r:1 w:1)
+// Storage: EVM ContractStorageSizes (r:0 w:1)
+// Storage: EVM ContractLastTouched (r:0 w:1)
+// Storage: EVM AccountStorages (r:0 w:1)
+pub fn revive_contract<DbWeight: Get<RuntimeDbWeight>>(n: u32, ) -> Weight {
+	(95_873_000 as Weight)
+		.saturating_add((3_612_000 as Weight).saturating_mul(n as Weight))
+		.saturating_add(DbWeight::get().reads(6 as Weight))
+		.saturating_add(DbWeight::get().writes(7 as Weight))
+		.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+}
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EVM Accounts (r:1 w:0)
+// Storage: EVM PendingUpgrades (r:1 w:1)
+// Storage: Balances Reserves (r:1 w:1)
+// Storage: System Account (r:1 w:1)
+// Storage: IdleScheduler NextTaskId (r:1 w:1)
+// Storage: IdleScheduler Tasks (r:0 w:1)
+pub fn propose_upgrade<DbWeight: Get<RuntimeDbWeight>>(c: u32, ) -> Weight {
+	(48_217_000 as Weight)
+		.saturating_add((2_000 as Weight).saturating_mul(c as Weight))
+		.saturating_add(DbWeight::get().reads(6 as Weight))
+		.saturating_add(DbWeight::get().writes(5 as Weight))
+}
+// Storage: EVM Accounts (r:1 w:0)
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EVM PendingUpgrades (r:1 w:1)
+// Storage: Balances Reserves (r:1 w:1)
+// Storage: System Account (r:1 w:1)
+pub fn cancel_upgrade<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(30_564_000 as Weight)
+		.saturating_add(DbWeight::get().reads(5 as Weight))
+		.saturating_add(DbWeight::get().writes(3 as Weight))
+}
+// Storage: EVM Accounts (r:1 w:0)
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EVM ContractVerifications (r:0 w:1)
+pub fn submit_verification<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(28_193_000 as Weight)
+		.saturating_add(DbWeight::get().reads(2 as Weight))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+}
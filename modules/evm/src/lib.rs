@@ -52,15 +52,16 @@ pub use module_evm_utility::{
 	Account,
 };
 pub use module_support::{
-	AddressMapping, DispatchableTask, EVMManager, ExecutionMode, IdleScheduler, InvokeContext, TransactionPayment,
-	EVM as EVMTrait,
+	AddressMapping, DispatchableTask, EVMLogs, EVMManager, ExecutionMode, IdleScheduler, InvokeContext,
+	TransactionPayment, EVM as EVMTrait,
 };
 pub use orml_traits::{currency::TransferAll, MultiCurrency};
 use primitive_types::{H160, H256, U256};
+use primitives::evm::{is_system_contract, DETERMINISTIC_DEPLOYER};
 pub use primitives::{
 	evm::{
-		convert_decimals_from_evm, convert_decimals_to_evm, CallInfo, CreateInfo, EvmAddress, ExecutionInfo, Vicinity,
-		MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
+		convert_decimals_from_evm, convert_decimals_to_evm, CallInfo, ContractVerification, CreateInfo, EvmAddress,
+		EvmReceipt, ExecutionInfo, Vicinity, MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
 	},
 	task::TaskResult,
 	Balance, CurrencyId, ReserveIdentifier,
@@ -70,7 +71,9 @@ use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
-	traits::{Convert, DispatchInfoOf, One, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto, Zero},
+	traits::{
+		Convert, DispatchInfoOf, One, PostDispatchInfoOf, Saturating, SignedExtension, UniqueSaturatedInto, Zero,
+	},
 	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
@@ -81,6 +84,7 @@ pub mod runner;
 
 pub mod bench;
 
+pub mod estimated_weights;
 mod mock;
 mod tests;
 pub mod weights;
@@ -97,6 +101,8 @@ pub type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 pub const RESERVE_ID_STORAGE_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmStorageDeposit;
 pub const RESERVE_ID_DEVELOPER_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmDeveloperDeposit;
+pub const RESERVE_ID_BASE_FEE: ReserveIdentifier = ReserveIdentifier::EvmBaseFee;
+pub const RESERVE_ID_UPGRADE_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmUpgradeDeposit;
 
 // Initially based on London hard fork configuration.
 static ACALA_CONFIG: EvmConfig = EvmConfig {
@@ -111,6 +117,13 @@ static ACALA_CONFIG: EvmConfig = EvmConfig {
 pub const BASE_CREATE_GAS: u64 = 67_066;
 /// Call function that just set a storage `function store(uint256 num) public { number = num; }`.
 pub const BASE_CALL_GAS: u64 = 43_702;
+/// Record the last touched block of a contract, one read and one write of `ContractLastTouched`.
+pub const TOUCH_CONTRACT_GAS: u64 = 13_889;
+
+/// The base fee per gas targets `BlockGasLimit / BASE_FEE_ELASTICITY_MULTIPLIER` gas usage.
+pub const BASE_FEE_ELASTICITY_MULTIPLIER: u64 = 2;
+/// The base fee per gas changes at most `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR` per block.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u128 = 8;
 
 /// Helper method to calculate `create` weight.
 fn create_weight<T: Config>(gas: u64) -> Weight {
@@ -152,6 +165,17 @@ fn call_weight<T: Config>(gas: u64) -> Weight {
 		.saturating_add(T::GasToWeight::convert(gas.saturating_sub(BASE_CALL_GAS)))
 }
 
+/// Helper method to calculate `batch` weight.
+fn batch_weight<T: Config>(operations: &[BatchOperation<BalanceOf<T>>], gas: u64) -> Weight {
+	// the gas is shared by all of the operations, so only charge it once
+	operations
+		.iter()
+		.fold(T::GasToWeight::convert(gas), |weight, operation| match operation {
+			BatchOperation::Call { .. } => weight.saturating_add(<T as Config>::WeightInfo::call()),
+			BatchOperation::Create { .. } => weight.saturating_add(<T as Config>::WeightInfo::create()),
+		})
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -186,11 +210,23 @@ pub mod module {
 		#[pallet::constant]
 		type StorageDepositPerByte: Get<BalanceOf<Self>>;
 
+		/// Number of blocks a contract must stay untouched before it can be archived.
+		#[pallet::constant]
+		type ContractExpiryPeriod: Get<Self::BlockNumber>;
+
+		/// The notice period before the proposed code of a published contract is applied.
+		#[pallet::constant]
+		type UpgradeDelay: Get<Self::BlockNumber>;
+
 		/// Tx fee required for per gas.
-		/// Provide to the client
+		/// Provide to the client, also the minimum of the base fee per gas.
 		#[pallet::constant]
 		type TxFeePerGas: Get<BalanceOf<Self>>;
 
+		/// The gas limit of the EVM block, the base fee per gas targets half of it.
+		#[pallet::constant]
+		type BlockGasLimit: Get<u64>;
+
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -216,6 +252,11 @@ pub mod module {
 		#[pallet::constant]
 		type NetworkContractSource: Get<EvmAddress>;
 
+		/// The address ranges `[start, end)` reserved for the predeployed contracts of the
+		/// network.
+		#[pallet::constant]
+		type PredeployAddressRanges: Get<Vec<(EvmAddress, EvmAddress)>>;
+
 		/// Deposit for the developer.
 		#[pallet::constant]
 		type DeveloperDeposit: Get<BalanceOf<Self>>;
@@ -264,6 +305,66 @@ pub mod module {
 		}
 	}
 
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub struct ArchivedContractInfo<BlockNumber> {
+		pub contract_info: ContractInfo,
+		/// The storage usage of the contract when it was archived.
+		pub storage_size: u32,
+		/// Commitment of the archived storages, see `archived_storage_root`.
+		pub storage_root: H256,
+		pub archived_at: BlockNumber,
+		/// All of the storages are removed and the storage deposit is refunded.
+		pub finished: bool,
+	}
+
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub struct PendingUpgrade<AccountId, BlockNumber, Balance> {
+		pub code: BoundedVec<u8, MaxCodeSize>,
+		pub code_hash: H256,
+		/// The code can be applied since this block.
+		pub enactable_at: BlockNumber,
+		/// The account which proposed the upgrade.
+		pub depositor: AccountId,
+		/// The deposit reserved from `depositor` for the pending code, until the upgrade is
+		/// applied or removed.
+		pub deposit: Balance,
+	}
+
+	/// An operation of the `batch` call.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub enum BatchOperation<Balance> {
+		/// Call the `target` contract.
+		Call {
+			target: EvmAddress,
+			input: Vec<u8>,
+			value: Balance,
+		},
+		/// Create a contract with the init code `input`.
+		Create { input: Vec<u8>, value: Balance },
+	}
+
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub struct BatchOperationResult {
+		/// The called contract, or the created contract.
+		pub contract: EvmAddress,
+		pub output: Vec<u8>,
+		pub logs: Vec<Log>,
+		pub used_gas: u64,
+		pub used_storage: i32,
+	}
+
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+	pub struct BatchInfo {
+		/// The exit reason of the last executed operation.
+		pub exit_reason: ExitReason,
+		/// The output of the failed operation.
+		pub output: Vec<u8>,
+		/// The results of the succeeded operations.
+		pub results: Vec<BatchOperationResult>,
+		pub used_gas: u64,
+		pub used_storage: i32,
+	}
+
 	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
 	pub struct CodeInfo {
 		pub code_size: u32,
@@ -332,6 +433,83 @@ pub mod module {
 	#[pallet::getter(fn code_infos)]
 	pub type CodeInfos<T: Config> = StorageMap<_, Identity, H256, CodeInfo, OptionQuery>;
 
+	/// The block number at which the contract was last touched.
+	///
+	/// ContractLastTouched: map EvmAddress => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_last_touched)]
+	pub type ContractLastTouched<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The block since which the last touched blocks of the contracts are recorded, the
+	/// contracts without a record are considered touched at it.
+	///
+	/// ContractExpiryStart: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn contract_expiry_start)]
+	pub type ContractExpiryStart<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The archived contracts.
+	///
+	/// ArchivedContracts: map EvmAddress => Option<ArchivedContractInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn archived_contracts)]
+	pub type ArchivedContracts<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, ArchivedContractInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The pending code upgrades of published contracts.
+	///
+	/// PendingUpgrades: map EvmAddress => Option<PendingUpgrade>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_upgrades)]
+	pub type PendingUpgrades<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		EvmAddress,
+		PendingUpgrade<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Whether a deterministic deployment is being executed, the deployments can't be nested.
+	///
+	/// DeterministicDeploying: bool
+	#[pallet::storage]
+	pub type DeterministicDeploying<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The source verifications of contracts, removed once the code of the contract changes.
+	///
+	/// ContractVerifications: map EvmAddress => Option<ContractVerification>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_verifications)]
+	pub type ContractVerifications<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, ContractVerification, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultBaseFeePerGas<T: Config>() -> BalanceOf<T> {
+		T::TxFeePerGas::get()
+	}
+
+	/// The base fee per gas, adjusted by the EVM gas usage of the previous block.
+	///
+	/// BaseFeePerGas: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn base_fee_per_gas)]
+	pub type BaseFeePerGas<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery, DefaultBaseFeePerGas<T>>;
+
+	/// The EVM gas used in the current block.
+	///
+	/// BlockGasUsed: u64
+	#[pallet::storage]
+	#[pallet::getter(fn block_gas_used)]
+	pub type BlockGasUsed<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The depth of the running EVM executions. Only the top-level executions count in
+	/// `BlockGasUsed` and pay the base fee, the nested ones (e.g. the EVMBridge calls of the
+	/// precompiles) are paid by their outer execution.
+	///
+	/// ExecutionDepth: u32
+	#[pallet::storage]
+	pub type ExecutionDepth<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// Next available system contract address.
 	///
 	/// NetworkContractIndex: u64
@@ -431,6 +609,7 @@ pub mod module {
 			});
 			ChainId::<T>::put(self.chain_id);
 			NetworkContractIndex::<T>::put(MIRRORED_NFT_ADDRESS_START);
+			ContractExpiryStart::<T>::put(frame_system::Pallet::<T>::block_number());
 		}
 	}
 
@@ -489,6 +668,45 @@ pub mod module {
 		ContractSetCode { contract: EvmAddress },
 		/// Selfdestructed contract code.
 		ContractSelfdestructed { contract: EvmAddress },
+		/// Logs of state changes made outside of the EVM.
+		Logged { from: EvmAddress, logs: Vec<Log> },
+		/// Archived contract, all of its storages are removed.
+		ContractArchived { contract: EvmAddress, storage_root: H256 },
+		/// Revived archived contract.
+		ContractRevived { contract: EvmAddress },
+		/// Proposed contract code upgrade.
+		ContractUpgradeProposed {
+			contract: EvmAddress,
+			code_hash: H256,
+			enactable_at: T::BlockNumber,
+		},
+		/// Cancelled contract code upgrade.
+		ContractUpgradeCancelled { contract: EvmAddress },
+		/// Applied contract code upgrade.
+		ContractUpgraded { contract: EvmAddress, code_hash: H256 },
+		/// Failed to apply contract code upgrade, the upgrade is removed.
+		ContractUpgradeFailed {
+			contract: EvmAddress,
+			code_hash: H256,
+			error: DispatchError,
+		},
+		/// Submitted contract source verification.
+		ContractVerified { contract: EvmAddress, code_hash: H256 },
+		/// A batch of operations executed successfully.
+		BatchExecuted {
+			from: EvmAddress,
+			results: Vec<BatchOperationResult>,
+			used_gas: u64,
+			used_storage: i32,
+		},
+		/// A batch of operations failed at operation `index`, all of the operations are reverted.
+		BatchExecutedFailed {
+			from: EvmAddress,
+			index: u32,
+			exit_reason: ExitReason,
+			output: Vec<u8>,
+			used_gas: u64,
+		},
 	}
 
 	#[pallet::error]
@@ -525,6 +743,30 @@ pub mod module {
 		InvalidDecimals,
 		/// Strict call failed
 		StrictCallFailed,
+		/// Contract is not expired yet
+		ContractNotExpired,
+		/// Contract is not archived
+		ContractNotArchived,
+		/// Contract storages are still being archived
+		ContractArchiving,
+		/// Storages do not match the archived storage root
+		InvalidStorageProof,
+		/// Contract is not published
+		ContractNotPublished,
+		/// Contract upgrade is not proposed
+		UpgradeNotProposed,
+		/// Contract upgrade is already proposed
+		UpgradeAlreadyProposed,
+		/// Address is not reserved for predeployed contracts
+		AddressNotReserved,
+		/// Salt is reserved by another address
+		SaltReserved,
+		/// Compiler version is empty or exceeds max length
+		InvalidCompilerVersion,
+		/// Contract is not verified
+		ContractNotVerified,
+		/// Deterministic deployments can't be nested
+		NestedDeterministicDeployment,
 	}
 
 	#[pallet::pallet]
@@ -533,6 +775,28 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			// weight of `on_finalize`
+			<T as frame_system::Config>::DbWeight::get().reads_writes(2, 2)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			// the contracts deployed before the state expiry are considered touched at the upgrade
+			if ContractExpiryStart::<T>::exists() {
+				<T as frame_system::Config>::DbWeight::get().reads(1)
+			} else {
+				ContractExpiryStart::<T>::put(frame_system::Pallet::<T>::block_number());
+				<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1)
+			}
+		}
+
+		fn on_finalize(_: T::BlockNumber) {
+			let gas_used = BlockGasUsed::<T>::take();
+			BaseFeePerGas::<T>::mutate(|base_fee| {
+				*base_fee = Self::next_base_fee_per_gas(*base_fee, gas_used);
+			});
+		}
+
 		fn integrity_test() {
 			assert!(convert_decimals_from_evm(T::StorageDepositPerByte::get()).is_some());
 		}
@@ -555,6 +819,7 @@ pub mod module {
 			access_list: Vec<AccessListItem>,
 			#[pallet::compact] _valid_until: T::BlockNumber, // checked by tx validation logic
 		) -> DispatchResultWithPostInfo {
+			// the base fee per gas is checked by tx validation logic and charged by the runner
 			match action {
 				TransactionAction::Call(target) => {
 					Self::call(origin, target, input, value, gas_limit, storage_limit, access_list)
@@ -891,6 +1156,81 @@ pub mod module {
 			}
 		}
 
+		/// Issue a deterministic deployment. The contract is created by CREATE2 from
+		/// `DETERMINISTIC_DEPLOYER`, so its address only depends on `salt` and `input`, and is the
+		/// same on every network.
+		///
+		/// - `input`: the data supplied for the contract's constructor
+		/// - `salt`: used for generating the new contract's address, a salt starting with a
+		///   non-zero address is reserved for that address, and the contracts deployed with the
+		///   other salts are published and immutable
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		#[pallet::weight(create2_weight::<T>(*gas_limit))]
+		#[transactional]
+		pub fn create_deterministic(
+			origin: OriginFor<T>,
+			input: Vec<u8>,
+			salt: H256,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+			Self::ensure_salt_permitted(&source, &salt)?;
+
+			match Self::deploy_deterministic(
+				source,
+				input,
+				salt,
+				gas_limit,
+				storage_limit,
+				access_list.into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				T::config(),
+			) {
+				Err(e) => {
+					Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
+						from: source,
+						contract: H160::default(),
+						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+						logs: vec![],
+						used_gas: gas_limit,
+						used_storage: Default::default(),
+					});
+
+					Ok(().into())
+				}
+				Ok(info) => {
+					let used_gas: u64 = info.used_gas.unique_saturated_into();
+
+					if info.exit_reason.is_succeed() {
+						Pallet::<T>::deposit_event(Event::<T>::Created {
+							from: source,
+							contract: info.value,
+							logs: info.logs,
+							used_gas,
+							used_storage: info.used_storage,
+						});
+					} else {
+						Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
+							from: source,
+							contract: info.value,
+							exit_reason: info.exit_reason.clone(),
+							logs: info.logs,
+							used_gas,
+							used_storage: Default::default(),
+						});
+					}
+
+					Ok(PostDispatchInfo {
+						actual_weight: Some(create2_weight::<T>(used_gas)),
+						pays_fee: Pays::Yes,
+					})
+				}
+			}
+		}
+
 		/// Create mirrored NFT contract. The next available system contract
 		/// address will be used as created contract address.
 		///
@@ -1001,6 +1341,12 @@ pub mod module {
 			T::NetworkContractOrigin::ensure_origin(origin)?;
 
 			ensure!(Self::accounts(target).is_none(), Error::<T>::ContractAlreadyExisted);
+			ensure!(
+				T::PredeployAddressRanges::get()
+					.iter()
+					.any(|(start, end)| (start..end).contains(&&target)),
+				Error::<T>::AddressNotReserved
+			);
 
 			let source = T::NetworkContractSource::get();
 			let source_account = T::AddressMapping::get_account_id(&source);
@@ -1076,7 +1422,7 @@ pub mod module {
 		/// Transfers Contract maintainership to a new EVM Address.
 		///
 		/// - `contract`: the contract whose maintainership is being transferred, the caller must be
-		///   the contract's maintainer
+		///   the contract's maintainer, or linked to it by `AddressMapping`
 		/// - `new_maintainer`: the address of the new maintainer
 		#[pallet::weight(<T as Config>::WeightInfo::transfer_maintainer())]
 		#[transactional]
@@ -1177,6 +1523,107 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Archive a contract which has not been touched for `ContractExpiryPeriod` blocks. The
+		/// storages are removed by the idle scheduler and the storage deposit is refunded to the
+		/// maintainer afterwards.
+		///
+		/// - `contract`: The contract to archive
+		#[pallet::weight(<T as Config>::WeightInfo::archive_contract())]
+		#[transactional]
+		pub fn archive_contract(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_archive_contract(&contract)?;
+
+			Ok(().into())
+		}
+
+		/// Revive an archived contract. The caller pays the storage deposit.
+		///
+		/// - `contract`: The archived contract
+		/// - `storage`: All of the storages of the contract when it was archived, must match the
+		///   archived storage root
+		#[pallet::weight(<T as Config>::WeightInfo::revive_contract(storage.len() as u32))]
+		#[transactional]
+		pub fn revive_contract(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			storage: Vec<(H256, H256)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_revive_contract(&who, &contract, storage)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractRevived { contract });
+
+			Ok(().into())
+		}
+
+		/// Propose the code upgrade of a published contract, which is applied after `UpgradeDelay`
+		/// blocks. A pending upgrade of the contract must be cancelled first with `cancel_upgrade`.
+		///
+		/// - `contract`: The published contract to upgrade, the caller must be the contract's
+		///   maintainer
+		/// - `code`: The new code of the contract
+		#[pallet::weight(<T as Config>::WeightInfo::propose_upgrade(code.len() as u32))]
+		#[transactional]
+		pub fn propose_upgrade(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (code_hash, enactable_at) = Self::do_propose_upgrade(who, contract, code)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractUpgradeProposed {
+				contract,
+				code_hash,
+				enactable_at,
+			});
+
+			Ok(().into())
+		}
+
+		/// Cancel the pending code upgrade of a contract.
+		///
+		/// - `contract`: The contract with the pending upgrade, the caller must be the contract's
+		///   maintainer
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_upgrade())]
+		#[transactional]
+		pub fn cancel_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_upgrade(who, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractUpgradeCancelled { contract });
+
+			Ok(().into())
+		}
+
+		/// Submit the source verification of the current code of a contract, which replaces the
+		/// previous one. The verification is removed once the code of the contract changes.
+		///
+		/// - `contract`: The contract to verify, the caller must be the contract's maintainer
+		/// - `compiler_version`: The version of the compiler which compiled the code
+		/// - `settings_hash`: Keccak256 hash of the standard JSON compiler settings
+		/// - `metadata_hash`: The sha2-256 digest of the IPFS CIDv0 of the contract metadata
+		/// - `abi_hash`: Keccak256 hash of the JSON ABI
+		#[pallet::weight(<T as Config>::WeightInfo::submit_verification())]
+		#[transactional]
+		pub fn submit_verification(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			compiler_version: Vec<u8>,
+			settings_hash: H256,
+			metadata_hash: H256,
+			abi_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let code_hash =
+				Self::do_submit_verification(who, contract, compiler_version, settings_hash, metadata_hash, abi_hash)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractVerified { contract, code_hash });
+
+			Ok(().into())
+		}
+
 		/// Issue an EVM call operation in `Utility::batch_all`. This is same as the evm.call but
 		/// returns error when it failed. The current evm.call always success and emit event to
 		/// indicate it failed.
@@ -1249,6 +1696,58 @@ pub mod module {
 				}
 			}
 		}
+
+		/// Issue a batch of EVM call and create operations atomically. The operations share the
+		/// gas and storage limits, and all of them are reverted if any of them fails.
+		///
+		/// - `operations`: the operations to execute in order
+		/// - `gas_limit`: the maximum gas the operations can use
+		/// - `storage_limit`: the total bytes the contracts' storage can increase by
+		#[pallet::weight(batch_weight::<T>(operations, *gas_limit))]
+		#[transactional]
+		pub fn batch(
+			origin: OriginFor<T>,
+			operations: Vec<BatchOperation<BalanceOf<T>>>,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+			let actual_weight = batch_weight::<T>(&operations, 0);
+
+			let info = Self::execute_batch(
+				source,
+				operations,
+				gas_limit,
+				storage_limit,
+				access_list.into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				T::config(),
+				false,
+			);
+
+			if info.exit_reason.is_succeed() {
+				Pallet::<T>::deposit_event(Event::<T>::BatchExecuted {
+					from: source,
+					results: info.results,
+					used_gas: info.used_gas,
+					used_storage: info.used_storage,
+				});
+			} else {
+				Pallet::<T>::deposit_event(Event::<T>::BatchExecutedFailed {
+					from: source,
+					index: info.results.len() as u32,
+					exit_reason: info.exit_reason,
+					output: info.output,
+					used_gas: info.used_gas,
+				});
+			}
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(actual_weight.saturating_add(T::GasToWeight::convert(info.used_gas))),
+				pays_fee: Pays::Yes,
+			})
+		}
 	}
 }
 
@@ -1259,38 +1758,260 @@ impl<T: Config> Pallet<T> {
 		convert_decimals_from_evm(T::StorageDepositPerByte::get()).expect("checked in integrity_test; qed")
 	}
 
-	/// Check whether an account is empty.
-	pub fn is_account_empty(address: &H160) -> bool {
-		let account_id = T::AddressMapping::get_account_id(address);
-		let balance = T::Currency::total_balance(&account_id);
+	/// Deploy the contract by CREATE2 from `DETERMINISTIC_DEPLOYER` on behalf of `source`. The
+	/// storage deposit is charged from `source`. `source` becomes the maintainer of the contract
+	/// if the salt is reserved for it, otherwise the contract is published and immutable.
+	pub fn deploy_deterministic(
+		source: EvmAddress,
+		init: Vec<u8>,
+		salt: H256,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CreateInfo, DispatchError> {
+		let reserved = Self::ensure_salt_permitted(&source, &salt)?;
+		ensure!(
+			!DeterministicDeploying::<T>::get(),
+			Error::<T>::NestedDeterministicDeployment
+		);
 
-		if !balance.is_zero() {
-			return false;
+		DeterministicDeploying::<T>::put(true);
+		let result = T::Runner::create_deterministic(source, init, salt, gas_limit, storage_limit, access_list, config);
+		DeterministicDeploying::<T>::kill();
+		let info = result?;
+
+		if info.exit_reason.is_succeed() {
+			Accounts::<T>::mutate(info.value, |maybe_account_info| {
+				if let Some(AccountInfo {
+					contract_info: Some(contract_info),
+					..
+				}) = maybe_account_info.as_mut()
+				{
+					if reserved {
+						contract_info.maintainer = source;
+					} else {
+						// anyone can deploy with a zero-prefix salt, so the contract is kept
+						// maintained by `DETERMINISTIC_DEPLOYER` and published, it can't be changed
+						// by anyone
+						contract_info.published = true;
+					}
+				}
+			});
 		}
 
-		Self::accounts(address).map_or(true, |account_info| {
-			account_info.contract_info.is_none() && account_info.nonce.is_zero()
-		})
+		Ok(info)
 	}
 
-	/// Remove an account if its empty.
-	/// Keep the non-zero nonce exists.
-	pub fn remove_account_if_empty(address: &H160) {
-		if Self::is_account_empty(address) {
-			let res = Self::remove_account(address);
-			debug_assert!(res.is_ok());
+	/// A salt starting with a non-zero address is reserved for that address, so that the
+	/// deterministic deployments can't be front-run. Returns whether the salt is reserved.
+	fn ensure_salt_permitted(source: &EvmAddress, salt: &H256) -> Result<bool, DispatchError> {
+		let prefix = &salt.as_bytes()[..20];
+		if prefix == H160::zero().as_bytes() {
+			return Ok(false);
 		}
+		ensure!(prefix == source.as_bytes(), Error::<T>::SaltReserved);
+		Ok(true)
 	}
 
-	#[transactional]
-	pub fn remove_contract(caller: &EvmAddress, contract: &EvmAddress) -> DispatchResult {
-		let contract_account = T::AddressMapping::get_account_id(contract);
+	/// Execute the operations in order with the shared gas and storage limits, the execution
+	/// stops at the first failed operation and all of the changes are reverted.
+	///
+	/// A `nested` batch is executed inside an EVM execution, e.g. by the batch precompile. Its
+	/// gas is not counted in `BlockGasUsed`, because it's charged to the outer execution.
+	pub fn execute_batch(
+		source: EvmAddress,
+		operations: Vec<BatchOperation<BalanceOf<T>>>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+		nested: bool,
+	) -> BatchInfo {
+		let block_gas_used = BlockGasUsed::<T>::get();
+		let info = frame_support::storage::with_transaction(|| {
+			let mut info = BatchInfo {
+				exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+				output: vec![],
+				results: vec![],
+				used_gas: 0,
+				used_storage: 0,
+			};
 
-		Accounts::<T>::try_mutate_exists(contract, |account_info| -> DispatchResult {
-			// We will keep the nonce until the storages are cleared.
-			// Only remove the `contract_info`
-			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
-			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
+			for operation in operations {
+				let gas_limit = gas_limit.saturating_sub(info.used_gas);
+				let storage_limit = storage_limit.saturating_sub(info.used_storage.max(0) as u32);
+
+				let result = match operation {
+					BatchOperation::Call { target, input, value } => T::Runner::call(
+						source,
+						source,
+						target,
+						input,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list.clone(),
+						config,
+					)
+					.map(|info| {
+						(
+							target,
+							info.value,
+							info.exit_reason,
+							info.used_gas,
+							info.used_storage,
+							info.logs,
+						)
+					}),
+					BatchOperation::Create { input, value } => T::Runner::create(
+						source,
+						input,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list.clone(),
+						config,
+					)
+					.map(|info| {
+						(
+							info.value,
+							vec![],
+							info.exit_reason,
+							info.used_gas,
+							info.used_storage,
+							info.logs,
+						)
+					}),
+				};
+
+				let (contract, output, exit_reason, used_gas, used_storage, logs) = match result {
+					Ok((contract, output, exit_reason, used_gas, used_storage, logs)) => (
+						contract,
+						output,
+						exit_reason,
+						used_gas.unique_saturated_into(),
+						used_storage,
+						logs,
+					),
+					Err(e) => (
+						H160::default(),
+						vec![],
+						ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+						gas_limit,
+						0,
+						vec![],
+					),
+				};
+
+				info.used_gas = info.used_gas.saturating_add(used_gas);
+				info.exit_reason = exit_reason;
+				if !info.exit_reason.is_succeed() {
+					info.output = output;
+					return TransactionOutcome::Rollback(Ok(info));
+				}
+
+				info.used_storage = info.used_storage.saturating_add(used_storage);
+				info.results.push(BatchOperationResult {
+					contract,
+					output,
+					logs,
+					used_gas,
+					used_storage,
+				});
+			}
+
+			TransactionOutcome::Commit(Ok(info))
+		});
+
+		let info: BatchInfo = info.unwrap_or_else(|e: DispatchError| BatchInfo {
+			exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+			output: vec![],
+			results: vec![],
+			used_gas: gas_limit,
+			used_storage: 0,
+		});
+
+		if nested {
+			BlockGasUsed::<T>::put(block_gas_used);
+		} else if !info.exit_reason.is_succeed() {
+			Self::charge_reverted_execution(&source, info.used_gas);
+		}
+
+		info
+	}
+
+	/// Count the gas and pay the base fee of a reverted top-level execution, the gas was used
+	/// even though its changes are reverted.
+	fn charge_reverted_execution(origin: &EvmAddress, used_gas: u64) {
+		if !ExecutionDepth::<T>::get().is_zero() {
+			return;
+		}
+
+		BlockGasUsed::<T>::mutate(|gas| *gas = gas.saturating_add(used_gas));
+
+		// the fee was reserved before the changes are reverted, so it can be paid
+		let who = T::AddressMapping::get_account_id(origin);
+		let res = Self::reserve_base_fee(&who, used_gas).and_then(|_| Self::charge_base_fee(&who, used_gas, used_gas));
+		debug_assert!(res.is_ok());
+	}
+
+	/// Calculate the base fee per gas of the next block, see EIP-1559.
+	/// The changes are rounded to the native decimals so that the fee can be charged exactly.
+	pub fn next_base_fee_per_gas(base_fee: BalanceOf<T>, gas_used: u64) -> BalanceOf<T> {
+		let target = T::BlockGasLimit::get() / BASE_FEE_ELASTICITY_MULTIPLIER;
+		if target.is_zero() || gas_used == target {
+			return base_fee;
+		}
+
+		let unit = convert_decimals_to_evm::<BalanceOf<T>>(1);
+		let delta = |diff: u64| -> BalanceOf<T> {
+			let delta =
+				base_fee.saturating_mul(diff.into()) / BalanceOf::<T>::from(target) / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+			delta - delta % unit
+		};
+
+		let next = if gas_used > target {
+			base_fee.saturating_add(cmp::max(delta(gas_used - target), unit))
+		} else {
+			base_fee.saturating_sub(delta(target - gas_used))
+		};
+
+		cmp::max(next, T::TxFeePerGas::get())
+	}
+
+	/// Check whether an account is empty.
+	pub fn is_account_empty(address: &H160) -> bool {
+		let account_id = T::AddressMapping::get_account_id(address);
+		let balance = T::Currency::total_balance(&account_id);
+
+		if !balance.is_zero() {
+			return false;
+		}
+
+		Self::accounts(address).map_or(true, |account_info| {
+			account_info.contract_info.is_none() && account_info.nonce.is_zero()
+		})
+	}
+
+	/// Remove an account if its empty.
+	/// Keep the non-zero nonce exists.
+	pub fn remove_account_if_empty(address: &H160) {
+		if Self::is_account_empty(address) {
+			let res = Self::remove_account(address);
+			debug_assert!(res.is_ok());
+		}
+	}
+
+	#[transactional]
+	pub fn remove_contract(caller: &EvmAddress, contract: &EvmAddress) -> DispatchResult {
+		let contract_account = T::AddressMapping::get_account_id(contract);
+
+		Accounts::<T>::try_mutate_exists(contract, |account_info| -> DispatchResult {
+			// We will keep the nonce until the storages are cleared.
+			// Only remove the `contract_info`
+			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
+			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
 
 			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
 				if let Some(code_info) = maybe_code_info.as_mut() {
@@ -1306,6 +2027,9 @@ impl<T: Config> Pallet<T> {
 			});
 
 			ContractStorageSizes::<T>::take(contract);
+			ContractLastTouched::<T>::remove(contract);
+			Self::remove_pending_upgrade(contract);
+			ContractVerifications::<T>::remove(contract);
 
 			T::IdleScheduler::schedule(
 				EvmTask::Remove {
@@ -1410,9 +2134,24 @@ impl<T: Config> Pallet<T> {
 			}
 		});
 
+		ContractLastTouched::<T>::insert(address, frame_system::Pallet::<T>::block_number());
+
 		frame_system::Pallet::<T>::inc_providers(&T::AddressMapping::get_account_id(&address));
 	}
 
+	/// Whether touching the contract records the current block as its last touched block.
+	pub fn is_contract_touch_recorded(address: &EvmAddress) -> bool {
+		Self::is_contract(address)
+			&& Self::contract_last_touched(address) != Some(frame_system::Pallet::<T>::block_number())
+	}
+
+	/// Record the current block as the last time the contract was touched.
+	pub fn touch_contract(address: &EvmAddress) {
+		if Self::is_contract_touch_recorded(address) {
+			ContractLastTouched::<T>::insert(address, frame_system::Pallet::<T>::block_number());
+		}
+	}
+
 	/// Get the account basic in EVM format.
 	pub fn account_basic(address: &EvmAddress) -> Account {
 		let account_id = T::AddressMapping::get_account_id(address);
@@ -1484,6 +2223,82 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// Archive an expired contract.
+	/// - Move the contract info to `ArchivedContracts`, the code is kept for the revival.
+	/// - Schedule the task to remove the storages and refund the storage deposit.
+	fn do_archive_contract(contract: &EvmAddress) -> DispatchResult {
+		ensure!(!is_system_contract(*contract), Error::<T>::NoPermission);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let last_touched = Self::contract_last_touched(contract)
+			.or_else(Self::contract_expiry_start)
+			.ok_or(Error::<T>::ContractNotExpired)?;
+		ensure!(
+			now.saturating_sub(last_touched) >= T::ContractExpiryPeriod::get(),
+			Error::<T>::ContractNotExpired
+		);
+
+		Accounts::<T>::try_mutate_exists(contract, |account_info| -> DispatchResult {
+			// The nonce and the provider are kept, so the address can't be reused.
+			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
+			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
+
+			ArchivedContracts::<T>::insert(
+				contract,
+				ArchivedContractInfo {
+					contract_info,
+					storage_size: ContractStorageSizes::<T>::take(contract),
+					storage_root: H256::zero(),
+					archived_at: now,
+					finished: false,
+				},
+			);
+			ContractLastTouched::<T>::remove(contract);
+			Self::remove_pending_upgrade(contract);
+
+			T::IdleScheduler::schedule(EvmTask::Archive { contract: *contract }.into())
+		})
+	}
+
+	/// Revive an archived contract with its storages.
+	fn do_revive_contract(who: &T::AccountId, contract: &EvmAddress, mut storage: Vec<(H256, H256)>) -> DispatchResult {
+		let archived = ArchivedContracts::<T>::take(contract).ok_or(Error::<T>::ContractNotArchived)?;
+		ensure!(archived.finished, Error::<T>::ContractArchiving);
+
+		// storages are archived in the order of their storage keys
+		storage.sort_by_cached_key(|(index, _)| AccountStorages::<T>::hashed_key_for(contract, index));
+		let storage_root = storage.iter().fold(H256::zero(), |root, (index, value)| {
+			archived_storage_root(root, index, value)
+		});
+		ensure!(storage_root == archived.storage_root, Error::<T>::InvalidStorageProof);
+
+		for (index, value) in storage {
+			AccountStorages::<T>::insert(contract, index, value);
+		}
+
+		let contract_acc = T::AddressMapping::get_account_id(contract);
+		let amount = Self::get_storage_deposit_per_byte().saturating_mul(archived.storage_size.into());
+
+		log::debug!(
+			target: "evm",
+			"revive_contract: [from: {:?}, contract: {:?}, contract_acc: {:?}, storage_size: {:?}, amount: {:?}]",
+			who, contract, contract_acc, archived.storage_size, amount
+		);
+
+		T::Currency::transfer(who, &contract_acc, amount, ExistenceRequirement::AllowDeath)?;
+		T::Currency::reserve_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_acc, amount)?;
+
+		ContractStorageSizes::<T>::insert(contract, archived.storage_size);
+		ContractLastTouched::<T>::insert(contract, frame_system::Pallet::<T>::block_number());
+		Accounts::<T>::mutate(contract, |maybe_account_info| {
+			maybe_account_info
+				.get_or_insert_with(|| AccountInfo::<T::Index>::new(Default::default(), None))
+				.contract_info = Some(archived.contract_info);
+		});
+
+		Ok(())
+	}
+
 	/// Sets a given contract's contract info to a new maintainer.
 	fn do_transfer_maintainer(who: T::AccountId, contract: EvmAddress, new_maintainer: EvmAddress) -> DispatchResult {
 		Accounts::<T>::mutate(contract, |maybe_account_info| -> DispatchResult {
@@ -1494,7 +2309,11 @@ impl<T: Config> Pallet<T> {
 				.ok_or(Error::<T>::ContractNotFound)?;
 
 			let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
-			ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+			// allows to recover the contracts maintained by the retired addresses of `who`
+			ensure!(
+				contract_info.maintainer == maintainer || T::AddressMapping::is_linked(&who, &contract_info.maintainer),
+				Error::<T>::NoPermission
+			);
 
 			contract_info.maintainer = new_maintainer;
 			Ok(())
@@ -1582,56 +2401,192 @@ impl<T: Config> Pallet<T> {
 				T::NetworkContractSource::get()
 			};
 
-			let old_code_info = Self::code_infos(&contract_info.code_hash).ok_or(Error::<T>::ContractNotFound)?;
-
 			let bounded_code: BoundedVec<u8, MaxCodeSize> =
 				code.try_into().map_err(|_| Error::<T>::ContractExceedsMaxCodeSize)?;
-			let code_hash = code_hash(bounded_code.as_slice());
-			let code_size = bounded_code.len() as u32;
-			// The code_hash of the same contract is definitely different.
-			// The `contract_info.code_hash` hashed by on_contract_initialization which constructed.
-			// Still check it here.
-			if code_hash == contract_info.code_hash {
-				return Ok(());
+
+			Self::update_code(&source, &contract, contract_info, bounded_code)
+		})
+	}
+
+	/// Replace the code of the contract, the storage deposit of the code size changes are charged
+	/// from `source`.
+	fn update_code(
+		source: &EvmAddress,
+		contract: &EvmAddress,
+		contract_info: &mut ContractInfo,
+		bounded_code: BoundedVec<u8, MaxCodeSize>,
+	) -> DispatchResult {
+		let old_code_info = Self::code_infos(&contract_info.code_hash).ok_or(Error::<T>::ContractNotFound)?;
+
+		let code_hash = code_hash(bounded_code.as_slice());
+		let code_size = bounded_code.len() as u32;
+		// The code_hash of the same contract is definitely different.
+		// The `contract_info.code_hash` hashed by on_contract_initialization which constructed.
+		// Still check it here.
+		if code_hash == contract_info.code_hash {
+			return Ok(());
+		}
+
+		let storage_size_changed: i32 =
+			code_size.saturating_add(T::NewContractExtraBytes::get()) as i32 - old_code_info.code_size as i32;
+
+		if storage_size_changed.is_positive() {
+			Self::reserve_storage(source, storage_size_changed as u32)?;
+		}
+		Self::charge_storage(source, contract, storage_size_changed)?;
+		Self::update_contract_storage_size(contract, storage_size_changed);
+
+		// try remove old codes
+		CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| -> DispatchResult {
+			let code_info = maybe_code_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
+			code_info.ref_count = code_info.ref_count.saturating_sub(1);
+			if code_info.ref_count == 0 {
+				Codes::<T>::remove(&contract_info.code_hash);
+				*maybe_code_info = None;
 			}
+			Ok(())
+		})?;
 
-			let storage_size_changed: i32 =
-				code_size.saturating_add(T::NewContractExtraBytes::get()) as i32 - old_code_info.code_size as i32;
+		CodeInfos::<T>::mutate_exists(&code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_add(1);
+			} else {
+				let new = CodeInfo {
+					code_size,
+					ref_count: 1,
+				};
+				*maybe_code_info = Some(new);
 
-			if storage_size_changed.is_positive() {
-				Self::reserve_storage(&source, storage_size_changed as u32)?;
+				Codes::<T>::insert(&code_hash, bounded_code);
 			}
-			Self::charge_storage(&source, &contract, storage_size_changed)?;
-			Self::update_contract_storage_size(&contract, storage_size_changed);
-
-			// try remove old codes
-			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| -> DispatchResult {
-				let code_info = maybe_code_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
-				code_info.ref_count = code_info.ref_count.saturating_sub(1);
-				if code_info.ref_count == 0 {
-					Codes::<T>::remove(&contract_info.code_hash);
-					*maybe_code_info = None;
-				}
-				Ok(())
-			})?;
+		});
+		// update code_hash, the verification of the old code is no longer valid
+		contract_info.code_hash = code_hash;
+		ContractVerifications::<T>::remove(contract);
 
-			CodeInfos::<T>::mutate_exists(&code_hash, |maybe_code_info| {
-				if let Some(code_info) = maybe_code_info.as_mut() {
-					code_info.ref_count = code_info.ref_count.saturating_add(1);
-				} else {
-					let new = CodeInfo {
-						code_size,
-						ref_count: 1,
-					};
-					*maybe_code_info = Some(new);
+		Ok(())
+	}
 
-					Codes::<T>::insert(&code_hash, bounded_code);
-				}
-			});
-			// update code_hash
-			contract_info.code_hash = code_hash;
+	/// Propose the code upgrade of a published contract.
+	fn do_propose_upgrade(
+		who: T::AccountId,
+		contract: EvmAddress,
+		code: Vec<u8>,
+	) -> Result<(H256, T::BlockNumber), DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
 
-			Ok(())
+		let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+		ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+		ensure!(contract_info.published, Error::<T>::ContractNotPublished);
+		ensure!(
+			!PendingUpgrades::<T>::contains_key(contract),
+			Error::<T>::UpgradeAlreadyProposed
+		);
+
+		let code: BoundedVec<u8, MaxCodeSize> = code.try_into().map_err(|_| Error::<T>::ContractExceedsMaxCodeSize)?;
+		let code_hash = code_hash(code.as_slice());
+		let enactable_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UpgradeDelay::get());
+
+		// the pending code is charged like the storage of a contract
+		let deposit = Self::get_storage_deposit_per_byte().saturating_mul((code.len() as u32).into());
+		T::Currency::reserve_named(&RESERVE_ID_UPGRADE_DEPOSIT, &who, deposit)?;
+
+		PendingUpgrades::<T>::insert(
+			contract,
+			PendingUpgrade {
+				code,
+				code_hash,
+				enactable_at,
+				depositor: who,
+				deposit,
+			},
+		);
+		T::IdleScheduler::schedule(
+			EvmTask::Upgrade {
+				contract,
+				code_hash,
+				enactable_at,
+			}
+			.into(),
+		)?;
+
+		Ok((code_hash, enactable_at))
+	}
+
+	/// Cancel the pending code upgrade of a contract.
+	fn do_cancel_upgrade(who: T::AccountId, contract: EvmAddress) -> DispatchResult {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+
+		let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+		ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+
+		ensure!(Self::remove_pending_upgrade(&contract), Error::<T>::UpgradeNotProposed);
+		Ok(())
+	}
+
+	/// Remove the pending code upgrade of a contract and unreserve its deposit. Returns whether
+	/// there was a pending upgrade.
+	fn remove_pending_upgrade(contract: &EvmAddress) -> bool {
+		match PendingUpgrades::<T>::take(contract) {
+			Some(upgrade) => {
+				T::Currency::unreserve_named(&RESERVE_ID_UPGRADE_DEPOSIT, &upgrade.depositor, upgrade.deposit);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Submit the source verification of the current code of a contract.
+	fn do_submit_verification(
+		who: T::AccountId,
+		contract: EvmAddress,
+		compiler_version: Vec<u8>,
+		settings_hash: H256,
+		metadata_hash: H256,
+		abi_hash: H256,
+	) -> Result<H256, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+
+		let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+		ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+
+		ensure!(!compiler_version.is_empty(), Error::<T>::InvalidCompilerVersion);
+		let compiler_version = compiler_version
+			.try_into()
+			.map_err(|_| Error::<T>::InvalidCompilerVersion)?;
+
+		ContractVerifications::<T>::insert(
+			contract,
+			ContractVerification {
+				code_hash: contract_info.code_hash,
+				compiler_version,
+				settings_hash,
+				metadata_hash,
+				abi_hash,
+			},
+		);
+
+		Ok(contract_info.code_hash)
+	}
+
+	/// Apply the code upgrade, the storage deposit is charged from the maintainer.
+	#[transactional]
+	fn apply_upgrade(contract: &EvmAddress, code: BoundedVec<u8, MaxCodeSize>) -> DispatchResult {
+		Accounts::<T>::mutate(contract, |maybe_account_info| -> DispatchResult {
+			let account_info = maybe_account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
+			let contract_info = account_info
+				.contract_info
+				.as_mut()
+				.ok_or(Error::<T>::ContractNotFound)?;
+
+			let maintainer = contract_info.maintainer;
+			Self::update_code(&maintainer, contract, contract_info, code)
 		})
 	}
 
@@ -1717,6 +2672,67 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The part of the base fee of `gas` above `TxFeePerGas`, the rest is covered by the weight
+	/// fee.
+	fn base_fee_of(gas: u64) -> Result<BalanceOf<T>, DispatchError> {
+		let fee_per_gas = Self::base_fee_per_gas().saturating_sub(T::TxFeePerGas::get());
+		convert_decimals_from_evm(fee_per_gas.saturating_mul(gas.into()))
+			.ok_or_else(|| Error::<T>::InvalidDecimals.into())
+	}
+
+	/// Reserve the base fee of `gas_limit` before the execution.
+	fn reserve_base_fee(who: &T::AccountId, gas_limit: u64) -> DispatchResult {
+		let amount = Self::base_fee_of(gas_limit)?;
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		log::debug!(
+			target: "evm",
+			"reserve_base_fee: [account: {:?}, gas_limit: {:?}, amount: {:?}]",
+			who, gas_limit, amount
+		);
+
+		T::ChargeTransactionPayment::reserve_fee(who, amount, Some(RESERVE_ID_BASE_FEE)).map_err(|e| {
+			log::debug!(
+				target: "evm",
+				"ReserveBaseFeeFailed {:?} [account: {:?}, gas_limit: {:?}]",
+				e, who, gas_limit
+			);
+			Error::<T>::ChargeFeeFailed
+		})?;
+		Ok(())
+	}
+
+	/// Pay the base fee of `used_gas` to the treasury from the fee reserved by
+	/// `reserve_base_fee`, and unreserve the rest.
+	fn charge_base_fee(who: &T::AccountId, gas_limit: u64, used_gas: u64) -> DispatchResult {
+		let reserved = Self::base_fee_of(gas_limit)?;
+		if reserved.is_zero() {
+			return Ok(());
+		}
+		let amount = Self::base_fee_of(used_gas.min(gas_limit))?;
+
+		log::debug!(
+			target: "evm",
+			"charge_base_fee: [account: {:?}, used_gas: {:?}, amount: {:?}]",
+			who, used_gas, amount
+		);
+
+		// the fee is reserved, so it can always be paid
+		T::Currency::repatriate_reserved_named(
+			&RESERVE_ID_BASE_FEE,
+			who,
+			&T::TreasuryAccount::get(),
+			amount,
+			BalanceStatus::Free,
+		)?;
+		let err_amount =
+			T::ChargeTransactionPayment::unreserve_fee(who, reserved.saturating_sub(amount), Some(RESERVE_ID_BASE_FEE));
+		debug_assert!(err_amount.is_zero());
+		Ok(())
+	}
+
 	fn charge_storage(caller: &H160, contract: &H160, storage: i32) -> DispatchResult {
 		if storage.is_zero() {
 			return Ok(());
@@ -1783,6 +2799,116 @@ impl<T: Config> Pallet<T> {
 
 		Ok(())
 	}
+
+	fn refund_archived_storage(contract: &H160, maintainer: &H160) -> DispatchResult {
+		let contract_acc = T::AddressMapping::get_account_id(contract);
+		let maintainer_acc = T::AddressMapping::get_account_id(maintainer);
+		let amount = T::Currency::reserved_balance_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_acc);
+
+		log::debug!(
+			target: "evm",
+			"refund_archived_storage: [contract: {:?}, contract_acc: {:?}, maintainer: {:?}, maintainer_acc: {:?}, amount: {:?}]",
+			contract, contract_acc, maintainer, maintainer_acc, amount
+		);
+
+		let val = T::Currency::repatriate_reserved_named(
+			&RESERVE_ID_STORAGE_DEPOSIT,
+			&contract_acc,
+			&maintainer_acc,
+			amount,
+			BalanceStatus::Free,
+		)?;
+		debug_assert!(val.is_zero());
+
+		Ok(())
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	<T as frame_system::Config>::Event: TryInto<Event<T>>,
+{
+	/// The receipts of the EVM transactions in the current block, collected from the events.
+	/// The logs of state changes made outside of the EVM are attached to the receipt of the
+	/// extrinsic that made them.
+	pub fn block_receipts() -> Vec<EvmReceipt> {
+		let mut receipts: Vec<EvmReceipt> = vec![];
+		let mut logged: Vec<EvmReceipt> = vec![];
+
+		for record in frame_system::Pallet::<T>::events() {
+			let extrinsic_index = match record.phase {
+				frame_system::Phase::ApplyExtrinsic(index) => index,
+				_ => continue,
+			};
+			let receipt = |from, to, contract_address, succeed, used_gas, logs| EvmReceipt {
+				extrinsic_index,
+				from,
+				to,
+				contract_address,
+				succeed,
+				used_gas,
+				logs,
+			};
+
+			match TryInto::<Event<T>>::try_into(record.event) {
+				Ok(Event::Created {
+					from,
+					contract,
+					logs,
+					used_gas,
+					..
+				}) => receipts.push(receipt(from, None, Some(contract), true, used_gas, logs)),
+				Ok(Event::CreatedFailed {
+					from,
+					contract,
+					logs,
+					used_gas,
+					..
+				}) => receipts.push(receipt(from, None, Some(contract), false, used_gas, logs)),
+				Ok(Event::Executed {
+					from,
+					contract,
+					logs,
+					used_gas,
+					..
+				}) => receipts.push(receipt(from, Some(contract), None, true, used_gas, logs)),
+				Ok(Event::ExecutedFailed {
+					from,
+					contract,
+					logs,
+					used_gas,
+					..
+				}) => receipts.push(receipt(from, Some(contract), None, false, used_gas, logs)),
+				Ok(Event::BatchExecuted {
+					from,
+					results,
+					used_gas,
+					..
+				}) => {
+					let logs = results.into_iter().flat_map(|result| result.logs).collect();
+					receipts.push(receipt(from, None, None, true, used_gas, logs))
+				}
+				Ok(Event::BatchExecutedFailed { from, used_gas, .. }) => {
+					receipts.push(receipt(from, None, None, false, used_gas, vec![]))
+				}
+				Ok(Event::Logged { from, logs }) => logged.push(receipt(from, None, None, true, 0, logs)),
+				_ => {}
+			}
+		}
+
+		for logged in logged {
+			match receipts
+				.iter_mut()
+				.find(|receipt| receipt.extrinsic_index == logged.extrinsic_index)
+			{
+				Some(receipt) => receipt.logs.extend(logged.logs),
+				None => receipts.push(logged),
+			}
+		}
+		receipts.sort_by_key(|receipt| receipt.extrinsic_index);
+
+		receipts
+	}
 }
 
 impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
@@ -1800,7 +2926,7 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 			config.estimate = true;
 		}
 
-		frame_support::storage::with_transaction(|| {
+		let result = frame_support::storage::with_transaction(|| {
 			let result = T::Runner::call(
 				context.sender,
 				context.origin,
@@ -1842,7 +2968,15 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 				},
 				Err(e) => TransactionOutcome::Rollback(Err(e)),
 			}
-		})
+		});
+
+		if let (ExecutionMode::Execute, Ok(info)) = (mode, &result) {
+			if !info.exit_reason.is_succeed() {
+				Pallet::<T>::charge_reverted_execution(&context.origin, info.used_gas.unique_saturated_into());
+			}
+		}
+
+		result
 	}
 
 	/// Get the real origin account and charge storage rent from the origin.
@@ -1863,6 +2997,14 @@ impl<T: Config> Get<u64> for EvmChainId<T> {
 	}
 }
 
+impl<T: Config> EVMLogs for Pallet<T> {
+	fn deposit_logs(from: EvmAddress, logs: Vec<Log>) {
+		if !logs.is_empty() {
+			Pallet::<T>::deposit_event(Event::<T>::Logged { from, logs });
+		}
+	}
+}
+
 impl<T: Config> EVMManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	fn query_new_contract_extra_bytes() -> u32 {
 		T::NewContractExtraBytes::get()
@@ -1881,6 +3023,10 @@ impl<T: Config> EVMManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
 		})
 	}
 
+	fn query_verification(contract: EvmAddress) -> Result<ContractVerification, DispatchError> {
+		ContractVerifications::<T>::get(contract).ok_or_else(|| Error::<T>::ContractNotVerified.into())
+	}
+
 	fn query_developer_deposit() -> BalanceOf<T> {
 		convert_decimals_to_evm(T::DeveloperDeposit::get())
 	}
@@ -1923,6 +3069,25 @@ pub fn code_hash(code: &[u8]) -> H256 {
 	H256::from_slice(Keccak256::digest(code).as_slice())
 }
 
+/// The address of the deterministic deployment, see `create_deterministic`.
+pub fn deterministic_address(salt: &H256, code_hash: &H256) -> EvmAddress {
+	let mut hasher = Keccak256::new();
+	hasher.update([0xff]);
+	hasher.update(DETERMINISTIC_DEPLOYER.as_bytes());
+	hasher.update(salt.as_bytes());
+	hasher.update(code_hash.as_bytes());
+	H160::from_slice(&hasher.finalize()[12..])
+}
+
+/// Fold a storage of the archived contract into the storage root.
+pub fn archived_storage_root(root: H256, index: &H256, value: &H256) -> H256 {
+	let mut data = [0u8; 96];
+	data[0..32].copy_from_slice(root.as_bytes());
+	data[32..64].copy_from_slice(index.as_bytes());
+	data[64..96].copy_from_slice(value.as_bytes());
+	H256::from_slice(Keccak256::digest(&data).as_slice())
+}
+
 #[allow(dead_code)]
 fn encode_revert_message(msg: &[u8]) -> Vec<u8> {
 	// A minimum size of error function selector (4) + offset (32) + string length
@@ -2023,6 +3188,14 @@ pub enum EvmTask<T: Config> {
 		contract: EvmAddress,
 		maintainer: EvmAddress,
 	},
+	Archive {
+		contract: EvmAddress,
+	},
+	Upgrade {
+		contract: EvmAddress,
+		code_hash: H256,
+		enactable_at: T::BlockNumber,
+	},
 }
 
 impl<T: Config> DispatchableTask for EvmTask<T> {
@@ -2081,6 +3254,118 @@ impl<T: Config> DispatchableTask for EvmTask<T> {
 					}
 				}
 			}
+			EvmTask::Archive { contract } => {
+				// default limit 100
+				let limit = cmp::min(
+					weight
+						.checked_div(<T as frame_system::Config>::DbWeight::get().write)
+						.unwrap_or(100),
+					100,
+				) as usize;
+
+				let mut count: u64 = 0;
+				let maybe_finished = ArchivedContracts::<T>::mutate(contract, |maybe_archived| {
+					maybe_archived.as_mut().map(|archived| {
+						for (index, value) in AccountStorages::<T>::drain_prefix(contract).take(limit) {
+							archived.storage_root = archived_storage_root(archived.storage_root, &index, &value);
+							count += 1;
+						}
+						archived.finished = count < limit as u64;
+						archived
+							.finished
+							.then(|| (archived.contract_info.maintainer, archived.storage_root))
+					})
+				});
+				let used_weight = <T as frame_system::Config>::DbWeight::get().reads_writes(count + 1, count + 1);
+				log::debug!(
+					target: "evm",
+					"EvmTask::Archive: [contract: {:?}, count: {:?}]",
+					contract, count
+				);
+
+				match maybe_finished {
+					// AllRemoved
+					Some(Some((maintainer, storage_root))) => {
+						let result = Pallet::<T>::refund_archived_storage(&contract, &maintainer);
+						Pallet::<T>::deposit_event(Event::<T>::ContractArchived { contract, storage_root });
+
+						TaskResult {
+							result,
+							used_weight,
+							finished: true,
+						}
+					}
+					// SomeRemaining
+					Some(None) => TaskResult {
+						result: Ok(()),
+						used_weight,
+						finished: false,
+					},
+					// revived or removed
+					None => TaskResult {
+						result: Ok(()),
+						used_weight,
+						finished: true,
+					},
+				}
+			}
+			EvmTask::Upgrade {
+				contract,
+				code_hash,
+				enactable_at,
+			} => {
+				// the task itself is read from the storage on every poll
+				let read_weight = <T as frame_system::Config>::DbWeight::get().reads(1);
+				if frame_system::Pallet::<T>::block_number() < enactable_at {
+					return TaskResult {
+						result: Ok(()),
+						used_weight: read_weight,
+						finished: false,
+					};
+				}
+
+				match Pallet::<T>::pending_upgrades(contract) {
+					Some(upgrade) if upgrade.code_hash == code_hash && upgrade.enactable_at == enactable_at => {
+						let apply_weight = <T as Config>::WeightInfo::set_code(upgrade.code.len() as u32);
+						if weight < apply_weight {
+							return TaskResult {
+								result: Ok(()),
+								used_weight: read_weight,
+								finished: false,
+							};
+						}
+
+						// the storage of the new code is charged before the deposit is unreserved
+						let result = Pallet::<T>::apply_upgrade(&contract, upgrade.code);
+						Pallet::<T>::remove_pending_upgrade(&contract);
+						log::debug!(
+							target: "evm",
+							"EvmTask::Upgrade: [contract: {:?}, code_hash: {:?}, result: {:?}]",
+							contract, code_hash, result
+						);
+						match result {
+							Ok(()) => Pallet::<T>::deposit_event(Event::<T>::ContractUpgraded { contract, code_hash }),
+							Err(error) => Pallet::<T>::deposit_event(Event::<T>::ContractUpgradeFailed {
+								contract,
+								code_hash,
+								error,
+							}),
+						}
+
+						TaskResult {
+							result,
+							used_weight: apply_weight,
+							finished: true,
+						}
+					}
+					// cancelled, or cancelled and proposed again
+					_ => TaskResult {
+						result: Ok(()),
+						used_weight: read_weight,
+						finished: true,
+					},
+				}
+			}
 		}
 	}
 }
@@ -178,6 +178,7 @@ impl FindAuthor<AccountId32> for AuthorGiven {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -196,7 +197,10 @@ impl Config for Runtime {
 	type TransferAll = Currencies;
 	type NewContractExtraBytes = ConstU32<NEW_CONTRACT_EXTRA_BYTES>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = ConstU128<20_000_000>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 
 	type Event = Event;
 	type PrecompilesType = ();
@@ -206,6 +210,7 @@ impl Config for Runtime {
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 	type DeveloperDeposit = ConstU128<DEVELOPER_DEPOSIT>;
 	type PublicationFee = ConstU128<PUBLICATION_FEE>;
 	type TreasuryAccount = TreasuryAccount;
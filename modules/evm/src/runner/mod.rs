@@ -71,6 +71,17 @@ pub trait Runner<T: Config> {
 		access_list: Vec<(H160, Vec<H256>)>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError>;
+
+	/// CREATE2 from `DETERMINISTIC_DEPLOYER`, the storage deposit is charged from `origin`.
+	fn create_deterministic(
+		origin: H160,
+		init: Vec<u8>,
+		salt: H256,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CreateInfo, DispatchError>;
 }
 
 pub trait RunnerExtended<T: Config>: Runner<T> {
@@ -24,8 +24,8 @@ use crate::{
 		state::{Accessed, StackExecutor, StackState as StackStateT, StackSubstateMetadata},
 		Runner as RunnerT, RunnerExtended,
 	},
-	AccountInfo, AccountStorages, Accounts, BalanceOf, CallInfo, Config, CreateInfo, Error, ExecutionInfo, One, Pallet,
-	STORAGE_SIZE,
+	AccountInfo, AccountStorages, Accounts, BalanceOf, BlockGasUsed, CallInfo, Config, CreateInfo, Error,
+	ExecutionDepth, ExecutionInfo, One, Pallet, STORAGE_SIZE, TOUCH_CONTRACT_GAS,
 };
 use frame_support::{
 	dispatch::DispatchError,
@@ -39,7 +39,7 @@ use module_evm_utility::{
 };
 use module_support::AddressMapping;
 pub use primitives::{
-	evm::{convert_decimals_from_evm, EvmAddress, Vicinity, MIRRORED_NFT_ADDRESS_START},
+	evm::{convert_decimals_from_evm, EvmAddress, Vicinity, DETERMINISTIC_DEPLOYER, MIRRORED_NFT_ADDRESS_START},
 	ReserveIdentifier,
 };
 use sha3::{Digest, Keccak256};
@@ -80,6 +80,7 @@ impl<T: Config> Runner<T> {
 		let vicinity = Vicinity {
 			gas_price,
 			origin,
+			block_base_fee_per_gas: Some(Pallet::<T>::base_fee_per_gas().into()),
 			..Default::default()
 		};
 
@@ -108,8 +109,23 @@ impl<T: Config> Runner<T> {
 			})?;
 		}
 
+		// Only the top-level executions count in `BlockGasUsed` and pay the base fee, the nested
+		// ones are paid by their outer execution.
+		let depth = ExecutionDepth::<T>::get();
+		let origin_account = T::AddressMapping::get_account_id(&origin);
+		let charge_base_fee = depth.is_zero() && !skip_storage_rent;
+		if charge_base_fee {
+			Pallet::<T>::reserve_base_fee(&origin_account, gas_limit)?;
+		}
+
 		// Execute the EVM call.
+		ExecutionDepth::<T>::put(depth.saturating_add(1));
 		let (reason, retv) = f(&mut executor);
+		if depth.is_zero() {
+			ExecutionDepth::<T>::kill();
+		} else {
+			ExecutionDepth::<T>::put(depth);
+		}
 
 		let used_gas = U256::from(executor.used_gas());
 		log::debug!(
@@ -124,6 +140,13 @@ impl<T: Config> Runner<T> {
 
 		let state = executor.into_state();
 
+		if depth.is_zero() {
+			BlockGasUsed::<T>::mutate(|gas| *gas = gas.saturating_add(used_gas.low_u64()));
+		}
+		if charge_base_fee {
+			Pallet::<T>::charge_base_fee(&origin_account, gas_limit, used_gas.low_u64())?;
+		}
+
 		// charge storage
 		let actual_storage = state
 			.metadata()
@@ -178,6 +201,10 @@ impl<T: Config> Runner<T> {
 			})?;
 		}
 
+		for address in &state.touched {
+			Pallet::<T>::touch_contract(address);
+		}
+
 		for address in state.substate.deletes {
 			log::debug!(
 				target: "evm",
@@ -352,6 +379,45 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 			},
 		)
 	}
+
+	/// Require transactional here. Always need to send events.
+	#[transactional]
+	fn create_deterministic(
+		origin: H160,
+		init: Vec<u8>,
+		salt: H256,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CreateInfo, DispatchError> {
+		let source = DETERMINISTIC_DEPLOYER;
+		let precompiles = T::PrecompilesValue::get();
+		let code_hash = H256::from_slice(Keccak256::digest(&init).as_slice());
+		Self::execute(
+			source,
+			origin,
+			U256::zero(),
+			gas_limit,
+			storage_limit,
+			config,
+			false,
+			&precompiles,
+			|executor| {
+				let address = executor
+					.create_address(evm::CreateScheme::Create2 {
+						caller: source,
+						code_hash,
+						salt,
+					})
+					.unwrap_or_default(); // transact_create2 will check the address
+				(
+					executor.transact_create2(source, U256::zero(), init, salt, gas_limit, access_list),
+					address,
+				)
+			},
+		)
+	}
 }
 
 impl<T: Config> RunnerExtended<T> for Runner<T> {
@@ -569,6 +635,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 pub struct SubstrateStackState<'vicinity, 'config, T> {
 	vicinity: &'vicinity Vicinity,
 	substate: SubstrateStackSubstate<'config>,
+	/// The addresses touched by the execution, including the reverted call frames.
+	touched: BTreeSet<H160>,
 	_marker: PhantomData<T>,
 }
 
@@ -585,6 +653,7 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 				parent: None,
 				known_original_storage: BTreeMap::new(),
 			},
+			touched: BTreeSet::new(),
 			_marker: PhantomData,
 		}
 	}
@@ -891,16 +960,25 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config> for SubstrateStackState
 		}
 	}
 
-	fn touch(&mut self, _address: H160) {
-		// Do nothing on touch in Substrate.
-		//
+	fn touch(&mut self, address: H160) -> u64 {
 		// EVM pallet considers all accounts to exist, and distinguish
 		// only empty and non-empty accounts. This avoids many of the
 		// subtle issues in EIP-161.
+		//
+		// Only keep track of the last touched block of contracts for state expiry, which is
+		// recorded once per transaction by the runner. The first touch of a contract pays for the
+		// record if it's not already recorded in the block.
+		let cost = if self.touched.insert(address) && Pallet::<T>::is_contract_touch_recorded(&address) {
+			TOUCH_CONTRACT_GAS
+		} else {
+			0
+		};
 
 		// this is needed only for evm-tests to keep track of dirty accounts
 		#[cfg(feature = "evm-tests")]
-		self.substate.mark_account_dirty(_address);
+		self.substate.mark_account_dirty(address);
+
+		cost
 	}
 
 	fn is_cold(&self, address: H160) -> bool {
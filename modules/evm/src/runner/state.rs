@@ -332,7 +332,8 @@ pub trait StackState<'config>: Backend {
 	fn set_code(&mut self, address: H160, code: Vec<u8>);
 	fn transfer(&mut self, transfer: Transfer) -> Result<(), ExitError>;
 	fn reset_balance(&mut self, address: H160);
-	fn touch(&mut self, address: H160);
+	/// Touch the address, returns the gas cost of the touch.
+	fn touch(&mut self, address: H160) -> u64;
 }
 
 /// Data returned by a precompile on success.
@@ -990,7 +991,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		let code = self.code(code_address);
 
 		self.enter_substate(gas_limit, is_static);
-		self.state.touch(context.address);
+		let touch_cost = self.state.touch(context.address);
+		if let Err(e) = self.state.metadata_mut().gasometer.record_cost(touch_cost) {
+			let _ = self.exit_substate(StackExitKind::Failed);
+			return Capture::Exit((e.into(), Vec::new()));
+		}
 
 		if let Some(depth) = self.state.metadata().depth {
 			if depth > self.config.call_stack_limit {
@@ -27,6 +27,7 @@ use crate::runner::{
 };
 use frame_support::{assert_noop, assert_ok, dispatch::DispatchErrorWithPostInfo};
 use module_support::AddressMapping;
+use primitives::evm::ERC20_TRANSFER_TOPIC;
 use sp_core::{
 	bytes::{from_hex, to_hex},
 	H160,
@@ -881,6 +882,117 @@ fn create_predeploy_contract_works() {
 		);
 
 		assert_eq!(CodeInfos::<Runtime>::get(&EVM::code_hash_at_address(&token_addr)), None);
+
+		// the address is not reserved
+		assert_noop!(
+			EVM::create_predeploy_contract(
+				Origin::signed(NetworkContractAccount::get()),
+				H160::repeat_byte(0xff),
+				contract,
+				0,
+				1000000,
+				1000000,
+				vec![],
+			),
+			Error::<Runtime>::AddressNotReserved
+		);
+	});
+}
+
+#[test]
+fn should_create_deterministic() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		let mut salt = H256::from_low_u64_be(1);
+		salt.as_bytes_mut()[..20].copy_from_slice(alice().as_bytes());
+		let address = deterministic_address(&salt, &code_hash(&contract));
+
+		// the salt is reserved by alice
+		assert_noop!(
+			EVM::create_deterministic(
+				Origin::signed(bob_account_id.clone()),
+				contract.clone(),
+				salt,
+				1000000,
+				1000000,
+				vec![],
+			),
+			Error::<Runtime>::SaltReserved
+		);
+
+		assert_ok!(EVM::create_deterministic(
+			Origin::signed(alice_account_id),
+			contract.clone(),
+			salt,
+			1000000,
+			1000000,
+			vec![],
+		));
+		assert!(matches!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EVM(crate::Event::Created { from, contract, used_storage: 284, .. }))
+				if from == alice() && contract == address
+		));
+		assert_eq!(
+			EVM::accounts(address).and_then(|account| account.contract_info),
+			Some(ContractInfo {
+				code_hash: code_hash(&contract),
+				maintainer: alice(),
+				published: false,
+			})
+		);
+		assert_eq!(
+			balance(alice()),
+			INITIAL_BALANCE - 284 * EVM::get_storage_deposit_per_byte()
+		);
+
+		// anyone can use the salt without the address prefix, and the contract is immutable
+		assert_ok!(EVM::create_deterministic(
+			Origin::signed(bob_account_id),
+			contract.clone(),
+			H256::from_low_u64_be(1),
+			1000000,
+			1000000,
+			vec![],
+		));
+		assert_eq!(
+			EVM::accounts(deterministic_address(&H256::from_low_u64_be(1), &code_hash(&contract)))
+				.and_then(|account| account.contract_info),
+			Some(ContractInfo {
+				code_hash: code_hash(&contract),
+				maintainer: DETERMINISTIC_DEPLOYER,
+				published: true,
+			})
+		);
+
+		// the deployments can't be nested
+		DeterministicDeploying::<Runtime>::put(true);
+		assert_eq!(
+			EVM::deploy_deterministic(
+				bob(),
+				contract,
+				H256::from_low_u64_be(2),
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+			.map(|_| ()),
+			Err(Error::<Runtime>::NestedDeterministicDeployment.into())
+		);
 	});
 }
 
@@ -1252,83 +1364,903 @@ fn should_set_code() {
 			EVM::set_code(Origin::signed(bob_account_id), contract_address, contract.clone()),
 			Error::<Runtime>::NoPermission
 		);
-		assert_ok!(EVM::set_code(
+		assert_ok!(EVM::set_code(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			contract.clone()
+		));
+		assert_ok!(EVM::set_code(Origin::root(), contract_address, contract));
+
+		assert_eq!(reserved_balance(contract_address), 4150);
+
+		let new_code_hash = H256::from_str("9061d510f6235de4eae304e1a2a2ae22e1610ba893c018b7fabc1f1635f49877").unwrap();
+		assert_eq!(
+			Accounts::<Runtime>::get(&contract_address),
+			Some(AccountInfo {
+				nonce: 1,
+				contract_info: Some(ContractInfo {
+					code_hash: new_code_hash,
+					maintainer: alice(),
+					published: false
+				})
+			})
+		);
+		assert_eq!(CodeInfos::<Runtime>::get(&code_hash), None);
+		assert_eq!(
+			CodeInfos::<Runtime>::get(&new_code_hash),
+			Some(CodeInfo {
+				code_size: 215,
+				ref_count: 1,
+			})
+		);
+		assert_eq!(Codes::<Runtime>::contains_key(&code_hash), false);
+		assert_eq!(Codes::<Runtime>::contains_key(&new_code_hash), true);
+
+		assert_ok!(EVM::set_code(Origin::root(), contract_address, vec![]));
+		let new_code_hash = H256::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap();
+		assert_eq!(
+			Accounts::<Runtime>::get(&contract_address),
+			Some(AccountInfo {
+				nonce: 1,
+				contract_info: Some(ContractInfo {
+					code_hash: new_code_hash,
+					maintainer: alice(),
+					published: false
+				})
+			})
+		);
+		assert_eq!(
+			CodeInfos::<Runtime>::get(&new_code_hash),
+			Some(CodeInfo {
+				code_size: 0,
+				ref_count: 1,
+			})
+		);
+		assert_eq!(reserved_balance(contract_address), 3000);
+
+		assert_noop!(
+			EVM::set_code(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				[8u8; (MaxCodeSize::get() + 1) as usize].to_vec(),
+			),
+			Error::<Runtime>::ContractExceedsMaxCodeSize
+		);
+
+		assert_ok!(EVM::publish_free(
+			Origin::signed(CouncilAccount::get()),
+			contract_address
+		));
+
+		assert_noop!(
+			EVM::set_code(Origin::signed(alice_account_id), contract_address, contract_err),
+			Error::<Runtime>::ContractAlreadyPublished
+		);
+	});
+}
+
+#[test]
+fn should_selfdestruct() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		let amount = 1000u128;
+
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
+
+		// create contract
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			convert_decimals_to_evm(amount),
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+
+		let contract_address = result.value;
+		assert_eq!(result.used_storage, 287);
+		let alice_balance = INITIAL_BALANCE - 287 * EVM::get_storage_deposit_per_byte() - amount;
+
+		assert_eq!(balance(alice()), alice_balance);
+
+		let code_hash = H256::from_str("21fe816097a50d298f819bc6d40cff473c43c87d99bcd7d3c3b2b85417f66f5a").unwrap();
+		let code_size = 123u32;
+
+		assert_eq!(
+			ContractStorageSizes::<Runtime>::get(&contract_address),
+			code_size + NEW_CONTRACT_EXTRA_BYTES + STORAGE_SIZE
+		);
+		assert_eq!(
+			CodeInfos::<Runtime>::get(&code_hash),
+			Some(CodeInfo {
+				code_size,
+				ref_count: 1,
+			})
+		);
+		assert!(Codes::<Runtime>::contains_key(&code_hash));
+
+		assert_noop!(
+			EVM::selfdestruct(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		let contract_account_id = <Runtime as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_eq!(System::providers(&contract_account_id), 2);
+		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract_address));
+
+		assert_eq!(System::providers(&contract_account_id), 1);
+		assert!(System::account_exists(&contract_account_id));
+		assert!(Accounts::<Runtime>::contains_key(&contract_address));
+		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 1);
+		assert!(!CodeInfos::<Runtime>::contains_key(&code_hash));
+		assert!(!Codes::<Runtime>::contains_key(&code_hash));
+
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(balance(contract_address), 1000);
+
+		let reserved_amount = 287 * EVM::get_storage_deposit_per_byte();
+		assert_eq!(reserved_balance(contract_address), reserved_amount);
+
+		// can't publish at the same address
+		assert_noop!(
+			EVM::create_predeploy_contract(
+				Origin::signed(NetworkContractAccount::get()),
+				contract_address,
+				vec![],
+				0,
+				1000000,
+				1000000,
+				vec![],
+			),
+			DispatchErrorWithPostInfo {
+				post_info: PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: Pays::Yes,
+				},
+				error: Error::<Runtime>::ContractAlreadyExisted.into()
+			}
+		);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		// refund storage deposit
+		assert_eq!(balance(alice()), alice_balance + amount + reserved_amount);
+		assert_eq!(balance(contract_address), 0);
+		assert_eq!(reserved_balance(contract_address), 0);
+
+		assert_eq!(System::providers(&contract_account_id), 0);
+		assert!(!System::account_exists(&contract_account_id));
+		assert!(Accounts::<Runtime>::contains_key(&contract_address));
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
+	});
+}
+
+#[test]
+fn should_archive_and_revive_contract() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
+
+		// create contract
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+		let storage_size = ContractStorageSizes::<Runtime>::get(&contract_address);
+		let reserved_amount = 287 * EVM::get_storage_deposit_per_byte();
+		let alice_balance = INITIAL_BALANCE - reserved_amount;
+		assert_eq!(storage_size, 287);
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(EVM::contract_last_touched(&contract_address), Some(1));
+
+		System::set_block_number(100);
+		assert_noop!(
+			EVM::archive_contract(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::ContractNotExpired
+		);
+
+		System::set_block_number(101);
+		assert_ok!(EVM::archive_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address
+		));
+		assert!(!EVM::is_contract(&contract_address));
+		assert!(Codes::<Runtime>::contains_key(&code_hash));
+		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
+		assert_eq!(EVM::contract_last_touched(&contract_address), None);
+		assert_noop!(
+			EVM::revive_contract(Origin::signed(bob_account_id.clone()), contract_address, vec![]),
+			Error::<Runtime>::ContractArchiving
+		);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		let index = H256::zero();
+		let value = H256::from_low_u64_be(0x7b);
+		let storage_root = archived_storage_root(H256::zero(), &index, &value);
+		System::assert_last_event(Event::EVM(crate::Event::ContractArchived {
+			contract: contract_address,
+			storage_root,
+		}));
+		assert_eq!(
+			EVM::archived_contracts(&contract_address).map(|archived| (archived.storage_root, archived.finished)),
+			Some((storage_root, true))
+		);
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
+
+		// refund storage deposit to the maintainer
+		assert_eq!(balance(alice()), alice_balance + reserved_amount);
+		assert_eq!(reserved_balance(contract_address), 0);
+
+		assert_noop!(
+			EVM::revive_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				vec![(index, H256::from_low_u64_be(0x7c))]
+			),
+			Error::<Runtime>::InvalidStorageProof
+		);
+		assert_ok!(EVM::revive_contract(
+			Origin::signed(bob_account_id),
+			contract_address,
+			vec![(index, value)]
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractRevived {
+			contract: contract_address,
+		}));
+
+		assert!(EVM::is_contract(&contract_address));
+		assert_eq!(EVM::code_hash_at_address(&contract_address), code_hash);
+		assert_eq!(AccountStorages::<Runtime>::get(&contract_address, index), value);
+		assert_eq!(ContractStorageSizes::<Runtime>::get(&contract_address), storage_size);
+		assert_eq!(EVM::contract_last_touched(&contract_address), Some(101));
+		assert_eq!(EVM::archived_contracts(&contract_address), None);
+
+		// the reviver pays the storage deposit
+		assert_eq!(balance(bob()), INITIAL_BALANCE - reserved_amount);
+		assert_eq!(reserved_balance(contract_address), reserved_amount);
+	});
+}
+
+#[test]
+fn should_charge_recording_contract_touch() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let contract_address = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap()
+		.value;
+		let call = || {
+			<Runtime as Config>::Runner::call(
+				alice(),
+				alice(),
+				contract_address,
+				multiply.clone(),
+				0,
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+			.unwrap()
+			.used_gas
+		};
+
+		// already recorded by the creation
+		let used_gas = call();
+
+		// the first touch in a block pays for the record
+		System::set_block_number(2);
+		assert_eq!(call(), used_gas + TOUCH_CONTRACT_GAS);
+		assert_eq!(EVM::contract_last_touched(&contract_address), Some(2));
+		assert_eq!(call(), used_gas);
+	});
+}
+
+#[test]
+fn contracts_without_last_touched_expire_from_upgrade() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		// deployed before the state expiry
+		ContractLastTouched::<Runtime>::remove(&contract_address);
+		ContractExpiryStart::<Runtime>::kill();
+		System::set_block_number(200);
+		assert_noop!(
+			EVM::archive_contract(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::ContractNotExpired
+		);
+
+		// the upgrade starts the state expiry
+		EVM::on_runtime_upgrade();
+		assert_eq!(EVM::contract_expiry_start(), Some(200));
+		System::set_block_number(250);
+		EVM::on_runtime_upgrade();
+		assert_eq!(EVM::contract_expiry_start(), Some(200));
+
+		System::set_block_number(299);
+		assert_noop!(
+			EVM::archive_contract(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::ContractNotExpired
+		);
+		System::set_block_number(300);
+		assert_ok!(EVM::archive_contract(Origin::signed(bob_account_id), contract_address));
+	});
+}
+
+#[test]
+fn should_upgrade_published_contract() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	let new_code = from_hex("0x6080604052").unwrap();
+	let new_code_hash = code_hash(&new_code);
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		// create contract
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+
+		assert_noop!(
+			EVM::propose_upgrade(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				new_code.clone()
+			),
+			Error::<Runtime>::ContractNotPublished
+		);
+		assert_ok!(EVM::publish_free(
+			Origin::signed(CouncilAccount::get()),
+			contract_address
+		));
+		assert_noop!(
+			EVM::propose_upgrade(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				new_code.clone()
+			),
+			Error::<Runtime>::NoPermission
+		);
+
+		// propose and cancel
+		assert_ok!(EVM::propose_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code.clone()
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractUpgradeProposed {
+			contract: contract_address,
+			code_hash: new_code_hash,
+			enactable_at: 11,
+		}));
+		let deposit = EVM::get_storage_deposit_per_byte() * new_code.len() as u128;
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_UPGRADE_DEPOSIT, &alice_account_id),
+			deposit
+		);
+		assert_noop!(
+			EVM::propose_upgrade(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				new_code.clone()
+			),
+			Error::<Runtime>::UpgradeAlreadyProposed
+		);
+		assert_noop!(
+			EVM::cancel_upgrade(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EVM::cancel_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractUpgradeCancelled {
+			contract: contract_address,
+		}));
+		assert_noop!(
+			EVM::cancel_upgrade(Origin::signed(alice_account_id.clone()), contract_address),
+			Error::<Runtime>::UpgradeNotProposed
+		);
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_UPGRADE_DEPOSIT, &alice_account_id),
+			0
+		);
+
+		// the cancelled upgrade is never applied
+		System::set_block_number(11);
+		IdleScheduler::on_idle(11, 1_000_000_000_000);
+		assert_eq!(EVM::code_hash_at_address(&contract_address), code_hash);
+
+		// propose again and wait for the delay
+		assert_ok!(EVM::propose_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code
+		));
+		assert_eq!(
+			EVM::pending_upgrades(&contract_address).map(|upgrade| upgrade.enactable_at),
+			Some(21)
+		);
+
+		System::set_block_number(20);
+		IdleScheduler::on_idle(20, 1_000_000_000_000);
+		assert_eq!(EVM::code_hash_at_address(&contract_address), code_hash);
+
+		System::set_block_number(21);
+		IdleScheduler::on_idle(21, 1_000_000_000_000);
+		System::assert_last_event(Event::EVM(crate::Event::ContractUpgraded {
+			contract: contract_address,
+			code_hash: new_code_hash,
+		}));
+		assert_eq!(EVM::code_hash_at_address(&contract_address), new_code_hash);
+		assert_eq!(EVM::pending_upgrades(&contract_address), None);
+		assert_eq!(CodeInfos::<Runtime>::get(&code_hash), None);
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_UPGRADE_DEPOSIT, &alice_account_id),
+			0
+		);
+	});
+}
+
+#[test]
+fn should_report_failed_upgrade() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// larger than the current code, so more storage is charged
+	let new_code = vec![0x60; 1024];
+	let new_code_hash = code_hash(&new_code);
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+		assert_ok!(EVM::publish_free(
+			Origin::signed(CouncilAccount::get()),
+			contract_address
+		));
+
+		assert_ok!(EVM::propose_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code.clone()
+		));
+		let deposit = EVM::get_storage_deposit_per_byte() * new_code.len() as u128;
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_UPGRADE_DEPOSIT, &alice_account_id),
+			deposit
+		);
+
+		// the maintainer can't pay the storage of the new code
+		Balances::make_free_balance_be(&alice_account_id, 0);
+
+		System::set_block_number(11);
+		IdleScheduler::on_idle(11, 1_000_000_000_000);
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::EVM(crate::Event::ContractUpgradeFailed { contract, code_hash, .. })
+				if contract == contract_address && code_hash == new_code_hash
+		)));
+
+		// the upgrade is removed with its deposit
+		assert_eq!(EVM::code_hash_at_address(&contract_address), code_hash);
+		assert_eq!(EVM::pending_upgrades(&contract_address), None);
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_UPGRADE_DEPOSIT, &alice_account_id),
+			0
+		);
+		assert_eq!(Balances::free_balance(&alice_account_id), deposit);
+	});
+}
+
+#[test]
+fn should_submit_contract_verification() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	let compiler_version = b"solc-0.5.17+commit.d19bba13".to_vec();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		// create contract
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(alice_account_id.clone()),
+				H160::from_low_u64_be(1234),
+				compiler_version.clone(),
+				H256::repeat_byte(1),
+				H256::repeat_byte(2),
+				H256::repeat_byte(3),
+			),
+			Error::<Runtime>::ContractNotFound
+		);
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(bob_account_id),
+				contract_address,
+				compiler_version.clone(),
+				H256::repeat_byte(1),
+				H256::repeat_byte(2),
+				H256::repeat_byte(3),
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				vec![],
+				H256::repeat_byte(1),
+				H256::repeat_byte(2),
+				H256::repeat_byte(3),
+			),
+			Error::<Runtime>::InvalidCompilerVersion
+		);
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				vec![b'0'; 65],
+				H256::repeat_byte(1),
+				H256::repeat_byte(2),
+				H256::repeat_byte(3),
+			),
+			Error::<Runtime>::InvalidCompilerVersion
+		);
+		assert_eq!(
+			EVM::query_verification(contract_address),
+			Err(Error::<Runtime>::ContractNotVerified.into())
+		);
+
+		assert_ok!(EVM::submit_verification(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			compiler_version.clone(),
+			H256::repeat_byte(1),
+			H256::repeat_byte(2),
+			H256::repeat_byte(3),
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractVerified {
+			contract: contract_address,
+			code_hash,
+		}));
+		assert_eq!(
+			EVM::query_verification(contract_address),
+			Ok(ContractVerification {
+				code_hash,
+				compiler_version: compiler_version.clone().try_into().unwrap(),
+				settings_hash: H256::repeat_byte(1),
+				metadata_hash: H256::repeat_byte(2),
+				abi_hash: H256::repeat_byte(3),
+			})
+		);
+
+		// resubmit replaces the verification
+		assert_ok!(EVM::submit_verification(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			compiler_version,
+			H256::repeat_byte(4),
+			H256::repeat_byte(2),
+			H256::repeat_byte(3),
+		));
+		assert_eq!(
+			EVM::contract_verifications(&contract_address).map(|verification| verification.settings_hash),
+			Some(H256::repeat_byte(4))
+		);
+
+		// the verification is removed once the code changes
+		assert_ok!(EVM::set_code(
+			Origin::signed(alice_account_id),
+			contract_address,
+			from_hex("0x6080604052").unwrap()
+		));
+		assert_eq!(EVM::contract_verifications(&contract_address), None);
+	});
+}
+
+#[test]
+fn should_batch_operations() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		assert_ok!(EVM::batch(
+			Origin::signed(alice_account_id.clone()),
+			vec![
+				BatchOperation::Create {
+					input: contract.clone(),
+					value: 0
+				},
+				BatchOperation::Create {
+					input: contract,
+					value: 0
+				},
+			],
+			1_000_000,
+			1_000,
+			vec![],
+		));
+		let results = match System::events().last().map(|record| record.event.clone()) {
+			Some(Event::EVM(crate::Event::BatchExecuted {
+				from,
+				results,
+				used_storage,
+				..
+			})) => {
+				assert_eq!(from, alice());
+				assert_eq!(used_storage, 284 * 2);
+				results
+			}
+			event => panic!("unexpected event: {:?}", event),
+		};
+		assert_eq!(results.len(), 2);
+		assert!(results.iter().all(|result| EVM::is_contract(&result.contract)));
+		assert_eq!(
+			balance(alice()),
+			INITIAL_BALANCE - 284 * 2 * EVM::get_storage_deposit_per_byte()
+		);
+
+		// the second operation fails and reverts the first one
+		let nonce = EVM::accounts(alice()).unwrap().nonce;
+		assert_ok!(EVM::batch(
 			Origin::signed(alice_account_id.clone()),
-			contract_address,
-			contract.clone()
+			vec![
+				BatchOperation::Call {
+					target: results[0].contract,
+					input: multiply.clone(),
+					value: 0
+				},
+				BatchOperation::Create {
+					input: vec![0xfe],
+					value: 0
+				},
+			],
+			1_000_000,
+			1_000,
+			vec![],
 		));
-		assert_ok!(EVM::set_code(Origin::root(), contract_address, contract));
+		assert!(matches!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EVM(crate::Event::BatchExecutedFailed { index: 1, .. }))
+		));
+		assert_eq!(EVM::accounts(alice()).unwrap().nonce, nonce);
 
-		assert_eq!(reserved_balance(contract_address), 4150);
+		// the operations share the gas limit
+		assert_ok!(EVM::batch(
+			Origin::signed(alice_account_id),
+			vec![
+				BatchOperation::Call {
+					target: results[0].contract,
+					input: multiply.clone(),
+					value: 0
+				},
+				BatchOperation::Call {
+					target: results[1].contract,
+					input: multiply,
+					value: 0
+				},
+			],
+			30_000,
+			0,
+			vec![],
+		));
+		assert!(matches!(
+			System::events().last().map(|record| record.event.clone()),
+			Some(Event::EVM(crate::Event::BatchExecutedFailed {
+				index: 1,
+				exit_reason: ExitReason::Error(ExitError::OutOfGas),
+				..
+			}))
+		));
+	});
+}
 
-		let new_code_hash = H256::from_str("9061d510f6235de4eae304e1a2a2ae22e1610ba893c018b7fabc1f1635f49877").unwrap();
+#[test]
+fn base_fee_per_gas_should_work() {
+	new_test_ext().execute_with(|| {
+		let min_base_fee = <Runtime as Config>::TxFeePerGas::get();
+		assert_eq!(EVM::base_fee_per_gas(), min_base_fee);
+
+		// full block, rounded to the native decimals
 		assert_eq!(
-			Accounts::<Runtime>::get(&contract_address),
-			Some(AccountInfo {
-				nonce: 1,
-				contract_info: Some(ContractInfo {
-					code_hash: new_code_hash,
-					maintainer: alice(),
-					published: false
-				})
-			})
+			EVM::next_base_fee_per_gas(min_base_fee, 30_000_000),
+			min_base_fee + 2_000_000
 		);
-		assert_eq!(CodeInfos::<Runtime>::get(&code_hash), None);
+		// at target
 		assert_eq!(
-			CodeInfos::<Runtime>::get(&new_code_hash),
-			Some(CodeInfo {
-				code_size: 215,
-				ref_count: 1,
-			})
+			EVM::next_base_fee_per_gas(min_base_fee * 10, 15_000_000),
+			min_base_fee * 10
 		);
-		assert_eq!(Codes::<Runtime>::contains_key(&code_hash), false);
-		assert_eq!(Codes::<Runtime>::contains_key(&new_code_hash), true);
-
-		assert_ok!(EVM::set_code(Origin::root(), contract_address, vec![]));
-		let new_code_hash = H256::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap();
+		// empty block
 		assert_eq!(
-			Accounts::<Runtime>::get(&contract_address),
-			Some(AccountInfo {
-				nonce: 1,
-				contract_info: Some(ContractInfo {
-					code_hash: new_code_hash,
-					maintainer: alice(),
-					published: false
-				})
-			})
+			EVM::next_base_fee_per_gas(min_base_fee * 10, 0),
+			min_base_fee * 10 - 25_000_000
 		);
+		// at least one native unit
 		assert_eq!(
-			CodeInfos::<Runtime>::get(&new_code_hash),
-			Some(CodeInfo {
-				code_size: 0,
-				ref_count: 1,
-			})
-		);
-		assert_eq!(reserved_balance(contract_address), 3000);
-
-		assert_noop!(
-			EVM::set_code(
-				Origin::signed(alice_account_id.clone()),
-				contract_address,
-				[8u8; (MaxCodeSize::get() + 1) as usize].to_vec(),
-			),
-			Error::<Runtime>::ContractExceedsMaxCodeSize
+			EVM::next_base_fee_per_gas(min_base_fee, 15_000_001),
+			min_base_fee + 1_000_000
 		);
+		// never below `TxFeePerGas`
+		assert_eq!(EVM::next_base_fee_per_gas(min_base_fee, 0), min_base_fee);
 
-		assert_ok!(EVM::publish_free(
-			Origin::signed(CouncilAccount::get()),
-			contract_address
-		));
-
-		assert_noop!(
-			EVM::set_code(Origin::signed(alice_account_id), contract_address, contract_err),
-			Error::<Runtime>::ContractAlreadyPublished
-		);
+		BlockGasUsed::<Runtime>::put(30_000_000);
+		EVM::on_finalize(1);
+		assert_eq!(EVM::block_gas_used(), 0);
+		assert_eq!(EVM::base_fee_per_gas(), min_base_fee + 2_000_000);
 	});
 }
 
 #[test]
-fn should_selfdestruct() {
+fn should_charge_base_fee() {
 	// pragma solidity ^0.5.0;
 	//
 	// contract Test {
@@ -1341,107 +2273,113 @@ fn should_selfdestruct() {
 	// 	     return value;
 	// 	 }
 	// }
-	let mut contract = from_hex(
-		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	let contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032000000000000000000000000000000000000000000000000000000000000007b"
 	).unwrap();
 
 	new_test_ext().execute_with(|| {
 		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
-		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
-
-		let amount = 1000u128;
-
-		let mut stored_value: Vec<u8> =
-			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
-		contract.append(&mut stored_value);
+		let treasury_balance = Balances::free_balance(TreasuryAccount::get());
+		// one native unit per gas above `TxFeePerGas`
+		BaseFeePerGas::<Runtime>::put(<Runtime as Config>::TxFeePerGas::get() + 1_000_000);
 
-		// create contract
+		// every top-level execution is charged
 		let result = <Runtime as Config>::Runner::create(
 			alice(),
-			contract,
-			convert_decimals_to_evm(amount),
+			contract.clone(),
+			0,
 			1000000,
 			100000,
 			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
-
+		assert!(result.exit_reason.is_succeed());
+		assert_eq!(EVM::block_gas_used(), result.used_gas.as_u64());
+		let treasury_balance = treasury_balance + result.used_gas.as_u128();
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance);
 		let contract_address = result.value;
-		assert_eq!(result.used_storage, 287);
-		let alice_balance = INITIAL_BALANCE - 287 * EVM::get_storage_deposit_per_byte() - amount;
-
-		assert_eq!(balance(alice()), alice_balance);
-
-		let code_hash = H256::from_str("21fe816097a50d298f819bc6d40cff473c43c87d99bcd7d3c3b2b85417f66f5a").unwrap();
-		let code_size = 123u32;
 
+		// the reverted executions are charged
+		let info = EVM::execute(
+			InvokeContext {
+				contract: contract_address,
+				sender: alice(),
+				origin: alice(),
+			},
+			from_hex("0x12345678").unwrap(),
+			0,
+			1000000,
+			100000,
+			ExecutionMode::Execute,
+		)
+		.unwrap();
+		assert!(!info.exit_reason.is_succeed());
+		let block_gas_used = EVM::block_gas_used();
+		assert_eq!(block_gas_used, (result.used_gas + info.used_gas).as_u64());
+		let treasury_balance = treasury_balance + info.used_gas.as_u128();
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance);
+
+		// the nested executions are paid by their outer execution
+		ExecutionDepth::<Runtime>::put(1);
+		assert!(<Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap()
+		.exit_reason
+		.is_succeed());
+		assert_eq!(ExecutionDepth::<Runtime>::get(), 1);
+		ExecutionDepth::<Runtime>::kill();
+		assert_eq!(EVM::block_gas_used(), block_gas_used);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance);
+		let alice_balance = balance(alice());
+
+		assert_ok!(EVM::eth_call(
+			Origin::signed(alice_account_id.clone()),
+			TransactionAction::Create,
+			contract,
+			0,
+			1000000,
+			100000,
+			vec![],
+			0
+		));
+		let used_gas = (EVM::block_gas_used() - block_gas_used) as u128;
 		assert_eq!(
-			ContractStorageSizes::<Runtime>::get(&contract_address),
-			code_size + NEW_CONTRACT_EXTRA_BYTES + STORAGE_SIZE
+			Balances::free_balance(TreasuryAccount::get()),
+			treasury_balance + used_gas
 		);
 		assert_eq!(
-			CodeInfos::<Runtime>::get(&code_hash),
-			Some(CodeInfo {
-				code_size,
-				ref_count: 1,
-			})
+			balance(alice()),
+			alice_balance - 287 * EVM::get_storage_deposit_per_byte() - used_gas
 		);
-		assert!(Codes::<Runtime>::contains_key(&code_hash));
-
-		assert_noop!(
-			EVM::selfdestruct(Origin::signed(bob_account_id), contract_address),
-			Error::<Runtime>::NoPermission
+		// the unused fee is unreserved
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_BASE_FEE, &alice_account_id),
+			0
 		);
-		let contract_account_id = <Runtime as Config>::AddressMapping::get_account_id(&contract_address);
-		assert_eq!(System::providers(&contract_account_id), 2);
-		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract_address));
-
-		assert_eq!(System::providers(&contract_account_id), 1);
-		assert!(System::account_exists(&contract_account_id));
-		assert!(Accounts::<Runtime>::contains_key(&contract_address));
-		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
-		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 1);
-		assert!(!CodeInfos::<Runtime>::contains_key(&code_hash));
-		assert!(!Codes::<Runtime>::contains_key(&code_hash));
-
-		assert_eq!(balance(alice()), alice_balance);
-		assert_eq!(balance(contract_address), 1000);
-
-		let reserved_amount = 287 * EVM::get_storage_deposit_per_byte();
-		assert_eq!(reserved_balance(contract_address), reserved_amount);
 
-		// can't publish at the same address
+		// the fee of the gas limit is reserved upfront
+		BaseFeePerGas::<Runtime>::put(<Runtime as Config>::TxFeePerGas::get() + INITIAL_BALANCE * 1_000_000);
 		assert_noop!(
-			EVM::create_predeploy_contract(
-				Origin::signed(NetworkContractAccount::get()),
-				contract_address,
+			EVM::eth_call(
+				Origin::signed(alice_account_id),
+				TransactionAction::Create,
 				vec![],
 				0,
 				1000000,
-				1000000,
+				100000,
 				vec![],
+				0
 			),
-			DispatchErrorWithPostInfo {
-				post_info: PostDispatchInfo {
-					actual_weight: None,
-					pays_fee: Pays::Yes,
-				},
-				error: Error::<Runtime>::ContractAlreadyExisted.into()
-			}
+			Error::<Runtime>::ChargeFeeFailed
 		);
-
-		IdleScheduler::on_idle(0, 1_000_000_000_000);
-
-		// refund storage deposit
-		assert_eq!(balance(alice()), alice_balance + amount + reserved_amount);
-		assert_eq!(balance(contract_address), 0);
-		assert_eq!(reserved_balance(contract_address), 0);
-
-		assert_eq!(System::providers(&contract_account_id), 0);
-		assert!(!System::account_exists(&contract_account_id));
-		assert!(Accounts::<Runtime>::contains_key(&contract_address));
-		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
 	});
 }
 
@@ -2436,3 +3374,148 @@ fn strict_call_works() {
 		));
 	})
 }
+
+#[test]
+fn should_collect_block_receipts() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		// events deposited out of the extrinsics are ignored
+		assert_ok!(EVM::create(
+			Origin::signed(alice_account_id.clone()),
+			contract.clone(),
+			0,
+			1_000_000,
+			1_000,
+			vec![]
+		));
+		assert_eq!(EVM::block_receipts(), vec![]);
+
+		System::note_finished_initialize();
+		assert_ok!(EVM::create(
+			Origin::signed(alice_account_id.clone()),
+			contract,
+			0,
+			1_000_000,
+			1_000,
+			vec![]
+		));
+		let contract_address = match System::events().last().map(|record| record.event.clone()) {
+			Some(Event::EVM(crate::Event::Created { contract, .. })) => contract,
+			event => panic!("unexpected event: {:?}", event),
+		};
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+
+		assert_ok!(EVM::call(
+			Origin::signed(alice_account_id),
+			contract_address,
+			multiply,
+			0,
+			1_000_000,
+			0,
+			vec![]
+		));
+
+		let receipts = EVM::block_receipts();
+		assert_eq!(receipts.len(), 2);
+		assert_eq!(receipts[0].extrinsic_index, 0);
+		assert_eq!(receipts[0].from, alice());
+		assert_eq!(receipts[0].to, None);
+		assert_eq!(receipts[0].contract_address, Some(contract_address));
+		assert!(receipts[0].succeed);
+		assert_eq!(receipts[1].extrinsic_index, 1);
+		assert_eq!(receipts[1].to, Some(contract_address));
+		assert_eq!(receipts[1].contract_address, None);
+		assert!(receipts[1].succeed);
+		assert!(receipts[1].used_gas > 0);
+	});
+}
+
+#[test]
+fn should_attach_logged_state_changes_to_block_receipts() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	let transfer_log = |from: H160, to: H160| crate::Log {
+		address: H160::from_low_u64_be(0x1000),
+		topics: vec![ERC20_TRANSFER_TOPIC, H256::from(from), H256::from(to)],
+		data: H256::from_low_u64_be(10).as_bytes().to_vec(),
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		// logs deposited out of the extrinsics are ignored
+		<EVM as EVMLogs>::deposit_logs(alice(), vec![transfer_log(alice(), bob())]);
+		// empty logs are not deposited
+		<EVM as EVMLogs>::deposit_logs(alice(), vec![]);
+		assert_eq!(EVM::block_receipts(), vec![]);
+
+		System::note_finished_initialize();
+		// a transfer of an extrinsic without EVM executions has its own receipt
+		<EVM as EVMLogs>::deposit_logs(alice(), vec![transfer_log(alice(), bob())]);
+		<EVM as EVMLogs>::deposit_logs(alice(), vec![transfer_log(alice(), charlie())]);
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+
+		// a transfer of an extrinsic with EVM executions is attached to their receipt
+		<EVM as EVMLogs>::deposit_logs(bob(), vec![transfer_log(bob(), alice())]);
+		assert_ok!(EVM::create(
+			Origin::signed(alice_account_id),
+			contract,
+			0,
+			1_000_000,
+			1_000,
+			vec![]
+		));
+		let (contract_address, used_gas) = match System::events().last().map(|record| record.event.clone()) {
+			Some(Event::EVM(crate::Event::Created { contract, used_gas, .. })) => (contract, used_gas),
+			event => panic!("unexpected event: {:?}", event),
+		};
+
+		assert_eq!(
+			EVM::block_receipts(),
+			vec![
+				EvmReceipt {
+					extrinsic_index: 0,
+					from: alice(),
+					to: None,
+					contract_address: None,
+					succeed: true,
+					used_gas: 0,
+					logs: vec![transfer_log(alice(), bob()), transfer_log(alice(), charlie())],
+				},
+				EvmReceipt {
+					extrinsic_index: 1,
+					from: alice(),
+					to: None,
+					contract_address: Some(contract_address),
+					succeed: true,
+					used_gas,
+					logs: vec![transfer_log(bob(), alice())],
+				},
+			]
+		);
+	});
+}
@@ -59,6 +59,11 @@ pub trait WeightInfo {
 	fn disable_contract_development() -> Weight;
 	fn set_code(c: u32, ) -> Weight;
 	fn selfdestruct() -> Weight;
+	fn archive_contract() -> Weight;
+	fn revive_contract(n: u32, ) -> Weight;
+	fn propose_upgrade(c: u32, ) -> Weight;
+	fn cancel_upgrade() -> Weight;
+	fn submit_verification() -> Weight;
 }
 
 /// Weights for module_evm using the Acala node and recommended hardware.
@@ -201,6 +206,21 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	fn archive_contract() -> Weight {
+		crate::estimated_weights::archive_contract::<T::DbWeight>()
+	}
+	fn revive_contract(n: u32, ) -> Weight {
+		crate::estimated_weights::revive_contract::<T::DbWeight>(n)
+	}
+	fn propose_upgrade(c: u32, ) -> Weight {
+		crate::estimated_weights::propose_upgrade::<T::DbWeight>(c)
+	}
+	fn cancel_upgrade() -> Weight {
+		crate::estimated_weights::cancel_upgrade::<T::DbWeight>()
+	}
+	fn submit_verification() -> Weight {
+		crate::estimated_weights::submit_verification::<T::DbWeight>()
+	}
 }
 
 // For backwards compatibility and tests
@@ -267,4 +287,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
+	fn archive_contract() -> Weight {
+		crate::estimated_weights::archive_contract::<RocksDbWeight>()
+	}
+	fn revive_contract(n: u32, ) -> Weight {
+		crate::estimated_weights::revive_contract::<RocksDbWeight>(n)
+	}
+	fn propose_upgrade(c: u32, ) -> Weight {
+		crate::estimated_weights::propose_upgrade::<RocksDbWeight>(c)
+	}
+	fn cancel_upgrade() -> Weight {
+		crate::estimated_weights::cancel_upgrade::<RocksDbWeight>()
+	}
+	fn submit_verification() -> Weight {
+		crate::estimated_weights::submit_verification::<RocksDbWeight>()
+	}
 }
@@ -209,6 +209,7 @@ impl module_currencies::Config for NoFeeRuntime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<Root, AccountId>;
 	type OnDust = ();
+	type EVMLogs = ();
 }
 
 parameter_types! {
@@ -23,16 +23,24 @@
 //! This will require validators to lock some Liquid Token into insurance fund
 //! and if slash happened, HomaCouncil can burn those Liquid Token to compensate
 //! Liquid Token holders.
+//!
+//! Slashes can also be reported by anyone with a relay chain state proof of the
+//! staking `UnappliedSlashes`, without a governance round trip. The reported slashes
+//! are pending until the relay chain applies them after the slash defer duration,
+//! only then anyone can apply them to the insurance with a proof of the relay chain
+//! active era. Reporting again before that replaces the pending slashes of the era,
+//! which drops the slashes cancelled on the relay chain, and the pending slashes can
+//! also be cancelled by SlashOrigin.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::collapsible_if)]
 
 use codec::MaxEncodedLen;
-use frame_support::{pallet_prelude::*, traits::Contains, transactional};
+use frame_support::{pallet_prelude::*, traits::Contains, transactional, StorageHasher};
 use frame_system::pallet_prelude::*;
 use orml_traits::{BasicCurrency, BasicLockableCurrency, Happened, LockIdentifier};
-use primitives::Balance;
+use primitives::{Balance, EraIndex};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -40,8 +48,8 @@ use sp_runtime::{
 	traits::{BlockNumberProvider, Bounded, MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
 	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::{fmt::Debug, vec::Vec};
-use support::{ExchangeRateProvider, Ratio};
+use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, vec::Vec};
+use support::{ExchangeRateProvider, Ratio, RelaychainStateProofReader};
 
 mod mock;
 mod tests;
@@ -58,6 +66,9 @@ pub trait WeightInfo {
 	fn freeze(u: u32) -> Weight;
 	fn thaw() -> Weight;
 	fn slash() -> Weight;
+	fn report_slashes(n: u32) -> Weight;
+	fn apply_reported_slash(n: u32, g: u32) -> Weight;
+	fn cancel_reported_slashes(n: u32) -> Weight;
 }
 
 // TODO: do benchmarking test.
@@ -83,6 +94,15 @@ impl WeightInfo for () {
 	fn slash() -> Weight {
 		10_000
 	}
+	fn report_slashes(_n: u32) -> Weight {
+		10_000
+	}
+	fn apply_reported_slash(_n: u32, _g: u32) -> Weight {
+		10_000
+	}
+	fn cancel_reported_slashes(_n: u32) -> Weight {
+		10_000
+	}
 }
 
 /// Insurance for a validator from a single address
@@ -147,9 +167,52 @@ impl<BlockNumber: PartialOrd> Guarantee<BlockNumber> {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SlashInfo<Balance, RelaychainAccountId> {
 	/// Address of a validator on the relay chain
-	validator: RelaychainAccountId,
+	pub validator: RelaychainAccountId,
 	/// The amount of tokens a validator has in backing on the relay chain
-	relaychain_token_amount: Balance,
+	pub relaychain_token_amount: Balance,
+}
+
+/// The staking `UnappliedSlash` of the relay chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, TypeInfo)]
+pub struct RelaychainUnappliedSlash<RelaychainAccountId> {
+	/// The stash ID of the offending validator.
+	pub validator: RelaychainAccountId,
+	/// The validator's own slash.
+	pub own: Balance,
+	/// All other slashed stakers and amounts.
+	pub others: Vec<(RelaychainAccountId, Balance)>,
+	/// Reporters of the offence; bounty payout recipients.
+	pub reporters: Vec<RelaychainAccountId>,
+	/// The amount of payout.
+	pub payout: Balance,
+}
+
+/// The staking `ActiveEraInfo` of the relay chain.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, TypeInfo)]
+pub struct RelaychainActiveEraInfo {
+	/// Index of era.
+	pub index: EraIndex,
+	/// Moment of start expressed as millisecond from `$UNIX_EPOCH`.
+	pub start: Option<u64>,
+}
+
+/// The storage key of the relay chain `Staking::UnappliedSlashes` for `era`.
+pub fn relaychain_unapplied_slashes_key(era: EraIndex) -> Vec<u8> {
+	let mut key = frame_support::storage::storage_prefix(b"Staking", b"UnappliedSlashes").to_vec();
+	key.extend(era.using_encoded(Twox64Concat::hash));
+	key
+}
+
+/// The storage key of the relay chain `Staking::ActiveEra`.
+pub fn relaychain_active_era_key() -> Vec<u8> {
+	frame_support::storage::storage_prefix(b"Staking", b"ActiveEra").to_vec()
+}
+
+/// The total size in bytes of the trie nodes of a state proof.
+fn proof_size(proof: &[Vec<u8>]) -> u32 {
+	proof
+		.iter()
+		.fold(0u32, |acc, node| acc.saturating_add(node.len() as u32))
 }
 
 /// Validator insurance and frozen status
@@ -175,7 +238,6 @@ pub mod module {
 			+ Debug
 			+ MaybeDisplay
 			+ Ord
-			+ Default
 			+ MaxEncodedLen;
 		/// The liquid representation of the staking token on the relay chain.
 		type LiquidTokenCurrency: BasicLockableCurrency<Self::AccountId, Balance = Balance>;
@@ -204,6 +266,20 @@ pub mod module {
 
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// Reader of the relay chain state proofs, to verify the reported slashes.
+		type RelaychainStateProofReader: RelaychainStateProofReader;
+
+		/// The relay chain nominators whose slashes are compensated by the insurance.
+		type InsuredNominators: Contains<Self::RelaychainAccountId>;
+
+		/// The number of eras the relay chain defers the slashes for before applying them.
+		#[pallet::constant]
+		type RelaychainSlashDeferDuration: Get<EraIndex>;
+
+		/// The maximum size in bytes of the relay chain state proofs.
+		#[pallet::constant]
+		type MaxSlashProofSize: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -211,6 +287,11 @@ pub mod module {
 		BelowMinBondAmount,
 		UnbondingExists,
 		FrozenValidator,
+		InvalidSlashProof,
+		NoSlashToReport,
+		SlashProofTooLarge,
+		SlashNotApplied,
+		TooManyGuarantors,
 	}
 
 	#[pallet::event]
@@ -242,6 +323,18 @@ pub mod module {
 			validator: T::RelaychainAccountId,
 			bond: Balance,
 		},
+		SlashesReported {
+			era: EraIndex,
+			slashes: Vec<SlashInfo<Balance, T::RelaychainAccountId>>,
+		},
+		ReportedSlashApplied {
+			era: EraIndex,
+			slash: SlashInfo<Balance, T::RelaychainAccountId>,
+		},
+		ReportedSlashCancelled {
+			era: EraIndex,
+			validator: T::RelaychainAccountId,
+		},
 	}
 
 	/// The slash guarantee deposits for relaychain validators.
@@ -274,6 +367,24 @@ pub mod module {
 	pub type ValidatorBackings<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::RelaychainAccountId, ValidatorBacking, OptionQuery>;
 
+	/// The insured relay chain slash amounts of validators that have been reported by proofs,
+	/// pending until the relay chain applies the slashes of the era.
+	///
+	/// PendingSlashes: double_map EraIndex, RelaychainAccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_slashes)]
+	pub type PendingSlashes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Blake2_128Concat, T::RelaychainAccountId, Balance, ValueQuery>;
+
+	/// The reported slashes of validators cancelled by SlashOrigin, which cannot be reported
+	/// again.
+	///
+	/// CancelledSlashes: double_map EraIndex, RelaychainAccountId => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn cancelled_slashes)]
+	pub type CancelledSlashes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Blake2_128Concat, T::RelaychainAccountId, (), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -467,48 +578,183 @@ pub mod module {
 		#[transactional]
 		pub fn slash(origin: OriginFor<T>, slashes: Vec<SlashInfo<Balance, T::RelaychainAccountId>>) -> DispatchResult {
 			T::SlashOrigin::ensure_origin(origin)?;
-			let liquid_staking_exchange_rate = T::LiquidStakingExchangeRateProvider::get_exchange_rate();
-			let staking_liquid_exchange_rate = liquid_staking_exchange_rate.reciprocal().unwrap_or_default();
-			let mut actual_total_slashing: Balance = Zero::zero();
+			Self::do_slash(slashes);
+			Ok(())
+		}
 
-			for SlashInfo {
-				validator,
-				relaychain_token_amount,
-			} in slashes
-			{
-				let ValidatorBacking { total_insurance, .. } = Self::validator_backings(&validator).unwrap_or_default();
-				let insurance_loss = staking_liquid_exchange_rate
-					.saturating_mul_int(relaychain_token_amount)
-					.min(total_insurance);
-
-				for (guarantor, _) in Guarantees::<T>::iter_prefix(&validator) {
-					// NOTE: ignoring result because the closure will not throw err.
-					let res = Self::update_guarantee(&guarantor, &validator, |guarantee| -> DispatchResult {
-						let should_slashing = Ratio::checked_from_rational(guarantee.total, total_insurance)
-							.unwrap_or_else(Ratio::max_value)
-							.saturating_mul_int(insurance_loss);
-						let gap = T::LiquidTokenCurrency::slash(&guarantor, should_slashing);
-						let actual_slashing = should_slashing.saturating_sub(gap);
-						*guarantee = guarantee.slash(actual_slashing);
-						Self::deposit_event(Event::SlashGuarantee {
-							who: guarantor.clone(),
-							validator: validator.clone(),
-							bond: actual_slashing,
-						});
-						actual_total_slashing = actual_total_slashing.saturating_add(actual_slashing);
-						Ok(())
-					});
-					debug_assert!(res.is_ok());
+		/// Report the relay chain staking `UnappliedSlashes` of `era`, proven by the relay chain
+		/// state `proof`. The slashes of insured nominators replace the pending slashes of `era`,
+		/// they are applied by `apply_reported_slash` once the relay chain has applied them. The
+		/// slashes cancelled by `cancel_reported_slashes` are skipped.
+		///
+		/// - `era`: The era index of the `UnappliedSlashes` on the relay chain
+		/// - `proof`: The relay chain state proof of the `UnappliedSlashes`
+		#[pallet::weight(T::WeightInfo::report_slashes(proof_size(proof)))]
+		#[transactional]
+		pub fn report_slashes(origin: OriginFor<T>, era: EraIndex, proof: Vec<Vec<u8>>) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(
+				proof_size(&proof) <= T::MaxSlashProofSize::get(),
+				Error::<T>::SlashProofTooLarge
+			);
+
+			let unapplied_slashes: Vec<RelaychainUnappliedSlash<T::RelaychainAccountId>> =
+				T::RelaychainStateProofReader::read_value(&relaychain_unapplied_slashes_key(era), proof)
+					.ok_or(Error::<T>::InvalidSlashProof)?;
+
+			let mut insured_losses: BTreeMap<T::RelaychainAccountId, Balance> = BTreeMap::new();
+			for unapplied_slash in unapplied_slashes {
+				let loss = unapplied_slash
+					.others
+					.iter()
+					.filter(|(nominator, _)| T::InsuredNominators::contains(nominator))
+					.fold(Zero::zero(), |acc: Balance, (_, amount)| acc.saturating_add(*amount));
+				if !loss.is_zero() && !CancelledSlashes::<T>::contains_key(era, &unapplied_slash.validator) {
+					let total_loss = insured_losses.entry(unapplied_slash.validator).or_default();
+					*total_loss = total_loss.saturating_add(loss);
 				}
 			}
 
-			T::OnSlash::happened(&actual_total_slashing);
+			let pending_losses: BTreeMap<T::RelaychainAccountId, Balance> =
+				PendingSlashes::<T>::iter_prefix(era).collect();
+			ensure!(insured_losses != pending_losses, Error::<T>::NoSlashToReport);
+
+			// the slashes cancelled on the relay chain are no longer in the `UnappliedSlashes`.
+			for validator in pending_losses.into_keys() {
+				PendingSlashes::<T>::remove(era, validator);
+			}
+			let mut slashes: Vec<SlashInfo<Balance, T::RelaychainAccountId>> = Vec::new();
+			for (validator, loss) in insured_losses {
+				PendingSlashes::<T>::insert(era, &validator, loss);
+				slashes.push(SlashInfo {
+					validator,
+					relaychain_token_amount: loss,
+				});
+			}
+
+			Self::deposit_event(Event::SlashesReported { era, slashes });
+			Ok(())
+		}
+
+		/// Slash the insurance of `validator` by its pending slash of `era`, once the relay chain
+		/// active era proven by the relay chain state `proof` shows the slashes of `era` have been
+		/// applied.
+		///
+		/// - `era`: The era index of the reported `UnappliedSlashes` on the relay chain
+		/// - `validator`: The AccountId of the slashed validator on the relay chain
+		/// - `guarantors`: The upper bound of the number of the guarantors of `validator`
+		/// - `proof`: The relay chain state proof of the `ActiveEra`
+		#[pallet::weight(T::WeightInfo::apply_reported_slash(proof_size(proof), *guarantors))]
+		#[transactional]
+		pub fn apply_reported_slash(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			validator: T::RelaychainAccountId,
+			#[pallet::compact] guarantors: u32,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(
+				proof_size(&proof) <= T::MaxSlashProofSize::get(),
+				Error::<T>::SlashProofTooLarge
+			);
+			ensure!(
+				Guarantees::<T>::iter_prefix(&validator)
+					.take((guarantors as usize).saturating_add(1))
+					.count() <= guarantors as usize,
+				Error::<T>::TooManyGuarantors
+			);
+
+			let active_era: RelaychainActiveEraInfo =
+				T::RelaychainStateProofReader::read_value(&relaychain_active_era_key(), proof)
+					.ok_or(Error::<T>::InvalidSlashProof)?;
+			// the relay chain applies the `UnappliedSlashes` of `era` when starting the era
+			// `era + SlashDeferDuration + 1`.
+			ensure!(
+				active_era.index
+					>= era
+						.saturating_add(T::RelaychainSlashDeferDuration::get())
+						.saturating_add(1),
+				Error::<T>::SlashNotApplied
+			);
+
+			let loss = PendingSlashes::<T>::take(era, &validator);
+			ensure!(!loss.is_zero(), Error::<T>::NoSlashToReport);
+
+			let slash = SlashInfo {
+				validator,
+				relaychain_token_amount: loss,
+			};
+			Self::do_slash(sp_std::vec![slash.clone()]);
+			Self::deposit_event(Event::ReportedSlashApplied { era, slash });
+			Ok(())
+		}
+
+		/// Cancel the reported slashes of `validators` of `era`, e.g. the slashes cancelled by the
+		/// relay chain governance. Ensures the caller can perform a slash.
+		///
+		/// - `era`: The era index of the reported `UnappliedSlashes` on the relay chain
+		/// - `validators`: The AccountIds of the slashed validators on the relay chain
+		#[pallet::weight(T::WeightInfo::cancel_reported_slashes(validators.len() as u32))]
+		#[transactional]
+		pub fn cancel_reported_slashes(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			validators: Vec<T::RelaychainAccountId>,
+		) -> DispatchResult {
+			T::SlashOrigin::ensure_origin(origin)?;
+			for validator in validators {
+				PendingSlashes::<T>::remove(era, &validator);
+				if !CancelledSlashes::<T>::contains_key(era, &validator) {
+					CancelledSlashes::<T>::insert(era, &validator, ());
+					Self::deposit_event(Event::ReportedSlashCancelled { era, validator });
+				}
+			}
 			Ok(())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	fn do_slash(slashes: Vec<SlashInfo<Balance, T::RelaychainAccountId>>) {
+		let liquid_staking_exchange_rate = T::LiquidStakingExchangeRateProvider::get_exchange_rate();
+		let staking_liquid_exchange_rate = liquid_staking_exchange_rate.reciprocal().unwrap_or_default();
+		let mut actual_total_slashing: Balance = Zero::zero();
+
+		for SlashInfo {
+			validator,
+			relaychain_token_amount,
+		} in slashes
+		{
+			let ValidatorBacking { total_insurance, .. } = Self::validator_backings(&validator).unwrap_or_default();
+			let insurance_loss = staking_liquid_exchange_rate
+				.saturating_mul_int(relaychain_token_amount)
+				.min(total_insurance);
+
+			for (guarantor, _) in Guarantees::<T>::iter_prefix(&validator) {
+				// NOTE: ignoring result because the closure will not throw err.
+				let res = Self::update_guarantee(&guarantor, &validator, |guarantee| -> DispatchResult {
+					let should_slashing = Ratio::checked_from_rational(guarantee.total, total_insurance)
+						.unwrap_or_else(Ratio::max_value)
+						.saturating_mul_int(insurance_loss);
+					let gap = T::LiquidTokenCurrency::slash(&guarantor, should_slashing);
+					let actual_slashing = should_slashing.saturating_sub(gap);
+					*guarantee = guarantee.slash(actual_slashing);
+					Self::deposit_event(Event::SlashGuarantee {
+						who: guarantor.clone(),
+						validator: validator.clone(),
+						bond: actual_slashing,
+					});
+					actual_total_slashing = actual_total_slashing.saturating_add(actual_slashing);
+					Ok(())
+				});
+				debug_assert!(res.is_ok());
+			}
+		}
+
+		T::OnSlash::happened(&actual_total_slashing);
+	}
+
 	fn update_guarantee(
 		guarantor: &T::AccountId,
 		validator: &T::RelaychainAccountId,
@@ -42,6 +42,7 @@ pub const BOB: AccountId = 1;
 pub const VALIDATOR_1: AccountId = 2;
 pub const VALIDATOR_2: AccountId = 3;
 pub const VALIDATOR_3: AccountId = 4;
+pub const HOMA_SUB_ACCOUNT: AccountId = 20;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
 
@@ -190,6 +191,31 @@ impl BlockNumberProvider for MockBlockNumberProvider {
 	}
 }
 
+parameter_types! {
+	pub static MockRelaychainState: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+}
+
+/// Reads the mocked relay chain state, any non-empty proof is treated as valid.
+pub struct MockRelaychainStateProofReader;
+impl RelaychainStateProofReader for MockRelaychainStateProofReader {
+	fn read_value<T: Decode>(key: &[u8], proof: Vec<Vec<u8>>) -> Option<T> {
+		if proof.is_empty() {
+			return None;
+		}
+		MockRelaychainState::get()
+			.into_iter()
+			.find(|(k, _)| k == key)
+			.and_then(|(_, v)| T::decode(&mut &v[..]).ok())
+	}
+}
+
+pub struct MockInsuredNominators;
+impl Contains<AccountId> for MockInsuredNominators {
+	fn contains(a: &AccountId) -> bool {
+		*a == HOMA_SUB_ACCOUNT
+	}
+}
+
 ord_parameter_types! {
 	pub const Admin: AccountId = 10;
 }
@@ -209,6 +235,10 @@ impl Config for Runtime {
 	type OnIncreaseGuarantee = MockOnIncreaseGuarantee;
 	type OnDecreaseGuarantee = MockOnDecreaseGuarantee;
 	type BlockNumberProvider = MockBlockNumberProvider;
+	type RelaychainStateProofReader = MockRelaychainStateProofReader;
+	type InsuredNominators = MockInsuredNominators;
+	type RelaychainSlashDeferDuration = ConstU32<2>;
+	type MaxSlashProofSize = ConstU32<100>;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
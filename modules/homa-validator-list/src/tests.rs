@@ -784,3 +784,254 @@ fn contains_work() {
 		assert!(HomaValidatorListModule::contains(&VALIDATOR_1));
 	});
 }
+
+#[test]
+fn report_slashes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(ALICE), VALIDATOR_1, 100));
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(BOB), VALIDATOR_1, 200));
+
+		let unapplied_slash = |validator: AccountId, others: Vec<(AccountId, Balance)>| RelaychainUnappliedSlash {
+			validator,
+			own: 1000,
+			others,
+			reporters: vec![],
+			payout: 0,
+		};
+		MockRelaychainState::set(vec![(
+			relaychain_unapplied_slashes_key(5),
+			vec![
+				unapplied_slash(VALIDATOR_1, vec![(HOMA_SUB_ACCOUNT, 40), (ALICE, 500)]),
+				unapplied_slash(VALIDATOR_1, vec![(HOMA_SUB_ACCOUNT, 5)]),
+				unapplied_slash(VALIDATOR_2, vec![(ALICE, 100)]),
+			]
+			.encode(),
+		)]);
+
+		assert_noop!(
+			HomaValidatorListModule::report_slashes(Origin::signed(ALICE), 5, vec![]),
+			Error::<Runtime>::InvalidSlashProof
+		);
+		assert_noop!(
+			HomaValidatorListModule::report_slashes(Origin::signed(ALICE), 6, vec![vec![1]]),
+			Error::<Runtime>::InvalidSlashProof
+		);
+		assert_noop!(
+			HomaValidatorListModule::report_slashes(Origin::signed(ALICE), 5, vec![vec![1; 60], vec![1; 41]]),
+			Error::<Runtime>::SlashProofTooLarge
+		);
+
+		assert_ok!(HomaValidatorListModule::report_slashes(
+			Origin::signed(ALICE),
+			5,
+			vec![vec![1]]
+		));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(crate::Event::SlashesReported {
+			era: 5,
+			slashes: vec![SlashInfo {
+				validator: VALIDATOR_1,
+				relaychain_token_amount: 45,
+			}],
+		}));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 45);
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_2), 0);
+
+		// the insurance is not slashed until the relay chain applies the slashes
+		assert_eq!(
+			HomaValidatorListModule::validator_backings(VALIDATOR_1)
+				.unwrap_or_default()
+				.total_insurance,
+			300
+		);
+
+		// the same slashes cannot be reported twice
+		assert_noop!(
+			HomaValidatorListModule::report_slashes(Origin::signed(BOB), 5, vec![vec![1]]),
+			Error::<Runtime>::NoSlashToReport
+		);
+
+		// the slashes cancelled on the relay chain are dropped
+		MockRelaychainState::set(vec![(
+			relaychain_unapplied_slashes_key(5),
+			Vec::<RelaychainUnappliedSlash<AccountId>>::new().encode(),
+		)]);
+		assert_ok!(HomaValidatorListModule::report_slashes(
+			Origin::signed(BOB),
+			5,
+			vec![vec![1]]
+		));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(crate::Event::SlashesReported {
+			era: 5,
+			slashes: vec![],
+		}));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 0);
+
+		MockRelaychainState::set(vec![(
+			relaychain_unapplied_slashes_key(5),
+			vec![unapplied_slash(VALIDATOR_1, vec![(HOMA_SUB_ACCOUNT, 40)])].encode(),
+		)]);
+		assert_ok!(HomaValidatorListModule::report_slashes(
+			Origin::signed(BOB),
+			5,
+			vec![vec![1]]
+		));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 40);
+	});
+}
+
+#[test]
+fn apply_reported_slash_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(ALICE), VALIDATOR_1, 100));
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(BOB), VALIDATOR_1, 200));
+		PendingSlashes::<Runtime>::insert(5, VALIDATOR_1, 40);
+
+		let set_active_era = |index: EraIndex| {
+			MockRelaychainState::set(vec![(
+				relaychain_active_era_key(),
+				RelaychainActiveEraInfo { index, start: None }.encode(),
+			)])
+		};
+
+		set_active_era(7);
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_1, 2, vec![]),
+			Error::<Runtime>::InvalidSlashProof
+		);
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_1, 2, vec![vec![1; 101]]),
+			Error::<Runtime>::SlashProofTooLarge
+		);
+		// the relay chain applies the slashes of era 5 at era 8
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_1, 2, vec![vec![1]]),
+			Error::<Runtime>::SlashNotApplied
+		);
+
+		set_active_era(8);
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_1, 1, vec![vec![1]]),
+			Error::<Runtime>::TooManyGuarantors
+		);
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_2, 0, vec![vec![1]]),
+			Error::<Runtime>::NoSlashToReport
+		);
+		assert_ok!(HomaValidatorListModule::apply_reported_slash(
+			Origin::signed(ALICE),
+			5,
+			VALIDATOR_1,
+			2,
+			vec![vec![1]]
+		));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(
+			crate::Event::ReportedSlashApplied {
+				era: 5,
+				slash: SlashInfo {
+					validator: VALIDATOR_1,
+					relaychain_token_amount: 40,
+				},
+			},
+		));
+		System::assert_has_event(mock::Event::HomaValidatorListModule(crate::Event::SlashGuarantee {
+			who: ALICE,
+			validator: VALIDATOR_1,
+			bond: 26,
+		}));
+		System::assert_has_event(mock::Event::HomaValidatorListModule(crate::Event::SlashGuarantee {
+			who: BOB,
+			validator: VALIDATOR_1,
+			bond: 53,
+		}));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 0);
+		assert_eq!(
+			HomaValidatorListModule::validator_backings(VALIDATOR_1)
+				.unwrap_or_default()
+				.total_insurance,
+			221
+		);
+
+		// the slash is applied only once
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(BOB), 5, VALIDATOR_1, 2, vec![vec![1]]),
+			Error::<Runtime>::NoSlashToReport
+		);
+	});
+}
+
+#[test]
+fn cancel_reported_slashes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(ALICE), VALIDATOR_1, 100));
+		let unapplied_slashes = vec![
+			RelaychainUnappliedSlash {
+				validator: VALIDATOR_1,
+				own: 1000,
+				others: vec![(HOMA_SUB_ACCOUNT, 40)],
+				reporters: vec![],
+				payout: 0,
+			},
+			RelaychainUnappliedSlash {
+				validator: VALIDATOR_2,
+				own: 1000,
+				others: vec![(HOMA_SUB_ACCOUNT, 20)],
+				reporters: vec![],
+				payout: 0,
+			},
+		];
+		MockRelaychainState::set(vec![
+			(relaychain_unapplied_slashes_key(5), unapplied_slashes.encode()),
+			(
+				relaychain_active_era_key(),
+				RelaychainActiveEraInfo { index: 8, start: None }.encode(),
+			),
+		]);
+		assert_ok!(HomaValidatorListModule::report_slashes(
+			Origin::signed(ALICE),
+			5,
+			vec![vec![1]]
+		));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 40);
+
+		assert_noop!(
+			HomaValidatorListModule::cancel_reported_slashes(Origin::signed(ALICE), 5, vec![VALIDATOR_1]),
+			BadOrigin
+		);
+		assert_ok!(HomaValidatorListModule::cancel_reported_slashes(
+			Origin::signed(10),
+			5,
+			vec![VALIDATOR_1]
+		));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(
+			crate::Event::ReportedSlashCancelled {
+				era: 5,
+				validator: VALIDATOR_1,
+			},
+		));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_1), 0);
+		assert_eq!(HomaValidatorListModule::cancelled_slashes(5, VALIDATOR_1), Some(()));
+		assert_eq!(HomaValidatorListModule::pending_slashes(5, VALIDATOR_2), 20);
+
+		// the cancelled slash cannot be reported or applied
+		assert_noop!(
+			HomaValidatorListModule::report_slashes(Origin::signed(ALICE), 5, vec![vec![1]]),
+			Error::<Runtime>::NoSlashToReport
+		);
+		assert_noop!(
+			HomaValidatorListModule::apply_reported_slash(Origin::signed(ALICE), 5, VALIDATOR_1, 1, vec![vec![1]]),
+			Error::<Runtime>::NoSlashToReport
+		);
+		assert_eq!(
+			HomaValidatorListModule::validator_backings(VALIDATOR_1)
+				.unwrap_or_default()
+				.total_insurance,
+			100
+		);
+	});
+}
@@ -0,0 +1,72 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_homa
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights of the steps of the migration
+//! from homa-lite are estimated from their storage accesses and the benchmarked weights of
+//! `request_redeem` and `on_initialize_with_bump_era`. Replace them by running the `module_homa`
+//! benchmarks, which generate them into the weights of the module and the runtimes.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: HomaLite RedeemRequests (r:2 w:1)
+// Storage: Tokens Accounts (r:2 w:2)
+// Storage: Homa RedeemRequests (r:1 w:1)
+// Storage: System Account (r:1 w:1)
+pub fn migrate_homa_lite_redeem_request<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(50_317_000 as Weight)
+		.saturating_add(DbWeight::get().reads(6 as Weight))
+		.saturating_add(DbWeight::get().writes(5 as Weight))
+}
+// Storage: HomaLite ScheduledUnbond (r:1 w:1)
+// Storage: ParachainSystem ValidationData (r:1 w:0)
+// Storage: XcmInterface XcmDestWeightAndFee (r:1 w:0)
+// Storage: ParachainInfo ParachainId (r:1 w:0)
+// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+// Storage: HomaLite AvailableStakingBalance (r:1 w:1)
+pub fn withdraw_homa_lite_scheduled_unbonds<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(41_946_000 as Weight)
+		.saturating_add(DbWeight::get().reads(10 as Weight))
+		.saturating_add(DbWeight::get().writes(4 as Weight))
+}
+// Storage: HomaLite TotalStakingCurrency (r:1 w:1)
+// Storage: HomaLite AvailableStakingBalance (r:1 w:1)
+// Storage: Tokens Accounts (r:1 w:1)
+// Storage: Tokens TotalIssuance (r:1 w:1)
+// Storage: System Account (r:1 w:1)
+// Storage: Homa ToBondPool (r:1 w:1)
+// Storage: Homa StakingLedgers (r:1 w:1)
+// Storage: Homa TotalStakingBonded (r:1 w:1)
+// Storage: HomaLite StakingCurrencyMintCap (r:0 w:1)
+// Storage: HomaLite XcmDestWeight (r:0 w:1)
+// Storage: HomaLite StakingInterestRatePerUpdate (r:0 w:1)
+// Storage: HomaLite LastRedeemRequestKeyIterated (r:0 w:1)
+pub fn migrate_homa_lite_staking_totals<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(53_572_000 as Weight)
+		.saturating_add(DbWeight::get().reads(8 as Weight))
+		.saturating_add(DbWeight::get().writes(12 as Weight))
+}
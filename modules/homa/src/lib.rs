@@ -24,7 +24,7 @@
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::{ensure_signed, pallet_prelude::*};
 use module_support::{ExchangeRate, ExchangeRateProvider, HomaManager, HomaSubAccountXcm, Rate, Ratio};
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use primitives::{Balance, CurrencyId, EraIndex};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -39,6 +39,7 @@ use sp_std::{cmp::Ordering, convert::From, prelude::*, vec, vec::Vec};
 pub use module::*;
 pub use weights::WeightInfo;
 
+pub mod estimated_weights;
 pub mod migrations;
 mod mock;
 mod tests;
@@ -69,6 +70,26 @@ pub mod module {
 		pub era: EraIndex,
 	}
 
+	/// The stage of the multi-block migration from homa-lite.
+	#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub enum HomaLiteMigrationStage {
+		/// Moving the redeem requests of homa-lite into `RedeemRequests`.
+		RedeemRequests,
+		/// Withdrawing the scheduled unbonds of homa-lite once they are expired on relaychain.
+		ScheduledUnbonds,
+		/// Moving the staking totals of homa-lite into `StakingLedgers` and `ToBondPool`.
+		StakingTotals,
+	}
+
+	/// The status of the multi-block migration from homa-lite.
+	#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct HomaLiteMigrationStatus {
+		/// The subaccount index used by homa-lite on relaychain.
+		pub sub_account_index: u16,
+		/// The current stage of the migration.
+		pub stage: HomaLiteMigrationStage,
+	}
+
 	impl StakingLedger {
 		/// Remove entries from `unlocking` that are sufficiently old and the sum of expired
 		/// unlocking.
@@ -102,7 +123,7 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// Multi-currency support for asset management
-		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
 		/// Origin represented Governance
 		type GovernanceOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
@@ -238,6 +259,20 @@ pub mod module {
 		LastEraBumpedBlockUpdated { last_era_bumped_block: T::BlockNumber },
 		/// The frequency to bump era has been updated.
 		BumpEraFrequencyUpdated { frequency: T::BlockNumber },
+		/// The redeem request of homa-lite cannot be migrated, the liquid currency has been
+		/// unreserved to the redeemer.
+		HomaLiteRedeemRequestRefunded {
+			redeemer: T::AccountId,
+			liquid_amount: Balance,
+		},
+		/// The expired scheduled unbonds of homa-lite have been withdrawn from relaychain.
+		HomaLiteScheduledUnbondWithdrawn { staking_amount: Balance },
+		/// The migration from homa-lite has been completed.
+		HomaLiteMigrated {
+			sub_account_index: u16,
+			bonded_amount: Balance,
+			to_bond_amount: Balance,
+		},
 	}
 
 	/// The current era of relaychain
@@ -352,12 +387,23 @@ pub mod module {
 	#[pallet::getter(fn bump_era_frequency)]
 	pub type BumpEraFrequency<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// The status of the migration from homa-lite, exists only while the migration is in progress.
+	///
+	/// HomaLiteMigration: value: Option<HomaLiteMigrationStatus>
+	#[pallet::storage]
+	#[pallet::getter(fn homa_lite_migration)]
+	pub type HomaLiteMigration<T: Config> = StorageValue<_, HomaLiteMigrationStatus, OptionQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			migrations::v2::step::<T>(remaining_weight)
+		}
+
 		fn on_initialize(_: T::BlockNumber) -> Weight {
 			let bump_era_number = Self::era_amount_should_to_bump(T::RelayChainBlockNumber::current_block_number());
 			if !bump_era_number.is_zero() {
@@ -20,3 +20,8 @@
 ///
 /// Adds TotalStakingBonded to homa storage
 pub mod v1;
+
+/// Version 2
+///
+/// Migrates the redeem requests, scheduled unbonds and staking totals of homa-lite into homa
+pub mod v2;
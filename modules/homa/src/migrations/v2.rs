@@ -0,0 +1,378 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Migration from the storage of the retired homa-lite pallet.
+//!
+//! The migration is started by `MigrateFromHomaLite` on runtime upgrade, and then executed by
+//! `on_idle` of homa across several blocks:
+//! 1. Move the redeem requests of homa-lite into `RedeemRequests`, the liquid currency reserved by
+//! homa-lite is transferred to the homa account.
+//! 2. Withdraw the scheduled unbonds of homa-lite once they are expired on relaychain, the
+//! withdrawn amount is added to the available staking balance of homa-lite.
+//! 3. Move the bonded amount of homa-lite into the ledger of its subaccount, and the available
+//! staking balance into `ToBondPool`.
+//!
+//! The total staking currency and the total liquid currency across homa-lite and homa are not
+//! changed by any step, so the exchange rate of liquid currency is preserved.
+
+use crate::{self as homa, HomaLiteMigrationStage, HomaLiteMigrationStatus, WeightInfo};
+use frame_support::{
+	log,
+	storage::migration::{get_storage_value, put_storage_value, storage_key_iter, take_storage_value},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+	Twox64Concat,
+};
+use module_support::ExchangeRate;
+use primitives::Balance;
+use sp_runtime::{
+	traits::{BlockNumberProvider, UniqueSaturatedInto, Zero},
+	FixedPointNumber, Permill,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub const HOMA_LITE: &[u8] = b"HomaLite";
+pub const TOTAL_STAKING_CURRENCY: &[u8] = b"TotalStakingCurrency";
+pub const AVAILABLE_STAKING_BALANCE: &[u8] = b"AvailableStakingBalance";
+pub const SCHEDULED_UNBOND: &[u8] = b"ScheduledUnbond";
+pub const REDEEM_REQUESTS: &[u8] = b"RedeemRequests";
+const OBSOLETE_ITEMS: [&[u8]; 4] = [
+	b"StakingCurrencyMintCap",
+	b"XcmDestWeight",
+	b"StakingInterestRatePerUpdate",
+	b"LastRedeemRequestKeyIterated",
+];
+
+/// Starts the migration from homa-lite if there is homa-lite storage left.
+pub struct MigrateFromHomaLite<T, P, SubAccountIndex>(PhantomData<(T, P, SubAccountIndex)>);
+
+impl<T, P, SubAccountIndex> OnRuntimeUpgrade for MigrateFromHomaLite<T, P, SubAccountIndex>
+where
+	T: homa::Config,
+	P: GetStorageVersion + PalletInfoAccess,
+	SubAccountIndex: Get<u16>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		migrate::<T, P>(SubAccountIndex::get())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		pre_migrate::<P>();
+		Self::set_temp_storage(combined_exchange_rate::<T>(), "combined_exchange_rate");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let exchange_rate: ExchangeRate =
+			Self::get_temp_storage("combined_exchange_rate").ok_or("combined_exchange_rate is not stored")?;
+		post_migrate::<T, P>(exchange_rate);
+		Ok(())
+	}
+}
+
+/// Starts the migration if homa-lite storage exists, and puts the storage version to 2.
+pub fn migrate<T: homa::Config, P: GetStorageVersion + PalletInfoAccess>(sub_account_index: u16) -> Weight {
+	let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+	log::info!(
+		target: "runtime::homa",
+		"Running migration to v2 for homa with storage version {:?}",
+		on_chain_storage_version,
+	);
+
+	if on_chain_storage_version < 2 {
+		if homa_lite_storage_exists::<T>() {
+			homa::HomaLiteMigration::<T>::put(HomaLiteMigrationStatus {
+				sub_account_index,
+				stage: HomaLiteMigrationStage::RedeemRequests,
+			});
+		}
+
+		StorageVersion::new(2).put::<P>();
+		T::DbWeight::get().reads_writes(3, 2)
+	} else {
+		log::warn!(
+			target: "runtime::homa",
+			"Attempted to apply migration to v2 but failed because storage version is {:?}",
+			on_chain_storage_version,
+		);
+		0
+	}
+}
+
+/// Executes the migration within `remaining_weight`, returns the weight consumed.
+pub fn step<T: homa::Config>(remaining_weight: Weight) -> Weight {
+	let db_weight = T::DbWeight::get();
+	let mut consumed_weight = db_weight.reads(1);
+	let mut status = match homa::HomaLiteMigration::<T>::get() {
+		Some(status) => status,
+		None => return consumed_weight,
+	};
+
+	loop {
+		match status.stage {
+			HomaLiteMigrationStage::RedeemRequests => {
+				let weight_per_request = <T as homa::Config>::WeightInfo::migrate_homa_lite_redeem_request();
+				let max_requests = remaining_weight
+					.saturating_sub(consumed_weight)
+					.checked_div(weight_per_request)
+					.unwrap_or(Weight::MAX);
+				if max_requests.is_zero() {
+					break;
+				}
+
+				let migrated = migrate_redeem_requests::<T>(max_requests.unique_saturated_into());
+				consumed_weight =
+					consumed_weight.saturating_add(weight_per_request.saturating_mul(Weight::from(migrated)));
+
+				if Weight::from(migrated) < max_requests {
+					status.stage = HomaLiteMigrationStage::ScheduledUnbonds;
+				} else {
+					break;
+				}
+			}
+			HomaLiteMigrationStage::ScheduledUnbonds => {
+				let weight_of_unbonds = <T as homa::Config>::WeightInfo::withdraw_homa_lite_scheduled_unbonds();
+				if remaining_weight.saturating_sub(consumed_weight) < weight_of_unbonds {
+					break;
+				}
+				consumed_weight = consumed_weight.saturating_add(weight_of_unbonds);
+
+				if withdraw_expired_scheduled_unbonds::<T>(status.sub_account_index) {
+					status.stage = HomaLiteMigrationStage::StakingTotals;
+				} else {
+					// wait for the remaining scheduled unbonds to be expired on relaychain.
+					break;
+				}
+			}
+			HomaLiteMigrationStage::StakingTotals => {
+				let weight_of_totals = <T as homa::Config>::WeightInfo::migrate_homa_lite_staking_totals();
+				if remaining_weight.saturating_sub(consumed_weight) < weight_of_totals {
+					break;
+				}
+				consumed_weight = consumed_weight.saturating_add(weight_of_totals);
+
+				match migrate_staking_totals::<T>(status.sub_account_index) {
+					Ok(_) => {
+						homa::HomaLiteMigration::<T>::kill();
+						return consumed_weight;
+					}
+					Err(e) => {
+						log::error!(
+							target: "runtime::homa",
+							"Failed to migrate the staking totals of homa-lite: {:?}",
+							e,
+						);
+						break;
+					}
+				}
+			}
+		}
+	}
+
+	homa::HomaLiteMigration::<T>::put(status);
+	consumed_weight.saturating_add(db_weight.writes(1))
+}
+
+/// Moves at most `max_requests` redeem requests of homa-lite to homa, returns the number of the
+/// requests moved.
+pub fn migrate_redeem_requests<T: homa::Config>(max_requests: u32) -> u32 {
+	let mut migrated: u32 = 0;
+	for (redeemer, (liquid_amount, extra_fee)) in
+		storage_key_iter::<T::AccountId, (Balance, Permill), Twox64Concat>(HOMA_LITE, REDEEM_REQUESTS)
+			.drain()
+			.take(max_requests as usize)
+	{
+		migrate_redeem_request::<T>(redeemer, liquid_amount, extra_fee);
+		migrated = migrated.saturating_add(1);
+	}
+	migrated
+}
+
+/// Moves a redeem request of homa-lite to homa. The liquid amount reserved by homa-lite is
+/// unreserved, if it cannot be requested to redeem on homa, it's kept by the redeemer.
+fn migrate_redeem_request<T: homa::Config>(redeemer: T::AccountId, liquid_amount: Balance, extra_fee: Permill) {
+	let liquid_currency_id = T::LiquidCurrencyId::get();
+	let remaining = T::Currency::unreserve(liquid_currency_id, &redeemer, liquid_amount);
+	let unreserved = liquid_amount.saturating_sub(remaining);
+	if unreserved.is_zero() {
+		return;
+	}
+
+	// homa-lite redeemers paid the extra fee to be matched by minters, which is what fast match is
+	// for on homa.
+	let (previous_amount, previous_allow_fast_match) =
+		homa::Pallet::<T>::redeem_requests(&redeemer).unwrap_or_default();
+	let res = homa::Pallet::<T>::do_request_redeem(
+		redeemer.clone(),
+		previous_amount.saturating_add(unreserved),
+		previous_allow_fast_match || !extra_fee.is_zero(),
+	);
+
+	if let Err(e) = res {
+		log::warn!(
+			target: "runtime::homa",
+			"Refund the homa-lite redeem request of {:?} with {:?}: {:?}",
+			redeemer, unreserved, e,
+		);
+		homa::Pallet::<T>::deposit_event(homa::Event::<T>::HomaLiteRedeemRequestRefunded {
+			redeemer,
+			liquid_amount: unreserved,
+		});
+	}
+}
+
+/// Withdraws the scheduled unbonds of homa-lite which are expired on relaychain. Returns true if
+/// there is no scheduled unbond left.
+pub fn withdraw_expired_scheduled_unbonds<T: homa::Config>(sub_account_index: u16) -> bool {
+	let scheduled_unbond: Vec<(Balance, T::BlockNumber)> =
+		get_storage_value(HOMA_LITE, SCHEDULED_UNBOND, &[]).unwrap_or_default();
+	let current_relay_chain_block = T::RelayChainBlockNumber::current_block_number();
+	let (expired, remaining): (Vec<_>, Vec<_>) = scheduled_unbond
+		.into_iter()
+		.partition(|(_, unbond_at)| *unbond_at <= current_relay_chain_block);
+	let expired_amount = expired.iter().fold(Zero::zero(), |total: Balance, (amount, _)| {
+		total.saturating_add(*amount)
+	});
+
+	if !expired_amount.is_zero() {
+		if let Err(e) = T::XcmInterface::withdraw_unbonded_from_sub_account(sub_account_index, expired_amount) {
+			log::error!(
+				target: "runtime::homa",
+				"Failed to withdraw the scheduled unbonds of homa-lite: {:?}",
+				e,
+			);
+			return false;
+		}
+
+		let available_staking_balance: Balance =
+			get_storage_value(HOMA_LITE, AVAILABLE_STAKING_BALANCE, &[]).unwrap_or_default();
+		put_storage_value(
+			HOMA_LITE,
+			AVAILABLE_STAKING_BALANCE,
+			&[],
+			available_staking_balance.saturating_add(expired_amount),
+		);
+		homa::Pallet::<T>::deposit_event(homa::Event::<T>::HomaLiteScheduledUnbondWithdrawn {
+			staking_amount: expired_amount,
+		});
+	}
+
+	if remaining.is_empty() {
+		let _: Option<Vec<(Balance, T::BlockNumber)>> = take_storage_value(HOMA_LITE, SCHEDULED_UNBOND, &[]);
+		true
+	} else {
+		put_storage_value(HOMA_LITE, SCHEDULED_UNBOND, &[], remaining);
+		false
+	}
+}
+
+/// Moves the bonded amount of homa-lite to the ledger of `sub_account_index`, and the available
+/// staking balance of homa-lite to `ToBondPool`. Then removes the rest of homa-lite storage.
+pub fn migrate_staking_totals<T: homa::Config>(sub_account_index: u16) -> sp_runtime::DispatchResult {
+	let total_staking_currency: Balance = get_storage_value(HOMA_LITE, TOTAL_STAKING_CURRENCY, &[]).unwrap_or_default();
+	let available_staking_balance: Balance =
+		get_storage_value(HOMA_LITE, AVAILABLE_STAKING_BALANCE, &[]).unwrap_or_default();
+	let to_bond_amount = available_staking_balance.min(total_staking_currency);
+	let bonded_amount = total_staking_currency.saturating_sub(to_bond_amount);
+
+	// the available staking balance of homa-lite has been withdrawn to the parachain account on
+	// relaychain, issue it to homa account as ToBondPool.
+	T::Currency::deposit(
+		T::StakingCurrencyId::get(),
+		&homa::Pallet::<T>::account_id(),
+		to_bond_amount,
+	)?;
+	homa::ToBondPool::<T>::mutate(|pool| *pool = pool.saturating_add(to_bond_amount));
+	homa::Pallet::<T>::do_update_ledger(sub_account_index, |ledger| -> sp_runtime::DispatchResult {
+		ledger.bonded = ledger.bonded.saturating_add(bonded_amount);
+		Ok(())
+	})?;
+
+	let _: Option<Balance> = take_storage_value(HOMA_LITE, TOTAL_STAKING_CURRENCY, &[]);
+	let _: Option<Balance> = take_storage_value(HOMA_LITE, AVAILABLE_STAKING_BALANCE, &[]);
+	for item in OBSOLETE_ITEMS {
+		frame_support::storage::unhashed::kill(&frame_support::storage::storage_prefix(HOMA_LITE, item));
+	}
+
+	homa::Pallet::<T>::deposit_event(homa::Event::<T>::HomaLiteMigrated {
+		sub_account_index,
+		bonded_amount,
+		to_bond_amount,
+	});
+	Ok(())
+}
+
+fn homa_lite_storage_exists<T: homa::Config>() -> bool {
+	get_storage_value::<Balance>(HOMA_LITE, TOTAL_STAKING_CURRENCY, &[]).is_some()
+		|| get_storage_value::<Balance>(HOMA_LITE, AVAILABLE_STAKING_BALANCE, &[]).is_some()
+		|| storage_key_iter::<T::AccountId, (Balance, Permill), Twox64Concat>(HOMA_LITE, REDEEM_REQUESTS)
+			.next()
+			.is_some()
+}
+
+/// The exchange rate of liquid currency with the staking currency of homa and the homa-lite
+/// storage that has not been migrated yet.
+pub fn combined_exchange_rate<T: homa::Config>() -> ExchangeRate {
+	let homa_lite_total_staking: Balance =
+		get_storage_value(HOMA_LITE, TOTAL_STAKING_CURRENCY, &[]).unwrap_or_default();
+	let total_staking = homa::Pallet::<T>::get_total_staking_currency().saturating_add(homa_lite_total_staking);
+	let total_liquid = homa::Pallet::<T>::get_total_liquid_currency();
+	if total_staking.is_zero() {
+		T::DefaultExchangeRate::get()
+	} else {
+		ExchangeRate::checked_from_rational(total_staking, total_liquid).unwrap_or_else(T::DefaultExchangeRate::get)
+	}
+}
+
+/// Ensures version is correct
+///
+/// Panics if anything goes wrong
+pub fn pre_migrate<P: GetStorageVersion>() {
+	assert!(P::on_chain_storage_version() < 2);
+}
+
+/// Executes the whole migration and checks the exchange rate is preserved after each step.
+///
+/// Panics if anything goes wrong
+pub fn post_migrate<T: homa::Config, P: GetStorageVersion>(exchange_rate: ExchangeRate) {
+	assert_eq!(P::on_chain_storage_version(), 2);
+	assert_eq!(combined_exchange_rate::<T>(), exchange_rate);
+
+	while let Some(before) = homa::HomaLiteMigration::<T>::get() {
+		step::<T>(Weight::MAX);
+		assert_eq!(combined_exchange_rate::<T>(), exchange_rate);
+
+		if homa::HomaLiteMigration::<T>::get() == Some(before) {
+			log::warn!(
+				target: "runtime::homa",
+				"Migration from homa-lite is waiting at {:?}",
+				before,
+			);
+			return;
+		}
+	}
+
+	assert!(!homa_lite_storage_exists::<T>());
+	assert_eq!(homa::Pallet::<T>::current_exchange_rate(), exchange_rate);
+}
@@ -157,6 +157,7 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureRoot<AccountId>;
 	type OnDust = ();
+	type EVMLogs = ();
 }
 
 impl BlockNumberProvider for MockRelayBlockNumberProvider {
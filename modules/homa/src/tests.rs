@@ -1359,3 +1359,114 @@ fn bump_current_era_works() {
 			);
 		});
 }
+
+#[test]
+fn migrate_from_homa_lite_works() {
+	use frame_support::{storage::migration::put_storage_value, traits::Hooks, StorageHasher, Twox64Concat};
+	use orml_traits::MultiReservableCurrency;
+	use sp_runtime::Permill;
+
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, LIQUID_CURRENCY_ID, 1_000_000),
+			(BOB, LIQUID_CURRENCY_ID, 100),
+		])
+		.build()
+		.execute_with(|| {
+			RedeemThreshold::set(1_000);
+			MockRelayBlockNumberProvider::set(10);
+
+			// mock the storage left by homa-lite
+			assert_ok!(Currencies::reserve(LIQUID_CURRENCY_ID, &ALICE, 500_000));
+			assert_ok!(Currencies::reserve(LIQUID_CURRENCY_ID, &BOB, 100));
+			put_storage_value(
+				b"HomaLite",
+				b"RedeemRequests",
+				&Twox64Concat::hash(&ALICE.encode()),
+				(500_000u128, Permill::from_percent(1)),
+			);
+			put_storage_value(
+				b"HomaLite",
+				b"RedeemRequests",
+				&Twox64Concat::hash(&BOB.encode()),
+				(100u128, Permill::zero()),
+			);
+			put_storage_value(b"HomaLite", b"TotalStakingCurrency", &[], 200_000u128);
+			put_storage_value(b"HomaLite", b"AvailableStakingBalance", &[], 10_000u128);
+			put_storage_value(
+				b"HomaLite",
+				b"ScheduledUnbond",
+				&[],
+				vec![(20_000u128, 5u64), (30_000u128, 100u64)],
+			);
+			let exchange_rate = ExchangeRate::saturating_from_rational(200_000, 1_000_100);
+			assert_eq!(migrations::v2::combined_exchange_rate::<Runtime>(), exchange_rate);
+
+			migrations::v2::migrate::<Runtime, Homa>(0);
+			assert_eq!(
+				Homa::homa_lite_migration(),
+				Some(HomaLiteMigrationStatus {
+					sub_account_index: 0,
+					stage: HomaLiteMigrationStage::RedeemRequests,
+				})
+			);
+
+			// migrate redeem requests and withdraw the expired scheduled unbond.
+			Homa::on_idle(1, Weight::MAX);
+			assert_eq!(Homa::redeem_requests(&ALICE), Some((500_000, true)));
+			assert_eq!(Homa::redeem_requests(&BOB), None);
+			System::assert_has_event(Event::Homa(crate::Event::HomaLiteRedeemRequestRefunded {
+				redeemer: BOB,
+				liquid_amount: 100,
+			}));
+			System::assert_has_event(Event::Homa(crate::Event::HomaLiteScheduledUnbondWithdrawn {
+				staking_amount: 20_000,
+			}));
+			assert_eq!(Currencies::reserved_balance(LIQUID_CURRENCY_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(LIQUID_CURRENCY_ID, &ALICE), 500_000);
+			assert_eq!(Currencies::free_balance(LIQUID_CURRENCY_ID, &BOB), 100);
+			assert_eq!(
+				Currencies::free_balance(LIQUID_CURRENCY_ID, &Homa::account_id()),
+				500_000
+			);
+			assert_eq!(
+				Homa::homa_lite_migration(),
+				Some(HomaLiteMigrationStatus {
+					sub_account_index: 0,
+					stage: HomaLiteMigrationStage::ScheduledUnbonds,
+				})
+			);
+			assert_eq!(migrations::v2::combined_exchange_rate::<Runtime>(), exchange_rate);
+
+			// wait for the scheduled unbond to be expired.
+			Homa::on_idle(2, Weight::MAX);
+			assert_eq!(
+				Homa::homa_lite_migration().map(|status| status.stage),
+				Some(HomaLiteMigrationStage::ScheduledUnbonds)
+			);
+
+			MockRelayBlockNumberProvider::set(100);
+			Homa::on_idle(3, Weight::MAX);
+			System::assert_has_event(Event::Homa(crate::Event::HomaLiteMigrated {
+				sub_account_index: 0,
+				bonded_amount: 140_000,
+				to_bond_amount: 60_000,
+			}));
+			assert_eq!(Homa::homa_lite_migration(), None);
+			assert_eq!(
+				Homa::staking_ledgers(0),
+				Some(StakingLedger {
+					bonded: 140_000,
+					unlocking: vec![]
+				})
+			);
+			assert_eq!(Homa::to_bond_pool(), 60_000);
+			assert_eq!(
+				Currencies::free_balance(STAKING_CURRENCY_ID, &Homa::account_id()),
+				60_000
+			);
+			assert_eq!(Homa::get_total_staking_currency(), 200_000);
+			assert_eq!(Homa::current_exchange_rate(), exchange_rate);
+			assert_eq!(migrations::v2::combined_exchange_rate::<Runtime>(), exchange_rate);
+		});
+}
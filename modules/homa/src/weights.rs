@@ -57,6 +57,9 @@ pub trait WeightInfo {
 	fn update_bump_era_params() -> Weight;
 	fn reset_ledgers(n: u32, ) -> Weight;
 	fn reset_current_era() -> Weight;
+	fn migrate_homa_lite_redeem_request() -> Weight;
+	fn withdraw_homa_lite_scheduled_unbonds() -> Weight;
+	fn migrate_homa_lite_staking_totals() -> Weight;
 }
 
 /// Weights for module_homa using the Acala node and recommended hardware.
@@ -187,6 +190,15 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	fn migrate_homa_lite_redeem_request() -> Weight {
+		crate::estimated_weights::migrate_homa_lite_redeem_request::<T::DbWeight>()
+	}
+	fn withdraw_homa_lite_scheduled_unbonds() -> Weight {
+		crate::estimated_weights::withdraw_homa_lite_scheduled_unbonds::<T::DbWeight>()
+	}
+	fn migrate_homa_lite_staking_totals() -> Weight {
+		crate::estimated_weights::migrate_homa_lite_staking_totals::<T::DbWeight>()
+	}
 }
 
 // For backwards compatibility and tests
@@ -248,4 +260,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	fn migrate_homa_lite_redeem_request() -> Weight {
+		crate::estimated_weights::migrate_homa_lite_redeem_request::<RocksDbWeight>()
+	}
+	fn withdraw_homa_lite_scheduled_unbonds() -> Weight {
+		crate::estimated_weights::withdraw_homa_lite_scheduled_unbonds::<RocksDbWeight>()
+	}
+	fn migrate_homa_lite_staking_totals() -> Weight {
+		crate::estimated_weights::migrate_homa_lite_staking_totals::<RocksDbWeight>()
+	}
 }
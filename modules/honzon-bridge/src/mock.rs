@@ -136,10 +136,12 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureRoot<AccountId>;
 	type OnDust = ();
+	type EVMLogs = ();
 }
 
 parameter_types! {
 	pub NetworkContractSource: EvmAddress = EvmAddress::default();
+	pub PredeployAddressRanges: Vec<(EvmAddress, EvmAddress)> = vec![(EvmAddress::zero(), EvmAddress::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -153,7 +155,10 @@ impl module_evm::Config for Runtime {
 	type TransferAll = ();
 	type NewContractExtraBytes = ConstU32<1>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = ConstU128<10>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 	type Event = Event;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
@@ -161,6 +166,7 @@ impl module_evm::Config for Runtime {
 	type ChargeTransactionPayment = module_support::mocks::MockReservedTransactionPayment<Balances>;
 	type NetworkContractOrigin = EnsureRoot<AccountId>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
@@ -184,6 +190,9 @@ impl module_evm_accounts::Config for Runtime {
 	type ChainId = ();
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
+	type RotationDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -145,7 +145,9 @@ pub mod module {
 		/// - `debit_adjustment`: signed amount, positive means to issue some amount of stablecoin
 		///   to caller according to the debit adjustment, negative means caller will payback some
 		///   amount of stablecoin to CDP according to to the debit adjustment.
-		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan())]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan()
+			.saturating_add(<cdp_engine::Pallet<T>>::erc20_transfer_weight(*currency_id, 1))
+		)]
 		#[transactional]
 		pub fn adjust_loan(
 			origin: OriginFor<T>,
@@ -163,7 +165,9 @@ pub mod module {
 		/// - `currency_id`: collateral currency id.
 		/// - `max_collateral_amount`: the max collateral amount which is used to swap enough
 		/// 	stable token to clear debit.
-		#[pallet::weight(<T as Config>::WeightInfo::close_loan_has_debit_by_dex())]
+		#[pallet::weight(<T as Config>::WeightInfo::close_loan_has_debit_by_dex()
+			.saturating_add(<cdp_engine::Pallet<T>>::erc20_transfer_weight(*currency_id, 3))
+		)]
 		#[transactional]
 		pub fn close_loan_has_debit_by_dex(
 			origin: OriginFor<T>,
@@ -268,7 +272,9 @@ pub mod module {
 		/// - `currency_id`: collateral currency id.
 		/// - `increase_debit_value`: the specific increased debit value for CDP
 		/// - `min_increase_collateral`: the minimal increased collateral amount for CDP
-		#[pallet::weight(<T as Config>::WeightInfo::expand_position_collateral())]
+		#[pallet::weight(<T as Config>::WeightInfo::expand_position_collateral()
+			.saturating_add(<cdp_engine::Pallet<T>>::erc20_transfer_weight(*currency_id, 1))
+		)]
 		#[transactional]
 		pub fn expand_position_collateral(
 			origin: OriginFor<T>,
@@ -291,7 +297,9 @@ pub mod module {
 		/// - `currency_id`: collateral currency id.
 		/// - `decrease_collateral`: the specific decreased collateral amount for CDP
 		/// - `min_decrease_debit_value`: the minimal decreased debit value for CDP
-		#[pallet::weight(<T as Config>::WeightInfo::shrink_position_debit())]
+		#[pallet::weight(<T as Config>::WeightInfo::shrink_position_debit()
+			.saturating_add(<cdp_engine::Pallet<T>>::erc20_transfer_weight(*currency_id, 1))
+		)]
 		#[transactional]
 		pub fn shrink_position_debit(
 			origin: OriginFor<T>,
@@ -317,7 +325,9 @@ pub mod module {
 		///   into CDP, negative means withdraw collateral currency from CDP.
 		/// - `debit_value_adjustment`: signed amount, positive means to issue some amount of
 		///   stablecoin, negative means caller will payback some amount of stablecoin to CDP.
-		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan())]
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan()
+			.saturating_add(<cdp_engine::Pallet<T>>::erc20_transfer_weight(*currency_id, 1))
+		)]
 		#[transactional]
 		pub fn adjust_loan_by_debit_value(
 			origin: OriginFor<T>,
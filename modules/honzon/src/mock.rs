@@ -37,7 +37,7 @@ use sp_runtime::{
 	FixedPointNumber,
 };
 use sp_std::cell::RefCell;
-use support::mocks::MockStableAsset;
+use support::mocks::{MockErc20InfoMapping, MockStableAsset};
 use support::{AuctionManager, ExchangeRate, Price, PriceProvider, Rate, Ratio, SpecificJointsSwap};
 
 mod honzon {
@@ -237,6 +237,9 @@ impl evm_accounts::Config for Runtime {
 	type ChainId = ();
 	type AddressMapping = evm_accounts::EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type EVMBridge = ();
+	type RotationDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -278,8 +281,10 @@ impl cdp_engine::Config for Runtime {
 	type LiquidationEvmBridge = ();
 	type PalletId = CDPEnginePalletId;
 	type EvmAddressMapping = evm_accounts::EvmAddressMapping<Runtime>;
+	type Erc20InfoMapping = MockErc20InfoMapping;
 	type Swap = SpecificJointsSwap<(), AlternativeSwapPathJointList>;
 	type WeightInfo = ();
+	type GasToWeight = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
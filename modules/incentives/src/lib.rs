@@ -622,6 +622,21 @@ impl<T: Config> IncentivesManager<T::AccountId, Balance, CurrencyId, PoolId> for
 		}
 		reward_balances
 	}
+
+	#[transactional]
+	fn accumulate_rewards(
+		from: &T::AccountId,
+		pool_id: PoolId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		T::Currency::transfer(currency_id, from, &Self::account_id(), amount)?;
+		<orml_rewards::Pallet<T>>::accumulate_reward(&pool_id, currency_id, amount)
+	}
 }
 
 pub struct OnUpdateLoan<T>(sp_std::marker::PhantomData<T>);
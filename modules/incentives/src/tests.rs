@@ -1073,3 +1073,40 @@ fn earning_booster_should_work() {
 		);
 	});
 }
+
+#[test]
+fn accumulate_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(ACA, &BOB::get(), 10000));
+
+		// no shares in the pool
+		assert_noop!(
+			IncentivesModule::accumulate_rewards(&BOB::get(), PoolId::Loans(ACA), ACA, 1000),
+			orml_rewards::Error::<Runtime>::PoolDoesNotExist
+		);
+
+		OnEarningBonded::<Runtime>::happened(&(ALICE::get(), 100));
+		assert_ok!(IncentivesModule::accumulate_rewards(
+			&BOB::get(),
+			PoolId::Loans(ACA),
+			ACA,
+			1000
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &BOB::get()), 9000);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT::get()), 1000);
+		assert_eq!(
+			RewardsModule::pool_infos(PoolId::Loans(ACA)),
+			PoolInfo {
+				total_shares: 150,
+				rewards: vec![(ACA, (1000, 0))].into_iter().collect(),
+			}
+		);
+
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE::get()),
+			PoolId::Loans(ACA)
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE::get()), 1000);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT::get()), 0);
+	});
+}
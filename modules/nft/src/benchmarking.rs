@@ -148,6 +148,43 @@ benchmarks! {
 
 		let module_account = create_token_class::<T>(caller)?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), Properties(ClassProperty::Transferable.into()))
+
+	// approve an account to transfer NFT token
+	approve {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let approved: T::AccountId = account("approved", 0, SEED);
+		let approved_lookup = T::Lookup::unlookup(approved);
+
+		let module_account = create_token_class::<T>(caller)?;
+
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], test_attr(), 1)?;
+	}: _(RawOrigin::Signed(to), Some(approved_lookup), (0u32.into(), 0u32.into()))
+
+	// approve an operator to manage all NFT tokens in the class
+	set_approval_for_all {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let operator: T::AccountId = account("operator", 0, SEED);
+		let operator_lookup = T::Lookup::unlookup(operator);
+
+		create_token_class::<T>(caller.clone())?;
+	}: _(RawOrigin::Signed(caller), operator_lookup, 0u32.into(), true)
+
+	// transfer NFT token by the approved account
+	transfer_from {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let approved: T::AccountId = account("approved", 0, SEED);
+		let approved_lookup = T::Lookup::unlookup(approved.clone());
+
+		let module_account = create_token_class::<T>(caller)?;
+
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup.clone(), 0u32.into(), vec![1], test_attr(), 1)?;
+		crate::Pallet::<T>::approve(RawOrigin::Signed(to).into(), Some(approved_lookup), (0u32.into(), 0u32.into()))?;
+	}: _(RawOrigin::Signed(approved), to_lookup, caller_lookup, (0u32.into(), 0u32.into()))
 }
 
 #[cfg(test)]
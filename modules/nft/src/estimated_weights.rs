@@ -0,0 +1,69 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_nft
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights of the approvals are estimated
+//! from their storage accesses and the benchmarked weights of `transfer`, and the `ClassBalances`
+//! accesses are not counted in the benchmarked weights of `mint`, `transfer` and `burn` yet.
+//! Replace them by running the `module_nft` benchmarks, which generate them into the weights of
+//! the module and the runtimes, and then remove `class_balances` from the call weights.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: NFT ClassBalances (r:1 w:1)
+pub fn class_balances<DbWeight: Get<RuntimeDbWeight>>(accounts: u32, ) -> Weight {
+	(0 as Weight)
+		.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(accounts as Weight)))
+		.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(accounts as Weight)))
+}
+// Storage: OrmlNFT Tokens (r:1 w:0)
+// Storage: NFT OperatorApprovals (r:1 w:0)
+// Storage: NFT TokenApprovals (r:0 w:1)
+pub fn approve<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(14_720_000 as Weight)
+		.saturating_add(DbWeight::get().reads(2 as Weight))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+}
+// Storage: OrmlNFT Classes (r:1 w:0)
+// Storage: NFT OperatorApprovals (r:0 w:1)
+pub fn set_approval_for_all<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(12_583_000 as Weight)
+		.saturating_add(DbWeight::get().reads(1 as Weight))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+}
+// Storage: OrmlNFT Tokens (r:1 w:1)
+// Storage: NFT TokenApprovals (r:1 w:1)
+// Storage: NFT OperatorApprovals (r:1 w:0)
+// Storage: OrmlNFT Classes (r:1 w:0)
+// Storage: Balances Reserves (r:2 w:2)
+// Storage: System Account (r:1 w:1)
+// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+// Storage: EVM Accounts (r:1 w:1)
+// Storage: EvmAccounts Accounts (r:0 w:1)
+// Storage: OrmlNFT TokensByOwner (r:0 w:2)
+// Storage: NFT ClassBalances (r:2 w:2)
+pub fn transfer_from<DbWeight: Get<RuntimeDbWeight>>() -> Weight {
+	(104_362_000 as Weight)
+		.saturating_add(DbWeight::get().reads(11 as Weight))
+		.saturating_add(DbWeight::get().writes(11 as Weight))
+}
@@ -49,6 +49,7 @@ use sp_runtime::{
 use sp_std::prelude::*;
 
 pub mod benchmarking;
+pub mod estimated_weights;
 mod mock;
 mod tests;
 pub mod weights;
@@ -151,6 +152,10 @@ pub mod module {
 		AttributesTooLarge,
 		/// The given token ID is not correct
 		IncorrectTokenId,
+		/// Cannot approve the owner to itself
+		ApproveToOwner,
+		/// The `from` account is not the owner of the token
+		IncorrectOwner,
 	}
 
 	#[pallet::event]
@@ -193,14 +198,72 @@ pub mod module {
 			owner: T::AccountId,
 			class_id: ClassIdOf<T>,
 		},
+		/// Approved account to transfer NFT token, `None` means cleared.
+		Approved {
+			owner: T::AccountId,
+			approved: Option<T::AccountId>,
+			class_id: ClassIdOf<T>,
+			token_id: TokenIdOf<T>,
+		},
+		/// Approved or revoked operator to manage all NFT tokens of the owner in the class.
+		ApprovedForAll {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			class_id: ClassIdOf<T>,
+			approved: bool,
+		},
 	}
 
+	/// The account approved to transfer the NFT token.
+	///
+	/// TokenApprovals: double_map ClassId, TokenId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::AccountId, OptionQuery>;
+
+	/// The operators approved to manage all NFT tokens of the owner in the class.
+	///
+	/// OperatorApprovals: double_map (Owner, ClassId), Operator => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn operator_approvals)]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (T::AccountId, ClassIdOf<T>), Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The number of NFT tokens of the class owned by the account.
+	///
+	/// ClassBalances: double_map AccountId, ClassId => NFTBalance
+	#[pallet::storage]
+	pub type ClassBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ClassIdOf<T>, NFTBalance, ValueQuery>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return 0;
+			}
+
+			// count the tokens already owned before `ClassBalances` was introduced
+			let mut reads: Weight = 0;
+			let mut writes: Weight = 0;
+			for (owner, class_id, _) in orml_nft::TokensByOwner::<T>::iter_keys() {
+				ClassBalances::<T>::mutate(owner, class_id, |balance| *balance = balance.saturating_add(1));
+				reads = reads.saturating_add(2);
+				writes = writes.saturating_add(1);
+			}
+			STORAGE_VERSION.put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads.saturating_add(1), writes.saturating_add(1))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -254,7 +317,8 @@ pub mod module {
 		/// - `class_id`: token belong to the class id
 		/// - `metadata`: external metadata
 		/// - `quantity`: token quantity
-		#[pallet::weight(<T as Config>::WeightInfo::mint(*quantity))]
+		#[pallet::weight(<T as Config>::WeightInfo::mint(*quantity)
+			.saturating_add(estimated_weights::class_balances::<T::DbWeight>(1)))]
 		#[transactional]
 		pub fn mint(
 			origin: OriginFor<T>,
@@ -274,7 +338,8 @@ pub mod module {
 		///
 		/// - `to`: the token owner's account
 		/// - `token`: (class_id, token_id)
-		#[pallet::weight(<T as Config>::WeightInfo::transfer())]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer()
+			.saturating_add(estimated_weights::class_balances::<T::DbWeight>(2)))]
 		#[transactional]
 		pub fn transfer(
 			origin: OriginFor<T>,
@@ -289,7 +354,8 @@ pub mod module {
 		/// Burn NFT token
 		///
 		/// - `token`: (class_id, token_id)
-		#[pallet::weight(<T as Config>::WeightInfo::burn())]
+		#[pallet::weight(<T as Config>::WeightInfo::burn()
+			.saturating_add(estimated_weights::class_balances::<T::DbWeight>(1)))]
 		#[transactional]
 		pub fn burn(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -300,7 +366,8 @@ pub mod module {
 		///
 		/// - `token`: (class_id, token_id)
 		/// - `remark`: Vec<u8>
-		#[pallet::weight(<T as Config>::WeightInfo::burn_with_remark(remark.len() as u32))]
+		#[pallet::weight(<T as Config>::WeightInfo::burn_with_remark(remark.len() as u32)
+			.saturating_add(estimated_weights::class_balances::<T::DbWeight>(1)))]
 		#[transactional]
 		pub fn burn_with_remark(
 			origin: OriginFor<T>,
@@ -380,6 +447,59 @@ pub mod module {
 				Ok(())
 			})
 		}
+
+		/// Approve an account to transfer the NFT token on behalf of the owner.
+		///
+		/// - `approved`: the approved account, `None` to clear the approval
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		#[transactional]
+		pub fn approve(
+			origin: OriginFor<T>,
+			approved: Option<<T::Lookup as StaticLookup>::Source>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let approved = approved.map(T::Lookup::lookup).transpose()?;
+			Self::do_approve(&who, approved, token)
+		}
+
+		/// Approve or revoke an operator to manage all NFT tokens of the origin in the class.
+		///
+		/// - `operator`: the operator account
+		/// - `class_id`: the class ID
+		/// - `approved`: approve or revoke
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		#[transactional]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			class_id: ClassIdOf<T>,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			Self::do_set_approval_for_all(&who, &operator, class_id, approved)
+		}
+
+		/// Transfer NFT token from the owner by the approved account or operator.
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the receiver's account
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_transfer_from(&who, &from, &to, token)
+		}
 	}
 }
 
@@ -396,6 +516,13 @@ impl<T: Config> Pallet<T> {
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		TokenApprovals::<T>::remove(token.0, token.1);
+		if from != to {
+			ClassBalances::<T>::mutate_exists(from, token.0, |balance| {
+				*balance = balance.map(|b| b.saturating_sub(1)).filter(|b| !b.is_zero())
+			});
+			ClassBalances::<T>::mutate(to, token.0, |balance| *balance = balance.saturating_add(1));
+		}
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, from, token_info.data.deposit);
 		<T as module::Config>::Currency::transfer(from, to, token_info.data.deposit, AllowDeath)?;
@@ -447,6 +574,9 @@ impl<T: Config> Pallet<T> {
 				data.clone(),
 			)?);
 		}
+		ClassBalances::<T>::mutate(to, class_id, |balance| {
+			*balance = balance.saturating_add(quantity.into())
+		});
 
 		Self::deposit_event(Event::MintedToken {
 			from: who.clone(),
@@ -469,6 +599,10 @@ impl<T: Config> Pallet<T> {
 		ensure!(who == token_info.owner, Error::<T>::NoPermission);
 
 		orml_nft::Pallet::<T>::burn(&who, token)?;
+		TokenApprovals::<T>::remove(token.0, token.1);
+		ClassBalances::<T>::mutate_exists(&who, token.0, |balance| {
+			*balance = balance.map(|b| b.saturating_sub(1)).filter(|b| !b.is_zero())
+		});
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, token_info.data.deposit);
 
@@ -491,6 +625,100 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Approve `approved` to transfer the token, `who` must be the owner or an operator of the
+	/// owner.
+	pub fn do_approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		let owner = token_info.owner;
+		ensure!(
+			*who == owner || Self::is_approved_for_all(&owner, token.0, who),
+			Error::<T>::NoPermission
+		);
+		ensure!(approved.as_ref() != Some(&owner), Error::<T>::ApproveToOwner);
+
+		TokenApprovals::<T>::mutate_exists(token.0, token.1, |maybe_approved| *maybe_approved = approved.clone());
+
+		Self::deposit_event(Event::Approved {
+			owner,
+			approved,
+			class_id: token.0,
+			token_id: token.1,
+		});
+		Ok(())
+	}
+
+	/// Approve or revoke `operator` to manage all the tokens of `owner` in the class.
+	pub fn do_set_approval_for_all(
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(owner != operator, Error::<T>::ApproveToOwner);
+		ensure!(
+			orml_nft::Classes::<T>::contains_key(class_id),
+			Error::<T>::ClassIdNotFound
+		);
+
+		if approved {
+			OperatorApprovals::<T>::insert((owner, class_id), operator, ());
+		} else {
+			OperatorApprovals::<T>::remove((owner, class_id), operator);
+		}
+
+		Self::deposit_event(Event::ApprovedForAll {
+			owner: owner.clone(),
+			operator: operator.clone(),
+			class_id,
+			approved,
+		});
+		Ok(())
+	}
+
+	/// Transfer the token from `from` to `to`, `who` must be the owner, the approved account or
+	/// an operator of the owner.
+	#[require_transactional]
+	pub fn do_transfer_from(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(token_info.owner == *from, Error::<T>::IncorrectOwner);
+		ensure!(Self::is_approved_or_owner(who, token), Error::<T>::NoPermission);
+
+		Self::do_transfer(from, to, token)
+	}
+
+	/// Whether `operator` is approved to manage all the tokens of `owner` in the class.
+	pub fn is_approved_for_all(owner: &T::AccountId, class_id: ClassIdOf<T>, operator: &T::AccountId) -> bool {
+		OperatorApprovals::<T>::contains_key((owner, class_id), operator)
+	}
+
+	/// Whether `spender` is the owner, the approved account or an operator of the token.
+	pub fn is_approved_or_owner(spender: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> bool {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1).map_or(false, |token_info| {
+			token_info.owner == *spender
+				|| Self::token_approvals(token.0, token.1).as_ref() == Some(spender)
+				|| Self::is_approved_for_all(&token_info.owner, token.0, spender)
+		})
+	}
+
+	/// The metadata of the token.
+	pub fn token_metadata(token: (ClassIdOf<T>, TokenIdOf<T>)) -> Option<CID> {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1).map(|token_info| token_info.metadata.into_inner())
+	}
+
+	/// The number of tokens in the class owned by `who`.
+	pub fn class_balance(who: &T::AccountId, class_id: ClassIdOf<T>) -> NFTBalance {
+		ClassBalances::<T>::get(who, class_id)
+	}
+
 	fn data_deposit(metadata: &[u8], attributes: &Attributes) -> Result<BalanceOf<T>, DispatchError> {
 		// Addition can't overflow because we will be out of memory before that
 		let attributes_len = attributes.iter().fold(0, |acc, (k, v)| {
@@ -712,3 +712,209 @@ fn update_class_properties_should_work() {
 		);
 	});
 }
+
+fn create_class_and_mint_to_bob(quantity: u32) {
+	assert_ok!(NFTModule::create_class(
+		Origin::signed(ALICE),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+		Default::default(),
+	));
+	assert_ok!(Balances::deposit_into_existing(
+		&class_id_account(),
+		(quantity as u128) * (CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE)
+	));
+	assert_ok!(NFTModule::mint(
+		Origin::signed(class_id_account()),
+		BOB,
+		CLASS_ID,
+		vec![1],
+		Default::default(),
+		quantity
+	));
+}
+
+#[test]
+fn approve_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_class_and_mint_to_bob(1);
+
+		assert_noop!(
+			NFTModule::approve(Origin::signed(ALICE), Some(CHARLIE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::approve(Origin::signed(BOB), Some(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ApproveToOwner
+		);
+		assert_noop!(
+			NFTModule::approve(Origin::signed(BOB), Some(CHARLIE), (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(NFTModule::approve(
+			Origin::signed(BOB),
+			Some(CHARLIE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::Approved {
+			owner: BOB,
+			approved: Some(CHARLIE),
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), Some(CHARLIE));
+		assert!(NFTModule::is_approved_or_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+
+		// operator can approve on behalf of the owner
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			ALICE,
+			CLASS_ID,
+			true
+		));
+		assert_ok!(NFTModule::approve(Origin::signed(ALICE), None, (CLASS_ID, TOKEN_ID)));
+		System::assert_last_event(Event::NFTModule(crate::Event::Approved {
+			owner: BOB,
+			approved: None,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert!(!NFTModule::is_approved_or_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn set_approval_for_all_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_class_and_mint_to_bob(1);
+
+		assert_noop!(
+			NFTModule::set_approval_for_all(Origin::signed(BOB), BOB, CLASS_ID, true),
+			Error::<Runtime>::ApproveToOwner
+		);
+		assert_noop!(
+			NFTModule::set_approval_for_all(Origin::signed(BOB), CHARLIE, CLASS_ID_NOT_EXIST, true),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			CHARLIE,
+			CLASS_ID,
+			true
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ApprovedForAll {
+			owner: BOB,
+			operator: CHARLIE,
+			class_id: CLASS_ID,
+			approved: true,
+		}));
+		assert!(NFTModule::is_approved_for_all(&BOB, CLASS_ID, &CHARLIE));
+		assert!(NFTModule::is_approved_or_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			CHARLIE,
+			CLASS_ID,
+			false
+		));
+		assert!(!NFTModule::is_approved_for_all(&BOB, CLASS_ID, &CHARLIE));
+		assert!(!NFTModule::is_approved_or_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn transfer_from_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_class_and_mint_to_bob(2);
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 2);
+
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(CHARLIE), BOB, ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::approve(
+			Origin::signed(BOB),
+			Some(CHARLIE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(CHARLIE), ALICE, CHARLIE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::IncorrectOwner
+		);
+		assert_ok!(NFTModule::transfer_from(
+			Origin::signed(CHARLIE),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::TransferredToken {
+			from: BOB,
+			to: ALICE,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		// the approval is cleared after transfer
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 1);
+		assert_eq!(NFTModule::class_balance(&ALICE, CLASS_ID), 1);
+		assert_eq!(reserved_balance(&ALICE), CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE);
+
+		// operator can transfer all the tokens of the owner in the class
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			CHARLIE,
+			CLASS_ID,
+			true
+		));
+		assert_ok!(NFTModule::transfer_from(
+			Origin::signed(CHARLIE),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID + 1)
+		));
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 0);
+		assert_eq!(NFTModule::class_balance(&ALICE, CLASS_ID), 2);
+	});
+}
+
+#[test]
+fn class_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_class_and_mint_to_bob(3);
+		assert_eq!(ClassBalances::<Runtime>::get(BOB, CLASS_ID), 3);
+
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(ClassBalances::<Runtime>::get(BOB, CLASS_ID), 3);
+
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(ClassBalances::<Runtime>::get(BOB, CLASS_ID), 2);
+		assert_eq!(ClassBalances::<Runtime>::get(ALICE, CLASS_ID), 1);
+
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)));
+		assert!(!ClassBalances::<Runtime>::contains_key(ALICE, CLASS_ID));
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 2);
+	});
+}
+
+#[test]
+fn class_balances_should_be_counted_on_runtime_upgrade() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_class_and_mint_to_bob(2);
+		ClassBalances::<Runtime>::remove(BOB, CLASS_ID);
+		StorageVersion::new(0).put::<NFTModule>();
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 0);
+
+		NFTModule::on_runtime_upgrade();
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 2);
+		assert_eq!(StorageVersion::get::<NFTModule>(), 1);
+
+		// only runs once
+		ClassBalances::<Runtime>::remove(BOB, CLASS_ID);
+		NFTModule::on_runtime_upgrade();
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 0);
+	});
+}
@@ -54,6 +54,9 @@ pub trait WeightInfo {
 	fn burn_with_remark(b: u32, ) -> Weight;
 	fn destroy_class() -> Weight;
 	fn update_class_properties() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
 }
 
 /// Weights for module_nft using the Acala node and recommended hardware.
@@ -99,6 +102,15 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn approve() -> Weight {
+		crate::estimated_weights::approve::<T::DbWeight>()
+	}
+	fn set_approval_for_all() -> Weight {
+		crate::estimated_weights::set_approval_for_all::<T::DbWeight>()
+	}
+	fn transfer_from() -> Weight {
+		crate::estimated_weights::transfer_from::<T::DbWeight>()
+	}
 }
 
 // For backwards compatibility and tests
@@ -143,4 +155,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn approve() -> Weight {
+		crate::estimated_weights::approve::<RocksDbWeight>()
+	}
+	fn set_approval_for_all() -> Weight {
+		crate::estimated_weights::set_approval_for_all::<RocksDbWeight>()
+	}
+	fn transfer_from() -> Weight {
+		crate::estimated_weights::transfer_from::<RocksDbWeight>()
+	}
 }
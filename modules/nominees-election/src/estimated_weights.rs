@@ -0,0 +1,48 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_nominees_election
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weight of processing the expiries in
+//! `on_initialize` is estimated from its storage accesses and the benchmarked weights of `chill`
+//! and `withdraw_unbonded`. Replace it by running the `module_nominees_election` benchmarks, which
+//! generate it into the weights of the module and the runtimes.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{RuntimeDbWeight, Weight}};
+
+// Storage: NomineesElection CurrentEra (r:1 w:0)
+// Storage: NomineesElection NextExpiryEra (r:1 w:1)
+// Storage: NomineesElection ConvictionExpiries (r:1 w:1)
+// Storage: NomineesElection NominationExpiries (r:1 w:1)
+// Storage: NomineesElection NominationInfos (r:1 w:1)
+// Storage: NomineesElection ConvictionLocks (r:1 w:0)
+// Storage: NomineesElection Ledger (r:1 w:0)
+// Storage: NomineesElection Nominations (r:1 w:1)
+// Storage: NomineesElection Votes (r:7 w:7)
+pub fn on_initialize<DbWeight: Get<RuntimeDbWeight>>(n: u32, ) -> Weight {
+	(5_127_000 as Weight)
+		.saturating_add((31_482_000 as Weight).saturating_mul(n as Weight))
+		.saturating_add(DbWeight::get().reads(2 as Weight))
+		.saturating_add(DbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+		.saturating_add(DbWeight::get().writes(1 as Weight))
+		.saturating_add(DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+}
@@ -31,13 +31,15 @@ use primitives::{
 	bonding::{self, BondingController},
 	Balance, EraIndex,
 };
+use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
-	SaturatedConversion,
+	RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{fmt::Debug, prelude::*};
-use support::{NomineesProvider, OnNewEra};
+use support::{NomineesProvider, OnNewEra, WeightedNomineesProvider};
 
+pub mod estimated_weights;
 mod mock;
 mod tests;
 pub mod weights;
@@ -45,6 +47,58 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// A time-lock multiplier for votes. The longer the bonded tokens are locked, the more weight
+/// the votes carry.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Conviction {
+	/// 1x votes, no extra lock.
+	None,
+	/// 2x votes, locked for 1 conviction period.
+	Locked2x,
+	/// 3x votes, locked for 2 conviction periods.
+	Locked3x,
+	/// 4x votes, locked for 4 conviction periods.
+	Locked4x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// The multiplier applied to the bonded amount.
+	pub fn votes(self, amount: Balance) -> Balance {
+		let multiplier: Balance = match self {
+			Conviction::None => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+		};
+		amount.saturating_mul(multiplier)
+	}
+
+	/// The number of conviction periods the bonded tokens are locked for.
+	pub fn lock_periods(self) -> EraIndex {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked2x => 1,
+			Conviction::Locked3x => 2,
+			Conviction::Locked4x => 4,
+		}
+	}
+}
+
+/// The conviction and expiry of a nomination.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+pub struct NominationInfo {
+	/// The conviction the nomination was made with.
+	pub conviction: Conviction,
+	/// The era at which the nomination expires unless renewed.
+	pub expire_at: EraIndex,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -65,6 +119,15 @@ pub mod module {
 		#[pallet::constant]
 		type MaxUnbondingChunks: Get<u32>;
 		type NomineeFilter: Contains<Self::NomineeId>;
+		/// The number of eras of one conviction lock period.
+		#[pallet::constant]
+		type ConvictionPeriod: Get<EraIndex>;
+		/// The number of eras after which a nomination expires unless renewed.
+		#[pallet::constant]
+		type NominationExpiry: Get<EraIndex>;
+		/// The maximum number of expiries processed per block, each scanned era counts as one.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -79,12 +142,27 @@ pub mod module {
 		InvalidNominee,
 		NominateesCountExceeded,
 		NotBonded,
+		ConvictionLocked,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
-		Rebond { who: T::AccountId, amount: Balance },
+		Rebond {
+			who: T::AccountId,
+			amount: Balance,
+		},
+		Nominate {
+			who: T::AccountId,
+			conviction: Conviction,
+			expire_at: EraIndex,
+		},
+		NominationExpired {
+			who: T::AccountId,
+		},
+		ConvictionExpired {
+			who: T::AccountId,
+		},
 	}
 
 	/// The nominations for nominators.
@@ -100,6 +178,43 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The conviction and expiry of the nominations.
+	///
+	/// NominationInfos: map AccountId => Option<NominationInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn nomination_infos)]
+	pub type NominationInfos<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, NominationInfo, OptionQuery>;
+
+	/// The nominations expiring at an era.
+	///
+	/// NominationExpiries: double_map EraIndex, AccountId => ()
+	#[pallet::storage]
+	pub type NominationExpiries<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The convictions expiring at an era, the era being the conviction lock of the account.
+	///
+	/// ConvictionExpiries: double_map EraIndex, AccountId => ()
+	#[pallet::storage]
+	pub type ConvictionExpiries<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The first era whose expiries may not have been processed yet.
+	///
+	/// NextExpiryEra: EraIndex
+	#[pallet::storage]
+	#[pallet::getter(fn next_expiry_era)]
+	pub type NextExpiryEra<T: Config<I>, I: 'static = ()> = StorageValue<_, EraIndex, ValueQuery>;
+
+	/// The era until which the bonded tokens are locked by conviction.
+	///
+	/// ConvictionLocks: map AccountId => EraIndex
+	#[pallet::storage]
+	#[pallet::getter(fn conviction_locks)]
+	pub type ConvictionLocks<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, EraIndex, ValueQuery>;
+
 	/// The nomination bonding ledger.
 	///
 	/// Ledger: map AccountId => BondingLedger
@@ -136,7 +251,12 @@ pub mod module {
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::hooks]
-	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {}
+	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let processed = Self::process_expiries(T::MaxExpiriesPerBlock::get());
+			T::WeightInfo::on_initialize(processed)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -144,13 +264,20 @@ pub mod module {
 		#[transactional]
 		pub fn bond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::settle_conviction(&who);
 
 			let change = <Self as BondingController>::bond(&who, amount)?;
 
 			if let Some(change) = change {
 				let old_nominations = Self::nominations(&who);
+				let conviction = Self::conviction_of(&who);
 
-				Self::update_votes(change.old, &old_nominations, change.new, &old_nominations);
+				Self::update_votes(
+					conviction.votes(change.old),
+					&old_nominations,
+					conviction.votes(change.new),
+					&old_nominations,
+				);
 			}
 			Ok(())
 		}
@@ -159,14 +286,27 @@ pub mod module {
 		#[transactional]
 		pub fn unbond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::conviction_locks(&who) <= Self::current_era(),
+				Error::<T, I>::ConvictionLocked
+			);
+			// the lock is over, so are the extra votes of the conviction.
+			Self::settle_conviction(&who);
+			ConvictionLocks::<T, I>::remove(&who);
 
 			let unbond_at = Self::current_era().saturating_add(T::BondingDuration::get());
 			let change = <Self as BondingController>::unbond(&who, amount, unbond_at)?;
 
 			if let Some(change) = change {
 				let old_nominations = Self::nominations(&who);
+				let conviction = Self::conviction_of(&who);
 
-				Self::update_votes(change.old, &old_nominations, change.new, &old_nominations);
+				Self::update_votes(
+					conviction.votes(change.old),
+					&old_nominations,
+					conviction.votes(change.new),
+					&old_nominations,
+				);
 			}
 
 			Ok(())
@@ -176,13 +316,20 @@ pub mod module {
 		#[transactional]
 		pub fn rebond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::settle_conviction(&who);
 
 			let change = <Self as BondingController>::rebond(&who, amount)?;
 
 			if let Some(change) = change {
 				let old_nominations = Self::nominations(&who);
+				let conviction = Self::conviction_of(&who);
 
-				Self::update_votes(change.old, &old_nominations, change.new, &old_nominations);
+				Self::update_votes(
+					conviction.votes(change.old),
+					&old_nominations,
+					conviction.votes(change.new),
+					&old_nominations,
+				);
 				Self::deposit_event(Event::Rebond {
 					who,
 					amount: change.change,
@@ -206,34 +353,20 @@ pub mod module {
 		#[transactional]
 		pub fn nominate(origin: OriginFor<T>, targets: Vec<T::NomineeId>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::do_nominate(who, targets, Conviction::None)
+		}
 
-			let ledger = Self::ledger(&who).ok_or(Error::<T, I>::NotBonded)?;
-
-			let bounded_targets: BoundedVec<<T as Config<I>>::NomineeId, <T as Config<I>>::NominateesCount> = {
-				if targets.is_empty() {
-					Err(Error::<T, I>::InvalidTargetsLength)
-				} else {
-					targets.try_into().map_err(|_| Error::<T, I>::InvalidTargetsLength)
-				}
-			}?;
-
-			let bounded_targets = bounded_targets
-				.try_mutate(|targets| {
-					targets.sort();
-					targets.dedup();
-				})
-				.expect("This only reduce size of the vector; qed");
-
-			for validator in bounded_targets.iter() {
-				ensure!(T::NomineeFilter::contains(validator), Error::<T, I>::InvalidNominee);
-			}
-
-			let old_nominations = Self::nominations(&who);
-			let old_active = ledger.active();
-
-			Self::update_votes(old_active, &old_nominations, old_active, &bounded_targets);
-			Nominations::<T, I>::insert(&who, &bounded_targets);
-			Ok(())
+		/// Nominate `targets` with votes multiplied by `conviction`, which locks the bonded tokens
+		/// from unbonding for the conviction periods.
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		#[transactional]
+		pub fn nominate_with_conviction(
+			origin: OriginFor<T>,
+			targets: Vec<T::NomineeId>,
+			conviction: Conviction,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_nominate(who, targets, conviction)
 		}
 
 		#[pallet::weight(T::WeightInfo::chill(T::NominateesCount::get()))]
@@ -242,12 +375,7 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 
 			let ledger = Self::ledger(&who).ok_or(Error::<T, I>::NotBonded)?;
-
-			let old_nominations = Self::nominations(&who);
-			let old_active = ledger.active();
-
-			Self::update_votes(old_active, &old_nominations, Zero::zero(), &[]);
-			Nominations::<T, I>::remove(&who);
+			Self::remove_nomination(&who, ledger.active());
 
 			Ok(())
 		}
@@ -255,6 +383,162 @@ pub mod module {
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	fn conviction_of(who: &T::AccountId) -> Conviction {
+		Self::nomination_infos(who)
+			.map(|info| info.conviction)
+			.unwrap_or_default()
+	}
+
+	fn do_nominate(who: T::AccountId, targets: Vec<T::NomineeId>, conviction: Conviction) -> DispatchResult {
+		let ledger = Self::ledger(&who).ok_or(Error::<T, I>::NotBonded)?;
+		Self::settle_conviction(&who);
+
+		let bounded_targets: BoundedVec<<T as Config<I>>::NomineeId, <T as Config<I>>::NominateesCount> = {
+			if targets.is_empty() {
+				Err(Error::<T, I>::InvalidTargetsLength)
+			} else {
+				targets.try_into().map_err(|_| Error::<T, I>::InvalidTargetsLength)
+			}
+		}?;
+
+		let bounded_targets = bounded_targets
+			.try_mutate(|targets| {
+				targets.sort();
+				targets.dedup();
+			})
+			.expect("This only reduce size of the vector; qed");
+
+		for validator in bounded_targets.iter() {
+			ensure!(T::NomineeFilter::contains(validator), Error::<T, I>::InvalidNominee);
+		}
+
+		let old_nominations = Self::nominations(&who);
+		let old_info = Self::nomination_infos(&who);
+		let old_conviction = old_info.map(|info| info.conviction).unwrap_or_default();
+		let active = ledger.active();
+
+		Self::update_votes(
+			old_conviction.votes(active),
+			&old_nominations,
+			conviction.votes(active),
+			&bounded_targets,
+		);
+		Nominations::<T, I>::insert(&who, &bounded_targets);
+
+		let current_era = Self::current_era();
+		let old_lock = Self::conviction_locks(&who);
+		if old_conviction != Conviction::None {
+			ConvictionExpiries::<T, I>::remove(old_lock, &who);
+		}
+		let lock_until = old_lock
+			.max(current_era.saturating_add(conviction.lock_periods().saturating_mul(T::ConvictionPeriod::get())));
+		if lock_until > current_era {
+			ConvictionLocks::<T, I>::insert(&who, lock_until);
+		}
+		// the conviction is downgraded once the tokens are no longer locked.
+		if conviction != Conviction::None {
+			ConvictionExpiries::<T, I>::insert(lock_until, &who, ());
+		}
+
+		if let Some(old_info) = old_info {
+			NominationExpiries::<T, I>::remove(old_info.expire_at, &who);
+		}
+		let expire_at = current_era.saturating_add(T::NominationExpiry::get());
+		NominationExpiries::<T, I>::insert(expire_at, &who, ());
+		NominationInfos::<T, I>::insert(&who, NominationInfo { conviction, expire_at });
+
+		Self::deposit_event(Event::Nominate {
+			who,
+			conviction,
+			expire_at,
+		});
+		Ok(())
+	}
+
+	fn remove_nomination(who: &T::AccountId, active: Balance) {
+		let old_nominations = Self::nominations(who);
+		let old_conviction = Self::conviction_of(who);
+
+		Self::update_votes(old_conviction.votes(active), &old_nominations, Zero::zero(), &[]);
+		Nominations::<T, I>::remove(who);
+		Self::remove_nomination_info(who);
+	}
+
+	fn remove_nomination_info(who: &T::AccountId) {
+		if let Some(info) = NominationInfos::<T, I>::take(who) {
+			NominationExpiries::<T, I>::remove(info.expire_at, who);
+			if info.conviction != Conviction::None {
+				ConvictionExpiries::<T, I>::remove(Self::conviction_locks(who), who);
+			}
+		}
+	}
+
+	/// Downgrade the votes of `who` to `Conviction::None`. The conviction expiry entry must have
+	/// been removed by the caller.
+	fn downgrade_conviction(who: &T::AccountId) {
+		if let Some(mut info) = Self::nomination_infos(who) {
+			if info.conviction != Conviction::None {
+				let active = Self::ledger(who).map(|ledger| ledger.active()).unwrap_or_default();
+				let nominations = Self::nominations(who);
+				Self::update_votes(
+					info.conviction.votes(active),
+					&nominations,
+					Conviction::None.votes(active),
+					&nominations,
+				);
+				info.conviction = Conviction::None;
+				NominationInfos::<T, I>::insert(who, info);
+				Self::deposit_event(Event::ConvictionExpired { who: who.clone() });
+			}
+		}
+	}
+
+	/// Downgrade the conviction of `who` if its lock is over but the expiry is not processed yet,
+	/// so the votes never carry a multiplier the remaining lock doesn't back.
+	fn settle_conviction(who: &T::AccountId) {
+		let lock_until = Self::conviction_locks(who);
+		if lock_until <= Self::current_era() && Self::conviction_of(who) != Conviction::None {
+			ConvictionExpiries::<T, I>::remove(lock_until, who);
+			Self::downgrade_conviction(who);
+		}
+	}
+
+	/// Process the convictions and nominations expired by the current era, at most `limit`
+	/// expiries and scanned eras. Returns the number processed.
+	fn process_expiries(limit: u32) -> u32 {
+		let current_era = Self::current_era();
+		let next_expiry_era = Self::next_expiry_era();
+		let mut era = next_expiry_era;
+		let mut processed: u32 = 0;
+
+		while era <= current_era && processed < limit {
+			// the convictions go first, the expired nominations remove their conviction expiries.
+			for (who, _) in ConvictionExpiries::<T, I>::drain_prefix(era).take((limit - processed) as usize) {
+				Self::downgrade_conviction(&who);
+				processed += 1;
+			}
+			for (who, _) in NominationExpiries::<T, I>::drain_prefix(era).take(limit.saturating_sub(processed) as usize)
+			{
+				// the expiry entry is drained, so only the votes and nominations remain to be removed.
+				let active = Self::ledger(&who).map(|ledger| ledger.active()).unwrap_or_default();
+				Self::remove_nomination(&who, active);
+				Self::deposit_event(Event::NominationExpired { who });
+				processed += 1;
+			}
+			if processed >= limit {
+				// the era may have expiries left, continue from it in the next block.
+				break;
+			}
+			era = era.saturating_add(1);
+			processed += 1;
+		}
+
+		if era != next_expiry_era {
+			NextExpiryEra::<T, I>::put(era);
+		}
+		processed
+	}
+
 	fn update_votes(
 		old_active: Balance,
 		old_nominations: &[T::NomineeId],
@@ -281,6 +565,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		let new_nominees: BoundedVec<<T as Config<I>>::NomineeId, <T as Config<I>>::NominateesCount> = voters
 			.into_iter()
+			.filter(|(_, votes)| !votes.is_zero())
 			.take(T::NominateesCount::get().saturated_into())
 			.map(|(nominee, _)| nominee)
 			.collect::<Vec<_>>()
@@ -297,8 +582,21 @@ impl<T: Config<I>, I: 'static> NomineesProvider<T::NomineeId> for Pallet<T, I> {
 	}
 }
 
+impl<T: Config<I>, I: 'static> WeightedNomineesProvider<T::NomineeId, Balance> for Pallet<T, I> {
+	fn weighted_nominees() -> Vec<(T::NomineeId, Balance)> {
+		Nominees::<T, I>::get()
+			.into_iter()
+			.map(|nominee| {
+				let votes = Self::votes(&nominee);
+				(nominee, votes)
+			})
+			.collect()
+	}
+}
+
 impl<T: Config<I>, I: 'static> OnNewEra<EraIndex> for Pallet<T, I> {
 	fn on_new_era(era: EraIndex) {
+		// the expiries up to `era` are processed by `on_initialize` within the block limit.
 		CurrentEra::<T, I>::put(era);
 		Self::rebalance();
 	}
@@ -331,6 +629,8 @@ impl<T: Config<I>, I: 'static> BondingController for Pallet<T, I> {
 			}
 
 			Nominations::<T, I>::remove(who);
+			Self::remove_nomination_info(who);
+			ConvictionLocks::<T, I>::remove(who);
 
 			res
 		} else {
@@ -141,6 +141,9 @@ impl Config for Runtime {
 	type NominateesCount = ConstU32<5>;
 	type MaxUnbondingChunks = ConstU32<3>;
 	type NomineeFilter = MockNomineeFilter;
+	type ConvictionPeriod = ConstU32<2>;
+	type NominationExpiry = ConstU32<10>;
+	type MaxExpiriesPerBlock = ConstU32<5>;
 	type WeightInfo = ();
 }
 
@@ -22,7 +22,7 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::*;
+use mock::{Event, *};
 
 #[test]
 fn bond_below_min_bond_threshold() {
@@ -271,3 +271,184 @@ fn update_votes_work() {
 		assert_eq!(NomineesElectionModule::votes(4), 10);
 	});
 }
+
+#[test]
+fn nominate_with_conviction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate_with_conviction(
+			Origin::signed(ALICE),
+			vec![1, 2],
+			Conviction::Locked3x
+		));
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::Nominate {
+			who: ALICE,
+			conviction: Conviction::Locked3x,
+			expire_at: 10,
+		}));
+		assert_eq!(NomineesElectionModule::votes(1), 1500);
+		assert_eq!(NomineesElectionModule::votes(2), 1500);
+		assert_eq!(NomineesElectionModule::conviction_locks(&ALICE), 4);
+
+		assert_noop!(
+			NomineesElectionModule::unbond(Origin::signed(ALICE), 100),
+			Error::<Runtime>::ConvictionLocked,
+		);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::votes(1), 1800);
+
+		// the conviction is downgraded with the lock, even before its expiry is processed
+		NomineesElectionModule::on_new_era(4);
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(ALICE), 100));
+		assert_eq!(NomineesElectionModule::votes(1), 500);
+		assert_eq!(NomineesElectionModule::conviction_locks(&ALICE), 0);
+		assert_eq!(
+			NomineesElectionModule::nomination_infos(&ALICE).unwrap().conviction,
+			Conviction::None
+		);
+		assert_eq!(ConvictionExpiries::<Runtime>::get(4, ALICE), None);
+		System::assert_has_event(Event::NomineesElectionModule(crate::Event::ConvictionExpired {
+			who: ALICE,
+		}));
+
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![2, 3]));
+		assert_eq!(NomineesElectionModule::votes(1), 0);
+		assert_eq!(NomineesElectionModule::votes(2), 500);
+		assert_eq!(NomineesElectionModule::votes(3), 500);
+		assert_eq!(
+			NomineesElectionModule::nomination_infos(&ALICE),
+			Some(NominationInfo {
+				conviction: Conviction::None,
+				expire_at: 14,
+			})
+		);
+	});
+}
+
+#[test]
+fn nomination_expiry_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 400));
+		assert_ok!(NomineesElectionModule::nominate_with_conviction(
+			Origin::signed(BOB),
+			vec![2],
+			Conviction::Locked2x
+		));
+		assert_eq!(ConvictionExpiries::<Runtime>::get(2, BOB), Some(()));
+		NomineesElectionModule::on_new_era(1);
+		NomineesElectionModule::on_initialize(1);
+		assert_eq!(NomineesElectionModule::weighted_nominees(), vec![(2, 800), (1, 500)]);
+
+		// the conviction expires with its lock
+		NomineesElectionModule::on_new_era(2);
+		NomineesElectionModule::on_initialize(2);
+		System::assert_has_event(Event::NomineesElectionModule(crate::Event::ConvictionExpired {
+			who: BOB,
+		}));
+		assert_eq!(ConvictionExpiries::<Runtime>::get(2, BOB), None);
+		assert_eq!(NomineesElectionModule::votes(2), 400);
+		assert_eq!(
+			NomineesElectionModule::nomination_infos(&BOB),
+			Some(NominationInfo {
+				conviction: Conviction::None,
+				expire_at: 10,
+			})
+		);
+
+		// renew the nomination
+		NomineesElectionModule::on_new_era(5);
+		NomineesElectionModule::on_initialize(5);
+		assert_ok!(NomineesElectionModule::nominate_with_conviction(
+			Origin::signed(BOB),
+			vec![2],
+			Conviction::Locked2x
+		));
+		assert_eq!(NomineesElectionModule::votes(2), 800);
+		assert_eq!(NominationExpiries::<Runtime>::get(10, BOB), None);
+		assert_eq!(NominationExpiries::<Runtime>::get(15, BOB), Some(()));
+		assert_eq!(ConvictionExpiries::<Runtime>::get(7, BOB), Some(()));
+
+		// the expiries of eras 6 to 10 take two blocks
+		NomineesElectionModule::on_new_era(10);
+		NomineesElectionModule::on_initialize(10);
+		NomineesElectionModule::on_initialize(11);
+		System::assert_has_event(Event::NomineesElectionModule(crate::Event::NominationExpired {
+			who: ALICE,
+		}));
+		assert_eq!(NomineesElectionModule::nominations(&ALICE), vec![]);
+		assert_eq!(NomineesElectionModule::nomination_infos(&ALICE), None);
+		assert_eq!(NomineesElectionModule::votes(1), 0);
+		assert_eq!(NomineesElectionModule::votes(2), 400);
+		assert_eq!(NomineesElectionModule::ledger(&ALICE).unwrap().active(), 500);
+		assert_eq!(NomineesElectionModule::next_expiry_era(), 11);
+
+		// the expiries take effect at the next election
+		NomineesElectionModule::on_new_era(11);
+		assert_eq!(NomineesElectionModule::weighted_nominees(), vec![(2, 400)]);
+
+		// skipped eras are expired as well
+		NomineesElectionModule::on_new_era(16);
+		NomineesElectionModule::on_initialize(16);
+		assert_eq!(NomineesElectionModule::votes(2), 0);
+		NomineesElectionModule::on_new_era(17);
+		assert_eq!(NomineesElectionModule::nominees(), vec![]);
+	});
+}
+
+#[test]
+fn process_expiries_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LDOTCurrency::deposit(&10, 1000));
+		for who in [ALICE, BOB, 10] {
+			assert_ok!(NomineesElectionModule::bond(Origin::signed(who), 100));
+			assert_ok!(NomineesElectionModule::nominate_with_conviction(
+				Origin::signed(who),
+				vec![1],
+				Conviction::Locked2x
+			));
+		}
+		assert_eq!(NomineesElectionModule::votes(1), 600);
+		NomineesElectionModule::on_new_era(10);
+
+		// eras 0 and 1 are scanned and 3 convictions expire at era 2, which may have more
+		assert_eq!(
+			NomineesElectionModule::on_initialize(1),
+			<() as WeightInfo>::on_initialize(5)
+		);
+		assert_eq!(NomineesElectionModule::votes(1), 300);
+		assert_eq!(NomineesElectionModule::next_expiry_era(), 2);
+
+		// eras 2 to 6 are scanned
+		assert_eq!(
+			NomineesElectionModule::on_initialize(2),
+			<() as WeightInfo>::on_initialize(5)
+		);
+		assert_eq!(NomineesElectionModule::next_expiry_era(), 7);
+
+		// eras 7 to 9 are scanned and 2 of the 3 nominations expire at era 10
+		assert_eq!(
+			NomineesElectionModule::on_initialize(3),
+			<() as WeightInfo>::on_initialize(5)
+		);
+		assert_eq!(NomineesElectionModule::next_expiry_era(), 10);
+		assert_eq!(NomineesElectionModule::votes(1), 100);
+
+		assert_eq!(
+			NomineesElectionModule::on_initialize(4),
+			<() as WeightInfo>::on_initialize(2)
+		);
+		assert_eq!(NomineesElectionModule::next_expiry_era(), 11);
+		assert_eq!(NomineesElectionModule::votes(1), 0);
+		assert_eq!(NominationExpiries::<Runtime>::iter().count(), 0);
+
+		assert_eq!(
+			NomineesElectionModule::on_initialize(5),
+			<() as WeightInfo>::on_initialize(0)
+		);
+	});
+}
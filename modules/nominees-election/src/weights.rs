@@ -53,6 +53,7 @@ pub trait WeightInfo {
 	fn withdraw_unbonded(c: u32, ) -> Weight;
 	fn nominate(c: u32, ) -> Weight;
 	fn chill(c: u32, ) -> Weight;
+	fn on_initialize(n: u32, ) -> Weight;
 }
 
 /// Weights for module_nominees_election using the Acala node and recommended hardware.
@@ -100,6 +101,9 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn on_initialize(n: u32, ) -> Weight {
+		crate::estimated_weights::on_initialize::<T::DbWeight>(n)
+	}
 }
 
 // For backwards compatibility and tests
@@ -146,4 +150,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn on_initialize(n: u32, ) -> Weight {
+		crate::estimated_weights::on_initialize::<RocksDbWeight>(n)
+	}
 }
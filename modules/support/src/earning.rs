@@ -0,0 +1,45 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use sp_runtime::DispatchError;
+use sp_std::result::Result;
+
+pub trait EarningManager<AccountId, Balance, BondingLedger> {
+	type Moment;
+	type FeeRatio;
+	/// Bond native tokens, returns the actual bonded amount.
+	fn bond(who: AccountId, amount: Balance) -> Result<Balance, DispatchError>;
+	/// Start unbonding native tokens, returns the actual unbonding amount.
+	fn unbond(who: AccountId, amount: Balance) -> Result<Balance, DispatchError>;
+	/// Unbond native tokens instantly by paying a fee, returns the unbonded amount after fee.
+	fn unbond_instant(who: AccountId, amount: Balance) -> Result<Balance, DispatchError>;
+	/// Rebond native tokens from unbonding, returns the actual rebonded amount.
+	fn rebond(who: AccountId, amount: Balance) -> Result<Balance, DispatchError>;
+	/// Withdraw all unlocked tokens, returns the withdrawn amount.
+	fn withdraw_unbonded(who: AccountId) -> Result<Balance, DispatchError>;
+	/// Gets the bonding ledger of `who`.
+	fn get_bonding_ledger(who: AccountId) -> BondingLedger;
+	/// Gets the minimum bond amount.
+	fn get_min_bond() -> Balance;
+	/// Gets the unbonding period.
+	fn get_unbonding_period() -> Self::Moment;
+	/// Gets the maximum unbonding chunks.
+	fn get_max_unbonding_chunks() -> u32;
+	/// Gets the fee ratio of instant unbonding.
+	fn get_instant_unstake_fee() -> Self::FeeRatio;
+}
@@ -16,13 +16,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use abi::{AbiCodec, Function};
 use codec::{Decode, Encode};
-use primitives::currency::AssetIds;
+use limits::Limit;
+use primitives::currency::{AssetIds, Erc20Behaviour};
 use primitives::{
-	evm::{CallInfo, EvmAddress},
+	evm::{CallInfo, ContractVerification, EvmAddress, Log},
 	Balance, CurrencyId,
 };
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize},
 	DispatchError, DispatchResult, RuntimeDebug,
@@ -32,6 +34,8 @@ use sp_std::{
 	prelude::*,
 };
 
+pub mod abi;
+
 /// Return true if the call of EVM precompile contract is allowed.
 pub trait PrecompileCallerFilter {
 	fn is_allowed(caller: H160) -> bool;
@@ -74,6 +78,17 @@ pub struct InvokeContext {
 	pub origin: EvmAddress,
 }
 
+/// Deposit EVM logs for state changes made outside of the EVM, so they are surfaced in the
+/// block's receipts.
+pub trait EVMLogs {
+	/// Deposit the `logs` of a state change made by `from` in the current extrinsic.
+	fn deposit_logs(from: EvmAddress, logs: Vec<Log>);
+}
+
+impl EVMLogs for () {
+	fn deposit_logs(_from: EvmAddress, _logs: Vec<Log>) {}
+}
+
 /// An abstraction of EVMBridge
 pub trait EVMBridge<AccountId, Balance> {
 	/// Execute ERC20.name() to read token name from ERC20 contract
@@ -89,6 +104,17 @@ pub trait EVMBridge<AccountId, Balance> {
 	fn balance_of(context: InvokeContext, address: EvmAddress) -> Result<Balance, DispatchError>;
 	/// Execute ERC20.transfer(address, uint256) to transfer value to `to`
 	fn transfer(context: InvokeContext, to: EvmAddress, value: Balance) -> DispatchResult;
+	/// Execute EIP1271.isValidSignature(bytes32, bytes) to check if `signature` of `hash` is
+	/// valid for the contract
+	fn is_valid_signature(context: InvokeContext, hash: H256, signature: Vec<u8>) -> Result<bool, DispatchError>;
+	/// Execute the contract `function` with the ABI encoded `args` and decode the return values
+	fn call<Args: AbiCodec, Ret: AbiCodec>(
+		context: InvokeContext,
+		function: Function<Args, Ret>,
+		args: Args,
+		limit: &Limit,
+		mode: ExecutionMode,
+	) -> Result<Ret, DispatchError>;
 	/// Get the real origin account and charge storage rent from the origin.
 	fn get_origin() -> Option<AccountId>;
 	/// Provide a method to set origin for `on_initialize`
@@ -115,6 +141,18 @@ impl<AccountId, Balance: Default> EVMBridge<AccountId, Balance> for () {
 	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
 		Err(DispatchError::Other("unimplemented evm bridge"))
 	}
+	fn is_valid_signature(_context: InvokeContext, _hash: H256, _signature: Vec<u8>) -> Result<bool, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn call<Args: AbiCodec, Ret: AbiCodec>(
+		_context: InvokeContext,
+		_function: Function<Args, Ret>,
+		_args: Args,
+		_limit: &Limit,
+		_mode: ExecutionMode,
+	) -> Result<Ret, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
 	fn get_origin() -> Option<AccountId> {
 		None
 	}
@@ -159,6 +197,8 @@ pub trait EVMManager<AccountId, Balance> {
 	fn query_storage_deposit_per_byte() -> Balance;
 	/// Query the maintainer address from the ERC20 contract.
 	fn query_maintainer(contract: H160) -> Result<H160, DispatchError>;
+	/// Query the source verification of the contract.
+	fn query_verification(contract: H160) -> Result<ContractVerification, DispatchError>;
 	/// Query the constants `DeveloperDeposit` value from evm module.
 	fn query_developer_deposit() -> Balance;
 	/// Query the constants `PublicationFee` value from evm module.
@@ -239,6 +279,17 @@ pub trait Erc20InfoMapping {
 	/// If is CurrencyId::DexShare and contain DexShare::Erc20,
 	/// will use the u32 to get the DexShare::Erc20 from the mapping.
 	fn decode_evm_address(v: EvmAddress) -> Option<CurrencyId>;
+	/// Returns the transfer behaviour of a registered ERC20 token.
+	fn erc20_behaviour(contract: EvmAddress) -> Option<Erc20Behaviour>;
+}
+
+/// Returns false for the ERC20 tokens which are unregistered or not `Erc20Behaviour::Standard`,
+/// their transfers break the accounting of the DEX and the CDPs.
+pub fn is_supported_currency<Mapping: Erc20InfoMapping>(currency_id: CurrencyId) -> bool {
+	match currency_id {
+		CurrencyId::Erc20(contract) => Mapping::erc20_behaviour(contract) == Some(Erc20Behaviour::Standard),
+		_ => true,
+	}
 }
 
 #[cfg(feature = "std")]
@@ -262,6 +313,10 @@ impl Erc20InfoMapping for () {
 	fn decode_evm_address(_v: EvmAddress) -> Option<CurrencyId> {
 		None
 	}
+
+	fn erc20_behaviour(_contract: EvmAddress) -> Option<Erc20Behaviour> {
+		None
+	}
 }
 
 pub mod limits {
@@ -285,6 +340,22 @@ pub mod limits {
 		pub const TOTAL_SUPPLY: Limit = Limit::new(100_000, 0);
 		pub const BALANCE_OF: Limit = Limit::new(100_000, 0);
 		pub const TRANSFER: Limit = Limit::new(200_000, 960);
+
+		/// The gas of a transfer checked by the balances of the sender and the receiver before and
+		/// after it, as done by the currencies module.
+		pub const CHECKED_TRANSFER_GAS: u64 = TRANSFER.gas + 4 * BALANCE_OF.gas;
+	}
+
+	pub mod erc1271 {
+		use super::*;
+
+		pub const IS_VALID_SIGNATURE: Limit = Limit::new(200_000, 0);
+	}
+
+	pub mod erc721 {
+		use super::*;
+
+		pub const ON_ERC721_RECEIVED: Limit = Limit::new(200_000, 960);
 	}
 
 	pub mod liquidation {
@@ -294,4 +365,10 @@ pub mod limits {
 		pub const ON_COLLATERAL_TRANSFER: Limit = Limit::new(200_000, 1_000);
 		pub const ON_REPAYMENT_REFUND: Limit = Limit::new(200_000, 1_000);
 	}
+
+	pub mod paymaster {
+		use super::*;
+
+		pub const VALIDATE_SPONSORSHIP: Limit = Limit::new(200_000, 0);
+	}
 }
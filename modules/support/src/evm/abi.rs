@@ -0,0 +1,675 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Typed Solidity ABI encoding and decoding.
+//!
+//! The params and the return values of a function are encoded as a tuple, `Function` binds the
+//! selector of a function to the Rust types of them. Use `AbiBytes` and `AbiString` for the
+//! `bytes` and `string` types, `Vec<T>` is `T[]`, and `abi_struct!` implements the codec of a
+//! struct as the tuple of its fields.
+
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[doc(hidden)]
+pub use sp_std::vec::Vec as AbiVec;
+
+/// The size of an ABI word.
+pub const WORD_SIZE: usize = 32;
+
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum AbiError {
+	/// The data is shorter than the encoding, or than the decoded values
+	OutOfBounds,
+	/// The value is invalid for its type
+	InvalidValue,
+	/// The function selector doesn't match
+	InvalidSelector,
+}
+
+/// A type with Solidity ABI encoding.
+pub trait AbiCodec: Sized {
+	/// Whether the value is encoded in the tail of a tuple, referenced by an offset in the head.
+	const IS_DYNAMIC: bool;
+	/// The size of the value in the head of a tuple.
+	const HEAD_SIZE: usize;
+
+	/// Appends the canonical type name, e.g. `uint256` or `(address,bytes)`.
+	fn type_name(out: &mut Vec<u8>);
+	/// Appends the encoding of the value.
+	fn encode_to(&self, out: &mut Vec<u8>);
+	/// Decodes the value encoded at `offset` of the data of `decoder`.
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError>;
+}
+
+/// Encodes the params or the return values, `T` is usually a tuple.
+pub fn encode<T: AbiCodec>(value: &T) -> Vec<u8> {
+	let mut out = Vec::with_capacity(T::HEAD_SIZE);
+	value.encode_to(&mut out);
+	out
+}
+
+/// Decodes the params or the return values, `T` is usually a tuple.
+pub fn decode<T: AbiCodec>(data: &[u8]) -> Result<T, AbiError> {
+	T::decode_at(&mut Decoder::new(data), 0)
+}
+
+/// Reads the words and the bytes of the encoded data.
+///
+/// The offsets of the dynamic values can reference the same data more than once, so the total
+/// size read is limited to the size of the data, which is the size read from a canonical
+/// encoding. Otherwise a small input could be decoded into an arbitrarily large value.
+pub struct Decoder<'a> {
+	data: &'a [u8],
+	remaining: usize,
+}
+
+impl<'a> Decoder<'a> {
+	pub fn new(data: &'a [u8]) -> Self {
+		Self {
+			data,
+			remaining: data.len(),
+		}
+	}
+
+	/// The size of the encoded data.
+	pub fn data_len(&self) -> usize {
+		self.data.len()
+	}
+
+	/// Reads `len` bytes at `offset`.
+	pub fn bytes_at(&mut self, offset: usize, len: usize) -> Result<&'a [u8], AbiError> {
+		let end = offset.checked_add(len).ok_or(AbiError::OutOfBounds)?;
+		let bytes = self.data.get(offset..end).ok_or(AbiError::OutOfBounds)?;
+		self.remaining = self.remaining.checked_sub(len).ok_or(AbiError::OutOfBounds)?;
+		Ok(bytes)
+	}
+
+	/// Reads the word at `offset`.
+	pub fn word_at(&mut self, offset: usize) -> Result<&'a [u8], AbiError> {
+		self.bytes_at(offset, WORD_SIZE)
+	}
+
+	/// Reads the word at `offset` as an offset or a length.
+	pub fn usize_at(&mut self, offset: usize) -> Result<usize, AbiError> {
+		let value = U256::from_big_endian(self.word_at(offset)?);
+		// offsets and lengths are bounded by the data length
+		let value: u32 = value.try_into().map_err(|_| AbiError::OutOfBounds)?;
+		Ok(value as usize)
+	}
+}
+
+fn encode_word(value: U256, out: &mut Vec<u8>) {
+	let mut word = [0u8; WORD_SIZE];
+	value.to_big_endian(&mut word);
+	out.extend_from_slice(&word);
+}
+
+/// Appends `value` to the head, or its offset to the head and itself to the tail if it's
+/// dynamic. `head_size` is the size of the head of the tuple.
+fn encode_element<T: AbiCodec>(value: &T, head_size: usize, head: &mut Vec<u8>, tail: &mut Vec<u8>) {
+	if T::IS_DYNAMIC {
+		encode_word(U256::from(head_size + tail.len()), head);
+		value.encode_to(tail);
+	} else {
+		value.encode_to(head);
+	}
+}
+
+/// Decodes the element at `position` of the head of the tuple at `base`, and moves `position`
+/// to the next element.
+fn decode_element<T: AbiCodec>(decoder: &mut Decoder<'_>, base: usize, position: &mut usize) -> Result<T, AbiError> {
+	let value = if T::IS_DYNAMIC {
+		let offset = decoder.usize_at(*position)?;
+		T::decode_at(decoder, base.checked_add(offset).ok_or(AbiError::OutOfBounds)?)?
+	} else {
+		T::decode_at(decoder, *position)?
+	};
+	*position = position.checked_add(T::HEAD_SIZE).ok_or(AbiError::OutOfBounds)?;
+	Ok(value)
+}
+
+impl AbiCodec for bool {
+	const IS_DYNAMIC: bool = false;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"bool");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		encode_word(U256::from(*self as u8), out);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		match U256::from_big_endian(decoder.word_at(offset)?) {
+			value if value.is_zero() => Ok(false),
+			value if value == U256::one() => Ok(true),
+			_ => Err(AbiError::InvalidValue),
+		}
+	}
+}
+
+macro_rules! impl_abi_codec_for_uint {
+	($($type:ty => $name:literal),*) => {
+		$(
+			impl AbiCodec for $type {
+				const IS_DYNAMIC: bool = false;
+				const HEAD_SIZE: usize = WORD_SIZE;
+
+				fn type_name(out: &mut Vec<u8>) {
+					out.extend_from_slice($name);
+				}
+
+				fn encode_to(&self, out: &mut Vec<u8>) {
+					encode_word(U256::from(*self), out);
+				}
+
+				fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+					U256::from_big_endian(decoder.word_at(offset)?)
+						.try_into()
+						.map_err(|_| AbiError::InvalidValue)
+				}
+			}
+		)*
+	};
+}
+
+impl_abi_codec_for_uint!(
+	u8 => b"uint8",
+	u16 => b"uint16",
+	u32 => b"uint32",
+	u64 => b"uint64",
+	u128 => b"uint128"
+);
+
+impl AbiCodec for U256 {
+	const IS_DYNAMIC: bool = false;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"uint256");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		encode_word(*self, out);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		Ok(U256::from_big_endian(decoder.word_at(offset)?))
+	}
+}
+
+impl AbiCodec for H160 {
+	const IS_DYNAMIC: bool = false;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"address");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		out.extend_from_slice(H256::from(*self).as_bytes());
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		let word = decoder.word_at(offset)?;
+		if word[..12].iter().any(|b| *b != 0) {
+			return Err(AbiError::InvalidValue);
+		}
+		Ok(H160::from_slice(&word[12..]))
+	}
+}
+
+impl AbiCodec for H256 {
+	const IS_DYNAMIC: bool = false;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"bytes32");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		out.extend_from_slice(self.as_bytes());
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		Ok(H256::from_slice(decoder.word_at(offset)?))
+	}
+}
+
+fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+	encode_word(U256::from(bytes.len()), out);
+	out.extend_from_slice(bytes);
+	// pad to words
+	let padding = (WORD_SIZE - bytes.len() % WORD_SIZE) % WORD_SIZE;
+	out.resize(out.len() + padding, 0);
+}
+
+fn decode_bytes(decoder: &mut Decoder<'_>, offset: usize) -> Result<Vec<u8>, AbiError> {
+	let len = decoder.usize_at(offset)?;
+	let start = offset.checked_add(WORD_SIZE).ok_or(AbiError::OutOfBounds)?;
+	decoder.bytes_at(start, len).map(|bytes| bytes.to_vec())
+}
+
+/// The Solidity `bytes` type.
+#[derive(Clone, Default, Eq, PartialEq, RuntimeDebug)]
+pub struct AbiBytes(pub Vec<u8>);
+
+impl AbiCodec for AbiBytes {
+	const IS_DYNAMIC: bool = true;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"bytes");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		encode_bytes(&self.0, out);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		decode_bytes(decoder, offset).map(AbiBytes)
+	}
+}
+
+/// The Solidity `string` type, the content is not checked to be UTF-8.
+#[derive(Clone, Default, Eq, PartialEq, RuntimeDebug)]
+pub struct AbiString(pub Vec<u8>);
+
+impl AbiCodec for AbiString {
+	const IS_DYNAMIC: bool = true;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		out.extend_from_slice(b"string");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		encode_bytes(&self.0, out);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		decode_bytes(decoder, offset).map(AbiString)
+	}
+}
+
+impl<T: AbiCodec> AbiCodec for Vec<T> {
+	const IS_DYNAMIC: bool = true;
+	const HEAD_SIZE: usize = WORD_SIZE;
+
+	fn type_name(out: &mut Vec<u8>) {
+		T::type_name(out);
+		out.extend_from_slice(b"[]");
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		encode_word(U256::from(self.len()), out);
+		let head_size = self.len() * T::HEAD_SIZE;
+		let mut head = Vec::with_capacity(head_size);
+		let mut tail = Vec::new();
+		for element in self {
+			encode_element(element, head_size, &mut head, &mut tail);
+		}
+		out.extend(head);
+		out.extend(tail);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		let len = decoder.usize_at(offset)?;
+		let base = offset.checked_add(WORD_SIZE).ok_or(AbiError::OutOfBounds)?;
+		// reject the lengths which can't fit in the data before allocating
+		let head_size = len.checked_mul(T::HEAD_SIZE.max(1)).ok_or(AbiError::OutOfBounds)?;
+		if base.saturating_add(head_size) > decoder.data_len() {
+			return Err(AbiError::OutOfBounds);
+		}
+
+		let mut position = base;
+		let mut values = Vec::with_capacity(len);
+		for _ in 0..len {
+			values.push(decode_element(decoder, base, &mut position)?);
+		}
+		Ok(values)
+	}
+}
+
+impl<T: AbiCodec, const N: usize> AbiCodec for [T; N] {
+	const IS_DYNAMIC: bool = T::IS_DYNAMIC;
+	const HEAD_SIZE: usize = if T::IS_DYNAMIC { WORD_SIZE } else { N * T::HEAD_SIZE };
+
+	fn type_name(out: &mut Vec<u8>) {
+		T::type_name(out);
+		out.push(b'[');
+		let mut n = N;
+		let mut digits = Vec::new();
+		loop {
+			digits.push(b'0' + (n % 10) as u8);
+			n /= 10;
+			if n == 0 {
+				break;
+			}
+		}
+		out.extend(digits.into_iter().rev());
+		out.push(b']');
+	}
+
+	fn encode_to(&self, out: &mut Vec<u8>) {
+		let head_size = N * T::HEAD_SIZE;
+		let mut head = Vec::with_capacity(head_size);
+		let mut tail = Vec::new();
+		for element in self {
+			encode_element(element, head_size, &mut head, &mut tail);
+		}
+		out.extend(head);
+		out.extend(tail);
+	}
+
+	fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+		let mut position = offset;
+		let mut values = Vec::with_capacity(N);
+		for _ in 0..N {
+			values.push(decode_element(decoder, offset, &mut position)?);
+		}
+		values.try_into().map_err(|_| AbiError::InvalidValue)
+	}
+}
+
+macro_rules! impl_abi_codec_for_tuple {
+	($($name:ident),*) => {
+		impl<$($name: AbiCodec),*> AbiCodec for ($($name,)*) {
+			const IS_DYNAMIC: bool = false $(|| $name::IS_DYNAMIC)*;
+			const HEAD_SIZE: usize = if Self::IS_DYNAMIC { WORD_SIZE } else { 0 $(+ $name::HEAD_SIZE)* };
+
+			#[allow(unused_assignments, unused_mut)]
+			fn type_name(out: &mut Vec<u8>) {
+				out.push(b'(');
+				let mut first = true;
+				$(
+					if !first {
+						out.push(b',');
+					}
+					first = false;
+					$name::type_name(out);
+				)*
+				out.push(b')');
+			}
+
+			#[allow(non_snake_case, unused_variables, unused_mut)]
+			fn encode_to(&self, out: &mut Vec<u8>) {
+				let ($($name,)*) = self;
+				let head_size = 0 $(+ $name::HEAD_SIZE)*;
+				let mut head = Vec::with_capacity(head_size);
+				let mut tail = Vec::new();
+				$(encode_element($name, head_size, &mut head, &mut tail);)*
+				out.extend(head);
+				out.extend(tail);
+			}
+
+			#[allow(unused_variables, unused_mut)]
+			fn decode_at(decoder: &mut Decoder<'_>, offset: usize) -> Result<Self, AbiError> {
+				let mut position = offset;
+				Ok(($(decode_element::<$name>(decoder, offset, &mut position)?,)*))
+			}
+		}
+	};
+}
+
+impl_abi_codec_for_tuple!();
+impl_abi_codec_for_tuple!(A);
+impl_abi_codec_for_tuple!(A, B);
+impl_abi_codec_for_tuple!(A, B, C);
+impl_abi_codec_for_tuple!(A, B, C, D);
+impl_abi_codec_for_tuple!(A, B, C, D, E);
+impl_abi_codec_for_tuple!(A, B, C, D, E, F);
+impl_abi_codec_for_tuple!(A, B, C, D, E, F, G);
+impl_abi_codec_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// Implements `AbiCodec` for a struct as the tuple of its fields.
+///
+/// ```ignore
+/// struct Operation {
+/// 	to: H160,
+/// 	value: U256,
+/// 	input: AbiBytes,
+/// }
+/// abi_struct!(Operation { to: H160, value: U256, input: AbiBytes });
+/// ```
+#[macro_export]
+macro_rules! abi_struct {
+	($name:ident { $($field:ident: $type:ty),* $(,)? }) => {
+		impl $crate::evm::abi::AbiCodec for $name {
+			const IS_DYNAMIC: bool = <($($type,)*) as $crate::evm::abi::AbiCodec>::IS_DYNAMIC;
+			const HEAD_SIZE: usize = <($($type,)*) as $crate::evm::abi::AbiCodec>::HEAD_SIZE;
+
+			fn type_name(out: &mut $crate::evm::abi::AbiVec<u8>) {
+				<($($type,)*) as $crate::evm::abi::AbiCodec>::type_name(out)
+			}
+
+			fn encode_to(&self, out: &mut $crate::evm::abi::AbiVec<u8>) {
+				$crate::evm::abi::AbiCodec::encode_to(&($(self.$field.clone(),)*), out)
+			}
+
+			fn decode_at(
+				decoder: &mut $crate::evm::abi::Decoder<'_>,
+				offset: usize,
+			) -> Result<Self, $crate::evm::abi::AbiError> {
+				let ($($field,)*) = <($($type,)*) as $crate::evm::abi::AbiCodec>::decode_at(decoder, offset)?;
+				Ok(Self { $($field),* })
+			}
+		}
+	};
+}
+
+/// A contract function with the params `Args` and the return values `Ret`, both are tuples.
+pub struct Function<Args, Ret> {
+	selector: [u8; 4],
+	_marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args, Ret> Clone for Function<Args, Ret> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<Args, Ret> Copy for Function<Args, Ret> {}
+
+impl<Args, Ret> Function<Args, Ret> {
+	/// `hash` is the keccak256 hash of the function signature, e.g.
+	/// `keccak256!("transfer(address,uint256)")`.
+	pub const fn new(hash: &[u8; 32]) -> Self {
+		Self {
+			selector: [hash[0], hash[1], hash[2], hash[3]],
+			_marker: PhantomData,
+		}
+	}
+
+	pub const fn selector(&self) -> [u8; 4] {
+		self.selector
+	}
+}
+
+impl<Args: AbiCodec, Ret: AbiCodec> Function<Args, Ret> {
+	/// The signature of the function `name` with the params `Args`.
+	pub fn signature(name: &str) -> Vec<u8> {
+		let mut signature = name.as_bytes().to_vec();
+		Args::type_name(&mut signature);
+		signature
+	}
+
+	/// Whether the selector is the one of the function `name` with the params `Args`.
+	pub fn matches(&self, name: &str) -> bool {
+		keccak_256(&Self::signature(name))[..4] == self.selector
+	}
+
+	pub fn encode_call(&self, args: &Args) -> Vec<u8> {
+		let mut input = self.selector.to_vec();
+		args.encode_to(&mut input);
+		input
+	}
+
+	pub fn decode_call(&self, input: &[u8]) -> Result<Args, AbiError> {
+		if input.get(..4) != Some(&self.selector[..]) {
+			return Err(AbiError::InvalidSelector);
+		}
+		decode(&input[4..])
+	}
+
+	pub fn encode_output(&self, output: &Ret) -> Vec<u8> {
+		encode(output)
+	}
+
+	pub fn decode_output(&self, output: &[u8]) -> Result<Ret, AbiError> {
+		decode(output)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::bytes::from_hex;
+
+	#[derive(Clone, Debug, PartialEq)]
+	struct Operation {
+		to: H160,
+		value: U256,
+		input: AbiBytes,
+	}
+	abi_struct!(Operation {
+		to: H160,
+		value: U256,
+		input: AbiBytes
+	});
+
+	#[test]
+	fn encode_decode_static_works() {
+		let value = (
+			true,
+			1u8,
+			H160::repeat_byte(0x11),
+			H256::repeat_byte(0x22),
+			[3u64, 4u64],
+		);
+		let encoded = encode(&value);
+		assert_eq!(encoded.len(), 6 * WORD_SIZE);
+		assert_eq!(encoded[WORD_SIZE - 1], 1);
+		assert_eq!(
+			&encoded[2 * WORD_SIZE + 12..3 * WORD_SIZE],
+			H160::repeat_byte(0x11).as_bytes()
+		);
+		assert_eq!(decode(&encoded), Ok(value));
+
+		// dirty address
+		let mut dirty = encode(&(H160::repeat_byte(0x11),));
+		dirty[0] = 1;
+		assert_eq!(decode::<(H160,)>(&dirty), Err(AbiError::InvalidValue));
+		// overflow
+		assert_eq!(decode::<(u8,)>(&encode(&(256u32,))), Err(AbiError::InvalidValue));
+		assert_eq!(decode::<(bool,)>(&encode(&(2u8,))), Err(AbiError::InvalidValue));
+		assert_eq!(decode::<(U256, U256)>(&encode(&(1u8,))), Err(AbiError::OutOfBounds));
+	}
+
+	#[test]
+	fn encode_decode_dynamic_works() {
+		// abi.encode(uint256(1), "hello", [2, 3])
+		let value = (U256::one(), AbiString(b"hello".to_vec()), vec![2u32, 3u32]);
+		let encoded = encode(&value);
+		assert_eq!(
+			encoded,
+			from_hex(
+				"0000000000000000000000000000000000000000000000000000000000000001\
+				 0000000000000000000000000000000000000000000000000000000000000060\
+				 00000000000000000000000000000000000000000000000000000000000000a0\
+				 0000000000000000000000000000000000000000000000000000000000000005\
+				 68656c6c6f000000000000000000000000000000000000000000000000000000\
+				 0000000000000000000000000000000000000000000000000000000000000002\
+				 0000000000000000000000000000000000000000000000000000000000000002\
+				 0000000000000000000000000000000000000000000000000000000000000003"
+			)
+			.unwrap()
+		);
+		assert_eq!(decode(&encoded), Ok(value));
+
+		// the length of bytes exceeds the data
+		let mut invalid = encode(&(AbiBytes(vec![1u8; 32]),));
+		invalid[2 * WORD_SIZE - 1] = 33;
+		assert_eq!(decode::<(AbiBytes,)>(&invalid), Err(AbiError::OutOfBounds));
+	}
+
+	#[test]
+	fn decode_rejects_repeated_offsets() {
+		// a bytes[] whose elements all reference the same 1024 bytes
+		let count = 64usize;
+		let mut data = encode(&(WORD_SIZE as u32, count as u32));
+		for _ in 0..count {
+			data.extend(encode(&((count * WORD_SIZE) as u32,)));
+		}
+		data.extend(encode(&(AbiBytes(vec![1u8; 1024]),))[WORD_SIZE..].to_vec());
+		assert_eq!(decode::<(Vec<AbiBytes>,)>(&data), Err(AbiError::OutOfBounds));
+
+		// the canonical encoding of the same value is still decoded
+		let value = (vec![AbiBytes(vec![1u8; 1024]); 2],);
+		assert_eq!(decode(&encode(&value)), Ok(value));
+	}
+
+	#[test]
+	fn encode_decode_struct_works() {
+		let operations = vec![
+			Operation {
+				to: H160::repeat_byte(1),
+				value: 2.into(),
+				input: AbiBytes(vec![3u8; 40]),
+			},
+			Operation {
+				to: H160::zero(),
+				value: 0.into(),
+				input: AbiBytes::default(),
+			},
+		];
+		let value = (H160::repeat_byte(4), operations, 5u32);
+		assert_eq!(decode(&encode(&value)), Ok(value));
+
+		let mut name = Vec::new();
+		<(H160, Vec<Operation>, u32)>::type_name(&mut name);
+		assert_eq!(name, b"(address,(address,uint256,bytes)[],uint32)".to_vec());
+	}
+
+	#[test]
+	fn function_works() {
+		let transfer: Function<(H160, U256), (bool,)> = Function::new(&keccak_256(b"transfer(address,uint256)"));
+		assert_eq!(transfer.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+		assert!(transfer.matches("transfer"));
+		assert!(!transfer.matches("transferFrom"));
+
+		let input = transfer.encode_call(&(H160::repeat_byte(1), 2.into()));
+		assert_eq!(input.len(), 4 + 2 * WORD_SIZE);
+		assert_eq!(transfer.decode_call(&input), Ok((H160::repeat_byte(1), 2.into())));
+		assert_eq!(transfer.decode_call(&input[1..]), Err(AbiError::InvalidSelector));
+		assert_eq!(transfer.decode_output(&transfer.encode_output(&(true,))), Ok((true,)));
+
+		let names: Function<([u32; 2], [AbiString; 2]), ()> = Function::new(&[0u8; 32]);
+		assert_eq!(
+			Function::<([u32; 2], [AbiString; 2]), ()>::signature("names"),
+			b"names(uint32[2],string[2])".to_vec()
+		);
+		let args = ([1, 2], [AbiString(b"a".to_vec()), AbiString(b"b".to_vec())]);
+		assert_eq!(names.decode_call(&names.encode_call(&args)), Ok(args));
+	}
+}
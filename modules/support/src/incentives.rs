@@ -48,6 +48,13 @@ pub trait IncentivesManager<AccountId, Balance, CurrencyId, PoolId> {
 	fn get_claim_reward_deduction_rate(pool_id: PoolId) -> Rate;
 	/// Gets the pending rewards for a pool, for an account
 	fn get_pending_rewards(pool_id: PoolId, who: AccountId, reward_currency: Vec<CurrencyId>) -> Vec<Balance>;
+	/// Transfer `amount` of reward currency from `from` and distribute it to the shares of the pool
+	fn accumulate_rewards(
+		from: &AccountId,
+		pool_id: PoolId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult;
 }
 
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
@@ -21,7 +21,7 @@
 #![allow(clippy::from_over_into)]
 #![allow(clippy::type_complexity)]
 
-use codec::FullCodec;
+use codec::{Decode, FullCodec};
 use frame_support::pallet_prelude::{DispatchClass, Pays, Weight};
 use primitives::{task::TaskResult, Balance, CurrencyId, Multiplier, ReserveIdentifier};
 use sp_runtime::{
@@ -31,6 +31,7 @@ use sp_std::{prelude::*, result::Result};
 use xcm::latest::prelude::*;
 
 pub mod dex;
+pub mod earning;
 pub mod evm;
 pub mod homa;
 pub mod honzon;
@@ -39,6 +40,7 @@ pub mod mocks;
 pub mod stable_asset;
 
 pub use crate::dex::*;
+pub use crate::earning::*;
 pub use crate::evm::*;
 pub use crate::homa::*;
 pub use crate::honzon::*;
@@ -94,6 +96,18 @@ pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 	fn apply_multiplier_to_fee(fee: Balance, multiplier: Option<Multiplier>) -> Balance;
 }
 
+/// Find the account paying the transaction fee of the call instead of the signer.
+pub trait FeePayer<AccountId, Call> {
+	/// The sponsor paying the fee of the `call` signed by `who`, or `None` if the signer pays.
+	fn fee_payer(who: &AccountId, call: &Call) -> Option<AccountId>;
+}
+
+impl<AccountId, Call> FeePayer<AccountId, Call> for () {
+	fn fee_payer(_who: &AccountId, _call: &Call) -> Option<AccountId> {
+		None
+	}
+}
+
 /// Used to interface with the Compound's Cash module
 pub trait CompoundCashTrait<Balance, Moment> {
 	fn set_future_yield(next_cash_yield: Balance, yield_index: u128, timestamp_effective: Moment) -> DispatchResult;
@@ -178,6 +192,18 @@ pub trait NomineesProvider<AccountId> {
 	fn nominees() -> Vec<AccountId>;
 }
 
+/// Read the relay chain storage, verified by a state proof against the relay parent storage root.
+pub trait RelaychainStateProofReader {
+	/// Read and decode the value of `key` from the relay chain state `proof`.
+	/// Returns `None` if the proof is invalid or the value doesn't exist.
+	fn read_value<T: Decode>(key: &[u8], proof: Vec<Vec<u8>>) -> Option<T>;
+}
+
+pub trait WeightedNomineesProvider<AccountId, Balance>: NomineesProvider<AccountId> {
+	/// The elected nominees with their current weighted votes.
+	fn weighted_nominees() -> Vec<(AccountId, Balance)>;
+}
+
 pub trait LiquidateCollateral<AccountId> {
 	fn liquidate(
 		who: &AccountId,
@@ -24,7 +24,7 @@ use nutsfinance_stable_asset::{
 	traits::StableAsset, PoolTokenIndex, RedeemProportionResult, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
 };
 use primitives::{
-	currency::TokenInfo,
+	currency::{Erc20Behaviour, TokenInfo},
 	evm::{EvmAddress, H160_POSITION_TOKEN},
 	Multiplier, ReserveIdentifier,
 };
@@ -100,6 +100,10 @@ impl Erc20InfoMapping for MockErc20InfoMapping {
 			.map(|addr| if addr == v { Some(token) } else { None })
 			.ok()?
 	}
+
+	fn erc20_behaviour(_contract: EvmAddress) -> Option<Erc20Behaviour> {
+		Some(Erc20Behaviour::Standard)
+	}
 }
 
 #[cfg(feature = "std")]
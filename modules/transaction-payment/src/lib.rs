@@ -56,7 +56,7 @@ use sp_runtime::{
 	FixedPointNumber, FixedPointOperand, MultiSignature, Percent, Perquintill,
 };
 use sp_std::prelude::*;
-use support::{AggregatedSwapPath, BuyWeightRate, PriceProvider, Ratio, Swap, SwapLimit, TransactionPayment};
+use support::{AggregatedSwapPath, BuyWeightRate, FeePayer, PriceProvider, Ratio, Swap, SwapLimit, TransactionPayment};
 use xcm::opaque::latest::MultiLocation;
 
 mod mock;
@@ -306,6 +306,9 @@ pub mod module {
 		/// Swap
 		type Swap: Swap<Self::AccountId, Balance, CurrencyId>;
 
+		/// The sponsor paying the fee of the call instead of the signer.
+		type FeePayer: FeePayer<Self::AccountId, <Self as Config>::Call>;
+
 		/// When swap with DEX, the acceptable max slippage for the price from oracle.
 		#[pallet::constant]
 		type MaxSwapSlippageCompareToOracle: Get<Ratio>;
@@ -804,6 +807,8 @@ where
 	///   pool, else swap with dex.
 	/// - TransactionPayment::with_fee_path: swap with specific trading path.
 	/// - others call: first use native asset, if not enough use alternative, or else use default.
+	///   The fee is paid by the sponsor of the call if `FeePayer` returns one, e.g. the escrow of
+	///   an EVM paymaster.
 	fn ensure_can_charge_fee_with_call(
 		who: &T::AccountId,
 		fee: PalletBalanceOf<T>,
@@ -885,7 +890,10 @@ where
 				Self::native_then_alternative_or_default(payer_addr, fee, WithdrawReasons::TRANSACTION_PAYMENT)
 					.map(|surplus| (payer_addr.clone(), surplus))
 			}
-			_ => Self::native_then_alternative_or_default(who, fee, reason).map(|surplus| (who.clone(), surplus)),
+			_ => {
+				let payer = T::FeePayer::fee_payer(who, call).unwrap_or_else(|| who.clone());
+				Self::native_then_alternative_or_default(&payer, fee, reason).map(|surplus| (payer, surplus))
+			}
 		}
 	}
 
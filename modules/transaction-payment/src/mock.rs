@@ -41,7 +41,7 @@ use sp_runtime::{
 use sp_std::cell::RefCell;
 use support::{
 	mocks::{MockAddressMapping, MockStableAsset},
-	Price, SpecificJointsSwap,
+	FeePayer, Price, SpecificJointsSwap,
 };
 
 pub type AccountId = AccountId32;
@@ -159,6 +159,7 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<Zero, AccountId>;
 	type OnDust = ();
+	type EVMLogs = ();
 }
 
 thread_local! {
@@ -189,6 +190,7 @@ impl module_dex::Config for Runtime {
 	type Erc20InfoMapping = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type GasToWeight = ();
 	type ListingOrigin = EnsureSignedBy<Zero, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
@@ -283,6 +285,7 @@ impl Config for Runtime {
 	type TransactionByteFee = TransactionByteFee;
 	type FeeMultiplierUpdate = ();
 	type Swap = SpecificJointsSwap<DEXModule, AlternativeSwapPathJointList>;
+	type FeePayer = MockFeePayer;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
 	type TradingPathLimit = TradingPathLimit;
 	type PriceSource = MockPriceSource;
@@ -295,6 +298,17 @@ impl Config for Runtime {
 	type DefaultFeeTokens = DefaultFeeTokens;
 }
 
+/// DAVE pays the fee of the remark `sponsored`.
+pub struct MockFeePayer;
+impl FeePayer<AccountId, Call> for MockFeePayer {
+	fn fee_payer(_who: &AccountId, call: &Call) -> Option<AccountId> {
+		match call {
+			Call::System(frame_system::Call::remark { remark }) if remark == b"sponsored" => Some(DAVE),
+			_ => None,
+		}
+	}
+}
+
 thread_local! {
 	static WEIGHT_TO_FEE: RefCell<u128> = RefCell::new(1);
 }
@@ -871,6 +871,41 @@ fn charges_fee_when_validate_with_fee_currency_call_use_pool() {
 	});
 }
 
+#[test]
+fn charges_fee_from_fee_payer() {
+	builder_with_dex_and_fee_pool(false).execute_with(|| {
+		let sponsored_call = Call::System(frame_system::Call::remark {
+			remark: b"sponsored".to_vec(),
+		});
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		assert_ok!(Currencies::update_balance(Origin::root(), DAVE, ACA, 10_000));
+		let alice_balance = Currencies::free_balance(ACA, &ALICE);
+
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, &sponsored_call, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &DAVE), 10_000 - fee);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), alice_balance);
+
+		// the unused weight is refunded to the fee payer
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		assert_eq!(Currencies::free_balance(ACA, &DAVE), 10_000 - fee + 200);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), alice_balance);
+
+		// the signer pays the other calls
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(&ALICE, &call, &INFO, 23));
+		assert_eq!(Currencies::free_balance(ACA, &DAVE), 10_000 - fee + 200);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), alice_balance - fee);
+	});
+}
+
 #[test]
 fn charges_fee_when_validate_with_fee_paid_by_native_token() {
 	// Enable dex with Alice, and initialize tx charge fee pool
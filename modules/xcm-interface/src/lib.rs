@@ -90,6 +90,8 @@ pub mod module {
 	pub enum Error<T> {
 		/// The xcm operation have failed
 		XcmFailed,
+		/// The sender is not authorized to send arbitrary XCM messages
+		XcmSenderNotAuthorized,
 	}
 
 	#[pallet::event]
@@ -105,6 +107,14 @@ pub mod module {
 			xcm_operation: XcmInterfaceOperation,
 			new_xcm_dest_weight: Balance,
 		},
+		/// The authorization of XCM sender has been updated.
+		XcmSenderAuthorizationUpdated { who: T::AccountId, authorized: bool },
+		/// A XCM message has been sent by an authorized sender.
+		XcmSent {
+			sender: T::AccountId,
+			dest: MultiLocation,
+			message: Xcm<()>,
+		},
 	}
 
 	/// The dest weight limit and fee for execution XCM msg sended by XcmInterface. Must be
@@ -116,6 +126,13 @@ pub mod module {
 	pub type XcmDestWeightAndFee<T: Config> =
 		StorageMap<_, Twox64Concat, XcmInterfaceOperation, (Weight, Balance), ValueQuery>;
 
+	/// The accounts approved by governance to send arbitrary XCM messages.
+	///
+	/// AuthorizedXcmSenders: map: AccountId => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn authorized_xcm_senders)]
+	pub type AuthorizedXcmSenders<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -158,9 +175,64 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Authorizes or deauthorizes accounts to send arbitrary XCM messages.
+		///
+		/// Parameters:
+		/// - `updates`: vec of tuple: (AccountId, authorized).
+		#[pallet::weight(10_000_000)]
+		#[transactional]
+		pub fn update_authorized_xcm_senders(
+			origin: OriginFor<T>,
+			updates: Vec<(T::AccountId, bool)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			for (who, authorized) in updates {
+				if authorized {
+					AuthorizedXcmSenders::<T>::insert(&who, ());
+				} else {
+					AuthorizedXcmSenders::<T>::remove(&who);
+				}
+				Self::deposit_event(Event::<T>::XcmSenderAuthorizationUpdated { who, authorized });
+			}
+
+			Ok(())
+		}
 	}
 
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: Into<[u8; 32]>,
+	{
+		/// Send XCM message on behalf of an authorized sender. The sender is appended to the
+		/// origin of the message, so it can not act as the parachain sovereign account.
+		pub fn send_xcm_as(sender: &T::AccountId, dest: MultiLocation, message: Xcm<()>) -> DispatchResult {
+			ensure!(
+				AuthorizedXcmSenders::<T>::contains_key(sender),
+				Error::<T>::XcmSenderNotAuthorized
+			);
+
+			let interior = Junction::AccountId32 {
+				network: NetworkId::Any,
+				id: sender.clone().into(),
+			};
+			let result = pallet_xcm::Pallet::<T>::send_xcm(interior, dest.clone(), message.clone());
+			log::debug!(
+				target: "xcm-interface",
+				"account {:?} send XCM to {:?}, result: {:?}",
+				sender, dest, result
+			);
+
+			ensure!(result.is_ok(), Error::<T>::XcmFailed);
+			Self::deposit_event(Event::<T>::XcmSent {
+				sender: sender.clone(),
+				dest,
+				message,
+			});
+			Ok(())
+		}
+	}
 
 	impl<T: Config> HomaSubAccountXcm<T::AccountId, Balance> for Pallet<T> {
 		/// Cross-chain transfer staking currency to sub account on relaychain.
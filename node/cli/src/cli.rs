@@ -20,9 +20,9 @@
 
 use clap::Parser;
 use sc_cli::{KeySubcommand, SignCmd, VanityCmd, VerifyCmd};
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use service::chain_spec;
+use service::{chain_spec, EvmRpcConfig, ExecutionPoolConfig};
 
 /// Possible subcommands of the main binary.
 #[derive(Debug, Parser)]
@@ -118,6 +118,62 @@ pub struct Cli {
 	#[cfg(feature = "with-mandala-runtime")]
 	#[clap(long = "mnemonic", requires = "dev")]
 	pub mnemonic: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub evm_rpc: EvmRpcParams,
+}
+
+/// Parameters of the read-only EVM executions of the RPC calls.
+#[derive(Debug, Clone, clap::Args)]
+pub struct EvmRpcParams {
+	/// Enable the Ethereum compatible `eth_*` RPC, and the index of the EVM transactions it
+	/// requires.
+	#[clap(long = "enable-eth-rpc")]
+	pub enable_eth_rpc: bool,
+
+	/// The max number of the concurrent EVM executions of the RPC calls.
+	#[clap(long = "evm-rpc-max-concurrency", default_value = "8")]
+	pub evm_rpc_max_concurrency: usize,
+
+	/// The timeout of an EVM execution of the RPC calls, in milliseconds.
+	#[clap(long = "evm-rpc-timeout", default_value = "10000")]
+	pub evm_rpc_timeout: u64,
+
+	/// The number of the recent blocks whose EVM RPC results and state are cached.
+	///
+	/// Setting it to 0 disables the cache.
+	#[clap(long = "evm-rpc-cache-blocks", default_value = "4")]
+	pub evm_rpc_cache_blocks: usize,
+
+	/// The max number of the cached EVM RPC results of a block.
+	#[clap(long = "evm-rpc-cache-size", default_value = "1024")]
+	pub evm_rpc_cache_size: usize,
+
+	/// The max size of the cached state of a block read by the EVM RPC executions, in MiB.
+	#[clap(long = "evm-rpc-state-cache-size", default_value = "64")]
+	pub evm_rpc_state_cache_size: usize,
+}
+
+impl EvmRpcParams {
+	/// The config of the EVM RPC execution pool.
+	pub fn execution_pool_config(&self) -> ExecutionPoolConfig {
+		ExecutionPoolConfig {
+			max_concurrency: self.evm_rpc_max_concurrency,
+			timeout: Duration::from_millis(self.evm_rpc_timeout),
+			cache_blocks: self.evm_rpc_cache_blocks,
+			cache_size: self.evm_rpc_cache_size,
+			state_cache_size: self.evm_rpc_state_cache_size.saturating_mul(1024 * 1024),
+		}
+	}
+
+	/// The config of the EVM RPC.
+	pub fn evm_rpc_config(&self) -> EvmRpcConfig {
+		EvmRpcConfig {
+			enable_eth_rpc: self.enable_eth_rpc,
+			execution_pool: self.execution_pool_config(),
+		}
+	}
 }
 
 /// Relay chain CLI.
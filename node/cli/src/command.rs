@@ -444,6 +444,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let chain_spec = &runner.config().chain_spec;
 			let is_dev = chain_spec.is_dev();
 			let collator_options = cli.run.collator_options();
+			let evm_rpc_config = cli.evm_rpc.evm_rpc_config();
 
 			set_default_ss58_version(chain_spec);
 
@@ -451,7 +452,7 @@ pub fn run() -> sc_cli::Result<()> {
 				if is_dev {
 					with_runtime_or_err!(config.chain_spec, {
 						{
-							return service::start_dev_node::<RuntimeApi>(config, cli.instant_sealing)
+							return service::start_dev_node::<RuntimeApi>(config, cli.instant_sealing, evm_rpc_config)
 								.map_err(Into::into);
 						}
 					})
@@ -484,10 +485,16 @@ pub fn run() -> sc_cli::Result<()> {
 
 				with_runtime_or_err!(config.chain_spec, {
 					{
-						service::start_node::<RuntimeApi>(config, polkadot_config, collator_options, id)
-							.await
-							.map(|r| r.0)
-							.map_err(Into::into)
+						service::start_node::<RuntimeApi>(
+							config,
+							polkadot_config,
+							collator_options,
+							id,
+							evm_rpc_config,
+						)
+						.await
+						.map(|r| r.0)
+						.map_err(Into::into)
 					}
 				})
 			})
@@ -0,0 +1,314 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The index of the EVM transactions, receipts and logs, served by the `eth_*` rpc.
+
+use acala_primitives::{evm::EvmReceipt, Balance, Block, BlockNumber, Hash};
+use codec::{Decode, Encode};
+use evm_rpc::{
+	eth::{fetch_block_receipts, logs_bloom, Bloom},
+	EVMRuntimeRPCApi, EthMapping,
+};
+use futures::StreamExt;
+use kvdb::KeyValueDB;
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_service::{error::Error as ServiceError, Configuration};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{H160, H256};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
+
+mod columns {
+	pub const NUM_COLUMNS: u32 = 4;
+
+	/// Transaction hash => `Vec<(block hash, extrinsic index)>`, the keccak256 hash of the signed
+	/// raw transaction for Ethereum transactions, the extrinsic hash otherwise
+	pub const TRANSACTIONS: u32 = 0;
+	/// Block hash => `Vec<(transaction hash, receipt)>`
+	pub const RECEIPTS: u32 = 1;
+	/// Block hash => bloom filter of the logs
+	pub const BLOOMS: u32 = 2;
+	/// `posting_key` => `()`
+	pub const LOG_POSTINGS: u32 = 3;
+}
+
+const ADDRESS_POSTING: u8 = 0;
+const TOPIC_POSTING: u8 = 1;
+
+/// `kind ++ address or topic`, the prefix of the postings of an address or a topic.
+fn posting_prefix(kind: u8, value: &[u8]) -> Vec<u8> {
+	let mut prefix = Vec::with_capacity(1 + value.len());
+	prefix.push(kind);
+	prefix.extend_from_slice(value);
+	prefix
+}
+
+/// `kind ++ address or topic ++ block number (big endian) ++ block hash`
+fn posting_key(kind: u8, value: &[u8], number: BlockNumber, block: &Hash) -> Vec<u8> {
+	let mut key = posting_prefix(kind, value);
+	key.extend_from_slice(&number.to_be_bytes());
+	key.extend_from_slice(block.as_bytes());
+	key
+}
+
+/// The path of the mapping db, in the config dir of the chain.
+pub fn db_path(config: &Configuration) -> Result<PathBuf, ServiceError> {
+	config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()).join("evm-mapping"))
+		.ok_or_else(|| ServiceError::Other("base path is required by the evm mapping db".into()))
+}
+
+pub struct MappingDb {
+	db: Arc<dyn KeyValueDB>,
+}
+
+impl MappingDb {
+	pub fn open(config: &Configuration) -> Result<Self, ServiceError> {
+		let path = db_path(config)?;
+		let db = kvdb_rocksdb::Database::open(
+			&kvdb_rocksdb::DatabaseConfig::with_columns(columns::NUM_COLUMNS),
+			path.to_str()
+				.ok_or_else(|| ServiceError::Other(format!("invalid evm mapping db path: {:?}", path)))?,
+		)
+		.map_err(|err| ServiceError::Other(format!("failed to open evm mapping db: {}", err)))?;
+
+		Ok(Self { db: Arc::new(db) })
+	}
+
+	fn is_indexed(&self, block: &Hash) -> Result<bool, String> {
+		self.db
+			.get(columns::RECEIPTS, block.as_bytes())
+			.map(|value| value.is_some())
+			.map_err(|err| err.to_string())
+	}
+
+	fn write_block(&self, block: &Hash, number: BlockNumber, receipts: Vec<(Hash, EvmReceipt)>) -> Result<(), String> {
+		let mut transaction = self.db.transaction();
+
+		// failed transactions have no logs
+		let logs = receipts
+			.iter()
+			.filter(|(_, receipt)| receipt.succeed)
+			.flat_map(|(_, receipt)| receipt.logs.iter());
+		for log in logs.clone() {
+			transaction.put_vec(
+				columns::LOG_POSTINGS,
+				&posting_key(ADDRESS_POSTING, log.address.as_bytes(), number, block),
+				vec![],
+			);
+			for topic in &log.topics {
+				transaction.put_vec(
+					columns::LOG_POSTINGS,
+					&posting_key(TOPIC_POSTING, topic.as_bytes(), number, block),
+					vec![],
+				);
+			}
+		}
+		transaction.put_vec(columns::BLOOMS, block.as_bytes(), logs_bloom(logs).as_bytes().to_vec());
+
+		for (transaction_hash, receipt) in &receipts {
+			let mut blocks = self.transaction_blocks(transaction_hash)?;
+			let entry = (*block, receipt.extrinsic_index);
+			if !blocks.contains(&entry) {
+				blocks.push(entry);
+			}
+			transaction.put_vec(columns::TRANSACTIONS, transaction_hash.as_bytes(), blocks.encode());
+		}
+		transaction.put_vec(columns::RECEIPTS, block.as_bytes(), receipts.encode());
+
+		self.db.write(transaction).map_err(|err| err.to_string())
+	}
+
+	/// The blocks in `from..=to` with a posting of the address or the topic.
+	///
+	/// The postings are ordered by the block number, so only the postings sharing the leading
+	/// bytes of `from` and `to` are read, from `from` and up to `to`.
+	fn posting_blocks(&self, kind: u8, value: &[u8], from: u32, to: u32) -> BTreeSet<Hash> {
+		let value_prefix_len = 1 + value.len();
+		let (from_bytes, to_bytes) = (from.to_be_bytes(), to.to_be_bytes());
+		let common_len = from_bytes
+			.iter()
+			.zip(to_bytes.iter())
+			.take_while(|(a, b)| a == b)
+			.count();
+		let mut prefix = posting_prefix(kind, value);
+		prefix.extend_from_slice(&from_bytes[..common_len]);
+
+		self.db
+			.iter_with_prefix(columns::LOG_POSTINGS, &prefix)
+			.filter_map(|(key, _)| {
+				let rest = key[..].get(value_prefix_len..)?;
+				let number = BlockNumber::from_be_bytes(rest.get(..4)?.try_into().ok()?);
+				Some((number, Hash::from_slice(rest.get(4..)?)))
+			})
+			.skip_while(|(number, _)| *number < from)
+			.take_while(|(number, _)| *number <= to)
+			.map(|(_, hash)| hash)
+			.collect()
+	}
+}
+
+impl EthMapping for MappingDb {
+	fn transaction_blocks(&self, hash: &Hash) -> Result<Vec<(Hash, u32)>, String> {
+		match self
+			.db
+			.get(columns::TRANSACTIONS, hash.as_bytes())
+			.map_err(|err| err.to_string())?
+		{
+			Some(value) => Decode::decode(&mut &value[..]).map_err(|err| err.to_string()),
+			None => Ok(vec![]),
+		}
+	}
+
+	fn block_receipts(&self, block: &Hash) -> Result<Option<Vec<(Hash, EvmReceipt)>>, String> {
+		self.db
+			.get(columns::RECEIPTS, block.as_bytes())
+			.map_err(|err| err.to_string())?
+			.map(|value| Decode::decode(&mut &value[..]).map_err(|err| err.to_string()))
+			.transpose()
+	}
+
+	fn block_bloom(&self, block: &Hash) -> Result<Option<Bloom>, String> {
+		Ok(self
+			.db
+			.get(columns::BLOOMS, block.as_bytes())
+			.map_err(|err| err.to_string())?
+			.map(|value| Bloom::from_slice(&value)))
+	}
+
+	fn filter_blocks(&self, from: u32, to: u32, addresses: &[H160], topics: &[Vec<H256>]) -> Result<Vec<Hash>, String> {
+		// the blocks of every constraint are the union of the postings of its values, and the
+		// result is the intersection of all the constraints
+		let mut constraints = vec![];
+		if !addresses.is_empty() {
+			constraints.push(
+				addresses
+					.iter()
+					.flat_map(|address| self.posting_blocks(ADDRESS_POSTING, address.as_bytes(), from, to))
+					.collect::<BTreeSet<_>>(),
+			);
+		}
+		// the topic postings ignore the position of the topic, the logs of the blocks are filtered
+		// again by `block_logs`
+		for topics in topics.iter().filter(|topics| !topics.is_empty()) {
+			constraints.push(
+				topics
+					.iter()
+					.flat_map(|topic| self.posting_blocks(TOPIC_POSTING, topic.as_bytes(), from, to))
+					.collect(),
+			);
+		}
+
+		let mut constraints = constraints.into_iter();
+		let first = constraints
+			.next()
+			.ok_or_else(|| "at least one address or topic is required".to_string())?;
+		Ok(constraints
+			.fold(first, |blocks, other| blocks.intersection(&other).copied().collect())
+			.into_iter()
+			.collect())
+	}
+}
+
+/// The max number of the blocks indexed for an imported block, so that the backfill of the
+/// history is spread over the imports.
+const MAX_BATCH_BLOCKS: u32 = 256;
+
+/// Index the imported blocks, and their ancestors which are not indexed yet.
+///
+/// The runtime calls are blocking, so it must be spawned as a blocking task.
+pub async fn run_mapping_sync<C>(client: Arc<C>, db: Arc<MappingDb>)
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+{
+	// the lowest block which can be indexed, the state of the blocks below it is not available
+	let mut floor: BlockNumber = 0;
+	// the highest blocks of the chains which are not fully indexed yet, the last one is indexed
+	// first
+	let mut pending: Vec<Hash> = vec![];
+	let mut notifications = client.import_notification_stream();
+
+	while let Some(notification) = notifications.next().await {
+		pending.push(notification.hash);
+
+		let mut budget = MAX_BATCH_BLOCKS;
+		while budget > 0 {
+			let hash = match pending.pop() {
+				Some(hash) => hash,
+				None => break,
+			};
+			match index_blocks(&*client, &db, hash, &mut floor, &mut budget) {
+				Ok(Some(next)) => pending.push(next),
+				Ok(None) => {}
+				Err(err) => log::warn!(
+					target: "evm",
+					"evm mapping: failed to index block {:?}: {}",
+					hash, err
+				),
+			}
+		}
+	}
+}
+
+/// Index the block `hash` and its ancestors down to an indexed block, at most `budget` blocks.
+/// Returns the block to continue with if the budget is used up.
+fn index_blocks<C>(
+	client: &C,
+	db: &MappingDb,
+	hash: Hash,
+	floor: &mut BlockNumber,
+	budget: &mut u32,
+) -> Result<Option<Hash>, String>
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+{
+	let mut hash = hash;
+	loop {
+		let header = client
+			.header(BlockId::Hash(hash))
+			.map_err(|err| format!("{:?}", err))?
+			.ok_or_else(|| format!("header of block {:?} not found", hash))?;
+		let number = *header.number();
+		if number < *floor || db.is_indexed(&hash)? {
+			return Ok(None);
+		}
+		if *budget == 0 {
+			return Ok(Some(hash));
+		}
+
+		match fetch_block_receipts::<Block, C, Balance>(client, hash) {
+			Ok(receipts) => db.write_block(&hash, number, receipts)?,
+			Err(err) => {
+				// don't go further back than this block
+				*floor = number.saturating_add(1);
+				return Err(err);
+			}
+		}
+		*budget = budget.saturating_sub(1);
+
+		if number == 0 {
+			return Ok(None);
+		}
+		hash = *header.parent_hash();
+	}
+}
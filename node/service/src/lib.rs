@@ -57,12 +57,24 @@ use sp_trie::PrefixedMemoryDB;
 use std::{sync::Arc, time::Duration};
 use substrate_prometheus_endpoint::Registry;
 
+pub use acala_rpc::ExecutionPoolConfig;
+
+/// The config of the EVM RPC of the node.
+#[derive(Debug, Clone, Default)]
+pub struct EvmRpcConfig {
+	/// Whether the `eth_*` rpc, and the index of the EVM transactions it is served from, are
+	/// enabled.
+	pub enable_eth_rpc: bool,
+	/// The config of the pool of the read-only EVM executions.
+	pub execution_pool: ExecutionPoolConfig,
+}
 pub use client::*;
 
 use polkadot_service::CollatorPair;
 
 pub mod chain_spec;
 mod client;
+pub mod eth_mapping;
 mod instant_finalize;
 
 #[cfg(not(feature = "runtime-benchmarks"))]
@@ -178,6 +190,36 @@ type FullClient<RuntimeApi> = TFullClient<Block, RuntimeApi, WasmExecutor<HostFu
 /// Maybe Mandala Dev full select chain.
 type MaybeFullSelectChain = Option<LongestChain<FullBackend, Block>>;
 
+/// The pool of the read-only EVM executions of the RPC calls, running against the state of the
+/// backend with the trie nodes of the recent blocks cached.
+fn new_evm_execution_pool(
+	config: &Configuration,
+	pool_config: ExecutionPoolConfig,
+	backend: Arc<FullBackend>,
+	task_manager: &TaskManager,
+) -> Arc<acala_rpc::ExecutionPool> {
+	let executor = WasmExecutor::<HostFunctions>::new(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		None,
+		config.runtime_cache_size,
+	);
+	let state = acala_rpc::CachedStateExecutor::<Block, _, _>::new(
+		backend,
+		executor,
+		Box::new(task_manager.spawn_handle()),
+		pool_config.cache_blocks,
+		pool_config.state_cache_size,
+	);
+
+	Arc::new(acala_rpc::ExecutionPool::new(
+		pool_config,
+		Arc::new(task_manager.spawn_handle()),
+		Arc::new(state),
+	))
+}
+
 pub fn new_partial<RuntimeApi>(
 	config: &Configuration,
 	dev: bool,
@@ -373,6 +415,7 @@ async fn start_node_impl<RB, RuntimeApi, BIC>(
 	polkadot_config: Configuration,
 	collator_options: CollatorOptions,
 	id: ParaId,
+	evm_rpc_config: EvmRpcConfig,
 	_rpc_ext_builder: RB,
 	build_consensus: BIC,
 ) -> sc_service::error::Result<(TaskManager, Arc<FullClient<RuntimeApi>>)>
@@ -435,16 +478,38 @@ where
 		warp_sync: None,
 	})?;
 
+	let mapping_db = if evm_rpc_config.enable_eth_rpc {
+		let mapping_db = Arc::new(eth_mapping::MappingDb::open(&parachain_config)?);
+		task_manager.spawn_handle().spawn_blocking(
+			"evm-mapping-sync",
+			None,
+			eth_mapping::run_mapping_sync(client.clone(), mapping_db.clone()),
+		);
+		Some(mapping_db)
+	} else {
+		None
+	};
+
+	let evm_execution_pool = new_evm_execution_pool(
+		&parachain_config,
+		evm_rpc_config.execution_pool,
+		backend.clone(),
+		&task_manager,
+	);
+
 	let rpc_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
 
-		move |deny_unsafe, _| {
+		move |deny_unsafe, subscription_executor| {
 			let deps = acala_rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
 				command_sink: None,
+				eth_mapping: mapping_db.clone().map(|db| db as Arc<dyn acala_rpc::EthMapping>),
+				subscription_executor,
+				evm_execution_pool: evm_execution_pool.clone(),
 			};
 
 			acala_rpc::create_full(deps).map_err(Into::into)
@@ -536,6 +601,7 @@ pub async fn start_node<RuntimeApi>(
 	polkadot_config: Configuration,
 	collator_options: CollatorOptions,
 	id: ParaId,
+	evm_rpc_config: EvmRpcConfig,
 ) -> sc_service::error::Result<(TaskManager, Arc<FullClient<RuntimeApi>>)>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi>> + Send + Sync + 'static,
@@ -547,6 +613,7 @@ where
 		polkadot_config,
 		collator_options,
 		id,
+		evm_rpc_config,
 		|_| Ok(RpcModule::new(())),
 		|client,
 		 prometheus_registry,
@@ -685,7 +752,11 @@ pub fn new_chain_ops(
 	}
 }
 
-pub fn start_dev_node<RuntimeApi>(config: Configuration, instant_sealing: bool) -> Result<TaskManager, ServiceError>
+pub fn start_dev_node<RuntimeApi>(
+	config: Configuration,
+	instant_sealing: bool,
+	evm_rpc_config: EvmRpcConfig,
+) -> Result<TaskManager, ServiceError>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
@@ -873,16 +944,34 @@ where
 		None
 	};
 
+	let mapping_db = if evm_rpc_config.enable_eth_rpc {
+		let mapping_db = Arc::new(eth_mapping::MappingDb::open(&config)?);
+		task_manager.spawn_handle().spawn_blocking(
+			"evm-mapping-sync",
+			None,
+			eth_mapping::run_mapping_sync(client.clone(), mapping_db.clone()),
+		);
+		Some(mapping_db)
+	} else {
+		None
+	};
+
+	let evm_execution_pool =
+		new_evm_execution_pool(&config, evm_rpc_config.execution_pool, backend.clone(), &task_manager);
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
 
-		move |deny_unsafe, _| {
+		move |deny_unsafe, subscription_executor| {
 			let deps = acala_rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
 				command_sink: command_sink.clone(),
+				eth_mapping: mapping_db.clone().map(|db| db as Arc<dyn acala_rpc::EthMapping>),
+				subscription_executor,
+				evm_execution_pool: evm_execution_pool.clone(),
 			};
 
 			acala_rpc::create_full(deps).map_err(Into::into)
@@ -34,6 +34,16 @@ pub struct UnlockChunk<Moment> {
 	unlock_at: Moment,
 }
 
+impl<Moment: Copy> UnlockChunk<Moment> {
+	pub fn value(&self) -> Balance {
+		self.value
+	}
+
+	pub fn unlock_at(&self) -> Moment {
+		self.unlock_at
+	}
+}
+
 /// The ledger of a (bonded) account.
 #[derive(PartialEqNoBound, EqNoBound, CloneNoBound, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(MaxUnlockingChunks, MinBond))]
@@ -78,6 +88,10 @@ where
 		self.unlocking.len()
 	}
 
+	pub fn unlocking(&self) -> &[UnlockChunk<Moment>] {
+		&self.unlocking
+	}
+
 	/// Bond more funds.
 	pub fn bond(mut self, amount: Balance) -> Result<Self, Error> {
 		self.active = self.active.saturating_add(amount);
@@ -648,3 +648,21 @@ pub struct AssetMetadata<Balance> {
 	pub decimals: u8,
 	pub minimal_balance: Balance,
 }
+
+/// The transfer behaviour of an ERC20 token.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Erc20Behaviour {
+	/// The transferred amount equals the requested amount.
+	Standard,
+	/// A fee is deducted from the transferred amount.
+	FeeOnTransfer,
+	/// The balances change without transfers.
+	Rebasing,
+}
+
+impl Default for Erc20Behaviour {
+	fn default() -> Self {
+		Erc20Behaviour::Standard
+	}
+}
@@ -26,12 +26,13 @@ use hex_literal::hex;
 pub use module_evm_utility::{
 	ethereum::{AccessListItem, Log, TransactionAction},
 	evm::ExitReason,
+	Account,
 };
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::{H160, H256, U256};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
 use sp_std::vec::Vec;
 
 /// Evm Address.
@@ -96,6 +97,46 @@ pub struct EstimateResourcesRequest {
 	pub access_list: Option<Vec<AccessListItem>>,
 }
 
+/// The result of an EVM transaction in a block, collected from the `module_evm` events.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EvmReceipt {
+	/// Index of the extrinsic in the block
+	pub extrinsic_index: u32,
+	/// From
+	pub from: EvmAddress,
+	/// The called contract, `None` for contract creations
+	pub to: Option<EvmAddress>,
+	/// The created contract
+	pub contract_address: Option<EvmAddress>,
+	/// Whether the execution succeeded
+	pub succeed: bool,
+	/// Used gas
+	pub used_gas: u64,
+	/// Logs
+	pub logs: Vec<Log>,
+}
+
+/// Max length of the compiler version of a contract verification.
+pub type MaxCompilerVersionLength = ConstU32<64>;
+
+/// The source verification record of a contract, submitted by the maintainer. Anyone can verify it
+/// offline by recompiling the source pinned by `metadata_hash` with the same compiler and settings,
+/// and comparing the result with the deployed code.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContractVerification {
+	/// The hash of the verified code, the record is only valid while the contract has this code
+	pub code_hash: H256,
+	/// The compiler version, e.g. `solc-0.8.9+commit.e5eed63a`
+	pub compiler_version: BoundedVec<u8, MaxCompilerVersionLength>,
+	/// Keccak256 hash of the standard JSON compiler settings
+	pub settings_hash: H256,
+	/// The sha2-256 digest of the IPFS CIDv0 of the contract metadata
+	pub metadata_hash: H256,
+	/// Keccak256 hash of the JSON ABI
+	pub abi_hash: H256,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EthereumTransactionMessage {
@@ -117,6 +158,9 @@ pub struct EthereumTransactionMessage {
 /// Acala precompiles
 /// 0x0000000000000000000000000000000000000400 - 0x0000000000000000000000000000000000000800
 pub const PRECOMPILE_ADDRESS_START: EvmAddress = H160(hex!("0000000000000000000000000000000000000400"));
+/// The CREATE2 caller of deterministic deployments, also the address of the deterministic
+/// deployment precompile.
+pub const DETERMINISTIC_DEPLOYER: EvmAddress = H160(hex!("000000000000000000000000000000000000040f"));
 /// Predeployed system contracts (except Mirrored ERC20)
 /// 0x0000000000000000000000000000000000000800 - 0x0000000000000000000000000000000000001000
 pub const PREDEPLOY_ADDRESS_START: EvmAddress = H160(hex!("0000000000000000000000000000000000000800"));
@@ -126,6 +170,10 @@ pub const MIRRORED_NFT_ADDRESS_START: u64 = 0x2000000;
 pub const ERC20_HOLDING_ACCOUNT: EvmAddress = H160(hex_literal::hex!("000000000000000000ff00000000000000000000"));
 /// System contract address prefix
 pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
+/// Topic of the ERC20 `Transfer(address,address,uint256)` log
+pub const ERC20_TRANSFER_TOPIC: H256 = H256(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
+/// Topic of the ERC20 `Approval(address,address,uint256)` log
+pub const ERC20_APPROVAL_TOPIC: H256 = H256(hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"));
 
 #[rustfmt::skip]
 /// CurrencyId to H160([u8; 20]) bit encoding rule.
@@ -138,6 +186,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///                     ^^                     CurrencyId Type: 1-Token 2-DexShare 3-StableAsset
 ///                                                             4-LiquidCrowdloan
 ///                                                             5-ForeignAsset(ignore Erc20, without the prefix of system contracts)
+///                                                             FE-ERC721 facade of NFT class
 ///                                                             FF-Erc20 Holding Account
 ///                                         ^^ CurrencyId Type is 1-Token, Token
 ///                                   ^^^^^^^^ CurrencyId Type is 1-Token, NFT
@@ -150,6 +199,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///                                   ^^^^^^^^ CurrencyId Type is 3-StableAsset, StableAssetPoolId
 ///                                   ^^^^^^^^ CurrencyId Type is 4-LiquidCrowdloan, Lease
 ///                                       ^^^^ CurrencyId Type is 5-ForeignAsset, ForeignAssetId
+///                                   ^^^^^^^^ Type is FE-ERC721 facade, NFT ClassId
 
 /// Check if the given `address` is a system contract.
 ///
@@ -168,6 +218,34 @@ pub const H160_POSITION_DEXSHARE_RIGHT_FIELD: Range<usize> = 16..20;
 pub const H160_POSITION_STABLE_ASSET: Range<usize> = 16..20;
 pub const H160_POSITION_LIQUID_CROADLOAN: Range<usize> = 16..20;
 pub const H160_POSITION_FOREIGN_ASSET: Range<usize> = 18..20;
+pub const H160_POSITION_NFT_CLASS: Range<usize> = 16..20;
+/// The address type of the ERC721 facade of NFT class.
+pub const NFT_CLASS_ADDRESS_TYPE: u8 = 0xfe;
+
+/// Generate the ERC721 facade address of the NFT class.
+pub fn nft_class_to_evm_address(class_id: u32) -> EvmAddress {
+	let mut address = [0u8; 20];
+	address[H160_POSITION_CURRENCY_ID_TYPE] = NFT_CLASS_ADDRESS_TYPE;
+	address[H160_POSITION_NFT_CLASS].copy_from_slice(&class_id.to_be_bytes());
+	EvmAddress::from_slice(&address)
+}
+
+/// Decode the NFT class id from the ERC721 facade address.
+pub fn evm_address_to_nft_class(address: EvmAddress) -> Option<u32> {
+	let address = address.as_bytes();
+	if address[..H160_POSITION_CURRENCY_ID_TYPE] != SYSTEM_CONTRACT_ADDRESS_PREFIX
+		|| address[H160_POSITION_CURRENCY_ID_TYPE] != NFT_CLASS_ADDRESS_TYPE
+		|| address[H160_POSITION_CURRENCY_ID_TYPE + 1..H160_POSITION_NFT_CLASS.start]
+			.iter()
+			.any(|b| *b != 0)
+	{
+		return None;
+	}
+
+	let mut class_id = [0u8; 4];
+	class_id.copy_from_slice(&address[H160_POSITION_NFT_CLASS]);
+	Some(u32::from_be_bytes(class_id))
+}
 
 /// Generate the EvmAddress from CurrencyId so that evm contracts can call the erc20 contract.
 /// NOTE: Can not be used directly, need to check the erc20 is mapped.
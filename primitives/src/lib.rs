@@ -187,6 +187,8 @@ pub enum ReserveIdentifier {
 	Nft,
 	TransactionPayment,
 	TransactionPaymentDeposit,
+	EvmBaseFee,
+	EvmUpgradeDeposit,
 
 	// always the last, indicate number of variants
 	Count,
@@ -17,8 +17,11 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::evm::{is_system_contract, EvmAddress, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use crate::evm::{
+	evm_address_to_nft_class, is_system_contract, nft_class_to_evm_address, EvmAddress, SYSTEM_CONTRACT_ADDRESS_PREFIX,
+};
 use frame_support::assert_ok;
+use hex_literal::hex;
 use sp_core::H160;
 use std::str::FromStr;
 
@@ -179,3 +182,32 @@ fn is_system_contract_works() {
 
 	assert!(!is_system_contract(bytes.into()));
 }
+
+#[test]
+fn nft_class_evm_address_works() {
+	assert_eq!(
+		nft_class_to_evm_address(0x01020304),
+		EvmAddress::from(hex!("000000000000000000fe00000000000001020304"))
+	);
+	assert_eq!(
+		evm_address_to_nft_class(EvmAddress::from(hex!("000000000000000000fe00000000000001020304"))),
+		Some(0x01020304)
+	);
+	assert_eq!(
+		evm_address_to_nft_class(nft_class_to_evm_address(u32::MAX)),
+		Some(u32::MAX)
+	);
+
+	assert_eq!(
+		evm_address_to_nft_class(EvmAddress::from(hex!("000000000000000000ff00000000000001020304"))),
+		None
+	);
+	assert_eq!(
+		evm_address_to_nft_class(EvmAddress::from(hex!("000000000000000000fe00000000010001020304"))),
+		None
+	);
+	assert_eq!(
+		evm_address_to_nft_class(EvmAddress::from(hex!("010000000000000000fe00000000000001020304"))),
+		None
+	);
+}
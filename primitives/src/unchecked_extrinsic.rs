@@ -16,17 +16,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{evm::EthereumTransactionMessage, signature::AcalaMultiSignature, to_bytes, Address, Balance};
+use crate::{evm::EthereumTransactionMessage, signature::AcalaMultiSignature, to_bytes, Address, Balance, BlockNumber};
 use codec::{Decode, Encode};
 use frame_support::{
 	log,
 	traits::{ExtrinsicCall, Get},
 	weights::{DispatchInfo, GetDispatchInfo},
 };
-use module_evm_utility::ethereum::{EIP1559TransactionMessage, LegacyTransactionMessage, TransactionAction};
+use module_evm_utility::ethereum::{
+	EIP1559Transaction, EIP1559TransactionMessage, EnvelopedDecodable, EnvelopedEncodable, LegacyTransaction,
+	LegacyTransactionMessage, TransactionAction, TransactionSignature, TransactionV2,
+};
 use module_evm_utility_macro::keccak256;
 use scale_info::TypeInfo;
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 use sp_runtime::{
 	generic::{CheckedExtrinsic, UncheckedExtrinsic},
@@ -100,6 +103,37 @@ impl<Call, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePer
 	}
 }
 
+impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, CheckPayerTx>
+	AcalaUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, CheckPayerTx>
+where
+	Call: Clone,
+	Extra: SignedExtension,
+	ConvertEthTx: Convert<(Call, Extra), Result<(EthereumTransactionMessage, Extra), InvalidTransaction>>,
+	StorageDepositPerByte: Get<Balance>,
+	TxFeePerGas: Get<Balance>,
+{
+	/// The keccak256 hash of the signed raw Ethereum transaction the extrinsic is converted
+	/// from, which is the transaction hash computed by the wallets. `None` if the extrinsic
+	/// isn't signed by an Ethereum transaction.
+	pub fn ethereum_transaction_hash(&self) -> Option<H256> {
+		let (signature, extra) = match &self.0.signature {
+			Some((_, signature, extra)) => (signature, extra),
+			None => return None,
+		};
+		if !matches!(
+			signature,
+			AcalaMultiSignature::Ethereum(_) | AcalaMultiSignature::Eip1559(_)
+		) {
+			return None;
+		}
+
+		let (eth_msg, _) = ConvertEthTx::convert((self.0.function.clone(), extra.clone())).ok()?;
+		let transaction =
+			encode_ethereum_transaction(signature, eth_msg, TxFeePerGas::get(), StorageDepositPerByte::get())?;
+		Some(H256(keccak_256(&EnvelopedEncodable::encode(&transaction))))
+	}
+}
+
 impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, Lookup, CheckPayerTx> Checkable<Lookup>
 	for AcalaUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, CheckPayerTx>
 where
@@ -359,12 +393,185 @@ fn recover_sign_data(
 	Some((tx_gas_price, tx_gas_limit))
 }
 
+/// Reverse of `recover_sign_data`, returns the `gas_limit`, `storage_limit` and `valid_until`
+/// encoded in the gas price and gas limit of an Ethereum transaction.
+fn decode_sign_data(
+	tx_gas_price: U256,
+	tx_gas_limit: U256,
+	tx_fee_per_gas: u128,
+	storage_deposit_per_byte: u128,
+) -> Option<(u64, u32, BlockNumber)> {
+	let tx_gas_price: u128 = tx_gas_price.try_into().ok()?;
+	let tx_gas_limit: u128 = tx_gas_limit.try_into().ok()?;
+
+	let encoded = tx_gas_price.checked_sub(tx_fee_per_gas)?;
+	let storage_entry_limit = encoded & 0xffff;
+	let block_period: BlockNumber = (encoded >> 16).try_into().ok()?;
+	let storage_entry_deposit = storage_deposit_per_byte.saturating_mul(64);
+	let gas_limit = tx_gas_limit.checked_sub(
+		storage_entry_deposit
+			.checked_div(tx_fee_per_gas)?
+			.checked_mul(storage_entry_limit)?,
+	)?;
+
+	Some((
+		gas_limit.try_into().ok()?,
+		storage_entry_limit.checked_mul(64)?.try_into().ok()?,
+		block_period.checked_mul(30)?,
+	))
+}
+
+/// Decode a raw signed Ethereum transaction into the signer, the signature and the message of the
+/// `eth_call` extrinsic it maps to.
+pub fn decode_ethereum_transaction(
+	raw: &[u8],
+	chain_id: u64,
+	genesis: H256,
+	tx_fee_per_gas: u128,
+	storage_deposit_per_byte: u128,
+) -> Result<(H160, AcalaMultiSignature, EthereumTransactionMessage), InvalidTransaction> {
+	let transaction = <TransactionV2 as EnvelopedDecodable>::decode(raw).map_err(|_| InvalidTransaction::Call)?;
+
+	let mut sig = [0u8; 65];
+	match transaction {
+		TransactionV2::Legacy(tx) => {
+			if tx.signature.chain_id() != Some(chain_id) {
+				return Err(InvalidTransaction::BadProof);
+			}
+
+			sig[..32].copy_from_slice(tx.signature.r().as_bytes());
+			sig[32..64].copy_from_slice(tx.signature.s().as_bytes());
+			sig[64] = tx.signature.standard_v();
+
+			let (gas_limit, storage_limit, valid_until) =
+				decode_sign_data(tx.gas_price, tx.gas_limit, tx_fee_per_gas, storage_deposit_per_byte)
+					.ok_or(InvalidTransaction::BadProof)?;
+			let msg_hash = LegacyTransactionMessage::from(tx.clone()).hash();
+			let signer = recover_signer(&sig, msg_hash.as_fixed_bytes()).ok_or(InvalidTransaction::BadProof)?;
+
+			Ok((
+				signer,
+				AcalaMultiSignature::Ethereum(sig),
+				EthereumTransactionMessage {
+					chain_id,
+					genesis,
+					nonce: tx.nonce.try_into().map_err(|_| InvalidTransaction::BadProof)?,
+					tip: 0,
+					gas_limit,
+					storage_limit,
+					action: tx.action,
+					value: tx.value.try_into().map_err(|_| InvalidTransaction::BadProof)?,
+					input: tx.input,
+					valid_until,
+					access_list: vec![],
+				},
+			))
+		}
+		TransactionV2::EIP1559(tx) => {
+			if tx.chain_id != chain_id {
+				return Err(InvalidTransaction::BadProof);
+			}
+
+			sig[..32].copy_from_slice(tx.r.as_bytes());
+			sig[32..64].copy_from_slice(tx.s.as_bytes());
+			sig[64] = tx.odd_y_parity as u8;
+
+			let (gas_limit, storage_limit, valid_until) = decode_sign_data(
+				tx.max_fee_per_gas,
+				tx.gas_limit,
+				tx_fee_per_gas,
+				storage_deposit_per_byte,
+			)
+			.ok_or(InvalidTransaction::BadProof)?;
+			// tip = priority_fee * gas_limit
+			let priority_fee: Balance = tx
+				.max_priority_fee_per_gas
+				.try_into()
+				.map_err(|_| InvalidTransaction::BadProof)?;
+			let tip = priority_fee
+				.checked_mul(gas_limit.into())
+				.ok_or(InvalidTransaction::BadProof)?;
+			let msg_hash = EIP1559TransactionMessage::from(tx.clone()).hash();
+			let signer = recover_signer(&sig, msg_hash.as_fixed_bytes()).ok_or(InvalidTransaction::BadProof)?;
+
+			Ok((
+				signer,
+				AcalaMultiSignature::Eip1559(sig),
+				EthereumTransactionMessage {
+					chain_id,
+					genesis,
+					nonce: tx.nonce.try_into().map_err(|_| InvalidTransaction::BadProof)?,
+					tip,
+					gas_limit,
+					storage_limit,
+					action: tx.action,
+					value: tx.value.try_into().map_err(|_| InvalidTransaction::BadProof)?,
+					input: tx.input,
+					valid_until,
+					access_list: tx.access_list,
+				},
+			))
+		}
+		// Not yet supported
+		TransactionV2::EIP2930(_) => Err(InvalidTransaction::BadProof),
+	}
+}
+
+/// Rebuild the signed Ethereum transaction from its `signature` and `eth_msg`, the reverse of
+/// `decode_ethereum_transaction`.
+pub fn encode_ethereum_transaction(
+	signature: &AcalaMultiSignature,
+	eth_msg: EthereumTransactionMessage,
+	tx_fee_per_gas: u128,
+	storage_deposit_per_byte: u128,
+) -> Option<TransactionV2> {
+	let (tx_gas_price, tx_gas_limit) = recover_sign_data(&eth_msg, tx_fee_per_gas, storage_deposit_per_byte)?;
+
+	match signature {
+		AcalaMultiSignature::Ethereum(sig) => {
+			let recovery_id = u64::from(sig[64]);
+			let v = eth_msg
+				.chain_id
+				.checked_mul(2)?
+				.checked_add(35)?
+				.checked_add(recovery_id)?;
+			Some(TransactionV2::Legacy(LegacyTransaction {
+				nonce: eth_msg.nonce.into(),
+				gas_price: tx_gas_price.into(),
+				gas_limit: tx_gas_limit.into(),
+				action: eth_msg.action,
+				value: eth_msg.value.into(),
+				input: eth_msg.input,
+				signature: TransactionSignature::new(v, H256::from_slice(&sig[..32]), H256::from_slice(&sig[32..64]))?,
+			}))
+		}
+		AcalaMultiSignature::Eip1559(sig) => {
+			// tip = priority_fee * gas_limit
+			let priority_fee = eth_msg.tip.checked_div(eth_msg.gas_limit.into()).unwrap_or_default();
+			Some(TransactionV2::EIP1559(EIP1559Transaction {
+				chain_id: eth_msg.chain_id,
+				nonce: eth_msg.nonce.into(),
+				max_priority_fee_per_gas: priority_fee.into(),
+				max_fee_per_gas: tx_gas_price.into(),
+				gas_limit: tx_gas_limit.into(),
+				action: eth_msg.action,
+				value: eth_msg.value.into(),
+				input: eth_msg.input,
+				access_list: eth_msg.access_list,
+				odd_y_parity: sig[64] != 0,
+				r: H256::from_slice(&sig[..32]),
+				s: H256::from_slice(&sig[32..64]),
+			}))
+		}
+		_ => None,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use hex_literal::hex;
 	use module_evm_utility::ethereum::AccessListItem;
-	use sp_core::U256;
 	use std::{ops::Add, str::FromStr};
 
 	#[test]
@@ -636,4 +843,80 @@ mod tests {
 
 		assert_eq!(recover_sign_data(&msg, u128::MAX, u128::MAX), None);
 	}
+
+	#[test]
+	fn decode_ethereum_transaction_should_works() {
+		let to = H160::from_str("0x1111111111222222222233333333334444444444").unwrap();
+		let sign = hex!("f84345a6459785986a1b2df711fe02597d70c1393757a243f8f924ea541d2ecb51476de1aa437cd820d59e1d9836e37e643fec711fe419464e637cab592918751c");
+		let sender = H160::from_str("0x14791697260E4c9A71f18484C9f997B308e59325").unwrap();
+
+		// the transaction signed in `verify_eth_should_works`, v = chain_id * 2 + 35 + recovery_id
+		let tx = TransactionV2::Legacy(LegacyTransaction {
+			nonce: U256::from(1),
+			gas_price: U256::from("0x640000006a"),
+			gas_limit: U256::from(21000),
+			action: TransactionAction::Call(to),
+			value: U256::from(123123),
+			input: vec![],
+			signature: TransactionSignature::new(
+				595 * 2 + 35 + 1,
+				H256::from_slice(&sign[..32]),
+				H256::from_slice(&sign[32..64]),
+			)
+			.unwrap(),
+		});
+		let raw = EnvelopedEncodable::encode(&tx).to_vec();
+
+		let tx_fee_per_gas = 0x6400000000u128;
+		assert_eq!(
+			decode_ethereum_transaction(&raw, 595, Default::default(), tx_fee_per_gas, 0),
+			Ok((
+				sender,
+				AcalaMultiSignature::Ethereum(sign),
+				EthereumTransactionMessage {
+					chain_id: 595,
+					genesis: Default::default(),
+					nonce: 1,
+					tip: 0,
+					gas_limit: 21000,
+					storage_limit: 106 * 64,
+					action: TransactionAction::Call(to),
+					value: 123123,
+					input: vec![],
+					valid_until: 0,
+					access_list: vec![],
+				}
+			))
+		);
+
+		// the encoding is the reverse of `recover_sign_data`
+		let (_, signature, msg) =
+			decode_ethereum_transaction(&raw, 595, Default::default(), tx_fee_per_gas, 0).unwrap();
+		assert_eq!(recover_sign_data(&msg, tx_fee_per_gas, 0), Some((0x640000006a, 21000)));
+
+		// the signed transaction is rebuilt with the same hash
+		assert_eq!(
+			encode_ethereum_transaction(&signature, msg.clone(), tx_fee_per_gas, 0),
+			Some(tx.clone())
+		);
+		assert_eq!(
+			encode_ethereum_transaction(&AcalaMultiSignature::AcalaEip712(sign), msg, tx_fee_per_gas, 0),
+			None
+		);
+
+		// wrong chain id
+		assert_eq!(
+			decode_ethereum_transaction(&raw, 596, Default::default(), tx_fee_per_gas, 0),
+			Err(InvalidTransaction::BadProof)
+		);
+		// gas price lower than the tx fee per gas
+		assert_eq!(
+			decode_ethereum_transaction(&raw, 595, Default::default(), 0x6500000000, 0),
+			Err(InvalidTransaction::BadProof)
+		);
+		assert_eq!(
+			decode_ethereum_transaction(&[0x01], 595, Default::default(), tx_fee_per_gas, 0),
+			Err(InvalidTransaction::Call)
+		);
+	}
 }
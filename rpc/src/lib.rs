@@ -40,7 +40,10 @@ use orml_oracle_rpc::{Oracle, OracleApiServer};
 use orml_tokens_rpc::{Tokens, TokensApiServer};
 
 /// module rpc
-pub use evm_rpc::{EVMApiServer, EVMRuntimeRPCApi, EVM};
+pub use evm_rpc::{
+	CachedStateExecutor, EVMApiServer, EVMRuntimeRPCApi, EstimateConfig, Eth, EthApiServer, EthMapping, ExecutionPool,
+	ExecutionPoolConfig, EVM,
+};
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
@@ -55,12 +58,18 @@ pub struct FullDeps<C, P> {
 	pub deny_unsafe: DenyUnsafe,
 	/// Manual seal command sink
 	pub command_sink: Option<futures::channel::mpsc::Sender<EngineCommand<Hash>>>,
+	/// The index of the EVM transactions, the `eth_*` rpc is disabled if it's `None`
+	pub eth_mapping: Option<Arc<dyn EthMapping>>,
+	/// Executor of the rpc subscriptions
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// The pool of the read-only EVM executions
+	pub evm_execution_pool: Arc<ExecutionPool>,
 }
 
 /// Instantiate all Full RPC extensions.
 pub fn create_full<C, P>(deps: FullDeps<C, P>) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
-	C: ProvideRuntimeApi<Block> + sc_client_api::BlockBackend<Block>,
+	C: ProvideRuntimeApi<Block> + sc_client_api::BlockBackend<Block> + sc_client_api::BlockchainEvents<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
@@ -69,7 +78,7 @@ where
 	C::Api: orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Block = Block, Hash = Hash> + Sync + Send + 'static,
 {
 	let mut module = RpcExtension::new(());
 	let FullDeps {
@@ -77,9 +86,12 @@ where
 		pool,
 		deny_unsafe,
 		command_sink,
+		eth_mapping,
+		subscription_executor,
+		evm_execution_pool,
 	} = deps;
 
-	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
+	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 
 	// Making synchronous calls in light client freezes the browser currently,
@@ -87,7 +99,28 @@ where
 	// These RPCs should use an asynchronous caller instead.
 	module.merge(Oracle::new(client.clone()).into_rpc())?;
 	module.merge(Tokens::new(client.clone()).into_rpc())?;
-	module.merge(EVM::new(client.clone(), deny_unsafe).into_rpc())?;
+	module.merge(
+		EVM::new(
+			client.clone(),
+			deny_unsafe,
+			EstimateConfig::default(),
+			evm_execution_pool.clone(),
+		)
+		.into_rpc(),
+	)?;
+	if let Some(eth_mapping) = eth_mapping {
+		module.merge(
+			Eth::<Block, C, P, Balance>::new(
+				client.clone(),
+				pool,
+				eth_mapping,
+				subscription_executor,
+				EstimateConfig::default(),
+				evm_execution_pool,
+			)
+			.into_rpc(),
+		)?;
+	}
 	module.merge(Dev::new(client, deny_unsafe).into_rpc())?;
 
 	if let Some(command_sink) = command_sink {
@@ -175,4 +175,13 @@ impl<T: frame_system::Config> module_homa::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	fn migrate_homa_lite_redeem_request() -> Weight {
+		module_homa::estimated_weights::migrate_homa_lite_redeem_request::<T::DbWeight>()
+	}
+	fn withdraw_homa_lite_scheduled_unbonds() -> Weight {
+		module_homa::estimated_weights::withdraw_homa_lite_scheduled_unbonds::<T::DbWeight>()
+	}
+	fn migrate_homa_lite_staking_totals() -> Weight {
+		module_homa::estimated_weights::migrate_homa_lite_staking_totals::<T::DbWeight>()
+	}
 }
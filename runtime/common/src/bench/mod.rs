@@ -217,6 +217,52 @@ fn evm_query_maintainer(b: &mut Bencher) {
 	assert_eq!(resp.output, expected_output);
 }
 
+fn evm_query_verification(b: &mut Bencher) {
+	let caller = alice_evm_addr();
+	whitelist_keys(b, None);
+
+	let context = Context {
+		address: Default::default(),
+		caller,
+		apparent_value: Default::default(),
+	};
+
+	let contract_address = H160::from(hex!("2000000000000000000000000000000000000001"));
+	module_evm::ContractVerifications::<Test>::insert(
+		contract_address,
+		primitives::evm::ContractVerification {
+			code_hash: H256::repeat_byte(1),
+			compiler_version: b"solc-0.8.9".to_vec().try_into().unwrap(),
+			settings_hash: H256::repeat_byte(2),
+			metadata_hash: H256::repeat_byte(3),
+			abi_hash: H256::repeat_byte(4),
+		},
+	);
+
+	// verificationOf(address) -> 0x1b49547f
+	// contract_address
+	let input = hex! {"
+		1b49547f
+		000000000000000000000000 2000000000000000000000000000000000000001
+	"};
+
+	let expected_output = hex! {"
+		0101010101010101010101010101010101010101010101010101010101010101
+		00000000000000000000000000000000000000000000000000000000000000a0
+		0202020202020202020202020202020202020202020202020202020202020202
+		0303030303030303030303030303030303030303030303030303030303030303
+		0404040404040404040404040404040404040404040404040404040404040404
+		000000000000000000000000000000000000000000000000000000000000000a
+		736f6c632d302e382e3900000000000000000000000000000000000000000000
+	"};
+
+	let resp = b
+		.bench(|| EVMPrecompile::<Test>::execute(&input, None, &context, false))
+		.unwrap();
+
+	assert_eq!(resp.output, expected_output);
+}
+
 fn evm_query_developer_deposit(b: &mut Bencher) {
 	let caller = alice_evm_addr();
 	whitelist_keys(b, None);
@@ -302,6 +348,7 @@ benches!(
 	evm_query_new_contract_extra_bytes,
 	evm_query_storage_deposit_per_byte,
 	evm_query_maintainer,
+	evm_query_verification,
 	evm_query_developer_deposit,
 	evm_query_publication_fee,
 	evm_query_developer_status
@@ -33,19 +33,25 @@ use frame_support::{
 };
 use frame_system::{limits, EnsureRoot};
 use module_evm::GenesisAccount;
+use module_support::RelaychainStateProofReader;
 use orml_traits::GetByKey;
 use primitives::{evm::is_system_contract, Balance, CurrencyId, Nonce};
 use scale_info::TypeInfo;
 use sp_core::{Bytes, H160};
-use sp_runtime::{traits::Convert, transaction_validity::TransactionPriority, Perbill};
+use sp_runtime::{
+	traits::{BlakeTwo256, Convert},
+	transaction_validity::TransactionPriority,
+	Perbill,
+};
 use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
 use static_assertions::const_assert;
 
 pub use check_nonce::CheckNonce;
 pub use module_support::{ExchangeRate, PrecompileCallerFilter, Price, Rate, Ratio};
 pub use precompile::{
-	AllPrecompiles, DEXPrecompile, EVMPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile,
-	SchedulePrecompile, StableAssetPrecompile,
+	AllPrecompiles, DEXPrecompile, ERC721Precompile, EVMPrecompile, MultiCurrencyPrecompile, NFTPrecompile,
+	OraclePrecompile, SchedulePrecompile, StableAssetPrecompile, UnavailablePrecompile,
 };
 pub use primitives::{
 	currency::{
@@ -186,6 +192,46 @@ impl<AccountId> Contains<AccountId> for DummyNomineeFilter {
 	}
 }
 
+/// Verify the relay chain state proofs against the relay parent storage root of the current block.
+pub struct RelayParentStateProofReader<T>(PhantomData<T>);
+impl<T: cumulus_pallet_parachain_system::Config> RelaychainStateProofReader for RelayParentStateProofReader<T> {
+	fn read_value<V: Decode>(key: &[u8], proof: Vec<Vec<u8>>) -> Option<V> {
+		let relay_parent_storage_root =
+			cumulus_pallet_parachain_system::Pallet::<T>::validation_data()?.relay_parent_storage_root;
+		read_state_proof_value(&relay_parent_storage_root, key, proof)
+	}
+}
+
+/// Read and decode the value of `key` from the state `proof` of the `state_root`.
+pub fn read_state_proof_value<V: Decode>(state_root: &H256, key: &[u8], proof: Vec<Vec<u8>>) -> Option<V> {
+	let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+	let value = read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, state_root, key).ok()??;
+	V::decode(&mut &value[..]).ok()
+}
+
+/// Build the state proof of `key_values`, returns the state root and the proof.
+#[cfg(test)]
+pub fn build_state_proof(key_values: Vec<(Vec<u8>, Vec<u8>)>) -> (H256, Vec<Vec<u8>>) {
+	use sp_trie::{trie_types::TrieDBMutV1, MemoryDB, TrieMut};
+
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut state_root = H256::default();
+	{
+		let mut trie = TrieDBMutV1::<BlakeTwo256>::new(&mut db, &mut state_root);
+		for (key, value) in key_values {
+			trie.insert(&key, &value)
+				.expect("insert into the in-memory trie should work; qed");
+		}
+	}
+	let proof = db
+		.drain()
+		.into_iter()
+		.filter(|(_, (_, rc))| *rc > 0)
+		.map(|(_, (node, _))| node)
+		.collect();
+	(state_root, proof)
+}
+
 // TODO: make those const fn
 pub fn dollar(currency_id: CurrencyId) -> Balance {
 	10u128.saturating_pow(currency_id.decimals().expect("Not support Non-Token decimals").into())
@@ -359,6 +405,14 @@ where
 	}
 }
 
+/// The EVM block gas limit used by the base fee per gas.
+pub struct EvmBlockGasLimit<T>(PhantomData<T>);
+impl<T: frame_system::Config> Get<u64> for EvmBlockGasLimit<T> {
+	fn get() -> u64 {
+		EvmLimits::<T>::max_gas_limit()
+	}
+}
+
 #[cfg(feature = "std")]
 /// Returns `evm_genesis_accounts`
 pub fn evm_genesis(evm_accounts: Vec<H160>) -> BTreeMap<H160, GenesisAccount<Balance, Nonce>> {
@@ -414,6 +468,36 @@ mod tests {
 		assert!(!SystemContractsFilter::is_allowed(min_blocked_addr.into()));
 	}
 
+	#[test]
+	fn read_state_proof_value_works() {
+		let (state_root, proof) = build_state_proof(vec![
+			(b"key1".to_vec(), 1u32.encode()),
+			(b"key2".to_vec(), (2u32, Some(3u64)).encode()),
+		]);
+
+		assert_eq!(
+			read_state_proof_value::<u32>(&state_root, b"key1", proof.clone()),
+			Some(1)
+		);
+		assert_eq!(
+			read_state_proof_value::<(u32, Option<u64>)>(&state_root, b"key2", proof.clone()),
+			Some((2, Some(3)))
+		);
+		// the value doesn't exist
+		assert_eq!(read_state_proof_value::<u32>(&state_root, b"key3", proof.clone()), None);
+		// the value can't be decoded
+		assert_eq!(read_state_proof_value::<u64>(&state_root, b"key1", proof.clone()), None);
+		// the proof doesn't match the state root
+		assert_eq!(
+			read_state_proof_value::<u32>(&H256::repeat_byte(1), b"key1", proof.clone()),
+			None
+		);
+		// the proof is incomplete
+		let (other_state_root, _) = build_state_proof(vec![(b"key1".to_vec(), 2u32.encode())]);
+		assert_eq!(read_state_proof_value::<u32>(&other_state_root, b"key1", proof), None);
+		assert_eq!(read_state_proof_value::<u32>(&state_root, b"key1", vec![]), None);
+	}
+
 	#[test]
 	fn check_max_normal_priority() {
 		let max_normal_priority: TransactionPriority = (MaxTipsOfPriority::get() / TipPerWeightStep::get()
@@ -175,6 +175,7 @@ impl FindAuthor<AccountId32> for AuthorGiven {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(1);
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160::repeat_byte(0xff))];
 }
 
 ord_parameter_types! {
@@ -190,6 +191,9 @@ impl module_evm_accounts::Config for TestRuntime {
 	type AddressMapping = EvmAddressMapping<TestRuntime>;
 	type TransferAll = Currencies;
 	type ChainId = EvmChainId<TestRuntime>;
+	type EVMBridge = ();
+	type RotationDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -199,7 +203,10 @@ impl module_evm::Config for TestRuntime {
 	type TransferAll = Currencies;
 	type NewContractExtraBytes = ConstU32<100>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TxFeePerGas = ConstU128<20_000_000>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 
 	type Event = Event;
 	type PrecompilesType = ();
@@ -209,6 +216,7 @@ impl module_evm::Config for TestRuntime {
 
 	type NetworkContractOrigin = frame_system::EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
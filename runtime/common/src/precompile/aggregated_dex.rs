@@ -0,0 +1,566 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT, Output},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use ethabi::{ParamType, Token};
+use frame_support::{log, traits::Get};
+use module_aggregated_dex::{SwapPath, WeightInfo};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed,
+};
+use module_support::{Erc20InfoMapping as Erc20InfoMappingT, Swap, SwapLimit};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The `AggregatedDEX` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get swap target amount. Rest `input` bytes: `paths`, `supply_amount`.
+/// - Get swap supply amount. Rest `input` bytes: `paths`, `target_amount`.
+/// - Swap with exact supply. Rest `input` bytes: `who`, `paths`, `supply_amount`,
+///   `min_target_amount`.
+/// - Swap with exact target. Rest `input` bytes: `who`, `paths`, `target_amount`,
+///   `max_supply_amount`.
+///
+/// `paths` is an array of `SwapPath(address[] dexPath, uint32 poolId, uint32 supplyIndex, uint32
+/// targetIndex)`. A path with non-empty `dexPath` swaps by DEX, otherwise swaps by the StableAsset
+/// pool `poolId` from `supplyIndex` to `targetIndex`.
+pub struct AggregatedDEXPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetSwapTargetAmount = "getSwapTargetAmount((address[],uint32,uint32,uint32)[],uint256)",
+	GetSwapSupplyAmount = "getSwapSupplyAmount((address[],uint32,uint32,uint32)[],uint256)",
+	SwapWithExactSupply = "swapWithExactSupply(address,(address[],uint32,uint32,uint32)[],uint256,uint256)",
+	SwapWithExactTarget = "swapWithExactTarget(address,(address[],uint32,uint32,uint32)[],uint256,uint256)",
+}
+
+type AggregatedSwap<Runtime> = module_aggregated_dex::AggregatedSwap<Runtime>;
+
+impl<Runtime> Precompile for AggregatedDEXPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_aggregated_dex::Config + module_prices::Config,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		let ensure_gas = |gas_cost: u64| -> Result<(), PrecompileFailure> {
+			match target_gas {
+				Some(gas_limit) if gas_limit < gas_cost => Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				}),
+				_ => Ok(()),
+			}
+		};
+
+		// the swap paths are priced by their content, charge the decoding of them first
+		let decode_cost = Pricer::<Runtime>::decode_cost(input.len());
+		ensure_gas(decode_cost)?;
+
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			<Runtime as module_evm::Config>::AddressMapping,
+			Runtime::Erc20InfoMapping,
+		>::new(input, target_gas_limit(target_gas));
+
+		let paths = swap_paths::<Runtime>(&input, target_gas)?;
+		let gas_cost = Pricer::<Runtime>::cost(&input, &paths)?.saturating_add(decode_cost);
+		ensure_gas(gas_cost)?;
+
+		let action = input.action()?;
+
+		match action {
+			Action::GetSwapTargetAmount => {
+				let supply_amount = input.balance_at(2)?;
+				log::debug!(
+					target: "evm",
+					"aggregated dex: get_swap_target_amount paths: {:?}, supply_amount: {:?}",
+					paths, supply_amount
+				);
+
+				// If get_aggregated_swap_amount fail, return 0.
+				let target = module_aggregated_dex::Pallet::<Runtime>::get_aggregated_swap_amount(
+					&paths,
+					SwapLimit::ExactSupply(supply_amount, Balance::MIN),
+				)
+				.map(|(_, target)| target)
+				.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(target),
+					logs: Default::default(),
+				})
+			}
+			Action::GetSwapSupplyAmount => {
+				let target_amount = input.balance_at(2)?;
+				log::debug!(
+					target: "evm",
+					"aggregated dex: get_swap_supply_amount paths: {:?}, target_amount: {:?}",
+					paths, target_amount
+				);
+
+				// If get_aggregated_swap_amount fail, return 0.
+				let supply = module_aggregated_dex::Pallet::<Runtime>::get_aggregated_swap_amount(
+					&paths,
+					SwapLimit::ExactTarget(Balance::MAX, target_amount),
+				)
+				.map(|(supply, _)| supply)
+				.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(supply),
+					logs: Default::default(),
+				})
+			}
+			Action::SwapWithExactSupply => {
+				let who = input.account_id_at(1)?;
+				let supply_amount = input.balance_at(3)?;
+				let min_target_amount = input.balance_at(4)?;
+				log::debug!(
+					target: "evm",
+					"aggregated dex: swap_with_exact_supply who: {:?}, paths: {:?}, supply_amount: {:?}, min_target_amount: {:?}",
+					who, paths, supply_amount, min_target_amount
+				);
+
+				let (_, value) =
+					<AggregatedSwap<Runtime> as Swap<Runtime::AccountId, Balance, CurrencyId>>::swap_by_aggregated_path(
+						&who,
+						&paths,
+						SwapLimit::ExactSupply(supply_amount, min_target_amount),
+					)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(value),
+					logs: Default::default(),
+				})
+			}
+			Action::SwapWithExactTarget => {
+				let who = input.account_id_at(1)?;
+				let target_amount = input.balance_at(3)?;
+				let max_supply_amount = input.balance_at(4)?;
+				log::debug!(
+					target: "evm",
+					"aggregated dex: swap_with_exact_target who: {:?}, paths: {:?}, target_amount: {:?}, max_supply_amount: {:?}",
+					who, paths, target_amount, max_supply_amount
+				);
+
+				let (value, _) =
+					<AggregatedSwap<Runtime> as Swap<Runtime::AccountId, Balance, CurrencyId>>::swap_by_aggregated_path(
+						&who,
+						&paths,
+						SwapLimit::ExactTarget(max_supply_amount, target_amount),
+					)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(value),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+/// Decode the `SwapPath[]` param of the action.
+fn swap_paths<Runtime>(
+	input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	target_gas: Option<u64>,
+) -> Result<Vec<SwapPath>, PrecompileFailure>
+where
+	Runtime: module_evm::Config + module_aggregated_dex::Config + module_prices::Config,
+{
+	let swap_path_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+		ParamType::Array(Box::new(ParamType::Address)),
+		ParamType::Uint(32),
+		ParamType::Uint(32),
+		ParamType::Uint(32),
+	])));
+
+	let (types, index) = match input.action()? {
+		Action::GetSwapTargetAmount | Action::GetSwapSupplyAmount => (vec![swap_path_type, ParamType::Uint(256)], 0),
+		Action::SwapWithExactSupply | Action::SwapWithExactTarget => (
+			vec![
+				ParamType::Address,
+				swap_path_type,
+				ParamType::Uint(256),
+				ParamType::Uint(256),
+			],
+			1,
+		),
+	};
+
+	let invalid_swap_path = || PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: "invalid swap path".into(),
+		cost: target_gas_limit(target_gas).unwrap_or_default(),
+	};
+
+	let paths = match input.decode_params(&types)?.swap_remove(index) {
+		Token::Array(paths) => paths,
+		_ => return Err(invalid_swap_path()),
+	};
+
+	if paths.len() > <Runtime as module_aggregated_dex::Config>::SwapPathLimit::get() as usize {
+		return Err(invalid_swap_path());
+	}
+
+	paths
+		.into_iter()
+		.map(|path| match path {
+			Token::Tuple(fields) => match &fields[..] {
+				[Token::Array(dex_path), Token::Uint(pool_id), Token::Uint(supply_index), Token::Uint(target_index)] => {
+					if dex_path.is_empty() {
+						Some(SwapPath::Taiga(
+							(*pool_id).try_into().ok()?,
+							(*supply_index).try_into().ok()?,
+							(*target_index).try_into().ok()?,
+						))
+					} else {
+						dex_path
+							.iter()
+							.map(|address| match address {
+								Token::Address(address) => Runtime::Erc20InfoMapping::decode_evm_address(*address),
+								_ => None,
+							})
+							.collect::<Option<Vec<CurrencyId>>>()
+							.map(SwapPath::Dex)
+					}
+				}
+				_ => None,
+			},
+			_ => None,
+		})
+		.collect::<Option<Vec<SwapPath>>>()
+		.ok_or_else(invalid_swap_path)
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_aggregated_dex::Config + module_prices::Config,
+{
+	const BASE_COST: u64 = 200;
+	/// The cost of decoding a word of the input.
+	const DECODE_WORD_COST: u64 = 3;
+
+	fn decode_cost(input_len: usize) -> u64 {
+		let words = (input_len as u64).saturating_add(31) / 32;
+		Self::DECODE_WORD_COST.saturating_mul(words)
+	}
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+		paths: &[SwapPath],
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let mut read_currency = 0u64;
+		let mut dex_path_len = 0u32;
+		let mut dex_swaps = 0u32;
+		let mut taiga_swaps = 0u32;
+		for path in paths {
+			match path {
+				SwapPath::Dex(dex_path) => {
+					for currency_id in dex_path {
+						read_currency =
+							read_currency.saturating_add(InputPricer::<Runtime>::read_currency(*currency_id));
+					}
+					dex_path_len = dex_path_len.saturating_add(dex_path.len() as u32);
+					dex_swaps = dex_swaps.saturating_add(dex_path.len().saturating_sub(1) as u32);
+				}
+				SwapPath::Taiga(..) => {
+					taiga_swaps = taiga_swaps.saturating_add(1);
+				}
+			}
+		}
+
+		let cost: u64 = match action {
+			Action::GetSwapTargetAmount | Action::GetSwapSupplyAmount => {
+				// DEX::TradingPairStatuses (r: 1 * dex_swaps)
+				// DEX::LiquidityPool (r: 1 * dex_swaps)
+				// StableAsset::Pools (r: 1 * taiga_swaps)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get()
+					.reads(dex_swaps.saturating_mul(2).saturating_add(taiga_swaps).into());
+
+				read_currency.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::SwapWithExactSupply | Action::SwapWithExactTarget => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+
+				let weight = <Runtime as module_aggregated_dex::Config>::WeightInfo::swap_with_exact_supply(
+					dex_path_len.saturating_add(taiga_swaps),
+				);
+
+				read_currency
+					.saturating_add(read_account)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::precompile::mock::{
+		alice, alice_evm_addr, new_test_ext, DexModule, Origin, Test, Tokens, ALICE, AUSD, RENBTC,
+	};
+	use frame_support::{assert_noop, assert_ok};
+	use hex_literal::hex;
+	use orml_traits::MultiCurrency;
+	use primitives::evm::EvmAddress;
+
+	type AggregatedDEXPrecompile = super::AggregatedDEXPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	fn dex_path(currency_ids: Vec<CurrencyId>) -> Token {
+		Token::Tuple(vec![
+			Token::Array(
+				currency_ids
+					.into_iter()
+					.map(|currency_id| Token::Address(EvmAddress::try_from(currency_id).unwrap()))
+					.collect(),
+			),
+			Token::Uint(0.into()),
+			Token::Uint(0.into()),
+			Token::Uint(0.into()),
+		])
+	}
+
+	fn encode_input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+		let mut input = selector.to_vec();
+		input.extend(ethabi::encode(params));
+		input
+	}
+
+	fn enable_renbtc_ausd() {
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+	}
+
+	#[test]
+	fn get_swap_amount_works() {
+		new_test_ext().execute_with(|| {
+			enable_renbtc_ausd();
+
+			// getSwapTargetAmount((address[],uint32,uint32,uint32)[],uint256) -> 0x6658e949
+			let input = encode_input(
+				hex!("6658e949"),
+				&[Token::Array(vec![dex_path(vec![RENBTC, AUSD])]), Token::Uint(1.into())],
+			);
+			let resp = AggregatedDEXPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_uint(989u128));
+
+			// getSwapSupplyAmount((address[],uint32,uint32,uint32)[],uint256) -> 0x63ed7f89
+			let input = encode_input(
+				hex!("63ed7f89"),
+				&[Token::Array(vec![dex_path(vec![RENBTC, AUSD])]), Token::Uint(1.into())],
+			);
+			let resp = AggregatedDEXPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_uint(1u128));
+
+			// no liquidity
+			let input = encode_input(
+				hex!("6658e949"),
+				&[
+					Token::Array(vec![dex_path(vec![AUSD, RENBTC, AUSD])]),
+					Token::Uint(1.into()),
+				],
+			);
+			let resp = AggregatedDEXPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(0u128));
+		});
+	}
+
+	#[test]
+	fn swap_with_exact_supply_works() {
+		new_test_ext().execute_with(|| {
+			enable_renbtc_ausd();
+
+			let renbtc = Tokens::free_balance(RENBTC, &alice());
+			let ausd = Tokens::free_balance(AUSD, &alice());
+
+			// swapWithExactSupply(address,(address[],uint32,uint32,uint32)[],uint256,uint256) -> 0x34fdd474
+			let input = encode_input(
+				hex!("34fdd474"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![dex_path(vec![RENBTC, AUSD])]),
+					Token::Uint(1.into()),
+					Token::Uint(0.into()),
+				],
+			);
+			let resp = AggregatedDEXPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_uint(989u128));
+			assert_eq!(Tokens::free_balance(RENBTC, &alice()), renbtc - 1);
+			assert_eq!(Tokens::free_balance(AUSD, &alice()), ausd + 989);
+		});
+	}
+
+	#[test]
+	fn swap_with_exact_target_works() {
+		new_test_ext().execute_with(|| {
+			enable_renbtc_ausd();
+
+			// swapWithExactTarget(address,(address[],uint32,uint32,uint32)[],uint256,uint256) -> 0x52619443
+			let input = encode_input(
+				hex!("52619443"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![dex_path(vec![RENBTC, AUSD])]),
+					Token::Uint(1.into()),
+					Token::Uint(1.into()),
+				],
+			);
+			let resp = AggregatedDEXPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_uint(1u128));
+		});
+	}
+
+	#[test]
+	fn invalid_swap_path_reverts() {
+		new_test_ext().execute_with(|| {
+			enable_renbtc_ausd();
+
+			// exceeds SwapPathLimit
+			let input = encode_input(
+				hex!("34fdd474"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![dex_path(vec![RENBTC, AUSD]); 4]),
+					Token::Uint(1.into()),
+					Token::Uint(0.into()),
+				],
+			);
+			assert_noop!(
+				AggregatedDEXPrecompile::execute(&input, Some(10_000), &context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid swap path".into(),
+					cost: target_gas_limit(Some(10_000)).unwrap(),
+				}
+			);
+
+			// unknown currency
+			let mut path = dex_path(vec![RENBTC, AUSD]);
+			if let Token::Tuple(ref mut fields) = path {
+				fields[0] = Token::Array(vec![Token::Address(EvmAddress::from(hex!(
+					"0000000000000000000000000000000200000001"
+				)))]);
+			}
+			let input = encode_input(
+				hex!("34fdd474"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![path]),
+					Token::Uint(1.into()),
+					Token::Uint(0.into()),
+				],
+			);
+			assert_noop!(
+				AggregatedDEXPrecompile::execute(&input, Some(10_000), &context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid swap path".into(),
+					cost: target_gas_limit(Some(10_000)).unwrap(),
+				}
+			);
+		});
+	}
+
+	#[test]
+	fn checks_gas_before_decoding_swap_paths() {
+		new_test_ext().execute_with(|| {
+			let input = encode_input(
+				hex!("34fdd474"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![dex_path(vec![RENBTC, AUSD]); 4]),
+					Token::Uint(1.into()),
+					Token::Uint(0.into()),
+				],
+			);
+			let decode_cost = Pricer::<Test>::decode_cost(input.len());
+			assert_noop!(
+				AggregatedDEXPrecompile::execute(&input, Some(decode_cost - 1), &context(), false),
+				PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				}
+			);
+			assert_noop!(
+				AggregatedDEXPrecompile::execute(&input, Some(decode_cost), &context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid swap path".into(),
+					cost: target_gas_limit(Some(decode_cost)).unwrap(),
+				}
+			);
+		});
+	}
+}
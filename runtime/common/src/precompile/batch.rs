@@ -0,0 +1,390 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	ensure_non_static,
+	input::{Input, InputPricer, InputT},
+	target_gas_limit, BATCH,
+};
+use crate::SystemContractsFilter;
+use frame_support::{ensure, log};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	BalanceOf, BatchOperation, Context, ExitError, ExitRevert, ExitSucceed,
+};
+use module_support::{
+	abi_struct,
+	evm::abi::{self, AbiBytes},
+	PrecompileCallerFilter,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use sp_core::{H160, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The `Batch` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Batch all. Rest `input` bytes: `who`, `operations`, `storage_limit`. `who` must be the
+///   caller, unless the caller is a system contract, e.g. a predeploy contract batching for its
+///   own caller.
+///
+/// `operations` is an array of `Operation(address to, uint256 value, bytes input)`. An operation
+/// with the zero `to` address creates a contract with the init code `input`, otherwise calls the
+/// contract `to`. The `value` is in the native token's decimals. The operations share the gas and
+/// storage limits, and all of them are reverted if any of them fails.
+pub struct BatchPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	BatchAll = "batchAll(address,(address,uint256,bytes)[],uint32)",
+}
+
+impl<Runtime> Precompile for BatchPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			<Runtime as module_evm::Config>::AddressMapping,
+			Runtime::Erc20InfoMapping,
+		>::new(input, target_gas_limit(target_gas));
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		match action {
+			Action::BatchAll => {
+				ensure_non_static(is_static, target_gas)?;
+
+				let who = input.evm_address_at(1)?;
+				// `DELEGATECALL` can't batch on behalf of the caller of the delegating contract
+				let is_system_contract = SystemContractsFilter::is_allowed(context.caller)
+					&& module_evm::Pallet::<Runtime>::is_contract(&context.caller);
+				ensure!(
+					context.address == BATCH && (who == context.caller || is_system_contract),
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "NoPermission".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				);
+				let operations = batch_operations::<Runtime>(&input, target_gas)?;
+				let storage_limit = input.u32_at(3)?;
+				let gas_limit = target_gas.unwrap_or_default().saturating_sub(gas_cost);
+				log::debug!(
+					target: "evm",
+					"batch: batch_all who: {:?}, operations: {:?}, gas_limit: {:?}, storage_limit: {:?}",
+					who, operations, gas_limit, storage_limit
+				);
+
+				let info = module_evm::Pallet::<Runtime>::execute_batch(
+					who,
+					operations,
+					gas_limit,
+					storage_limit,
+					vec![],
+					<Runtime as module_evm::Config>::config(),
+					true,
+				);
+
+				if !info.exit_reason.is_succeed() {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: info.output,
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
+				let output = abi::encode(&(info
+					.results
+					.into_iter()
+					.map(|result| (result.contract, AbiBytes(result.output)))
+					.collect::<Vec<_>>(),));
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost.saturating_add(info.used_gas),
+					output,
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+/// The `Operation` struct of the action.
+#[derive(Clone)]
+struct Operation {
+	to: H160,
+	value: U256,
+	input: AbiBytes,
+}
+
+abi_struct!(Operation {
+	to: H160,
+	value: U256,
+	input: AbiBytes
+});
+
+/// Decode the `Operation[]` param of the action.
+fn batch_operations<Runtime>(
+	input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	target_gas: Option<u64>,
+) -> Result<Vec<BatchOperation<BalanceOf<Runtime>>>, PrecompileFailure>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	let (_, operations, _) = input.decode_args::<(H160, Vec<Operation>, u32)>()?;
+
+	operations
+		.into_iter()
+		.map(|Operation { to, value, input }| {
+			let value: BalanceOf<Runtime> = u128::try_from(value).ok()?.try_into().ok()?;
+			if to == H160::zero() {
+				Some(BatchOperation::Create { input: input.0, value })
+			} else {
+				Some(BatchOperation::Call {
+					target: to,
+					input: input.0,
+					value,
+				})
+			}
+		})
+		.collect::<Option<Vec<_>>>()
+		.ok_or_else(|| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid operation".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		})
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost = match action {
+			Action::BatchAll => {
+				// the gas used by the operations is charged after the execution
+				InputPricer::<Runtime>::read_accounts(1)
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::precompile::mock::{alice_evm_addr, new_test_ext, EVMModule, Test};
+	use ethabi::{ParamType, Token};
+	use hex_literal::hex;
+	use sp_core::bytes::from_hex;
+
+	type BatchPrecompile = super::BatchPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: BATCH,
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	fn encode_input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+		let mut input = selector.to_vec();
+		input.extend(ethabi::encode(params));
+		input
+	}
+
+	fn create_operation(init: Vec<u8>) -> Token {
+		Token::Tuple(vec![
+			Token::Address(H160::zero()),
+			Token::Uint(0.into()),
+			Token::Bytes(init),
+		])
+	}
+
+	#[test]
+	fn batch_all_works() {
+		new_test_ext().execute_with(|| {
+			// deploys a contract with the runtime code `0x00`
+			let init = from_hex("0x6001600c60003960016000f300").unwrap();
+			let nonce = EVMModule::accounts(alice_evm_addr()).unwrap().nonce;
+
+			// batchAll(address,(address,uint256,bytes)[],uint32) -> 0x8e426b51
+			let input = encode_input(
+				hex!("8e426b51"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![create_operation(init.clone()), create_operation(init)]),
+					Token::Uint(1_000.into()),
+				],
+			);
+			let resp = BatchPrecompile::execute(&input, Some(1_000_000), &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(EVMModule::accounts(alice_evm_addr()).unwrap().nonce, nonce + 2);
+
+			let contracts = match ethabi::decode(
+				&[ParamType::Array(Box::new(ParamType::Tuple(vec![
+					ParamType::Address,
+					ParamType::Bytes,
+				])))],
+				&resp.output,
+			)
+			.unwrap()
+			.swap_remove(0)
+			{
+				Token::Array(results) => results
+					.into_iter()
+					.map(|result| match result {
+						Token::Tuple(fields) => fields[0].clone().into_address().unwrap(),
+						_ => unreachable!(),
+					})
+					.collect::<Vec<_>>(),
+				_ => unreachable!(),
+			};
+			assert_eq!(contracts.len(), 2);
+			assert_ne!(contracts[0], contracts[1]);
+			assert!(contracts.iter().all(EVMModule::is_contract));
+		});
+	}
+
+	#[test]
+	fn batch_all_reverts_all_operations() {
+		new_test_ext().execute_with(|| {
+			let init = from_hex("0x6001600c60003960016000f300").unwrap();
+			let nonce = EVMModule::accounts(alice_evm_addr()).unwrap().nonce;
+
+			// the second operation fails with the invalid opcode
+			let input = encode_input(
+				hex!("8e426b51"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![create_operation(init), create_operation(vec![0xfe])]),
+					Token::Uint(1_000.into()),
+				],
+			);
+			assert_eq!(
+				BatchPrecompile::execute(&input, Some(1_000_000), &context(), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: vec![],
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				})
+			);
+			assert_eq!(EVMModule::accounts(alice_evm_addr()).unwrap().nonce, nonce);
+		});
+	}
+
+	#[test]
+	fn batch_all_checks_permission() {
+		new_test_ext().execute_with(|| {
+			let init = from_hex("0x6001600c60003960016000f300").unwrap();
+			let input = encode_input(
+				hex!("8e426b51"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![create_operation(init)]),
+					Token::Uint(1_000.into()),
+				],
+			);
+			let no_permission = Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "NoPermission".into(),
+				cost: target_gas_limit(Some(1_000_000)).unwrap(),
+			});
+
+			// can't batch for other accounts
+			let other = Context {
+				caller: H160::repeat_byte(0x11),
+				..context()
+			};
+			assert_eq!(
+				BatchPrecompile::execute(&input, Some(1_000_000), &other, false),
+				no_permission
+			);
+
+			// can't be delegate called
+			let delegated = Context {
+				address: H160::repeat_byte(0x22),
+				..context()
+			};
+			assert_eq!(
+				BatchPrecompile::execute(&input, Some(1_000_000), &delegated, false),
+				no_permission
+			);
+
+			assert_eq!(
+				BatchPrecompile::execute(&input, Some(1_000_000), &context(), true),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "Cannot be called in static context".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn batch_all_does_not_count_block_gas() {
+		new_test_ext().execute_with(|| {
+			let init = from_hex("0x6001600c60003960016000f300").unwrap();
+			let input = encode_input(
+				hex!("8e426b51"),
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Array(vec![create_operation(init)]),
+					Token::Uint(1_000.into()),
+				],
+			);
+			let block_gas_used = module_evm::BlockGasUsed::<Test>::get();
+			let resp = BatchPrecompile::execute(&input, Some(1_000_000), &context(), false).unwrap();
+			assert!(resp.cost > 0);
+			// the used gas is counted by the outer execution as the cost
+			assert_eq!(module_evm::BlockGasUsed::<Test>::get(), block_gas_used);
+		});
+	}
+}
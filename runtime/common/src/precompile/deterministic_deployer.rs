@@ -0,0 +1,274 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	ensure_non_static,
+	input::{Input, InputPricer, InputT, Output},
+	target_gas_limit,
+};
+use frame_support::log;
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The `DeterministicDeployer` impl precompile.
+///
+/// The contracts are created by CREATE2 from the address of this precompile, so the addresses
+/// only depend on the salt and the init code, and are the same on every network. A salt starting
+/// with a non-zero address is reserved for that address.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Compute address. Rest `input` bytes: `salt`, `code_hash`.
+/// - Deploy. Rest `input` bytes: `salt`, `init`, `storage_limit`. The caller pays the storage
+///   deposit, and becomes the maintainer of the contract if the salt is reserved for it. Can't be
+///   nested or called in a static context.
+pub struct DeterministicDeployerPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	ComputeAddress = "computeAddress(bytes32,bytes32)",
+	Deploy = "deploy(bytes32,bytes,uint32)",
+}
+
+impl<Runtime> Precompile for DeterministicDeployerPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			<Runtime as module_evm::Config>::AddressMapping,
+			Runtime::Erc20InfoMapping,
+		>::new(input, target_gas_limit(target_gas));
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		match action {
+			Action::ComputeAddress => {
+				let salt = H256::from_slice(&input.bytes_at(1, 32)?);
+				let code_hash = H256::from_slice(&input.bytes_at(2, 32)?);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_address(module_evm::deterministic_address(&salt, &code_hash)),
+					logs: Default::default(),
+				})
+			}
+			Action::Deploy => {
+				ensure_non_static(is_static, target_gas)?;
+
+				let salt = H256::from_slice(&input.bytes_at(1, 32)?);
+				let init = input.dynamic_bytes_at(2)?;
+				let storage_limit = input.u32_at(3)?;
+				let gas_limit = target_gas.unwrap_or_default().saturating_sub(gas_cost);
+				log::debug!(
+					target: "evm",
+					"deterministic deployer: deploy caller: {:?}, salt: {:?}, gas_limit: {:?}, storage_limit: {:?}",
+					context.caller, salt, gas_limit, storage_limit
+				);
+
+				let info = module_evm::Pallet::<Runtime>::deploy_deterministic(
+					context.caller,
+					init,
+					salt,
+					gas_limit,
+					storage_limit,
+					vec![],
+					<Runtime as module_evm::Config>::config(),
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				if !info.exit_reason.is_succeed() {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "deploy failed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost.saturating_add(info.used_gas.low_u64()),
+					output: Output::encode_address(info.value),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	const BASE_COST: u64 = 200;
+	/// The cost of hashing a word of the init code, the same as CREATE2.
+	const INIT_CODE_WORD_COST: u64 = 6;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost = match action {
+			Action::ComputeAddress => 0,
+			Action::Deploy => {
+				// the gas used by the deployment is charged after the execution
+				let init_len = input.dynamic_bytes_at(2)?.len() as u64;
+				let words = init_len.saturating_add(31) / 32;
+				InputPricer::<Runtime>::read_accounts(1).saturating_add(Self::INIT_CODE_WORD_COST.saturating_mul(words))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::precompile::mock::{alice_evm_addr, bob_evm_addr, new_test_ext, EVMModule, Test};
+	use ethabi::Token;
+	use hex_literal::hex;
+	use module_evm::code_hash;
+	use sp_core::{bytes::from_hex, H160};
+
+	type DeterministicDeployerPrecompile = super::DeterministicDeployerPrecompile<Test>;
+
+	fn context(caller: H160) -> Context {
+		Context {
+			address: Default::default(),
+			caller,
+			apparent_value: Default::default(),
+		}
+	}
+
+	fn encode_input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+		let mut input = selector.to_vec();
+		input.extend(ethabi::encode(params));
+		input
+	}
+
+	#[test]
+	fn deploy_works() {
+		new_test_ext().execute_with(|| {
+			// pragma solidity ^0.5.0;
+			//
+			// contract Test {
+			//	 function multiply(uint a, uint b) public pure returns(uint) {
+			// 	 	return a * b;
+			// 	 }
+			// }
+			let init = from_hex(
+				"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+			).unwrap();
+			let mut salt = [0u8; 32];
+			salt[..20].copy_from_slice(alice_evm_addr().as_bytes());
+			salt[31] = 1;
+
+			// computeAddress(bytes32,bytes32) -> 0x481286e6
+			let input = encode_input(
+				hex!("481286e6"),
+				&[
+					Token::FixedBytes(salt.to_vec()),
+					Token::FixedBytes(code_hash(&init).as_bytes().to_vec()),
+				],
+			);
+			let resp = DeterministicDeployerPrecompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			let address = module_evm::deterministic_address(&salt.into(), &code_hash(&init));
+			assert_eq!(resp.output, Output::encode_address(address));
+
+			// deploy(bytes32,bytes,uint32) -> 0x5a4439a2
+			let input = encode_input(
+				hex!("5a4439a2"),
+				&[
+					Token::FixedBytes(salt.to_vec()),
+					Token::Bytes(init),
+					Token::Uint(1_000.into()),
+				],
+			);
+
+			// the salt is reserved by alice
+			assert_eq!(
+				DeterministicDeployerPrecompile::execute(&input, Some(1_000_000), &context(bob_evm_addr()), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "SaltReserved".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				})
+			);
+
+			let resp =
+				DeterministicDeployerPrecompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), false)
+					.unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_address(address));
+			assert_eq!(
+				EVMModule::accounts(address)
+					.and_then(|account| account.contract_info)
+					.map(|contract_info| contract_info.maintainer),
+				Some(alice_evm_addr())
+			);
+
+			// can't deploy to the same address again
+			assert!(
+				DeterministicDeployerPrecompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), false)
+					.is_err()
+			);
+
+			// can't deploy in a static context
+			assert_eq!(
+				DeterministicDeployerPrecompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), true),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "Cannot be called in static context".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+}
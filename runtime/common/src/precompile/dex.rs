@@ -26,12 +26,19 @@ use module_dex::WeightInfo;
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
-	Context, ExitError, ExitRevert, ExitSucceed,
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
 };
-use module_support::{DEXManager, SwapLimit};
+use module_support::{AddressMapping, DEXManager, SwapLimit};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use primitives::{Balance, CurrencyId};
-use sp_runtime::{traits::Convert, RuntimeDebug};
+use primitives::{
+	evm::{EvmAddress, ERC20_TRANSFER_TOPIC},
+	Balance, CurrencyId, TradingPair,
+};
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{AccountIdConversion, Convert},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// The `DEX` impl precompile.
@@ -43,6 +50,8 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
 /// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
 ///   `supply_amount`, `min_target_amount`.
+///
+/// Swaps and adding and removing liquidity emit the ERC20 `Transfer` logs of the moved tokens.
 pub struct DEXPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -195,20 +204,24 @@ where
 					who, path, supply_amount, min_target_amount
 				);
 
-				let (_, value) =
-					<module_dex::Pallet<Runtime> as DEXManager<Runtime::AccountId, Balance, CurrencyId>>::swap_with_specific_path(&who, &path, SwapLimit::ExactSupply(supply_amount, min_target_amount))
-					.map_err(|e|
-							 PrecompileFailure::Revert {
-								 exit_status: ExitRevert::Reverted,
-								 output: Into::<&str>::into(e).as_bytes().to_vec(),
-								 cost: target_gas_limit(target_gas).unwrap_or_default(),
-							 })?;
+				let (supply, value) = <module_dex::Pallet<Runtime> as DEXManager<
+					Runtime::AccountId,
+					Balance,
+					CurrencyId,
+				>>::swap_with_specific_path(
+					&who, &path, SwapLimit::ExactSupply(supply_amount, min_target_amount)
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
 					cost: gas_cost,
 					output: Output::encode_uint(value),
-					logs: Default::default(),
+					logs: swap_logs::<Runtime>(&path, input.evm_address_at(1)?, supply, value),
 				})
 			}
 			Action::SwapWithExactTarget => {
@@ -227,20 +240,24 @@ where
 					who, path, target_amount, max_supply_amount
 				);
 
-				let (value, _) =
-					<module_dex::Pallet<Runtime> as DEXManager<Runtime::AccountId, Balance, CurrencyId>>::swap_with_specific_path(&who, &path, SwapLimit::ExactTarget(max_supply_amount, target_amount))
-					.map_err(|e|
-							 PrecompileFailure::Revert {
-								 exit_status: ExitRevert::Reverted,
-								 output: Into::<&str>::into(e).as_bytes().to_vec(),
-								 cost: target_gas_limit(target_gas).unwrap_or_default(),
-							 })?;
+				let (value, target) = <module_dex::Pallet<Runtime> as DEXManager<
+					Runtime::AccountId,
+					Balance,
+					CurrencyId,
+				>>::swap_with_specific_path(
+					&who, &path, SwapLimit::ExactTarget(max_supply_amount, target_amount)
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
 					cost: gas_cost,
 					output: Output::encode_uint(value),
-					logs: Default::default(),
+					logs: swap_logs::<Runtime>(&path, input.evm_address_at(1)?, value, target),
 				})
 			}
 			Action::AddLiquidity => {
@@ -257,7 +274,11 @@ where
 					who, currency_id_a, currency_id_b, max_amount_a, max_amount_b, min_share_increment,
 				);
 
-				<module_dex::Pallet<Runtime> as DEXManager<Runtime::AccountId, Balance, CurrencyId>>::add_liquidity(
+				let (amount_a, amount_b, share_increment) = <module_dex::Pallet<Runtime> as DEXManager<
+					Runtime::AccountId,
+					Balance,
+					CurrencyId,
+				>>::add_liquidity(
 					&who,
 					currency_id_a,
 					currency_id_b,
@@ -272,11 +293,24 @@ where
 					cost: target_gas_limit(target_gas).unwrap_or_default(),
 				})?;
 
+				let who_address = input.evm_address_at(1)?;
+				let dex = dex_address::<Runtime>();
+				let logs = transfer_logs(&[
+					(Some(currency_id_a), who_address, dex, amount_a),
+					(Some(currency_id_b), who_address, dex, amount_b),
+					(
+						dex_share_currency_id(currency_id_a, currency_id_b),
+						H160::zero(),
+						who_address,
+						share_increment,
+					),
+				]);
+
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
 					cost: gas_cost,
 					output: vec![],
-					logs: Default::default(),
+					logs,
 				})
 			}
 			Action::RemoveLiquidity => {
@@ -293,7 +327,11 @@ where
 					who, currency_id_a, currency_id_b, remove_share, min_withdrawn_a, min_withdrawn_b,
 				);
 
-				<module_dex::Pallet<Runtime> as DEXManager<Runtime::AccountId, Balance, CurrencyId>>::remove_liquidity(
+				let (withdrawn_a, withdrawn_b) = <module_dex::Pallet<Runtime> as DEXManager<
+					Runtime::AccountId,
+					Balance,
+					CurrencyId,
+				>>::remove_liquidity(
 					&who,
 					currency_id_a,
 					currency_id_b,
@@ -308,17 +346,77 @@ where
 					cost: target_gas_limit(target_gas).unwrap_or_default(),
 				})?;
 
+				let who_address = input.evm_address_at(1)?;
+				let dex = dex_address::<Runtime>();
+				let logs = transfer_logs(&[
+					(
+						dex_share_currency_id(currency_id_a, currency_id_b),
+						who_address,
+						H160::zero(),
+						remove_share,
+					),
+					(Some(currency_id_a), dex, who_address, withdrawn_a),
+					(Some(currency_id_b), dex, who_address, withdrawn_b),
+				]);
+
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
 					cost: gas_cost,
 					output: vec![],
-					logs: Default::default(),
+					logs,
 				})
 			}
 		}
 	}
 }
 
+/// The EVM address of the DEX account, which holds the tokens of the liquidity pools.
+fn dex_address<Runtime>() -> H160
+where
+	Runtime: module_evm::Config + module_dex::Config,
+{
+	let dex: Runtime::AccountId = <Runtime as module_dex::Config>::PalletId::get().into_account_truncating();
+	<Runtime as module_evm::Config>::AddressMapping::get_evm_address(&dex)
+		.unwrap_or_else(|| <Runtime as module_evm::Config>::AddressMapping::get_default_evm_address(&dex))
+}
+
+fn dex_share_currency_id(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<CurrencyId> {
+	TradingPair::from_currency_ids(currency_id_a, currency_id_b).map(|pair| pair.dex_share_currency_id())
+}
+
+/// The `Transfer` logs of the supply and the target of a swap by `who`.
+fn swap_logs<Runtime>(path: &[CurrencyId], who: H160, supply_amount: Balance, target_amount: Balance) -> Vec<Log>
+where
+	Runtime: module_evm::Config + module_dex::Config,
+{
+	let dex = dex_address::<Runtime>();
+	transfer_logs(&[
+		(path.first().copied(), who, dex, supply_amount),
+		(path.last().copied(), dex, who, target_amount),
+	])
+}
+
+/// The ERC20 `Transfer` logs of the tokens moved by the DEX. The DEX predeploy only emits its own
+/// events, so the logs are emitted from the addresses of the tokens, as the token predeploys do
+/// for their transfers. The zero address is the `from` of minting and the `to` of burning. The
+/// ERC20 tokens are skipped as their contracts emit the logs.
+fn transfer_logs(transfers: &[(Option<CurrencyId>, H160, H160, Balance)]) -> Vec<Log> {
+	transfers
+		.iter()
+		.filter_map(|(currency_id, from, to, amount)| {
+			let currency_id = (*currency_id)?;
+			if currency_id.is_erc20_currency_id() {
+				return None;
+			}
+			Some(Log {
+				address: EvmAddress::try_from(currency_id).ok()?,
+				topics: vec![ERC20_TRANSFER_TOPIC, H256::from(*from), H256::from(*to)],
+				data: Output::encode_uint(*amount),
+			})
+		})
+		.collect()
+}
+
 struct Pricer<R>(PhantomData<R>);
 
 impl<Runtime> Pricer<Runtime>
@@ -736,6 +834,30 @@ mod tests {
 			let resp = DEXPrecompile::execute(&input, None, &context, false).unwrap();
 			assert_eq!(resp.exit_status, ExitSucceed::Returned);
 			assert_eq!(resp.output, expected_output.to_vec());
+			let dex = dex_address::<Test>();
+			assert_eq!(
+				resp.logs,
+				vec![
+					Log {
+						address: H160::from(hex!("0000000000000000000100000000000000000014")),
+						topics: vec![
+							H256::from(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")),
+							H256::from(alice_evm_addr()),
+							H256::from(dex),
+						],
+						data: Output::encode_uint(1u128),
+					},
+					Log {
+						address: H160::from(hex!("0000000000000000000100000000000000000001")),
+						topics: vec![
+							H256::from(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")),
+							H256::from(dex),
+							H256::from(alice_evm_addr()),
+						],
+						data: Output::encode_uint(989u128),
+					},
+				]
+			);
 		});
 	}
 
@@ -0,0 +1,438 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT, Output},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use ethabi::Token;
+use frame_support::{log, traits::Get};
+use module_earning::{BondingLedgerOf, WeightInfo};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed,
+};
+use module_support::EarningManager;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::Balance;
+use sp_core::U256;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	Permill, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The Earning precompile
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Bond. Rest `input` bytes: `who`, `amount`.
+/// - Unbond. Rest `input` bytes: `who`, `amount`.
+/// - Unbond instantly. Rest `input` bytes: `who`, `amount`.
+/// - Rebond. Rest `input` bytes: `who`, `amount`.
+/// - Withdraw unbonded. Rest `input` bytes: `who`.
+/// - Get bonding ledger. Rest `input` bytes: `who`.
+/// - Get min bond.
+/// - Get unbonding period.
+/// - Get max unbonding chunks.
+/// - Get instant unstake fee.
+pub struct EarningPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Bond = "bond(address,uint256)",
+	Unbond = "unbond(address,uint256)",
+	UnbondInstant = "unbondInstant(address,uint256)",
+	Rebond = "rebond(address,uint256)",
+	WithdrawUnbonded = "withdrawUnbonded(address)",
+	GetBondingLedger = "getBondingLedger(address)",
+	GetMinBond = "getMinBond()",
+	GetUnbondingPeriod = "getUnbondingPeriod()",
+	GetMaxUnbondingChunks = "getMaxUnbondingChunks()",
+	GetInstantUnstakeFee = "getInstantUnstakeFee()",
+}
+
+impl<Runtime> Precompile for EarningPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_earning::Config + module_prices::Config,
+	module_earning::Pallet<Runtime>: EarningManager<
+		Runtime::AccountId,
+		Balance,
+		BondingLedgerOf<Runtime>,
+		Moment = Runtime::BlockNumber,
+		FeeRatio = Permill,
+	>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			<Runtime as module_evm::Config>::AddressMapping,
+			Runtime::Erc20InfoMapping,
+		>::new(input, target_gas_limit(target_gas));
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		match action {
+			Action::Bond | Action::Unbond | Action::UnbondInstant | Action::Rebond => {
+				let who = input.account_id_at(1)?;
+				let amount = input.balance_at(2)?;
+
+				log::debug!(
+					target: "evm",
+					"earning: {:?}, who: {:?}, amount: {:?}",
+					action, &who, amount
+				);
+
+				let result = match action {
+					Action::Bond => <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::bond(who, amount),
+					Action::Unbond => <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::unbond(who, amount),
+					Action::UnbondInstant => {
+						<module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::unbond_instant(who, amount)
+					}
+					_ => <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::rebond(who, amount),
+				};
+				let changed = result.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(changed),
+					logs: Default::default(),
+				})
+			}
+			Action::WithdrawUnbonded => {
+				let who = input.account_id_at(1)?;
+
+				log::debug!(
+					target: "evm",
+					"earning: withdraw_unbonded, who: {:?}",
+					&who
+				);
+
+				let withdrawn = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::withdraw_unbonded(who)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(withdrawn),
+					logs: Default::default(),
+				})
+			}
+			Action::GetBondingLedger => {
+				let who = input.account_id_at(1)?;
+				let ledger = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::get_bonding_ledger(who);
+
+				let unlocking = ledger
+					.unlocking()
+					.iter()
+					.map(|chunk| {
+						let unlock_at: u128 = chunk.unlock_at().unique_saturated_into();
+						Token::Tuple(vec![
+							Token::Uint(U256::from(chunk.value())),
+							Token::Uint(U256::from(unlock_at)),
+						])
+					})
+					.collect();
+
+				// (uint256 total, uint256 active, (uint256 value, uint256 unlockAt)[] unlocking)
+				let output = ethabi::encode(&[Token::Tuple(vec![
+					Token::Uint(U256::from(ledger.total())),
+					Token::Uint(U256::from(ledger.active())),
+					Token::Array(unlocking),
+				])]);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output,
+					logs: Default::default(),
+				})
+			}
+			Action::GetMinBond => {
+				let amount = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::get_min_bond();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(amount),
+					logs: Default::default(),
+				})
+			}
+			Action::GetUnbondingPeriod => {
+				let period: u128 = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::get_unbonding_period()
+					.unique_saturated_into();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(period),
+					logs: Default::default(),
+				})
+			}
+			Action::GetMaxUnbondingChunks => {
+				let chunks = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::get_max_unbonding_chunks();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(chunks),
+					logs: Default::default(),
+				})
+			}
+			Action::GetInstantUnstakeFee => {
+				let fee = <module_earning::Pallet<Runtime> as EarningManager<_, _, _>>::get_instant_unstake_fee();
+
+				// (parts, accuracy) of Permill
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint_tuple(vec![fee.deconstruct(), Permill::ACCURACY]),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_earning::Config + module_prices::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost: u64 = match action {
+			Action::Bond => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_earning::Config>::WeightInfo::bond();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::Unbond => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_earning::Config>::WeightInfo::unbond();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::UnbondInstant => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_earning::Config>::WeightInfo::unbond_instant();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::Rebond => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_earning::Config>::WeightInfo::rebond();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::WithdrawUnbonded => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_earning::Config>::WeightInfo::withdraw_unbonded();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetBondingLedger => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// Earning::Ledger (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetMinBond
+			| Action::GetUnbondingPeriod
+			| Action::GetMaxUnbondingChunks
+			| Action::GetInstantUnstakeFee => {
+				// constants
+				0
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::{alice, alice_evm_addr, new_test_ext, Balances, System, Test};
+	use frame_support::{assert_ok, traits::Currency};
+	use hex_literal::hex;
+
+	type EarningPrecompile = super::EarningPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn bond_and_unbond_works() {
+		new_test_ext().execute_with(|| {
+			let free = Balances::free_balance(alice());
+
+			// bond(address,uint256) -> 0xa515366a
+			// who
+			// amount
+			let input = hex! {"
+				a515366a
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 000000000000000000000000000f4240
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, Output::encode_uint(1_000_000u128));
+			assert_eq!(Balances::free_balance(alice()), free);
+			assert_eq!(Balances::usable_balance(alice()), free - 1_000_000);
+
+			// unbond(address,uint256) -> 0xa5d059ca
+			// who
+			// amount
+			let input = hex! {"
+				a5d059ca
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 0000000000000000000000000007a120
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(500_000u128));
+
+			// getBondingLedger(address) -> 0x361592d7
+			// who
+			let input = hex! {"
+				361592d7
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(
+				resp.output,
+				ethabi::encode(&[Token::Tuple(vec![
+					Token::Uint(1_000_000.into()),
+					Token::Uint(500_000.into()),
+					Token::Array(vec![Token::Tuple(vec![
+						Token::Uint(500_000.into()),
+						Token::Uint((System::block_number() + 10).into()),
+					])]),
+				])])
+			);
+
+			// rebond(address,uint256) -> 0x92d1b784
+			// who
+			// amount
+			let input = hex! {"
+				92d1b784
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 0000000000000000000000000007a120
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(500_000u128));
+
+			// unbondInstant(address,uint256) -> 0xd15a4d60
+			// who
+			// amount
+			let input = hex! {"
+				d15a4d60
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 000000000000000000000000000f4240
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(900_000u128));
+			assert_eq!(Balances::free_balance(alice()), free - 100_000);
+		});
+	}
+
+	#[test]
+	fn withdraw_unbonded_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(<module_earning::Pallet<Test> as EarningManager<_, _, _>>::bond(
+				alice(),
+				1_000_000
+			));
+			assert_ok!(<module_earning::Pallet<Test> as EarningManager<_, _, _>>::unbond(
+				alice(),
+				1_000_000
+			));
+
+			// withdrawUnbonded(address) -> 0xaeffaa47
+			// who
+			let input = hex! {"
+				aeffaa47
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(0u128));
+
+			System::set_block_number(System::block_number() + 10);
+			let resp = EarningPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(1_000_000u128));
+		});
+	}
+
+	#[test]
+	fn earning_queries_works() {
+		new_test_ext().execute_with(|| {
+			// getMinBond() -> 0x5990dc2b
+			let resp = EarningPrecompile::execute(&hex!("5990dc2b"), None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(100u128));
+
+			// getUnbondingPeriod() -> 0x6fd2c80b
+			let resp = EarningPrecompile::execute(&hex!("6fd2c80b"), None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(10u128));
+
+			// getMaxUnbondingChunks() -> 0x09bfc8a1
+			let resp = EarningPrecompile::execute(&hex!("09bfc8a1"), None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(10u32));
+
+			// getInstantUnstakeFee() -> 0xc3e07c04
+			let resp = EarningPrecompile::execute(&hex!("c3e07c04"), None, &context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint_tuple(vec![100_000u32, 1_000_000u32]));
+		});
+	}
+}
@@ -27,7 +27,10 @@ use module_evm::{
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
 	Context, ExitError, ExitRevert, ExitSucceed, WeightInfo,
 };
-use module_support::EVMManager;
+use module_support::{
+	evm::abi::{self, AbiString},
+	EVMManager,
+};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use primitives::Balance;
 use sp_runtime::{traits::Convert, RuntimeDebug};
@@ -41,6 +44,7 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - QueryNewContractExtraBytes.
 /// - QueryStorageDepositPerByte.
 /// - QueryMaintainer.
+/// - QueryVerification. Rest `input` bytes: `contract`.
 /// - QueryDeveloperDeposit.
 /// - QueryPublicationFee.
 /// - TransferMaintainer. Rest `input` bytes: `from`, `contract`, `new_maintainer`.
@@ -53,6 +57,7 @@ pub enum Action {
 	QueryNewContractExtraBytes = "newContractExtraBytes()",
 	QueryStorageDepositPerByte = "storageDepositPerByte()",
 	QueryMaintainer = "maintainerOf(address)",
+	QueryVerification = "verificationOf(address)",
 	QueryDeveloperDeposit = "developerDeposit()",
 	QueryPublicationFee = "publicationFee()",
 	TransferMaintainer = "transferMaintainer(address,address,address)",
@@ -122,6 +127,33 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::QueryVerification => {
+				let contract = input.evm_address_at(1)?;
+
+				let verification = module_evm::Pallet::<Runtime>::query_verification(contract).map_err(|e| {
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				})?;
+
+				// (codeHash, compilerVersion, settingsHash, metadataHash, abiHash)
+				let output = abi::encode(&(
+					verification.code_hash,
+					AbiString(verification.compiler_version.into_inner()),
+					verification.settings_hash,
+					verification.metadata_hash,
+					verification.abi_hash,
+				));
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output,
+					logs: Default::default(),
+				})
+			}
 			Action::QueryDeveloperDeposit => {
 				let deposit = module_evm::Pallet::<Runtime>::query_developer_deposit();
 				Ok(PrecompileOutput {
@@ -260,6 +292,10 @@ where
 				let weight = PrecompileWeights::<Runtime>::evm_query_maintainer();
 				WeightToGas::convert(weight)
 			}
+			Action::QueryVerification => {
+				let weight = PrecompileWeights::<Runtime>::evm_query_verification();
+				WeightToGas::convert(weight)
+			}
 			Action::QueryDeveloperDeposit => {
 				let weight = PrecompileWeights::<Runtime>::evm_query_developer_deposit();
 				WeightToGas::convert(weight)
@@ -310,12 +346,12 @@ mod tests {
 	use super::*;
 
 	use crate::precompile::mock::{
-		alice_evm_addr, bob, bob_evm_addr, new_test_ext, EVMModule, Event as TestEvent, Origin, System, Test,
+		alice, alice_evm_addr, bob, bob_evm_addr, new_test_ext, EVMModule, Event as TestEvent, Origin, System, Test,
 	};
 	use frame_support::assert_ok;
 	use hex_literal::hex;
 	use module_evm::{ExitReason, Runner};
-	use sp_core::H160;
+	use sp_core::{H160, H256};
 
 	type EVMPrecompile = crate::EVMPrecompile<Test>;
 
@@ -514,4 +550,92 @@ mod tests {
 			}));
 		});
 	}
+
+	#[test]
+	fn verification_of_works() {
+		new_test_ext().execute_with(|| {
+			// pragma solidity ^0.5.0;
+			//
+			// contract Test {
+			//	 function multiply(uint a, uint b) public pure returns(uint) {
+			// 	 	return a * b;
+			// 	 }
+			// }
+			let contract = hex! {"
+				608060405234801561001057600080fd5b5060b88061001f6000396000f3fe60
+				80604052348015600f57600080fd5b506004361060285760003560e01c806316
+				5c4a1614602d575b600080fd5b606060048036036040811015604157600080fd
+				5b8101908080359060200190929190803590602001909291905050506076565b
+				6040518082815260200191505060405180910390f35b60008183029050929150
+				5056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d1
+				6b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032
+			"};
+
+			// create contract
+			let info = <Test as module_evm::Config>::Runner::create(
+				alice_evm_addr(),
+				contract.to_vec(),
+				0,
+				21_000_000,
+				21_000_000,
+				vec![],
+				<Test as module_evm::Config>::config(),
+			)
+			.unwrap();
+			let contract_address = info.value;
+			let code_hash = EVMModule::code_hash_at_address(&contract_address);
+
+			let context = Context {
+				address: Default::default(),
+				caller: bob_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// verificationOf(address) -> 0x1b49547f
+			// contract_address
+			let input = hex! {"
+				1b49547f
+				000000000000000000000000 5f8bd49cd9f0cb2bd5bb9d4320dfe9b61023249d
+			"};
+
+			// reverts if not verified
+			assert_eq!(
+				EVMPrecompile::execute(&input, Some(100_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "ContractNotVerified".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				})
+			);
+
+			assert_ok!(EVMModule::submit_verification(
+				Origin::signed(alice()),
+				contract_address,
+				b"solc-0.5.17".to_vec(),
+				H256::repeat_byte(2),
+				H256::repeat_byte(3),
+				H256::repeat_byte(4),
+			));
+
+			// codeHash
+			// offset of compilerVersion
+			// settingsHash
+			// metadataHash
+			// abiHash
+			// compilerVersion
+			let mut expected_output = code_hash.as_bytes().to_vec();
+			expected_output.extend(hex! {"
+				00000000000000000000000000000000000000000000000000000000000000a0
+				0202020202020202020202020202020202020202020202020202020202020202
+				0303030303030303030303030303030303030303030303030303030303030303
+				0404040404040404040404040404040404040404040404040404040404040404
+				000000000000000000000000000000000000000000000000000000000000000b
+				736f6c632d302e352e3137000000000000000000000000000000000000000000
+			"});
+
+			let resp = EVMPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output);
+		});
+	}
 }
@@ -20,10 +20,13 @@ use frame_support::ensure;
 use sp_std::{marker::PhantomData, result::Result, vec::Vec};
 
 use crate::WeightToGas;
-use ethabi::Token;
+use ethabi::{ParamType, Token};
 use frame_support::traits::Get;
 use module_evm::{runner::state::PrecompileFailure, ExitRevert};
-use module_support::{AddressMapping as AddressMappingT, Erc20InfoMapping as Erc20InfoMappingT};
+use module_support::{
+	evm::abi::{self, AbiCodec},
+	AddressMapping as AddressMappingT, Erc20InfoMapping as Erc20InfoMappingT,
+};
 use primitives::{Balance, CurrencyId, DexShare};
 use sp_core::{H160, U256};
 use sp_runtime::traits::Convert;
@@ -50,11 +53,16 @@ pub trait InputT {
 	fn u256_at(&self, index: usize) -> Result<U256, Self::Error>;
 
 	fn balance_at(&self, index: usize) -> Result<Balance, Self::Error>;
+	/// The balance saturated to `Balance::MAX`, e.g. for the infinite approvals of ERC20.
+	fn saturated_balance_at(&self, index: usize) -> Result<Balance, Self::Error>;
 
 	fn u64_at(&self, index: usize) -> Result<u64, Self::Error>;
 	fn u32_at(&self, index: usize) -> Result<u32, Self::Error>;
 
 	fn bytes_at(&self, start: usize, len: usize) -> Result<Vec<u8>, Self::Error>;
+	fn dynamic_bytes_at(&self, index: usize) -> Result<Vec<u8>, Self::Error>;
+	fn decode_params(&self, types: &[ParamType]) -> Result<Vec<Token>, Self::Error>;
+	fn decode_args<Args: AbiCodec>(&self) -> Result<Args, Self::Error>;
 	fn bool_at(&self, index: usize) -> Result<bool, Self::Error>;
 }
 
@@ -176,6 +184,11 @@ where
 		})
 	}
 
+	fn saturated_balance_at(&self, index: usize) -> Result<Balance, Self::Error> {
+		let param = self.u256_at(index)?;
+		Ok(param.try_into().unwrap_or(Balance::MAX))
+	}
+
 	fn u64_at(&self, index: usize) -> Result<u64, Self::Error> {
 		let param = self.u256_at(index)?;
 		param.try_into().map_err(|_| PrecompileFailure::Revert {
@@ -200,6 +213,58 @@ where
 		Ok(bytes.to_vec())
 	}
 
+	fn dynamic_bytes_at(&self, index: usize) -> Result<Vec<u8>, Self::Error> {
+		// solidity abi encode bytes with an offset at input[index], the offset point to the
+		// length of bytes, and followed by the bytes.
+		let offset = self.u32_at(index)? as usize;
+		ensure!(
+			offset % PER_PARAM_BYTES == 0,
+			PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "invalid bytes offset".into(),
+				cost: self.target_gas.unwrap_or_default(),
+			}
+		);
+
+		let len_index = offset / PER_PARAM_BYTES + 1;
+		let len = self.u32_at(len_index)?;
+		self.bytes_at(len_index + 1, len as usize)
+	}
+
+	fn decode_params(&self, types: &[ParamType]) -> Result<Vec<Token>, Self::Error> {
+		let params = self
+			.content
+			.get(FUNCTION_SELECTOR_LENGTH..)
+			.ok_or_else(|| PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "invalid input".into(),
+				cost: self.target_gas.unwrap_or_default(),
+			})?;
+
+		ethabi::decode(types, params).map_err(|_| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "failed to decode params".into(),
+			cost: self.target_gas.unwrap_or_default(),
+		})
+	}
+
+	fn decode_args<Args: AbiCodec>(&self) -> Result<Args, Self::Error> {
+		let params = self
+			.content
+			.get(FUNCTION_SELECTOR_LENGTH..)
+			.ok_or_else(|| PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "invalid input".into(),
+				cost: self.target_gas.unwrap_or_default(),
+			})?;
+
+		abi::decode(params).map_err(|_| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "failed to decode params".into(),
+			cost: self.target_gas.unwrap_or_default(),
+		})
+	}
+
 	fn bool_at(&self, index: usize) -> Result<bool, Self::Error> {
 		const ONE: U256 = U256([1u64, 0, 0, 0]);
 		let param = self.u256_at(index)?;
@@ -490,6 +555,10 @@ mod tests {
 				cost: 10,
 			})
 		);
+
+		assert_ok!(input.saturated_balance_at(1), 127u128);
+		assert_ok!(input.saturated_balance_at(2), u128::MAX);
+		assert_ok!(input.saturated_balance_at(3), u128::MAX);
 	}
 
 	#[test]
@@ -568,6 +637,110 @@ mod tests {
 		assert_ok!(input.i128_at(6), 0);
 	}
 
+	#[test]
+	fn dynamic_bytes_works() {
+		let data = hex_literal::hex! {"
+			00000000
+			0000000000000000000000000000000000000000000000000000000000000060
+			00000000000000000000000000000000000000000000000000000000000000a0
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+			0102000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000003
+			0304050000000000000000000000000000000000000000000000000000000000
+		"};
+		let input = TestInput::new(&data[..], Some(10));
+		assert_ok!(input.dynamic_bytes_at(1), vec![1, 2]);
+		assert_ok!(input.dynamic_bytes_at(2), vec![3, 4, 5]);
+		assert_eq!(
+			input.dynamic_bytes_at(3),
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "invalid bytes offset".into(),
+				cost: 10,
+			})
+		);
+		assert_eq!(
+			input.dynamic_bytes_at(8),
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "invalid input".into(),
+				cost: 10,
+			})
+		);
+	}
+
+	#[test]
+	fn decode_params_works() {
+		let data = hex_literal::hex! {"
+			00000000
+			000000000000000000000000 1000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000007
+			0000000000000000000000000000000000000000000000000000000000000009
+		"};
+		let input = TestInput::new(&data[..], Some(10));
+		assert_ok!(
+			input.decode_params(&[ParamType::Address, ParamType::Array(Box::new(ParamType::Uint(32)))]),
+			vec![
+				Token::Address(H160::from_str("1000000000000000000000000000000000000001").unwrap()),
+				Token::Array(vec![Token::Uint(7.into()), Token::Uint(9.into())]),
+			]
+		);
+		assert_eq!(
+			input.decode_params(&[
+				ParamType::Address,
+				ParamType::Array(Box::new(ParamType::Uint(32))),
+				ParamType::Uint(256),
+				ParamType::Uint(256),
+				ParamType::Uint(256),
+				ParamType::Uint(256),
+			]),
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "failed to decode params".into(),
+				cost: 10,
+			})
+		);
+	}
+
+	#[test]
+	fn decode_args_works() {
+		let data = hex_literal::hex! {"
+			00000000
+			000000000000000000000000 1000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000007
+			0000000000000000000000000000000000000000000000000000000000000009
+		"};
+		let input = TestInput::new(&data[..], Some(10));
+		assert_ok!(
+			input.decode_args::<(H160, Vec<u32>)>(),
+			(
+				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+				vec![7, 9]
+			)
+		);
+		assert_eq!(
+			input.decode_args::<(H160, Vec<u32>, U256, U256, U256, U256)>(),
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "failed to decode params".into(),
+				cost: 10,
+			})
+		);
+		assert_eq!(
+			input.decode_args::<(H160, Vec<bool>)>(),
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "failed to decode params".into(),
+				cost: 10,
+			})
+		);
+	}
+
 	#[test]
 	fn bool_works() {
 		let data = hex_literal::hex! {"
@@ -23,8 +23,8 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	ord_parameter_types, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly, Everything, InstanceFilter, Nothing, OnFinalize,
-		OnInitialize, SortedMembers,
+		ConstBool, ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly, Everything, InstanceFilter, LockIdentifier,
+		Nothing, OnFinalize, OnInitialize, SortedMembers,
 	},
 	weights::IdentityFee,
 	PalletId, RuntimeDebug,
@@ -34,11 +34,11 @@ use module_cdp_engine::CollateralCurrencyIds;
 use module_evm::{EvmChainId, EvmTask};
 use module_evm_accounts::EvmAddressMapping;
 use module_support::{
-	mocks::MockStableAsset, AddressMapping as AddressMappingT, AuctionManager, DEXIncentives, DispatchableTask,
-	EmergencyShutdown, ExchangeRate, ExchangeRateProvider, HomaSubAccountXcm, PoolId, PriceProvider, Rate,
-	SpecificJointsSwap,
+	mocks::MockStableAsset, AddressMapping as AddressMappingT, AuctionManager, CallBuilder, DEXIncentives,
+	DispatchableTask, EmergencyShutdown, ExchangeRate, ExchangeRateProvider, HomaSubAccountXcm, PoolId, PriceProvider,
+	Rate, SpecificJointsSwap,
 };
-use orml_traits::{parameter_type_with_key, MultiCurrency, MultiReservableCurrency};
+use orml_traits::{parameter_type_with_key, MultiCurrency, MultiReservableCurrency, XcmTransfer};
 pub use primitives::{
 	define_combined_task,
 	evm::{convert_decimals_to_evm, EvmAddress},
@@ -50,10 +50,11 @@ use scale_info::TypeInfo;
 use sp_core::{H160, H256};
 use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, BlockNumberProvider, Convert, IdentityLookup, One as OneT, Zero},
-	AccountId32, DispatchResult, FixedPointNumber, FixedU128, Perbill, Percent, Permill,
+	AccountId32, DispatchError, DispatchResult, FixedPointNumber, FixedU128, Perbill, Percent, Permill,
 };
 use sp_std::prelude::*;
 use xcm::latest::prelude::*;
+use xcm_builder::{EnsureXcmOrigin, FixedWeightBounds, LocationInverter, SignedToAccountId32};
 
 pub type AccountId = AccountId32;
 type Key = CurrencyId;
@@ -182,6 +183,7 @@ impl module_currencies::Config for Test {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type OnDust = ();
+	type EVMLogs = ();
 }
 
 impl module_evm_bridge::Config for Test {
@@ -282,6 +284,7 @@ impl module_transaction_payment::Config for Test {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeePayer = ();
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -378,6 +381,7 @@ impl module_dex::Config for Test {
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = EvmErc20InfoMapping;
 	type WeightInfo = ();
+	type GasToWeight = GasToWeight;
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU32<0>;
@@ -445,8 +449,10 @@ impl module_cdp_engine::Config for Test {
 	type LiquidationEvmBridge = module_evm_bridge::LiquidationEvmBridge<Test>;
 	type PalletId = CDPEnginePalletId;
 	type EvmAddressMapping = module_evm_accounts::EvmAddressMapping<Test>;
+	type Erc20InfoMapping = EvmErc20InfoMapping;
 	type Swap = SpecificJointsSwap<DexModule, AlternativeSwapPathJointList>;
 	type WeightInfo = ();
+	type GasToWeight = GasToWeight;
 }
 
 pub struct MockAuctionManager;
@@ -549,6 +555,7 @@ pub type EvmErc20InfoMapping = module_asset_registry::EvmErc20InfoMapping<Test>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160::repeat_byte(0xff))];
 	pub PrecompilesValue: AllPrecompiles<Test> = AllPrecompiles::<_>::mandala();
 }
 
@@ -572,7 +579,10 @@ impl module_evm::Config for Test {
 	type TransferAll = Currencies;
 	type NewContractExtraBytes = ConstU32<100>;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ConstU32<100>;
+	type UpgradeDelay = ConstU32<10>;
 	type TxFeePerGas = ConstU128<10>;
+	type BlockGasLimit = ConstU64<30_000_000>;
 	type Event = Event;
 	type PrecompilesType = AllPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
@@ -580,6 +590,7 @@ impl module_evm::Config for Test {
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Test>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
@@ -597,6 +608,9 @@ impl module_evm_accounts::Config for Test {
 	type AddressMapping = EvmAddressMapping<Test>;
 	type ChainId = EvmChainId<Test>;
 	type TransferAll = ();
+	type EVMBridge = module_evm_bridge::EVMBridge<Test>;
+	type RotationDelay = ConstU32<10>;
+	type MaxSecondaryAddresses = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -720,6 +734,125 @@ impl module_homa::Config for Test {
 	type WeightInfo = ();
 }
 
+/// mock XCM transfer and router.
+pub struct MockXcm;
+impl XcmTransfer<AccountId, Balance, CurrencyId> for MockXcm {
+	fn transfer(
+		who: AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		Currencies::withdraw(currency_id, &who, amount)
+	}
+
+	fn transfer_multi_asset(
+		who: AccountId,
+		asset: MultiAsset,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		match asset {
+			MultiAsset {
+				id: Concrete(location),
+				fun: Fungible(amount),
+			} if location == MultiLocation::here() => Currencies::withdraw(GetNativeCurrencyId::get(), &who, amount),
+			_ => Err(DispatchError::Other("AssetNotSupported")),
+		}
+	}
+}
+
+impl SendXcm for MockXcm {
+	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+		let dest = dest.into();
+		match dest {
+			MultiLocation {
+				parents: 1,
+				interior: Junctions::Here,
+			} => Ok(()),
+			_ => Err(SendError::CannotReachDestination(dest, msg)),
+		}
+	}
+}
+
+impl ExecuteXcm<Call> for MockXcm {
+	fn execute_xcm_in_credit(
+		_origin: impl Into<MultiLocation>,
+		_message: Xcm<Call>,
+		_weight_limit: Weight,
+		_weight_credit: Weight,
+	) -> Outcome {
+		Outcome::Complete(0)
+	}
+}
+
+parameter_types! {
+	pub const AnyNetwork: NetworkId = NetworkId::Any;
+	pub Ancestry: MultiLocation = Parachain(2000).into();
+	pub const ParachainAccount: AccountId = AccountId::new([100u8; 32]);
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, AnyNetwork>;
+
+impl pallet_xcm::Config for Test {
+	type Event = Event;
+	type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmRouter = MockXcm;
+	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = MockXcm;
+	type XcmTeleportFilter = Nothing;
+	type XcmReserveTransferFilter = Everything;
+	type Weigher = FixedWeightBounds<ConstU64<10>, Call, ConstU32<100>>;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Origin = Origin;
+	type Call = Call;
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+pub struct MockSubAccountLocationConvert;
+impl Convert<u16, MultiLocation> for MockSubAccountLocationConvert {
+	fn convert(_: u16) -> MultiLocation {
+		MultiLocation::parent()
+	}
+}
+
+pub struct MockRelayChainCallBuilder;
+impl CallBuilder for MockRelayChainCallBuilder {
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type RelayChainCall = ();
+
+	fn utility_batch_call(_: Vec<()>) {}
+
+	fn utility_as_derivative_call(_: (), _: u16) {}
+
+	fn staking_bond_extra(_: Balance) {}
+
+	fn staking_unbond(_: Balance) {}
+
+	fn staking_withdraw_unbonded(_: u32) {}
+
+	fn balances_transfer_keep_alive(_: AccountId, _: Balance) {}
+
+	fn finalize_call_into_xcm_message(_: (), _: Balance, _: Weight) -> Xcm<()> {
+		Xcm(vec![])
+	}
+}
+
+impl module_xcm_interface::Config for Test {
+	type Event = Event;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type StakingCurrencyId = StakingCurrencyId;
+	type ParachainAccount = ParachainAccount;
+	type RelayChainUnbondingSlashingSpans = ConstU32<5>;
+	type SovereignSubAccountLocationConvert = MockSubAccountLocationConvert;
+	type RelayChainCallBuilder = MockRelayChainCallBuilder;
+	type XcmTransfer = MockXcm;
+}
+
 impl orml_rewards::Config for Test {
 	type Share = Balance;
 	type Balance = Balance;
@@ -753,6 +886,41 @@ impl module_incentives::Config for Test {
 	type WeightInfo = ();
 }
 
+impl module_aggregated_dex::Config for Test {
+	type DEX = DexModule;
+	type StableAsset = StableAsset;
+	type GovernanceOrigin = EnsureSignedBy<One, AccountId>;
+	type DexSwapJointList = AlternativeSwapPathJointList;
+	type SwapPathLimit = ConstU32<3>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const InstantUnstakeFee: Permill = Permill::from_percent(10);
+	pub const EarningLockIdentifier: LockIdentifier = *b"aca/earn";
+	pub const EarningPalletId: PalletId = PalletId(*b"aca/earn");
+}
+
+impl module_earning::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type OnBonded = ();
+	type OnUnbonded = ();
+	type OnUnstakeFee = ();
+	type MinBond = ConstU128<100>;
+	type UnbondingPeriod = ConstU32<10>;
+	type InstantUnstakeFee = InstantUnstakeFee;
+	type MaxUnbondingChunks = ConstU32<10>;
+	type LockIdentifier = EarningLockIdentifier;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type Incentives = Incentives;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type PalletId = EarningPalletId;
+	type UnbondingVotingPower = ConstBool<false>;
+	type MaxDelegators = ConstU32<10>;
+	type WeightInfo = ();
+}
+
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
 pub const EVA: AccountId = AccountId::new([5u8; 32]);
@@ -829,6 +997,10 @@ frame_support::construct_runtime!(
 		Incentives: module_incentives,
 		Rewards: orml_rewards,
 		StableAsset: nutsfinance_stable_asset,
+		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+		XcmInterface: module_xcm_interface,
+		AggregatedDex: module_aggregated_dex,
+		Earning: module_earning,
 	}
 );
 
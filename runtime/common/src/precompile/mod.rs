@@ -36,10 +36,15 @@ use module_evm::{
 	Context, ExitRevert,
 };
 use module_support::PrecompileCallerFilter as PrecompileCallerFilterT;
+use primitives::evm::evm_address_to_nft_class;
 use sp_core::H160;
 use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
 
+pub mod aggregated_dex;
+pub mod batch;
+pub mod deterministic_deployer;
 pub mod dex;
+pub mod earning;
 pub mod evm;
 pub mod evm_accounts;
 pub mod homa;
@@ -51,19 +56,25 @@ pub mod nft;
 pub mod oracle;
 pub mod schedule;
 pub mod stable_asset;
+pub mod xtokens;
 
 use crate::SystemContractsFilter;
+pub use aggregated_dex::AggregatedDEXPrecompile;
+pub use batch::BatchPrecompile;
+pub use deterministic_deployer::DeterministicDeployerPrecompile;
 pub use dex::DEXPrecompile;
+pub use earning::EarningPrecompile;
 pub use evm::EVMPrecompile;
 pub use evm_accounts::EVMAccountsPrecompile;
 pub use homa::HomaPrecompile;
 pub use honzon::HonzonPrecompile;
 pub use incentives::IncentivesPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
-pub use nft::NFTPrecompile;
+pub use nft::{ERC721Precompile, NFTPrecompile};
 pub use oracle::OraclePrecompile;
 pub use schedule::SchedulePrecompile;
 pub use stable_asset::StableAssetPrecompile;
+pub use xtokens::XtokensPrecompile;
 
 pub const ECRECOVER: H160 = H160(hex!("0000000000000000000000000000000000000001"));
 pub const SHA256: H160 = H160(hex!("0000000000000000000000000000000000000002"));
@@ -92,17 +103,49 @@ pub const HOMA: H160 = H160(hex!("0000000000000000000000000000000000000407"));
 pub const EVM_ACCOUNTS: H160 = H160(hex!("0000000000000000000000000000000000000408"));
 pub const HONZON: H160 = H160(hex!("0000000000000000000000000000000000000409"));
 pub const INCENTIVES: H160 = H160(hex!("000000000000000000000000000000000000040a"));
+pub const XTOKENS: H160 = H160(hex!("000000000000000000000000000000000000040b"));
+pub const AGGREGATED_DEX: H160 = H160(hex!("000000000000000000000000000000000000040c"));
+pub const EARNING: H160 = H160(hex!("000000000000000000000000000000000000040d"));
+pub const BATCH: H160 = H160(hex!("000000000000000000000000000000000000040e"));
+pub const DETERMINISTIC_DEPLOYER: H160 = H160(hex!("000000000000000000000000000000000000040f"));
 
 pub fn target_gas_limit(target_gas: Option<u64>) -> Option<u64> {
 	target_gas.map(|x| x.saturating_div(10).saturating_mul(9)) // 90%
 }
 
-pub struct AllPrecompiles<R> {
+/// Revert the state changing actions called in a static context, e.g. by `STATICCALL`.
+pub fn ensure_non_static(is_static: bool, target_gas: Option<u64>) -> Result<(), PrecompileFailure> {
+	if is_static {
+		return Err(PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "Cannot be called in static context".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		});
+	}
+	Ok(())
+}
+
+/// The precompile of a module the network doesn't have, which is never active.
+pub struct UnavailablePrecompile;
+
+impl Precompile for UnavailablePrecompile {
+	fn execute(_input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		Err(PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "Unavailable".into(),
+			cost: target_gas.unwrap_or_default(),
+		})
+	}
+}
+
+/// The precompiles of the network. The Earning precompile is only available on the networks with
+/// the earning module, the others use `UnavailablePrecompile` for it.
+pub struct AllPrecompiles<R, Earning = EarningPrecompile<R>> {
 	active: BTreeSet<H160>,
-	_marker: PhantomData<R>,
+	_marker: PhantomData<(R, Earning)>,
 }
 
-impl<R> AllPrecompiles<R>
+impl<R, Earning> AllPrecompiles<R, Earning>
 where
 	R: module_evm::Config,
 {
@@ -132,8 +175,13 @@ where
 				// STABLE_ASSET,
 				// HOMA,
 				EVM_ACCOUNTS,
+				DETERMINISTIC_DEPLOYER,
 				/* HONZON
-				 * INCENTIVES */
+				 * INCENTIVES
+				 * XTOKENS
+				 * AGGREGATED_DEX
+				 * EARNING
+				 * BATCH */
 			]),
 			_marker: Default::default(),
 		}
@@ -165,8 +213,13 @@ where
 				// STABLE_ASSET,
 				// HOMA,
 				EVM_ACCOUNTS,
+				DETERMINISTIC_DEPLOYER,
 				/* HONZON
-				 * INCENTIVES */
+				 * INCENTIVES
+				 * XTOKENS
+				 * AGGREGATED_DEX
+				 * EARNING
+				 * BATCH */
 			]),
 			_marker: Default::default(),
 		}
@@ -200,17 +253,23 @@ where
 				EVM_ACCOUNTS,
 				HONZON,
 				INCENTIVES,
+				XTOKENS,
+				AGGREGATED_DEX,
+				EARNING,
+				BATCH,
+				DETERMINISTIC_DEPLOYER,
 			]),
 			_marker: Default::default(),
 		}
 	}
 }
 
-impl<R> PrecompileSet for AllPrecompiles<R>
+impl<R, Earning> PrecompileSet for AllPrecompiles<R, Earning>
 where
 	R: module_evm::Config,
 	MultiCurrencyPrecompile<R>: Precompile,
 	NFTPrecompile<R>: Precompile,
+	ERC721Precompile<R>: Precompile,
 	EVMPrecompile<R>: Precompile,
 	EVMAccountsPrecompile<R>: Precompile,
 	OraclePrecompile<R>: Precompile,
@@ -220,6 +279,11 @@ where
 	HomaPrecompile<R>: Precompile,
 	HonzonPrecompile<R>: Precompile,
 	IncentivesPrecompile<R>: Precompile,
+	XtokensPrecompile<R>: Precompile,
+	AggregatedDEXPrecompile<R>: Precompile,
+	Earning: Precompile,
+	BatchPrecompile<R>: Precompile,
+	DeterministicDeployerPrecompile<R>: Precompile,
 {
 	fn execute(
 		&self,
@@ -276,6 +340,20 @@ where
 		} else if address == SHA3_512 {
 			Some(Sha3FIPS512::execute(input, target_gas, context, is_static))
 		}
+		// ERC-721 facades of NFT classes, callable by any account
+		else if self.is_nft_class_facade(address) {
+			Some(ERC721Precompile::<R>::execute(input, target_gas, context, is_static))
+		}
+		// deterministic deployments, callable by any account
+		else if address == DETERMINISTIC_DEPLOYER {
+			Some(DeterministicDeployerPrecompile::<R>::execute(
+				input, target_gas, context, is_static,
+			))
+		}
+		// batches on behalf of the caller, callable by any account
+		else if address == BATCH {
+			Some(BatchPrecompile::<R>::execute(input, target_gas, context, is_static))
+		}
 		// Acala precompile
 		else {
 			if !SystemContractsFilter::is_allowed(context.caller) {
@@ -326,6 +404,14 @@ where
 				Some(IncentivesPrecompile::<R>::execute(
 					input, target_gas, context, is_static,
 				))
+			} else if address == XTOKENS {
+				Some(XtokensPrecompile::<R>::execute(input, target_gas, context, is_static))
+			} else if address == AGGREGATED_DEX {
+				Some(AggregatedDEXPrecompile::<R>::execute(
+					input, target_gas, context, is_static,
+				))
+			} else if address == EARNING {
+				Some(Earning::execute(input, target_gas, context, is_static))
 			} else {
 				None
 			}
@@ -339,7 +425,14 @@ where
 	}
 
 	fn is_precompile(&self, address: H160) -> bool {
-		self.active.contains(&address)
+		self.active.contains(&address) || self.is_nft_class_facade(address)
+	}
+}
+
+impl<R, Earning> AllPrecompiles<R, Earning> {
+	/// The ERC-721 facades are enabled along with the NFT precompile.
+	fn is_nft_class_facade(&self, address: H160) -> bool {
+		self.active.contains(&NFT) && evm_address_to_nft_class(address).is_some()
 	}
 }
 
@@ -348,3 +441,8 @@ fn ensure_precompile_address_start() {
 	use primitives::evm::PRECOMPILE_ADDRESS_START;
 	assert_eq!(PRECOMPILE_ADDRESS_START, MULTI_CURRENCY);
 }
+
+#[test]
+fn ensure_deterministic_deployer_address() {
+	assert_eq!(primitives::evm::DETERMINISTIC_DEPLOYER, DETERMINISTIC_DEPLOYER);
+}
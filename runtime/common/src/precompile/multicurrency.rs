@@ -21,23 +21,36 @@ use super::{
 	target_gas_limit,
 };
 use crate::WeightToGas;
+use ethabi::Token;
 use frame_support::{
-	log,
+	ensure, log,
 	traits::{Currency, Get},
 };
 use module_currencies::WeightInfo;
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
-	Context, ExitError, ExitRevert, ExitSucceed,
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
 };
-use module_support::Erc20InfoMapping as Erc20InfoMappingT;
+use module_support::{AddressMapping as AddressMappingT, Erc20InfoMapping as Erc20InfoMappingT};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use orml_traits::MultiCurrency as MultiCurrencyT;
-use primitives::{currency::DexShare, Balance, CurrencyId};
-use sp_runtime::{traits::Convert, RuntimeDebug};
+use primitives::{
+	currency::DexShare,
+	evm::{ERC20_APPROVAL_TOPIC, ERC20_TRANSFER_TOPIC},
+	Balance, CurrencyId,
+};
+use sp_core::{H160, H256, U256};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
+/// Half of the order of the secp256k1 curve, the maximum `s` of the non-malleable signatures.
+const SECP256K1N_HALF: [u8; 32] = hex_literal::hex!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+
 /// The `MultiCurrency` impl precompile.
 ///
 ///
@@ -47,6 +60,50 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - Query total issuance.
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Transfer. Rest `input` bytes: `from`, `to`, `amount`.
+/// - Query allowance. Rest `input` bytes: `owner`, `spender`.
+/// - Approve. Rest `input` bytes: `owner`, `spender`, `amount`. Emits the ERC20 `Approval` log.
+/// - Transfer from. Rest `input` bytes: `spender`, `from`, `to`, `amount`. Emits the ERC20
+///   `Transfer` log.
+/// - Permit. Rest `input` bytes: `owner`, `spender`, `value`, `deadline`, `v`, `r`, `s`. The
+///   EIP-2612 permit, signed with the EIP-712 domain of the currency. Emits the ERC20 `Approval`
+///   log.
+/// - Query permit nonce. Rest `input` bytes: `owner`.
+/// - Query EIP-712 domain separator.
+///
+/// The actions are called by the token predeploy (`Token.sol` of the `predeploy-contracts`
+/// submodule) with `msg.sender` as the `from`, `owner` or `spender`. The allowance actions are
+/// only reachable once the submodule is bumped to a `Token.sol` that forwards them:
+///
+/// ```solidity
+/// function allowance(address owner, address spender) public view returns (uint256) {
+///     return MultiCurrency.allowance(owner, spender);
+/// }
+///
+/// function approve(address spender, uint256 amount) public returns (bool) {
+///     MultiCurrency.approve(msg.sender, spender, amount);
+///     return true;
+/// }
+///
+/// function transferFrom(address from, address to, uint256 amount) public returns (bool) {
+///     MultiCurrency.transferFrom(msg.sender, from, to, amount);
+///     return true;
+/// }
+///
+/// function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) public {
+///     MultiCurrency.permit(owner, spender, value, deadline, v, r, s);
+/// }
+///
+/// function nonces(address owner) public view returns (uint256) {
+///     return MultiCurrency.nonces(owner);
+/// }
+///
+/// function DOMAIN_SEPARATOR() public view returns (bytes32) {
+///     return MultiCurrency.DOMAIN_SEPARATOR();
+/// }
+/// ```
+///
+/// The `Approval` and `transferFrom` `Transfer` logs are emitted by the precompile, so
+/// `Token.sol` must not emit them again.
 pub struct MultiCurrencyPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -59,6 +116,12 @@ pub enum Action {
 	QueryTotalIssuance = "totalSupply()",
 	QueryBalance = "balanceOf(address)",
 	Transfer = "transfer(address,address,uint256)",
+	QueryAllowance = "allowance(address,address)",
+	Approve = "approve(address,address,uint256)",
+	TransferFrom = "transferFrom(address,address,address,uint256)",
+	Permit = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+	QueryNonces = "nonces(address)",
+	QueryDomainSeparator = "DOMAIN_SEPARATOR()",
 }
 
 impl<Runtime> Precompile for MultiCurrencyPrecompile<Runtime>
@@ -66,6 +129,8 @@ where
 	Runtime:
 		module_currencies::Config + module_evm::Config + module_prices::Config + module_transaction_payment::Config,
 	module_currencies::Pallet<Runtime>: MultiCurrencyT<Runtime::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
+	<Runtime as module_currencies::Config>::MultiCurrency:
+		MultiCurrencyT<Runtime::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
 {
 	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, _is_static: bool) -> PrecompileResult {
 		let input = Input::<
@@ -176,7 +241,7 @@ where
 			Action::Transfer => {
 				let from = input.account_id_at(1)?;
 				let to = input.account_id_at(2)?;
-				let amount = input.balance_at(3)?;
+				let amount = input.saturated_balance_at(3)?;
 				log::debug!(target: "evm", "multicurrency: transfer from: {:?}, to: {:?}, amount: {:?}", from, to, amount);
 
 				<module_currencies::Pallet<Runtime> as MultiCurrencyT<Runtime::AccountId>>::transfer(
@@ -195,6 +260,173 @@ where
 					exit_status: ExitSucceed::Returned,
 					cost: gas_cost,
 					output: vec![],
+					// the token predeploy emits the `Transfer` log of the transfers it forwards
+					logs: Default::default(),
+				})
+			}
+			Action::QueryAllowance => {
+				let owner = input.account_id_at(1)?;
+				let spender = input.account_id_at(2)?;
+
+				let allowance = module_currencies::Pallet::<Runtime>::allowances((currency_id, owner), spender);
+				log::debug!(target: "evm", "multicurrency: allowance: {:?}", allowance);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(allowance),
+					logs: Default::default(),
+				})
+			}
+			Action::Approve => {
+				let owner = input.account_id_at(1)?;
+				let spender = input.account_id_at(2)?;
+				let amount = input.saturated_balance_at(3)?;
+				log::debug!(target: "evm", "multicurrency: approve owner: {:?}, spender: {:?}, amount: {:?}", owner, spender, amount);
+
+				module_currencies::Pallet::<Runtime>::do_approve(currency_id, &owner, &spender, amount).map_err(
+					|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					},
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_log(
+						context.caller,
+						input.evm_address_at(1)?,
+						input.evm_address_at(2)?,
+						amount,
+					)],
+				})
+			}
+			Action::TransferFrom => {
+				let spender = input.account_id_at(1)?;
+				let from = input.account_id_at(2)?;
+				let to = input.account_id_at(3)?;
+				let amount = input.saturated_balance_at(4)?;
+				log::debug!(target: "evm", "multicurrency: transfer_from spender: {:?}, from: {:?}, to: {:?}, amount: {:?}", spender, from, to, amount);
+
+				module_currencies::Pallet::<Runtime>::do_transfer_from(currency_id, &spender, &from, &to, amount)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![Log {
+						address: context.caller,
+						topics: vec![
+							ERC20_TRANSFER_TOPIC,
+							H256::from(input.evm_address_at(2)?),
+							H256::from(input.evm_address_at(3)?),
+						],
+						data: Output::encode_uint(amount),
+					}],
+				})
+			}
+			Action::Permit => {
+				let owner_address = input.evm_address_at(1)?;
+				let spender_address = input.evm_address_at(2)?;
+				let signed_value = input.u256_at(3)?;
+				let value = input.saturated_balance_at(3)?;
+				let deadline = input.u256_at(4)?;
+				let v = input.u32_at(5)?;
+				let r = input.bytes_at(6, 32)?;
+				let s = input.bytes_at(7, 32)?;
+				log::debug!(target: "evm", "multicurrency: permit owner: {:?}, spender: {:?}, value: {:?}, deadline: {:?}", owner_address, spender_address, value, deadline);
+
+				let revert = |e: &str| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: e.as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				};
+
+				let now: u64 = pallet_timestamp::Pallet::<Runtime>::get().unique_saturated_into();
+				ensure!(deadline >= U256::from(now / 1000), revert("permit expired"));
+				ensure!(v == 27 || v == 28, revert("invalid signature"));
+				// reject the malleable signatures with the high `s`, see EIP-2
+				ensure!(
+					U256::from_big_endian(&s) <= U256::from_big_endian(&SECP256K1N_HALF),
+					revert("invalid signature")
+				);
+
+				let domain_separator =
+					Self::domain_separator(currency_id, context.caller).ok_or_else(|| revert("Get name failed"))?;
+				let nonce = module_currencies::Pallet::<Runtime>::permit_nonces(currency_id, owner_address);
+				let permit_hash = keccak_256(&ethabi::encode(&[
+					Token::FixedBytes(
+						module_evm_utility_macro::keccak256!(
+							"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+						)
+						.to_vec(),
+					),
+					Token::Address(owner_address),
+					Token::Address(spender_address),
+					Token::Uint(signed_value),
+					Token::Uint(nonce.into()),
+					Token::Uint(deadline),
+				]));
+
+				let mut msg = b"\x19\x01".to_vec();
+				msg.extend_from_slice(&domain_separator);
+				msg.extend_from_slice(&permit_hash);
+
+				let mut sig = [0u8; 65];
+				sig[0..32].copy_from_slice(&r);
+				sig[32..64].copy_from_slice(&s);
+				sig[64] = (v - 27) as u8;
+				let signer = secp256k1_ecdsa_recover(&sig, &keccak_256(&msg))
+					.map(|pubkey| H160::from_slice(&keccak_256(&pubkey)[12..]))
+					.map_err(|_| revert("invalid signature"))?;
+				ensure!(signer == owner_address, revert("invalid signature"));
+
+				module_currencies::Pallet::<Runtime>::use_permit_nonce(currency_id, owner_address);
+
+				let owner = <Runtime as module_evm::Config>::AddressMapping::get_account_id(&owner_address);
+				let spender = <Runtime as module_evm::Config>::AddressMapping::get_account_id(&spender_address);
+				module_currencies::Pallet::<Runtime>::do_approve(currency_id, &owner, &spender, value)
+					.map_err(|e| revert(Into::<&str>::into(e)))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_log(context.caller, owner_address, spender_address, value)],
+				})
+			}
+			Action::QueryNonces => {
+				let owner = input.evm_address_at(1)?;
+
+				let nonce = module_currencies::Pallet::<Runtime>::permit_nonces(currency_id, owner);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(nonce),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryDomainSeparator => {
+				let domain_separator =
+					Self::domain_separator(currency_id, context.caller).ok_or_else(|| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "Get name failed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_fixed_bytes(&domain_separator),
 					logs: Default::default(),
 				})
 			}
@@ -202,6 +434,37 @@ where
 	}
 }
 
+impl<Runtime> MultiCurrencyPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	/// The EIP-712 domain separator of the currency, `token` is the ERC20 address of it.
+	fn domain_separator(currency_id: CurrencyId, token: H160) -> Option<[u8; 32]> {
+		let name = Runtime::Erc20InfoMapping::name(currency_id)?;
+
+		Some(keccak_256(&ethabi::encode(&[
+			Token::FixedBytes(
+				module_evm_utility_macro::keccak256!(
+					"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+				)
+				.to_vec(),
+			),
+			Token::FixedBytes(keccak_256(&name).to_vec()),
+			Token::FixedBytes(module_evm_utility_macro::keccak256!("1").to_vec()),
+			Token::Uint(module_evm::Pallet::<Runtime>::chain_id().into()),
+			Token::Address(token),
+		])))
+	}
+}
+
+fn approval_log(token: H160, owner: H160, spender: H160, amount: Balance) -> Log {
+	Log {
+		address: token,
+		topics: vec![ERC20_APPROVAL_TOPIC, H256::from(owner), H256::from(spender)],
+		data: Output::encode_uint(amount),
+	}
+}
+
 struct Pricer<R>(PhantomData<R>);
 
 impl<Runtime> Pricer<Runtime>
@@ -210,6 +473,8 @@ where
 		module_currencies::Config + module_evm::Config + module_prices::Config + module_transaction_payment::Config,
 {
 	const BASE_COST: u64 = 200;
+	// Same as the ECRecover precompile
+	const ECRECOVER_COST: u64 = 3_000;
 
 	fn cost(
 		input: &Input<
@@ -251,6 +516,58 @@ where
 
 				cost.saturating_add(WeightToGas::convert(weight))
 			}
+			Action::QueryAllowance => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// Currencies::Allowances (r: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+				))
+			}
+			Action::Approve => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// Currencies::Allowances (w: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().writes(1),
+				))
+			}
+			Action::TransferFrom => {
+				let cost = InputPricer::<Runtime>::read_accounts(3);
+
+				// transfer weight
+				let weight = if currency_id == <Runtime as module_transaction_payment::Config>::NativeCurrencyId::get()
+				{
+					<Runtime as module_currencies::Config>::WeightInfo::transfer_native_currency()
+				} else {
+					<Runtime as module_currencies::Config>::WeightInfo::transfer_non_native_currency()
+				};
+				// Currencies::Allowances (r: 1, w: 1)
+				let weight =
+					weight.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(1, 1));
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::Permit => {
+				let cost = InputPricer::<Runtime>::read_accounts(2)
+					.saturating_add(Self::erc20_info(currency_id))
+					.saturating_add(Self::ECRECOVER_COST);
+				// EVM::ChainId (r: 1)
+				// Timestamp::Now (r: 1)
+				// Currencies::PermitNonces (r: 1, w: 1)
+				// Currencies::Allowances (w: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads_writes(3, 2),
+				))
+			}
+			Action::QueryNonces => {
+				// Currencies::PermitNonces (r: 1)
+				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+			}
+			Action::QueryDomainSeparator => {
+				// EVM::ChainId (r: 1)
+				Self::erc20_info(currency_id).saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+				))
+			}
 		};
 
 		Ok(Self::BASE_COST.saturating_add(read_currency).saturating_add(cost))
@@ -281,11 +598,12 @@ mod tests {
 	use super::*;
 
 	use crate::precompile::mock::{
-		aca_evm_address, alice, ausd_evm_address, bob, erc20_address_not_exists, lp_aca_ausd_evm_address, new_test_ext,
-		Balances, Test,
+		aca_evm_address, alice, alice_evm_addr, ausd_evm_address, bob, bob_evm_addr, erc20_address_not_exists,
+		lp_aca_ausd_evm_address, new_test_ext, Balances, Currencies, Test, AUSD,
 	};
-	use frame_support::assert_noop;
+	use frame_support::{assert_noop, assert_ok};
 	use hex_literal::hex;
+	use sp_core::{ecdsa, Pair};
 
 	type MultiCurrencyPrecompile = crate::MultiCurrencyPrecompile<Test>;
 
@@ -542,6 +860,7 @@ mod tests {
 			let resp = MultiCurrencyPrecompile::execute(&input, None, &context, false).unwrap();
 			assert_eq!(resp.exit_status, ExitSucceed::Returned);
 			assert_eq!(resp.output, [0u8; 0].to_vec());
+			assert_eq!(resp.logs, vec![]);
 
 			assert_eq!(Balances::free_balance(alice()), from_balance - 1);
 			assert_eq!(Balances::free_balance(bob()), to_balance + 1);
@@ -558,4 +877,200 @@ mod tests {
 			);
 		})
 	}
+
+	fn encode_input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+		let mut input = selector.to_vec();
+		input.extend(ethabi::encode(params));
+		input
+	}
+
+	fn ausd_context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: ausd_evm_address(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn approve_and_transfer_from_works() {
+		new_test_ext().execute_with(|| {
+			// approve(address,address,uint256) -> 0xe1f21c67
+			let input = encode_input(
+				[0xe1, 0xf2, 0x1c, 0x67],
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(100.into()),
+				],
+			);
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				resp.logs,
+				vec![Log {
+					address: ausd_evm_address(),
+					topics: vec![
+						ERC20_APPROVAL_TOPIC,
+						H256::from(alice_evm_addr()),
+						H256::from(bob_evm_addr()),
+					],
+					data: Output::encode_uint(100u128),
+				}]
+			);
+
+			// allowance(address,address) -> 0xdd62ed3e
+			let input = encode_input(
+				[0xdd, 0x62, 0xed, 0x3e],
+				&[Token::Address(alice_evm_addr()), Token::Address(bob_evm_addr())],
+			);
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(100u128));
+
+			// transferFrom(address,address,address,uint256) -> 0x15dacbea
+			let transfer_from = |amount: u128| {
+				encode_input(
+					[0x15, 0xda, 0xcb, 0xea],
+					&[
+						Token::Address(bob_evm_addr()),
+						Token::Address(alice_evm_addr()),
+						Token::Address(bob_evm_addr()),
+						Token::Uint(amount.into()),
+					],
+				)
+			};
+			let alice_balance = Currencies::free_balance(AUSD, &alice());
+			let resp = MultiCurrencyPrecompile::execute(&transfer_from(60), None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.logs[0].topics[0], ERC20_TRANSFER_TOPIC);
+			assert_eq!(Currencies::free_balance(AUSD, &alice()), alice_balance - 60);
+			assert_eq!(Currencies::free_balance(AUSD, &bob()), 60);
+			assert_eq!(
+				module_currencies::Pallet::<Test>::allowances((AUSD, alice()), bob()),
+				40
+			);
+
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&transfer_from(50), Some(1_000_000), &ausd_context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "InsufficientAllowance".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				}
+			);
+
+			// the infinite approval is saturated
+			let input = encode_input(
+				[0xe1, 0xf2, 0x1c, 0x67],
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(U256::MAX),
+				],
+			);
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				module_currencies::Pallet::<Test>::allowances((AUSD, alice()), bob()),
+				u128::MAX
+			);
+		});
+	}
+
+	#[test]
+	fn permit_works() {
+		new_test_ext().execute_with(|| {
+			let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+			let owner = {
+				let sig = pair.sign_prehashed(&[0u8; 32]);
+				let pubkey = secp256k1_ecdsa_recover(&sig.0, &[0u8; 32]).unwrap();
+				H160::from_slice(&keccak_256(&pubkey)[12..])
+			};
+
+			// DOMAIN_SEPARATOR() -> 0x3644e515
+			let resp = MultiCurrencyPrecompile::execute(&hex!("3644e515"), None, &ausd_context(), false).unwrap();
+			let domain_separator = resp.output;
+
+			let permit_input = |nonce: u64| {
+				let permit_hash = keccak_256(&ethabi::encode(&[
+					Token::FixedBytes(
+						hex!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9").to_vec(),
+					),
+					Token::Address(owner),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(1_000.into()),
+					Token::Uint(nonce.into()),
+					Token::Uint(U256::MAX),
+				]));
+				let mut msg = b"\x19\x01".to_vec();
+				msg.extend_from_slice(&domain_separator);
+				msg.extend_from_slice(&permit_hash);
+				let sig = pair.sign_prehashed(&keccak_256(&msg));
+
+				// permit(address,address,uint256,uint256,uint8,bytes32,bytes32) -> 0xd505accf
+				encode_input(
+					[0xd5, 0x05, 0xac, 0xcf],
+					&[
+						Token::Address(owner),
+						Token::Address(bob_evm_addr()),
+						Token::Uint(1_000.into()),
+						Token::Uint(U256::MAX),
+						Token::Uint((sig.0[64] + 27).into()),
+						Token::FixedBytes(sig.0[0..32].to_vec()),
+						Token::FixedBytes(sig.0[32..64].to_vec()),
+					],
+				)
+			};
+
+			let input = permit_input(0);
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.logs[0].topics[0], ERC20_APPROVAL_TOPIC);
+
+			let owner_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&owner);
+			assert_eq!(
+				module_currencies::Pallet::<Test>::allowances((AUSD, owner_account), bob()),
+				1_000
+			);
+
+			// nonces(address) -> 0x7ecebe00
+			let input = encode_input([0x7e, 0xce, 0xbe, 0x00], &[Token::Address(owner)]);
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &ausd_context(), false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(1u64));
+
+			// the signature can't be replayed
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&permit_input(0), Some(1_000_000), &ausd_context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid signature".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				}
+			);
+
+			// the malleable signature with the high `s` is rejected
+			let mut input = permit_input(1);
+			let (v_offset, s_offset) = (4 + 32 * 4, 4 + 32 * 6);
+			let n = U256::from_big_endian(&hex!(
+				"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+			));
+			let high_s = n - U256::from_big_endian(&input[s_offset..s_offset + 32]);
+			high_s.to_big_endian(&mut input[s_offset..s_offset + 32]);
+			input[v_offset + 31] = if input[v_offset + 31] == 27 { 28 } else { 27 };
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&input, Some(1_000_000), &ausd_context(), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid signature".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				}
+			);
+			assert_ok!(MultiCurrencyPrecompile::execute(
+				&permit_input(1),
+				None,
+				&ausd_context(),
+				false
+			));
+		});
+	}
 }
@@ -17,24 +17,30 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::{
-	input::{Input, InputT, Output},
+	ensure_non_static,
+	input::{Input, InputPricer, InputT, Output},
 	target_gas_limit,
 };
+use crate::WeightToGas;
 use frame_support::{
-	log,
-	traits::tokens::nonfungibles::{Inspect, Transfer},
+	ensure, log,
+	traits::{
+		tokens::nonfungibles::{Inspect, Transfer},
+		Get,
+	},
 };
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
-	Context, ExitError, ExitRevert, ExitSucceed,
+	Context, ExitError, ExitReason, ExitRevert, ExitSucceed, Log,
 };
-use module_support::AddressMapping;
+use module_nft::{ClassIdOf, TokenIdOf, WeightInfo};
+use module_support::{evm::limits::erc721, AddressMapping, ExecutionMode, InvokeContext, EVM as EVMTrait};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use orml_traits::InspectExtended;
-use primitives::nft::NFTBalance;
-use sp_core::H160;
-use sp_runtime::RuntimeDebug;
+use primitives::{evm::evm_address_to_nft_class, nft::NFTBalance};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Convert, RuntimeDebug};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// The `NFT` impl precompile.
@@ -63,7 +69,7 @@ where
 		+ Inspect<Runtime::AccountId, ItemId = u64, CollectionId = u32>
 		+ Transfer<Runtime::AccountId>,
 {
-	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, is_static: bool) -> PrecompileResult {
 		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
 			input,
 			target_gas_limit(target_gas),
@@ -117,6 +123,8 @@ where
 				})
 			}
 			Action::Transfer => {
+				ensure_non_static(is_static, target_gas)?;
+
 				let from = input.account_id_at(1)?;
 				let to = input.account_id_at(2)?;
 
@@ -159,3 +167,712 @@ where
 		Ok(Self::BASE_COST)
 	}
 }
+
+/// The ERC-721 facade of an NFT class.
+///
+/// Every NFT class is exposed as an ERC-721 contract at its own address, see
+/// `primitives::evm::nft_class_to_evm_address`. `input` data starts with `action`, the caller
+/// is the `msg.sender` of the facade.
+///
+/// Actions:
+/// - Query supported interface. Rest `input` bytes: `interface_id`.
+/// - Query balance. Rest `input` bytes: `owner`.
+/// - Query owner. Rest `input` bytes: `token_id`.
+/// - Query token URI. Rest `input` bytes: `token_id`.
+/// - Query approved. Rest `input` bytes: `token_id`.
+/// - Query approved for all. Rest `input` bytes: `owner`, `operator`.
+/// - Approve. Rest `input` bytes: `approved`, `token_id`.
+/// - Set approval for all. Rest `input` bytes: `operator`, `approved`.
+/// - Transfer from. Rest `input` bytes: `from`, `to`, `token_id`.
+/// - Safe transfer from. Rest `input` bytes: `from`, `to`, `token_id`, (`data`).
+pub struct ERC721Precompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum ERC721Action {
+	SupportsInterface = "supportsInterface(bytes4)",
+	BalanceOf = "balanceOf(address)",
+	OwnerOf = "ownerOf(uint256)",
+	TokenURI = "tokenURI(uint256)",
+	GetApproved = "getApproved(uint256)",
+	IsApprovedForAll = "isApprovedForAll(address,address)",
+	Approve = "approve(address,uint256)",
+	SetApprovalForAll = "setApprovalForAll(address,bool)",
+	TransferFrom = "transferFrom(address,address,uint256)",
+	SafeTransferFrom = "safeTransferFrom(address,address,uint256)",
+	SafeTransferFromWithData = "safeTransferFrom(address,address,uint256,bytes)",
+}
+
+/// ERC-165 interface id.
+const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+/// ERC-721 interface id.
+const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+/// `onERC721Received(address,address,uint256,bytes)` selector.
+const ON_ERC721_RECEIVED_SELECTOR: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+
+impl<Runtime> Precompile for ERC721Precompile<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config + module_nft::Config,
+	module_nft::Pallet<Runtime>: Inspect<Runtime::AccountId, ItemId = u64, CollectionId = u32>,
+	ClassIdOf<Runtime>: From<u32>,
+	TokenIdOf<Runtime>: From<u64>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<ERC721Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let gas_cost = ERC721Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let revert = |e: &str| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: e.as_bytes().to_vec(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		};
+
+		let class_id = evm_address_to_nft_class(context.address).ok_or_else(|| revert("invalid nft class"))?;
+		let caller = Runtime::AddressMapping::get_account_id(&context.caller);
+
+		let action = input.action()?;
+
+		match action {
+			ERC721Action::SupportsInterface => {
+				let interface_id = input.bytes_at(1, 4)?;
+
+				let supported = interface_id == ERC165_INTERFACE_ID || interface_id == ERC721_INTERFACE_ID;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bool(supported),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::BalanceOf => {
+				let who = input.account_id_at(1)?;
+
+				log::debug!(target: "evm", "erc721: balance_of who: {:?}, class_id: {:?}", who, class_id);
+
+				let balance = module_nft::Pallet::<Runtime>::class_balance(&who, class_id.into());
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(balance),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::OwnerOf => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: owner_of class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let owner = module_nft::Pallet::<Runtime>::owner(&class_id, &token_id)
+					.ok_or_else(|| revert("invalid token id"))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_address(Self::evm_address_of(&owner)),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::TokenURI => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: token_uri class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let metadata = module_nft::Pallet::<Runtime>::token_metadata((class_id.into(), token_id.into()))
+					.ok_or_else(|| revert("invalid token id"))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&metadata),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::GetApproved => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: get_approved class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				module_nft::Pallet::<Runtime>::owner(&class_id, &token_id).ok_or_else(|| revert("invalid token id"))?;
+
+				let approved = module_nft::Pallet::<Runtime>::token_approvals(
+					ClassIdOf::<Runtime>::from(class_id),
+					TokenIdOf::<Runtime>::from(token_id),
+				)
+				.map(|who| Self::evm_address_of(&who))
+				.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_address(approved),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::IsApprovedForAll => {
+				let owner = input.account_id_at(1)?;
+				let operator = input.account_id_at(2)?;
+
+				log::debug!(
+					target: "evm",
+					"erc721: is_approved_for_all owner: {:?}, operator: {:?}, class_id: {:?}",
+					owner, operator, class_id
+				);
+
+				let approved = module_nft::Pallet::<Runtime>::is_approved_for_all(&owner, class_id.into(), &operator);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bool(approved),
+					logs: Default::default(),
+				})
+			}
+			ERC721Action::Approve => {
+				ensure_non_static(is_static, target_gas)?;
+
+				let approved_address = input.evm_address_at(1)?;
+				let token_id = input.u64_at(2)?;
+
+				log::debug!(
+					target: "evm",
+					"erc721: approve who: {:?}, approved: {:?}, class_id: {:?}, token_id: {:?}",
+					caller, approved_address, class_id, token_id
+				);
+
+				let owner = module_nft::Pallet::<Runtime>::owner(&class_id, &token_id)
+					.ok_or_else(|| revert("invalid token id"))?;
+				let approved = if approved_address == H160::zero() {
+					None
+				} else {
+					Some(Runtime::AddressMapping::get_account_id(&approved_address))
+				};
+
+				module_nft::Pallet::<Runtime>::do_approve(&caller, approved, (class_id.into(), token_id.into()))
+					.map_err(|e| revert(Into::<&str>::into(e)))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(module_evm_utility_macro::keccak256!(
+								"Approval(address,address,uint256)"
+							)),
+							H256::from(Self::evm_address_of(&owner)),
+							H256::from(approved_address),
+							H256::from_low_u64_be(token_id),
+						],
+						data: vec![],
+					}],
+				})
+			}
+			ERC721Action::SetApprovalForAll => {
+				ensure_non_static(is_static, target_gas)?;
+
+				let operator_address = input.evm_address_at(1)?;
+				let approved = input.bool_at(2)?;
+
+				log::debug!(
+					target: "evm",
+					"erc721: set_approval_for_all owner: {:?}, operator: {:?}, class_id: {:?}, approved: {:?}",
+					caller, operator_address, class_id, approved
+				);
+
+				let operator = Runtime::AddressMapping::get_account_id(&operator_address);
+
+				module_nft::Pallet::<Runtime>::do_set_approval_for_all(&caller, &operator, class_id.into(), approved)
+					.map_err(|e| revert(Into::<&str>::into(e)))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(module_evm_utility_macro::keccak256!(
+								"ApprovalForAll(address,address,bool)"
+							)),
+							H256::from(context.caller),
+							H256::from(operator_address),
+						],
+						data: Output::encode_bool(approved),
+					}],
+				})
+			}
+			ERC721Action::TransferFrom | ERC721Action::SafeTransferFrom | ERC721Action::SafeTransferFromWithData => {
+				ensure_non_static(is_static, target_gas)?;
+
+				let from_address = input.evm_address_at(1)?;
+				let to_address = input.evm_address_at(2)?;
+				let token_id = input.u64_at(3)?;
+
+				log::debug!(
+					target: "evm",
+					"erc721: transfer_from who: {:?}, from: {:?}, to: {:?}, class_id: {:?}, token_id: {:?}",
+					caller, from_address, to_address, class_id, token_id
+				);
+
+				ensure!(
+					to_address != H160::zero(),
+					revert("ERC721: transfer to the zero address")
+				);
+
+				let from = Runtime::AddressMapping::get_account_id(&from_address);
+				let to = Runtime::AddressMapping::get_account_id(&to_address);
+
+				module_nft::Pallet::<Runtime>::do_transfer_from(
+					&caller,
+					&from,
+					&to,
+					(class_id.into(), token_id.into()),
+				)
+				.map_err(|e| revert(Into::<&str>::into(e)))?;
+
+				let mut cost = gas_cost;
+				if action != ERC721Action::TransferFrom && module_evm::Pallet::<Runtime>::is_contract(&to_address) {
+					let data = if action == ERC721Action::SafeTransferFromWithData {
+						input.dynamic_bytes_at(4)?
+					} else {
+						vec![]
+					};
+
+					let remaining_gas = target_gas.map_or(erc721::ON_ERC721_RECEIVED.gas, |gas_limit| {
+						gas_limit.saturating_sub(gas_cost).min(erc721::ON_ERC721_RECEIVED.gas)
+					});
+					let used_gas = Self::check_on_erc721_received(
+						context,
+						from_address,
+						to_address,
+						token_id,
+						data,
+						remaining_gas,
+					)
+					.map_err(|_| revert("ERC721: transfer to non ERC721Receiver implementer"))?;
+					cost = cost.saturating_add(used_gas);
+				}
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(module_evm_utility_macro::keccak256!(
+								"Transfer(address,address,uint256)"
+							)),
+							H256::from(from_address),
+							H256::from(to_address),
+							H256::from_low_u64_be(token_id),
+						],
+						data: vec![],
+					}],
+				})
+			}
+		}
+	}
+}
+
+impl<Runtime> ERC721Precompile<Runtime>
+where
+	Runtime: module_evm::Config + module_nft::Config,
+{
+	fn evm_address_of(who: &Runtime::AccountId) -> H160 {
+		Runtime::AddressMapping::get_evm_address(who)
+			.unwrap_or_else(|| Runtime::AddressMapping::get_default_evm_address(who))
+	}
+
+	/// Call `onERC721Received` of the receiver contract, returns the used gas if it accepts the
+	/// token.
+	fn check_on_erc721_received(
+		context: &Context,
+		from: H160,
+		to: H160,
+		token_id: u64,
+		data: Vec<u8>,
+		gas_limit: u64,
+	) -> Result<u64, ()> {
+		let mut input = ON_ERC721_RECEIVED_SELECTOR.to_vec();
+		input.extend_from_slice(&ethabi::encode(&[
+			ethabi::Token::Address(context.caller),
+			ethabi::Token::Address(from),
+			ethabi::Token::Uint(U256::from(token_id)),
+			ethabi::Token::Bytes(data),
+		]));
+
+		let info = <module_evm::Pallet<Runtime> as EVMTrait<Runtime::AccountId>>::execute(
+			InvokeContext {
+				contract: to,
+				sender: context.address,
+				origin: context.caller,
+			},
+			input,
+			Default::default(),
+			gas_limit,
+			erc721::ON_ERC721_RECEIVED.storage,
+			ExecutionMode::Execute,
+		)
+		.map_err(|_| ())?;
+
+		let accepted = matches!(info.exit_reason, ExitReason::Succeed(_))
+			&& info.value.len() >= 4
+			&& info.value[..4] == ON_ERC721_RECEIVED_SELECTOR;
+		if accepted {
+			Ok(info.used_gas.low_u64())
+		} else {
+			Err(())
+		}
+	}
+}
+
+pub struct ERC721Pricer<R>(PhantomData<R>);
+
+impl<Runtime> ERC721Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config + module_nft::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<ERC721Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost: u64 = match action {
+			ERC721Action::SupportsInterface => 0,
+			ERC721Action::BalanceOf => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// NFT::ClassBalances (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::OwnerOf | ERC721Action::TokenURI => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// OrmlNFT::Tokens (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::GetApproved => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// OrmlNFT::Tokens (r: 1)
+				// NFT::TokenApprovals (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(2);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::IsApprovedForAll => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// NFT::OperatorApprovals (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::Approve => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				let weight = <Runtime as module_nft::Config>::WeightInfo::approve();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::SetApprovalForAll => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				let weight = <Runtime as module_nft::Config>::WeightInfo::set_approval_for_all();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			ERC721Action::TransferFrom | ERC721Action::SafeTransferFrom | ERC721Action::SafeTransferFromWithData => {
+				let cost = InputPricer::<Runtime>::read_accounts(3);
+				let weight = <Runtime as module_nft::Config>::WeightInfo::transfer_from();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::precompile::mock::{
+		alice, alice_evm_addr, bob, bob_evm_addr, new_test_ext, AccountId, Balances, NFTModule, NftPalletId, Origin,
+		Test,
+	};
+	use ethabi::Token;
+	use frame_support::{assert_noop, assert_ok, traits::Currency};
+	use primitives::{
+		evm::nft_class_to_evm_address,
+		nft::{ClassProperty, Properties},
+	};
+	use sp_runtime::traits::AccountIdConversion;
+
+	type ERC721Precompile = super::ERC721Precompile<Test>;
+
+	const CLASS_ID: u32 = 0;
+	const TOKEN_ID: u64 = 0;
+
+	fn context(caller: H160) -> Context {
+		Context {
+			address: nft_class_to_evm_address(CLASS_ID),
+			caller,
+			apparent_value: Default::default(),
+		}
+	}
+
+	fn encode_input(selector: [u8; 4], params: &[Token]) -> Vec<u8> {
+		let mut input = selector.to_vec();
+		input.extend(ethabi::encode(params));
+		input
+	}
+
+	fn create_class_and_mint_to_alice() {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice()),
+			b"class".to_vec(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		let class_account: AccountId = NftPalletId::get().into_sub_account_truncating(CLASS_ID);
+		Balances::deposit_creating(&class_account, 1_000_000_000_000);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_account),
+			alice(),
+			CLASS_ID,
+			b"ipfs://token".to_vec(),
+			Default::default(),
+			1
+		));
+	}
+
+	#[test]
+	fn erc721_queries_works() {
+		new_test_ext().execute_with(|| {
+			create_class_and_mint_to_alice();
+
+			// supportsInterface(bytes4) -> 0x01ffc9a7
+			let input = encode_input(
+				[0x01, 0xff, 0xc9, 0xa7],
+				&[Token::FixedBytes(ERC721_INTERFACE_ID.to_vec())],
+			);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, Output::encode_bool(true));
+
+			// balanceOf(address) -> 0x70a08231
+			let input = encode_input([0x70, 0xa0, 0x82, 0x31], &[Token::Address(alice_evm_addr())]);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_uint(1));
+
+			// ownerOf(uint256) -> 0x6352211e
+			let input = encode_input([0x63, 0x52, 0x21, 0x1e], &[Token::Uint(TOKEN_ID.into())]);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_address(alice_evm_addr()));
+
+			// tokenURI(uint256) -> 0xc87b56dd
+			let input = encode_input([0xc8, 0x7b, 0x56, 0xdd], &[Token::Uint(TOKEN_ID.into())]);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"ipfs://token"));
+
+			// ownerOf(uint256) -> 0x6352211e
+			let input = encode_input([0x63, 0x52, 0x21, 0x1e], &[Token::Uint(1.into())]);
+			assert_noop!(
+				ERC721Precompile::execute(&input, Some(10_000), &context(alice_evm_addr()), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid token id".into(),
+					cost: target_gas_limit(Some(10_000)).unwrap(),
+				}
+			);
+		});
+	}
+
+	#[test]
+	fn approve_and_transfer_from_works() {
+		new_test_ext().execute_with(|| {
+			create_class_and_mint_to_alice();
+
+			// transferFrom(address,address,uint256) -> 0x23b872dd
+			let transfer_input = encode_input(
+				[0x23, 0xb8, 0x72, 0xdd],
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(TOKEN_ID.into()),
+				],
+			);
+			assert_noop!(
+				ERC721Precompile::execute(&transfer_input, Some(1_000_000), &context(bob_evm_addr()), false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "NoPermission".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				}
+			);
+
+			// approve(address,uint256) -> 0x095ea7b3
+			let input = encode_input(
+				[0x09, 0x5e, 0xa7, 0xb3],
+				&[Token::Address(bob_evm_addr()), Token::Uint(TOKEN_ID.into())],
+			);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.logs,
+				vec![Log {
+					address: nft_class_to_evm_address(CLASS_ID),
+					topics: vec![
+						H256::from_slice(&hex_literal::hex!(
+							"8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+						)),
+						H256::from(alice_evm_addr()),
+						H256::from(bob_evm_addr()),
+						H256::from_low_u64_be(TOKEN_ID),
+					],
+					data: vec![],
+				}]
+			);
+			assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), Some(bob()));
+
+			// getApproved(uint256) -> 0x081812fc
+			let input = encode_input([0x08, 0x18, 0x12, 0xfc], &[Token::Uint(TOKEN_ID.into())]);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_address(bob_evm_addr()));
+
+			let res = ERC721Precompile::execute(&transfer_input, None, &context(bob_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.logs,
+				vec![Log {
+					address: nft_class_to_evm_address(CLASS_ID),
+					topics: vec![
+						H256::from_slice(&hex_literal::hex!(
+							"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+						)),
+						H256::from(alice_evm_addr()),
+						H256::from(bob_evm_addr()),
+						H256::from_low_u64_be(TOKEN_ID),
+					],
+					data: vec![],
+				}]
+			);
+			assert_eq!(
+				<module_nft::Pallet<Test> as Inspect<_>>::owner(&CLASS_ID, &TOKEN_ID),
+				Some(bob())
+			);
+			assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		});
+	}
+
+	#[test]
+	fn state_changing_actions_revert_in_static_context() {
+		new_test_ext().execute_with(|| {
+			create_class_and_mint_to_alice();
+
+			let static_revert = PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "Cannot be called in static context".into(),
+				cost: target_gas_limit(Some(1_000_000)).unwrap(),
+			};
+
+			// approve(address,uint256) -> 0x095ea7b3
+			let input = encode_input(
+				[0x09, 0x5e, 0xa7, 0xb3],
+				&[Token::Address(bob_evm_addr()), Token::Uint(TOKEN_ID.into())],
+			);
+			assert_noop!(
+				ERC721Precompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), true),
+				static_revert.clone()
+			);
+
+			// setApprovalForAll(address,bool) -> 0xa22cb465
+			let input = encode_input(
+				[0xa2, 0x2c, 0xb4, 0x65],
+				&[Token::Address(bob_evm_addr()), Token::Bool(true)],
+			);
+			assert_noop!(
+				ERC721Precompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), true),
+				static_revert.clone()
+			);
+
+			// transferFrom(address,address,uint256) -> 0x23b872dd
+			let input = encode_input(
+				[0x23, 0xb8, 0x72, 0xdd],
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(TOKEN_ID.into()),
+				],
+			);
+			assert_noop!(
+				ERC721Precompile::execute(&input, Some(1_000_000), &context(alice_evm_addr()), true),
+				static_revert
+			);
+
+			// queries are allowed
+			// balanceOf(address) -> 0x70a08231
+			let input = encode_input([0x70, 0xa0, 0x82, 0x31], &[Token::Address(alice_evm_addr())]);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), true).unwrap();
+			assert_eq!(res.output, Output::encode_uint(1));
+		});
+	}
+
+	#[test]
+	fn set_approval_for_all_works() {
+		new_test_ext().execute_with(|| {
+			create_class_and_mint_to_alice();
+
+			// setApprovalForAll(address,bool) -> 0xa22cb465
+			let input = encode_input(
+				[0xa2, 0x2c, 0xb4, 0x65],
+				&[Token::Address(bob_evm_addr()), Token::Bool(true)],
+			);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.logs[0].data, Output::encode_bool(true));
+
+			// isApprovedForAll(address,address) -> 0xe985e9c5
+			let input = encode_input(
+				[0xe9, 0x85, 0xe9, 0xc5],
+				&[Token::Address(alice_evm_addr()), Token::Address(bob_evm_addr())],
+			);
+			let res = ERC721Precompile::execute(&input, None, &context(alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_bool(true));
+
+			// safeTransferFrom(address,address,uint256) -> 0x42842e0e
+			let input = encode_input(
+				[0x42, 0x84, 0x2e, 0x0e],
+				&[
+					Token::Address(alice_evm_addr()),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(TOKEN_ID.into()),
+				],
+			);
+			let res = ERC721Precompile::execute(&input, None, &context(bob_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				<module_nft::Pallet<Test> as Inspect<_>>::owner(&CLASS_ID, &TOKEN_ID),
+				Some(bob())
+			);
+		});
+	}
+}
@@ -43,6 +43,11 @@ impl<T: frame_system::Config> PrecompileWeights<T> {
 		(7_088_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 	}
+	// EVMModule::ContractVerifications (r: 1, w: 0)
+	pub fn evm_query_verification() -> Weight {
+		(7_523_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
 	pub fn evm_query_developer_deposit() -> Weight {
 		(1_716_000 as Weight)
 	}
@@ -55,4 +60,34 @@ impl<T: frame_system::Config> PrecompileWeights<T> {
 		(8_506_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 	}
+	// AssetRegistry::ForeignAssetLocations (r: 1, w: 0)
+	// Tokens::Accounts (r: 2, w: 2)
+	// System::Account (r: 2, w: 2)
+	// ParachainSystem::HostConfiguration (r: 1, w: 0)
+	// ParachainSystem::PendingUpwardMessages (r: 1, w: 1)
+	pub fn xtokens_transfer() -> Weight {
+		(75_381_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Tokens::Accounts (r: 2, w: 2)
+	// System::Account (r: 2, w: 2)
+	// ParachainSystem::HostConfiguration (r: 1, w: 0)
+	// ParachainSystem::PendingUpwardMessages (r: 1, w: 1)
+	pub fn xtokens_transfer_multi_asset() -> Weight {
+		(71_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// XcmInterface::AuthorizedXcmSenders (r: 1, w: 0)
+	// PolkadotXcm::SupportedVersion (r: 1, w: 0)
+	// PolkadotXcm::VersionDiscoveryQueue (r: 1, w: 1)
+	// PolkadotXcm::SafeXcmVersion (r: 1, w: 0)
+	// ParachainSystem::HostConfiguration (r: 1, w: 0)
+	// ParachainSystem::PendingUpwardMessages (r: 1, w: 1)
+	pub fn xcm_send() -> Weight {
+		(32_517_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
@@ -0,0 +1,405 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT},
+	target_gas_limit,
+	weights::PrecompileWeights,
+};
+use crate::WeightToGas;
+use codec::{Decode, DecodeLimit};
+use frame_support::log;
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use orml_traits::XcmTransfer;
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{latest::prelude::*, VersionedMultiAsset, VersionedMultiLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+
+/// The Xtokens precompile
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Transfer. Rest `input` bytes: `from`, `currency_id`, `amount`, `dest`, `dest_weight`.
+/// - Transfer multi asset. Rest `input` bytes: `from`, `asset`, `dest`, `dest_weight`.
+/// - Send XCM. Rest `input` bytes: `from`, `dest`, `message`. Only available for the accounts
+///   authorized by governance.
+///
+/// `dest`, `asset` and `message` are SCALE encoded `VersionedMultiLocation`,
+/// `VersionedMultiAsset` and `VersionedXcm`.
+pub struct XtokensPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Transfer = "transfer(address,address,uint256,bytes,uint64)",
+	TransferMultiAsset = "transferMultiAsset(address,bytes,bytes,uint64)",
+	SendXcm = "sendXcm(address,bytes,bytes)",
+}
+
+impl<Runtime> Precompile for XtokensPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config + module_xcm_interface::Config,
+	Runtime::AccountId: Into<[u8; 32]>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			<Runtime as module_evm::Config>::AddressMapping,
+			Runtime::Erc20InfoMapping,
+		>::new(input, target_gas_limit(target_gas));
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		match action {
+			Action::Transfer => {
+				let from = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				let dest = decode_multi_location(&input.dynamic_bytes_at(4)?, target_gas)?;
+				let dest_weight = input.u64_at(5)?;
+
+				log::debug!(
+					target: "evm",
+					"xtokens: transfer, from: {:?}, currency_id: {:?}, amount: {:?}, dest: {:?}, dest_weight: {:?}",
+					&from, currency_id, amount, dest, dest_weight
+				);
+
+				<Runtime as module_xcm_interface::Config>::XcmTransfer::transfer(
+					from,
+					currency_id,
+					amount,
+					dest,
+					dest_weight,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::TransferMultiAsset => {
+				let from = input.account_id_at(1)?;
+				let asset = decode_multi_asset(&input.dynamic_bytes_at(2)?, target_gas)?;
+				let dest = decode_multi_location(&input.dynamic_bytes_at(3)?, target_gas)?;
+				let dest_weight = input.u64_at(4)?;
+
+				log::debug!(
+					target: "evm",
+					"xtokens: transfer_multi_asset, from: {:?}, asset: {:?}, dest: {:?}, dest_weight: {:?}",
+					&from, asset, dest, dest_weight
+				);
+
+				<Runtime as module_xcm_interface::Config>::XcmTransfer::transfer_multi_asset(
+					from,
+					asset,
+					dest,
+					dest_weight,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::SendXcm => {
+				let from = input.account_id_at(1)?;
+				let dest = decode_multi_location(&input.dynamic_bytes_at(2)?, target_gas)?;
+				let message = decode_xcm(&input.dynamic_bytes_at(3)?, target_gas)?;
+
+				log::debug!(
+					target: "evm",
+					"xtokens: send_xcm, from: {:?}, dest: {:?}, message: {:?}",
+					&from, dest, message
+				);
+
+				module_xcm_interface::Pallet::<Runtime>::send_xcm_as(&from, dest, message).map_err(|e| {
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+fn decode_multi_location(bytes: &[u8], target_gas: Option<u64>) -> Result<MultiLocation, PrecompileFailure> {
+	VersionedMultiLocation::decode(&mut &bytes[..])
+		.ok()
+		.and_then(|location| MultiLocation::try_from(location).ok())
+		.ok_or_else(|| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid dest".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		})
+}
+
+fn decode_multi_asset(bytes: &[u8], target_gas: Option<u64>) -> Result<MultiAsset, PrecompileFailure> {
+	VersionedMultiAsset::decode(&mut &bytes[..])
+		.ok()
+		.and_then(|asset| MultiAsset::try_from(asset).ok())
+		.ok_or_else(|| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid asset".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		})
+}
+
+fn decode_xcm(bytes: &[u8], target_gas: Option<u64>) -> Result<Xcm<()>, PrecompileFailure> {
+	VersionedXcm::<()>::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &bytes[..])
+		.ok()
+		.and_then(|message| Xcm::<()>::try_from(message).ok())
+		.ok_or_else(|| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid xcm message".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		})
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_prices::Config + module_xcm_interface::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost: u64 = match action {
+			Action::Transfer => {
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let weight = PrecompileWeights::<Runtime>::xtokens_transfer();
+
+				read_currency
+					.saturating_add(read_account)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::TransferMultiAsset => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = PrecompileWeights::<Runtime>::xtokens_transfer_multi_asset();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::SendXcm => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = PrecompileWeights::<Runtime>::xcm_send();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::{
+		alice, alice_evm_addr, new_test_ext, Currencies, Event, Origin, System, Test, XcmInterface, ACA, ALICE, RENBTC,
+	};
+	use codec::Encode;
+	use frame_support::assert_ok;
+	use hex_literal::hex;
+	use orml_traits::MultiCurrency;
+
+	type XtokensPrecompile = super::XtokensPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	// Append `bytes` to the abi encoded `input` as a dynamic param.
+	fn encode_bytes(input: &mut Vec<u8>, bytes: &[u8]) {
+		let mut len = [0u8; 32];
+		len[24..].copy_from_slice(&(bytes.len() as u64).to_be_bytes());
+		input.extend_from_slice(&len);
+		input.extend_from_slice(bytes);
+		input.resize(input.len() + (32 - bytes.len() % 32) % 32, 0);
+	}
+
+	#[test]
+	fn transfer_works() {
+		new_test_ext().execute_with(|| {
+			let dest = VersionedMultiLocation::from(MultiLocation::new(
+				1,
+				X1(AccountId32 {
+					network: NetworkId::Any,
+					id: [2u8; 32],
+				}),
+			))
+			.encode();
+
+			// transfer(address,address,uint256,bytes,uint64) -> 0xdd2a3599
+			// from
+			// currency
+			// amount
+			// dest offset
+			// dest_weight
+			let mut input = hex! {"
+				dd2a3599
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000014
+				00000000000000000000000000000000 000000000000000000000000000003e8
+				00000000000000000000000000000000 000000000000000000000000000000a0
+				00000000000000000000000000000000 000000000000000000000000000f4240
+			"}
+			.to_vec();
+			encode_bytes(&mut input, &dest);
+
+			let balance = Currencies::free_balance(RENBTC, &alice());
+			let resp = XtokensPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(Currencies::free_balance(RENBTC, &alice()), balance - 1_000);
+
+			// invalid dest
+			let mut input = input[..4 + 32 * 5].to_vec();
+			encode_bytes(&mut input, &[0xff]);
+			assert_eq!(
+				XtokensPrecompile::execute(&input, Some(100_000), &context(), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid dest".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_multi_asset_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), ACA, 1_000_000));
+			let asset = VersionedMultiAsset::from(MultiAsset::from((Here, 1_000u128))).encode();
+			let dest = VersionedMultiLocation::from(MultiLocation::parent()).encode();
+
+			// transferMultiAsset(address,bytes,bytes,uint64) -> 0xc94c06e7
+			// from
+			// asset offset
+			// dest offset
+			// dest_weight
+			let mut input = hex! {"
+				c94c06e7
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000080
+				00000000000000000000000000000000 000000000000000000000000000000c0
+				00000000000000000000000000000000 000000000000000000000000000f4240
+			"}
+			.to_vec();
+			encode_bytes(&mut input, &asset);
+			encode_bytes(&mut input, &dest);
+
+			let balance = Currencies::free_balance(ACA, &alice());
+			let resp = XtokensPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(Currencies::free_balance(ACA, &alice()), balance - 1_000);
+		});
+	}
+
+	#[test]
+	fn send_xcm_works() {
+		new_test_ext().execute_with(|| {
+			let dest = VersionedMultiLocation::from(MultiLocation::parent()).encode();
+			let message = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin])).encode();
+
+			// sendXcm(address,bytes,bytes) -> 0x4df0afd0
+			// from
+			// dest offset
+			// message offset
+			let mut input = hex! {"
+				4df0afd0
+				000000000000000000000000 1000000000000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000060
+				00000000000000000000000000000000 000000000000000000000000000000a0
+			"}
+			.to_vec();
+			encode_bytes(&mut input, &dest);
+			encode_bytes(&mut input, &message);
+
+			assert_eq!(
+				XtokensPrecompile::execute(&input, Some(100_000), &context(), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "XcmSenderNotAuthorized".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				})
+			);
+
+			assert_ok!(XcmInterface::update_authorized_xcm_senders(
+				Origin::signed(ALICE),
+				vec![(alice(), true)]
+			));
+			let resp = XtokensPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			System::assert_last_event(Event::XcmInterface(module_xcm_interface::Event::XcmSent {
+				sender: alice(),
+				dest: MultiLocation::parent(),
+				message: Xcm(vec![ClearOrigin]),
+			}));
+		});
+	}
+}
@@ -322,6 +322,61 @@ fn dex_module_works_with_evm_contract() {
 		});
 }
 
+#[test]
+fn non_standard_erc20_cannot_be_listed() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY, 1_000_000 * dollar(NATIVE_CURRENCY)),
+			(
+				// evm alice
+				MockAddressMapping::get_account_id(&alice_evm_addr()),
+				NATIVE_CURRENCY,
+				1_000_000 * dollar(NATIVE_CURRENCY),
+			),
+		])
+		.build()
+		.execute_with(|| {
+			deploy_erc20_contracts();
+			assert_ok!(AssetRegistry::set_erc20_behaviour(
+				Origin::root(),
+				erc20_address_0(),
+				Erc20Behaviour::FeeOnTransfer
+			));
+
+			assert_noop!(
+				Dex::enable_trading_pair(Origin::root(), CurrencyId::Erc20(erc20_address_0()), NATIVE_CURRENCY),
+				module_dex::Error::<Runtime>::UnsupportedToken
+			);
+			assert_noop!(
+				CdpEngine::set_collateral_params(
+					Origin::root(),
+					CurrencyId::Erc20(erc20_address_0()),
+					Change::NewValue(Some(Default::default())),
+					Change::NoChange,
+					Change::NoChange,
+					Change::NoChange,
+					Change::NewValue(10000),
+				),
+				module_cdp_engine::Error::<Runtime>::UnsupportedCollateral
+			);
+
+			assert_ok!(Dex::enable_trading_pair(
+				Origin::root(),
+				CurrencyId::Erc20(erc20_address_1()),
+				NATIVE_CURRENCY
+			));
+			assert_ok!(CdpEngine::set_collateral_params(
+				Origin::root(),
+				CurrencyId::Erc20(erc20_address_1()),
+				Change::NewValue(Some(Default::default())),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(10000),
+			));
+		});
+}
+
 #[test]
 fn test_evm_module() {
 	ExtBuilder::default()
@@ -807,7 +862,7 @@ fn transaction_payment_module_works_with_evm_contract() {
 	let dollar = dollar(NATIVE_CURRENCY);
 	let alice_evm_account = MockAddressMapping::get_account_id(&alice_evm_addr());
 	let ed = NativeTokenExistentialDeposit::get(); // 100_000_000_000
-											   // new account
+												// new account
 	let empty_account = AccountId::new([1u8; 32]);
 	let empty_address = H160::from_slice(&[1u8; 20]);
 	let empty_address_account = MockAddressMapping::get_account_id(&empty_address);
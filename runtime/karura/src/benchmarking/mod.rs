@@ -59,6 +59,9 @@ pub mod evm {
 pub mod evm_accounts {
 	include!("../../../mandala/src/benchmarking/evm_accounts.rs");
 }
+pub mod evm_paymaster {
+	include!("../../../mandala/src/benchmarking/evm_paymaster.rs");
+}
 pub mod homa {
 	include!("../../../mandala/src/benchmarking/homa.rs");
 }
@@ -66,4 +66,25 @@ impl<T: frame_system::Config> module_evm_accounts::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn claim_contract_account() -> Weight {
+		module_evm_accounts::estimated_weights::claim_contract_account::<T::DbWeight>()
+	}
+	fn rotate_evm_address() -> Weight {
+		module_evm_accounts::estimated_weights::rotate_evm_address::<T::DbWeight>()
+	}
+	fn request_evm_address_rotation() -> Weight {
+		module_evm_accounts::estimated_weights::request_evm_address_rotation::<T::DbWeight>()
+	}
+	fn cancel_evm_address_rotation() -> Weight {
+		module_evm_accounts::estimated_weights::cancel_evm_address_rotation::<T::DbWeight>()
+	}
+	fn execute_evm_address_rotation() -> Weight {
+		module_evm_accounts::estimated_weights::execute_evm_address_rotation::<T::DbWeight>()
+	}
+	fn add_secondary_evm_address() -> Weight {
+		module_evm_accounts::estimated_weights::add_secondary_evm_address::<T::DbWeight>()
+	}
+	fn remove_secondary_evm_address() -> Weight {
+		module_evm_accounts::estimated_weights::remove_secondary_evm_address::<T::DbWeight>()
+	}
 }
@@ -24,7 +24,10 @@ use frame_system::RawOrigin;
 use module_evm::EvmAddress;
 use module_support::AddressMapping;
 use orml_benchmarking::runtime_benchmarks;
-use primitives::{currency::AssetMetadata, TokenSymbol};
+use primitives::{
+	currency::{AssetMetadata, Erc20Behaviour},
+	TokenSymbol,
+};
 use sp_std::{boxed::Box, str::FromStr, vec};
 use xcm::{v1::MultiLocation, VersionedMultiLocation};
 
@@ -145,6 +148,11 @@ runtime_benchmarks! {
 
 		AssetRegistry::register_native_asset(RawOrigin::Root.into(), currency_id, Box::new(asset_metadata.clone()))?;
 	}: _(RawOrigin::Root, currency_id, Box::new(asset_metadata))
+
+	set_erc20_behaviour {
+		deploy_contract();
+		AssetRegistry::register_erc20_asset(RawOrigin::Root.into(), erc20_address(), 1)?;
+	}: _(RawOrigin::Root, erc20_address(), Erc20Behaviour::FeeOnTransfer)
 }
 
 #[cfg(test)]
@@ -18,10 +18,13 @@
 
 use super::utils::{dollar, set_balance, NATIVE};
 use crate::{AccountId, DispatchResult, Earning, Get, NativeTokenExistentialDeposit, Origin, Runtime, System};
-use frame_benchmarking::whitelisted_caller;
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 
+const SEED: u32 = 0;
+
 fn make_max_unbonding_chunk(who: AccountId) -> DispatchResult {
 	System::set_block_number(0);
 	set_balance(NATIVE, &who, 100 * dollar(NATIVE));
@@ -66,6 +69,39 @@ runtime_benchmarks! {
 		// large number to unlock all chunks
 		System::set_block_number(1_000_000);
 	}: _(RawOrigin::Signed(caller))
+
+	claim_rewards {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, dollar(NATIVE));
+		Earning::bond(Origin::signed(caller.clone()), dollar(NATIVE) / 2)?;
+		Earning::set_rewards_per_block(RawOrigin::Root.into(), dollar(NATIVE))?;
+		Earning::on_initialize(1);
+	}: _(RawOrigin::Signed(caller), true)
+
+	set_rewards_per_block {
+	}: _(RawOrigin::Root, dollar(NATIVE))
+
+	on_initialize {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, dollar(NATIVE));
+		Earning::bond(Origin::signed(caller), dollar(NATIVE))?;
+		Earning::set_rewards_per_block(RawOrigin::Root.into(), dollar(NATIVE))?;
+	}: {
+		Earning::on_initialize(1)
+	}
+
+	delegate {
+		let caller: AccountId = whitelisted_caller();
+		let delegate: AccountId = account("delegate", 0, SEED);
+		let previous: AccountId = account("previous", 0, SEED);
+		Earning::delegate(Origin::signed(caller.clone()), previous)?;
+	}: _(RawOrigin::Signed(caller), delegate)
+
+	undelegate {
+		let caller: AccountId = whitelisted_caller();
+		let delegate: AccountId = account("delegate", 0, SEED);
+		Earning::delegate(Origin::signed(caller.clone()), delegate)?;
+	}: _(RawOrigin::Signed(caller))
 }
 
 #[cfg(test)]
@@ -19,14 +19,15 @@
 use crate::{AccountId, Event, EvmAccounts, Origin, Runtime, System, EVM};
 
 use super::utils::{dollar, set_balance, NATIVE};
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, traits::Get, weights::Weight};
 use frame_system::RawOrigin;
 use module_evm::MaxCodeSize;
-use module_support::AddressMapping;
+use module_support::{AddressMapping, DispatchableTask};
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
+use primitives::evm::MaxCompilerVersionLength;
 use sp_core::{H160, H256};
 use sp_io::hashing::keccak_256;
-use sp_std::{str::FromStr, vec};
+use sp_std::{str::FromStr, vec, vec::Vec};
 
 fn contract_addr() -> H160 {
 	H160::from_str("0x5e0b4bfa0b55932a3587e648c3552a6515ba56b1").unwrap()
@@ -244,6 +245,83 @@ runtime_benchmarks! {
 
 		whitelist_account!(alice_account);
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	archive_contract {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		System::set_block_number(1 + <Runtime as module_evm::Config>::ContractExpiryPeriod::get());
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+	verify {
+		assert!(module_evm::ArchivedContracts::<Runtime>::contains_key(contract));
+	}
+
+	revive_contract {
+		let n in 0..100;
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		for i in 0..n {
+			module_evm::AccountStorages::<Runtime>::insert(contract, H256::from_low_u64_be(i.into()), H256::from_low_u64_be(1));
+		}
+		let storage = module_evm::AccountStorages::<Runtime>::iter_prefix(contract).collect::<Vec<_>>();
+
+		System::set_block_number(1 + <Runtime as module_evm::Config>::ContractExpiryPeriod::get());
+		EVM::archive_contract(Origin::signed(alice_account_id()), contract)?;
+		let _ = module_evm::EvmTask::<Runtime>::Archive { contract }.dispatch(Weight::MAX);
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, storage)
+	verify {
+		assert!(EVM::is_contract(&contract));
+	}
+
+	propose_upgrade {
+		let c in 0..MaxCodeSize::get();
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::publish_free(RawOrigin::Root.into(), contract)?;
+
+		let new_contract = vec![0; c as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, new_contract)
+	verify {
+		assert!(module_evm::PendingUpgrades::<Runtime>::contains_key(contract));
+	}
+
+	cancel_upgrade {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::publish_free(RawOrigin::Root.into(), contract)?;
+		EVM::propose_upgrade(Origin::signed(alice_account_id()), contract, vec![0; MaxCodeSize::get() as usize])?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+	verify {
+		assert!(!module_evm::PendingUpgrades::<Runtime>::contains_key(contract));
+	}
+
+	submit_verification {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		let compiler_version = vec![b'0'; MaxCompilerVersionLength::get() as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, compiler_version, H256::repeat_byte(1), H256::repeat_byte(2), H256::repeat_byte(3))
+	verify {
+		assert!(module_evm::ContractVerifications::<Runtime>::contains_key(contract));
+	}
 }
 
 #[cfg(test)]
@@ -16,13 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AccountId, EvmAccounts, Runtime};
+use crate::{AccountId, Event, EvmAccounts, Runtime, System, EVM};
 
 use super::utils::{dollar, set_balance, NATIVE};
-use frame_benchmarking::{account, whitelisted_caller};
+use frame_benchmarking::{account, whitelisted_caller, BenchmarkError};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
+use hex_literal::hex;
 use orml_benchmarking::runtime_benchmarks;
+use sp_core::H160;
 use sp_io::hashing::keccak_256;
+use sp_std::vec;
 
 const SEED: u32 = 0;
 
@@ -34,6 +38,34 @@ fn bob() -> libsecp256k1::SecretKey {
 	libsecp256k1::SecretKey::parse(&keccak_256(b"Bob")).unwrap()
 }
 
+/// Deploys a contract wallet which accepts any signature, its runtime code returns the EIP-1271
+/// magic value for any call.
+fn deploy_contract_wallet(deployer: AccountId) -> Result<H160, BenchmarkError> {
+	System::set_block_number(1);
+	EVM::create(
+		RawOrigin::Signed(deployer).into(),
+		hex!("601080600b6000396000f3631626ba7e60e01b60005260206000f3").to_vec(),
+		0,
+		1_000_000,
+		1_000,
+		vec![],
+	)?;
+
+	match System::events().last().map(|record| &record.event) {
+		Some(Event::EVM(module_evm::Event::Created { contract, .. })) => Ok(*contract),
+		_ => Err("failed to deploy the contract wallet".into()),
+	}
+}
+
+fn claim_alice(caller: &AccountId) -> Result<(), BenchmarkError> {
+	EvmAccounts::claim_account(
+		RawOrigin::Signed(caller.clone()).into(),
+		EvmAccounts::eth_address(&alice()),
+		EvmAccounts::eth_sign(&alice(), caller),
+	)?;
+	Ok(())
+}
+
 pub fn bob_account_id() -> AccountId {
 	let address = EvmAccounts::eth_address(&bob());
 	let mut data = [0u8; 32];
@@ -54,6 +86,73 @@ runtime_benchmarks! {
 	claim_default_account {
 		let caller = whitelisted_caller();
 	}: _(RawOrigin::Signed(caller))
+
+	claim_contract_account {
+		let caller: AccountId = whitelisted_caller();
+		let deployer: AccountId = account("deployer", 0, SEED);
+		set_balance(NATIVE, &deployer, 1_000 * dollar(NATIVE));
+		let contract = deploy_contract_wallet(deployer)?;
+	}: _(RawOrigin::Signed(caller), contract, vec![1u8; 65])
+
+	rotate_evm_address {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller)?;
+		set_balance(NATIVE, &bob_account_id(), 1_000 * dollar(NATIVE));
+		let new_address = EvmAccounts::eth_address(&bob());
+		let old_signature = EvmAccounts::eth_sign_rotation(&alice(), &caller, &new_address);
+		let new_signature = EvmAccounts::eth_sign_rotation(&bob(), &caller, &new_address);
+	}: _(RawOrigin::Signed(caller), new_address, old_signature, new_signature)
+
+	request_evm_address_rotation {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller)?;
+		let new_address = EvmAccounts::eth_address(&bob());
+		let new_signature = EvmAccounts::eth_sign_rotation(&bob(), &caller, &new_address);
+	}: _(RawOrigin::Signed(caller), new_address, new_signature)
+
+	cancel_evm_address_rotation {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller)?;
+		let new_address = EvmAccounts::eth_address(&bob());
+		EvmAccounts::request_evm_address_rotation(
+			RawOrigin::Signed(caller.clone()).into(),
+			new_address,
+			EvmAccounts::eth_sign_rotation(&bob(), &caller, &new_address),
+		)?;
+	}: _(RawOrigin::Signed(caller))
+
+	execute_evm_address_rotation {
+		let caller: AccountId = whitelisted_caller();
+		let who: AccountId = account("who", 0, SEED);
+		claim_alice(&who)?;
+		set_balance(NATIVE, &bob_account_id(), 1_000 * dollar(NATIVE));
+		let new_address = EvmAccounts::eth_address(&bob());
+		EvmAccounts::request_evm_address_rotation(
+			RawOrigin::Signed(who.clone()).into(),
+			new_address,
+			EvmAccounts::eth_sign_rotation(&bob(), &who, &new_address),
+		)?;
+		System::set_block_number(System::block_number() + <Runtime as module_evm_accounts::Config>::RotationDelay::get());
+	}: _(RawOrigin::Signed(caller), who)
+
+	add_secondary_evm_address {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller)?;
+		set_balance(NATIVE, &bob_account_id(), 1_000 * dollar(NATIVE));
+		let eth_address = EvmAccounts::eth_address(&bob());
+		let eth_signature = EvmAccounts::eth_sign_secondary_address(&bob(), &caller);
+	}: _(RawOrigin::Signed(caller), eth_address, eth_signature)
+
+	remove_secondary_evm_address {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller)?;
+		let eth_address = EvmAccounts::eth_address(&bob());
+		EvmAccounts::add_secondary_evm_address(
+			RawOrigin::Signed(caller.clone()).into(),
+			eth_address,
+			EvmAccounts::eth_sign_secondary_address(&bob(), &caller),
+		)?;
+	}: _(RawOrigin::Signed(caller), eth_address)
 }
 
 #[cfg(test)]
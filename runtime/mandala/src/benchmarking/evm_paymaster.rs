@@ -0,0 +1,108 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{AccountId, Event, EvmAccounts, EvmPaymaster, Runtime, System, EVM};
+
+use super::utils::{dollar, set_balance, NATIVE};
+use frame_benchmarking::{whitelisted_caller, BenchmarkError};
+use frame_system::RawOrigin;
+use hex_literal::hex;
+use orml_benchmarking::runtime_benchmarks;
+use sp_core::H160;
+use sp_io::hashing::keccak_256;
+use sp_std::vec;
+
+fn alice() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Alice")).unwrap()
+}
+
+pub fn alice_account_id() -> AccountId {
+	let address = EvmAccounts::eth_address(&alice());
+	let mut data = [0u8; 32];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(&address[..]);
+	AccountId::from(Into::<[u8; 32]>::into(data))
+}
+
+/// Deploys and publishes a paymaster which accepts any call, its runtime code returns `true` for
+/// any call.
+fn deploy_paymaster() -> Result<H160, BenchmarkError> {
+	System::set_block_number(1);
+	set_balance(NATIVE, &alice_account_id(), 1_000 * dollar(NATIVE));
+	EVM::create(
+		RawOrigin::Signed(alice_account_id()).into(),
+		hex!("600a80600b6000396000f3600160005260206000f3").to_vec(),
+		0,
+		1_000_000,
+		1_000,
+		vec![],
+	)?;
+
+	let paymaster = match System::events().last().map(|record| &record.event) {
+		Some(Event::EVM(module_evm::Event::Created { contract, .. })) => *contract,
+		_ => return Err("failed to deploy the paymaster".into()),
+	};
+	EVM::publish_free(RawOrigin::Root.into(), paymaster)?;
+	Ok(paymaster)
+}
+
+fn register_paymaster() -> Result<H160, BenchmarkError> {
+	let paymaster = deploy_paymaster()?;
+	EvmPaymaster::register_paymaster(RawOrigin::Signed(alice_account_id()).into(), paymaster)?;
+	EvmPaymaster::deposit(
+		RawOrigin::Signed(alice_account_id()).into(),
+		paymaster,
+		100 * dollar(NATIVE),
+	)?;
+	Ok(paymaster)
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_evm_paymaster }
+
+	register_paymaster {
+		let paymaster = deploy_paymaster()?;
+	}: _(RawOrigin::Signed(alice_account_id()), paymaster)
+
+	unregister_paymaster {
+		let paymaster = register_paymaster()?;
+	}: _(RawOrigin::Signed(alice_account_id()), paymaster)
+
+	deposit {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(NATIVE, &caller, 1_000 * dollar(NATIVE));
+		let paymaster = register_paymaster()?;
+	}: _(RawOrigin::Signed(caller), paymaster, 100 * dollar(NATIVE))
+
+	withdraw {
+		let paymaster = register_paymaster()?;
+	}: _(RawOrigin::Signed(alice_account_id()), paymaster, 10 * dollar(NATIVE))
+
+	sponsored_call {
+		let caller: AccountId = whitelisted_caller();
+		let paymaster = register_paymaster()?;
+	}: _(RawOrigin::Signed(caller), paymaster, paymaster, vec![], 0, 100_000, 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -21,13 +21,14 @@ use crate::{
 };
 
 use super::utils::{set_balance, LIQUID, STAKING};
+use codec::Encode;
 use frame_benchmarking::{account, whitelisted_caller};
-use frame_support::traits::OnInitialize;
+use frame_support::{storage::migration::put_storage_value, traits::OnInitialize, StorageHasher, Twox64Concat};
 use frame_system::RawOrigin;
-use module_homa::UnlockChunk;
+use module_homa::{migrations::v2, UnlockChunk};
 use orml_benchmarking::runtime_benchmarks;
-use orml_traits::MultiCurrency;
-use sp_runtime::{traits::BlockNumberProvider, FixedPointNumber};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use sp_runtime::{traits::BlockNumberProvider, FixedPointNumber, Permill};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -144,6 +145,41 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Root, updates)
 
 	reset_current_era {}: _(RawOrigin::Root, 1)
+
+	migrate_homa_lite_redeem_request {
+		let redeemer: AccountId = account("redeemer", 0, SEED);
+		let amount = 10_000_000_000_000;
+
+		set_balance(LIQUID, &redeemer, amount * 2);
+		<Currencies as MultiReservableCurrency<_>>::reserve(LIQUID, &redeemer, amount)?;
+		put_storage_value(
+			v2::HOMA_LITE,
+			v2::REDEEM_REQUESTS,
+			&Twox64Concat::hash(&redeemer.encode()),
+			(amount, Permill::from_percent(1)),
+		);
+	}: {
+		assert_eq!(v2::migrate_redeem_requests::<Runtime>(1), 1);
+	}
+
+	withdraw_homa_lite_scheduled_unbonds {
+		let sub_account_index = ActiveSubAccountsIndexList::get().first().unwrap().clone();
+
+		put_storage_value(v2::HOMA_LITE, v2::SCHEDULED_UNBOND, &[], vec![(1_000_000_000_000 as Balance, 1u32)]);
+		put_storage_value(v2::HOMA_LITE, v2::AVAILABLE_STAKING_BALANCE, &[], 1_000_000_000_000 as Balance);
+		RelaychainBlockNumberProvider::<Runtime>::set_block_number(10);
+	}: {
+		assert!(v2::withdraw_expired_scheduled_unbonds::<Runtime>(sub_account_index));
+	}
+
+	migrate_homa_lite_staking_totals {
+		let sub_account_index = ActiveSubAccountsIndexList::get().first().unwrap().clone();
+
+		put_storage_value(v2::HOMA_LITE, v2::TOTAL_STAKING_CURRENCY, &[], 1_000_000_000_000_000 as Balance);
+		put_storage_value(v2::HOMA_LITE, v2::AVAILABLE_STAKING_BALANCE, &[], 1_000_000_000_000 as Balance);
+	}: {
+		v2::migrate_staking_totals::<Runtime>(sub_account_index)?;
+	}
 }
 
 #[cfg(test)]
@@ -20,7 +20,7 @@ use crate::{AccountId, MinCouncilBondThreshold, NomineesElection, Runtime};
 
 use super::utils::{set_balance, LIQUID};
 use frame_benchmarking::{account, whitelisted_caller};
-use frame_support::traits::Get;
+use frame_support::traits::{Get, Hooks};
 use frame_system::RawOrigin;
 use module_support::OnNewEra;
 use orml_benchmarking::runtime_benchmarks;
@@ -83,6 +83,24 @@ runtime_benchmarks! {
 		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
 		NomineesElection::nominate(RawOrigin::Signed(caller.clone()).into(), targets)?;
 	}: _(RawOrigin::Signed(caller))
+
+	on_initialize {
+		let n in 1 .. <Runtime as module_nominees_election::Config>::MaxExpiriesPerBlock::get();
+		let c = <Runtime as module_nominees_election::Config>::NominateesCount::get();
+		let targets = (0..c).map(|c| account("nominatees", c, SEED)).collect::<Vec<AccountId>>();
+
+		for i in 0..n {
+			let nominator: AccountId = account("nominator", i, SEED);
+			set_balance(LIQUID, &nominator, 2 * MinCouncilBondThreshold::get());
+			NomineesElection::bond(RawOrigin::Signed(nominator.clone()).into(), MinCouncilBondThreshold::get())?;
+			NomineesElection::nominate(RawOrigin::Signed(nominator).into(), targets.clone())?;
+		}
+		let expire_at = <Runtime as module_nominees_election::Config>::NominationExpiry::get();
+		module_nominees_election::NextExpiryEra::<Runtime>::put(expire_at);
+		NomineesElection::on_new_era(expire_at);
+	}: {
+		NomineesElection::on_initialize(1);
+	}
 }
 
 #[cfg(test)]
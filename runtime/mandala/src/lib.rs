@@ -72,7 +72,7 @@ use primitives::{
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, H256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
@@ -184,6 +184,8 @@ parameter_types! {
 	pub const StableAssetPalletId: PalletId = PalletId(*b"nuts/sta");
 	// lock identifier for earning module
 	pub const EarningLockIdentifier: LockIdentifier = *b"aca/earn";
+	pub const EarningPalletId: PalletId = PalletId(*b"aca/earn");
+	pub const EvmPaymasterPalletId: PalletId = PalletId(*b"aca/evmp");
 }
 
 pub fn get_all_module_accounts() -> Vec<AccountId> {
@@ -201,6 +203,7 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		StarportPalletId::get().into_account_truncating(),
 		UnreleasedNativeVaultAccountId::get(),
 		StableAssetPalletId::get().into_account_truncating(),
+		EarningPalletId::get().into_account_truncating(),
 	]
 }
 
@@ -922,6 +925,7 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = GasToWeight;
 	type SweepOrigin = EnsureRootOrOneGeneralCouncil;
 	type OnDust = module_currencies::TransferDust<Runtime, TreasuryAccount>;
+	type EVMLogs = EVM;
 }
 
 pub struct EnsureRootOrTreasury;
@@ -1119,8 +1123,10 @@ impl module_cdp_engine::Config for Runtime {
 	type LiquidationEvmBridge = module_evm_bridge::LiquidationEvmBridge<Runtime>;
 	type PalletId = CDPEnginePalletId;
 	type EvmAddressMapping = module_evm_accounts::EvmAddressMapping<Runtime>;
+	type Erc20InfoMapping = EvmErc20InfoMapping<Runtime>;
 	type Swap = AcalaSwap;
 	type WeightInfo = weights::module_cdp_engine::WeightInfo<Runtime>;
+	type GasToWeight = GasToWeight;
 }
 
 parameter_types! {
@@ -1172,6 +1178,7 @@ impl module_dex::Config for Runtime {
 	type Erc20InfoMapping = EvmErc20InfoMapping<Runtime>;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
+	type GasToWeight = GasToWeight;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
 	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
 	type OnLiquidityPoolUpdated = ();
@@ -1236,19 +1243,27 @@ type NegativeImbalance = <Balances as PalletCurrency<AccountId>>::NegativeImbala
 pub struct DealWithFees;
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
-		if let Some(mut fees) = fees_then_tips.next() {
-			if let Some(tips) = fees_then_tips.next() {
-				tips.merge_into(&mut fees);
-			}
-			// for fees and tips, 80% to treasury, 20% to collator-selection pot.
+		if let Some(fees) = fees_then_tips.next() {
+			// for fees, 70% to treasury, 20% to collator-selection pot, 10% to earning bonders.
 			let split = fees.ration(80, 20);
-			Treasury::on_unbalanced(split.0);
+			let (treasury, earning) = split.0.ration(70, 10);
+			Treasury::on_unbalanced(treasury);
+			Earning::on_unbalanced(earning);
 
 			Balances::resolve_creating(&CollatorSelection::account_id(), split.1);
 			// Due to performance consideration remove the event.
 			// let numeric_amount = split.1.peek();
 			// let staking_pot = CollatorSelection::account_id();
 			// System::deposit_event(pallet_balances::Event::Deposit(staking_pot, numeric_amount));
+
+			// for tips, 100% to block author
+			if let Some(tips) = fees_then_tips.next() {
+				if let Some(author) = Authorship::author() {
+					Balances::resolve_creating(&author, tips);
+				} else {
+					Treasury::on_unbalanced(tips);
+				}
+			}
 		}
 	}
 }
@@ -1278,6 +1293,7 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeePayer = EvmPaymasterFeePayer;
 }
 
 parameter_types! {
@@ -1295,6 +1311,12 @@ impl module_earning::Config for Runtime {
 	type InstantUnstakeFee = InstantUnstakeFee;
 	type MaxUnbondingChunks = ConstU32<3>;
 	type LockIdentifier = EarningLockIdentifier;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type Incentives = Incentives;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type PalletId = EarningPalletId;
+	type UnbondingVotingPower = ConstBool<true>;
+	type MaxDelegators = ConstU32<100>;
 	type WeightInfo = ();
 }
 
@@ -1304,9 +1326,35 @@ impl module_evm_accounts::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
 	type ChainId = EvmChainId<Runtime>;
+	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
+	type RotationDelay = ConstU32<{ 7 * DAYS }>;
+	type MaxSecondaryAddresses = ConstU32<4>;
 	type WeightInfo = weights::module_evm_accounts::WeightInfo<Runtime>;
 }
 
+impl module_evm_paymaster::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type AddressMapping = EvmAddressMapping<Runtime>;
+	type EVM = EVM;
+	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
+	type EVMManager = EVM;
+	type GasToWeight = GasToWeight;
+	type PalletId = EvmPaymasterPalletId;
+	type WeightInfo = weights::module_evm_paymaster::WeightInfo<Runtime>;
+}
+
+/// The escrow of the paymaster pays the fee of the sponsored call it accepts.
+pub struct EvmPaymasterFeePayer;
+impl module_support::FeePayer<AccountId, Call> for EvmPaymasterFeePayer {
+	fn fee_payer(who: &AccountId, call: &Call) -> Option<AccountId> {
+		match call {
+			Call::EvmPaymaster(call) => EvmPaymaster::sponsor_of(who, call),
+			_ => None,
+		}
+	}
+}
+
 impl module_asset_registry::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -1424,6 +1472,9 @@ impl module_nominees_election::Config for Runtime {
 	type NominateesCount = ConstU32<7>;
 	type MaxUnbondingChunks = ConstU32<7>;
 	type NomineeFilter = runtime_common::DummyNomineeFilter;
+	type ConvictionPeriod = ConstU32<7>;
+	type NominationExpiry = ConstU32<56>;
+	type MaxExpiriesPerBlock = ConstU32<50>;
 	type WeightInfo = weights::module_nominees_election::WeightInfo<Runtime>;
 }
 
@@ -1591,6 +1642,10 @@ impl ecosystem_compound_cash::Config for Runtime {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	// system contracts
+	pub PredeployAddressRanges: Vec<(H160, H160)> = vec![(H160::zero(), H160(hex_literal::hex!("0000000000000000010000000000000000000000")))];
+	pub const ContractExpiryPeriod: BlockNumber = 30 * DAYS;
+	pub const ContractUpgradeDelay: BlockNumber = HOURS;
 	pub PrecompilesValue: AllPrecompiles<Runtime> = AllPrecompiles::<_>::mandala();
 }
 
@@ -1640,7 +1695,10 @@ impl module_evm::Config for Runtime {
 	type TransferAll = Currencies;
 	type NewContractExtraBytes = NewContractExtraBytes;
 	type StorageDepositPerByte = StorageDepositPerByte;
+	type ContractExpiryPeriod = ContractExpiryPeriod;
+	type UpgradeDelay = ContractUpgradeDelay;
 	type TxFeePerGas = TxFeePerGas;
+	type BlockGasLimit = runtime_common::EvmBlockGasLimit<Runtime>;
 	type Event = Event;
 	type PrecompilesType = AllPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
@@ -1648,6 +1706,7 @@ impl module_evm::Config for Runtime {
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
+	type PredeployAddressRanges = PredeployAddressRanges;
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
 	type TreasuryAccount = TreasuryAccount;
@@ -1833,22 +1892,27 @@ impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtr
 
 				extra.5.mark_as_ethereum_tx(valid_until);
 
-				Ok((
-					EthereumTransactionMessage {
-						chain_id: EVM::chain_id(),
-						genesis: System::block_hash(0),
-						nonce,
-						tip,
-						gas_limit,
-						storage_limit,
-						action,
-						value,
-						input,
-						valid_until,
-						access_list,
-					},
-					extra,
-				))
+				let eth_msg = EthereumTransactionMessage {
+					chain_id: EVM::chain_id(),
+					genesis: System::block_hash(0),
+					nonce,
+					tip,
+					gas_limit,
+					storage_limit,
+					action,
+					value,
+					input,
+					valid_until,
+					access_list,
+				};
+
+				// the fee per gas signed by the transaction is the `TxFeePerGas` component of its gas
+				// price, the rest of the gas price encodes `valid_until` and `storage_limit`
+				if EVM::base_fee_per_gas() > TxFeePerGas::get() {
+					return Err(InvalidTransaction::Payment);
+				}
+
+				Ok((eth_msg, extra))
 			}
 			_ => Err(InvalidTransaction::BadProof),
 		}
@@ -2026,6 +2090,7 @@ construct_runtime!(
 		EVM: module_evm = 180,
 		EVMBridge: module_evm_bridge exclude_parts { Call } = 181,
 		EvmAccounts: module_evm_accounts = 182,
+		EvmPaymaster: module_evm_paymaster = 183,
 
 		// Collator support. the order of these 4 are important and shall not change.
 		Authorship: pallet_authorship = 190,
@@ -2075,6 +2140,7 @@ mod benches {
 		[module_incentives, benchmarking::incentives]
 		[module_prices, benchmarking::prices]
 		[module_evm_accounts, benchmarking::evm_accounts]
+		[module_evm_paymaster, benchmarking::evm_paymaster]
 		[module_currencies, benchmarking::currencies]
 		[module_session_manager, benchmarking::session_manager]
 		[orml_tokens, benchmarking::tokens]
@@ -2229,6 +2295,77 @@ impl_runtime_apis! {
 			}
 		}
 
+		fn base_fee_per_gas() -> Balance {
+			EVM::base_fee_per_gas()
+		}
+
+		fn chain_id() -> u64 {
+			EVM::chain_id()
+		}
+
+		fn account_basic(address: H160) -> module_evm::Account {
+			EVM::account_basic(&address)
+		}
+
+		fn code_at_address(address: H160) -> Vec<u8> {
+			EVM::code_at_address(&address).into_inner()
+		}
+
+		fn storage_at(address: H160, index: H256) -> H256 {
+			EVM::account_storages(address, index)
+		}
+
+		fn convert_eth_transaction(raw: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError> {
+			let (signer, signature, eth_msg) = primitives::unchecked_extrinsic::decode_ethereum_transaction(
+				&raw,
+				EVM::chain_id(),
+				System::block_hash(0),
+				TxFeePerGas::get(),
+				StorageDepositPerByte::get(),
+			)
+			.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter raw, decode failed"))?;
+
+			let call = Call::EVM(module_evm::Call::eth_call {
+				action: eth_msg.action,
+				input: eth_msg.input,
+				value: eth_msg.value,
+				gas_limit: eth_msg.gas_limit,
+				storage_limit: eth_msg.storage_limit,
+				access_list: eth_msg.access_list,
+				valid_until: eth_msg.valid_until,
+			});
+			// ethereum transactions are immortal
+			let extra: SignedExtra = (
+				frame_system::CheckNonZeroSender::<Runtime>::new(),
+				frame_system::CheckSpecVersion::<Runtime>::new(),
+				frame_system::CheckTxVersion::<Runtime>::new(),
+				frame_system::CheckGenesis::<Runtime>::new(),
+				frame_system::CheckEra::<Runtime>::from(generic::Era::Immortal),
+				runtime_common::CheckNonce::<Runtime>::from(eth_msg.nonce),
+				frame_system::CheckWeight::<Runtime>::new(),
+				module_evm::SetEvmOrigin::<Runtime>::new(),
+				module_transaction_payment::ChargeTransactionPayment::<Runtime>::from(eth_msg.tip),
+			);
+
+			<UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::new(
+				call,
+				Some((Address::Address20(signer.into()), signature, extra)),
+			)
+			.ok_or(sp_runtime::DispatchError::Other("Invalid parameter raw, build extrinsic failed"))
+		}
+
+		fn block_receipts() -> Vec<module_evm::EvmReceipt> {
+			EVM::block_receipts()
+		}
+
+		fn ethereum_transaction_hash(extrinsic: <Block as BlockT>::Extrinsic) -> Option<H256> {
+			extrinsic.ethereum_transaction_hash()
+		}
+
+		fn contract_verification(address: H160) -> Option<module_evm::ContractVerification> {
+			EVM::contract_verifications(address)
+		}
+
 		fn call(
 			from: H160,
 			to: H160,
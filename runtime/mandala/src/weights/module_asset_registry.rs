@@ -103,4 +103,7 @@ impl<T: frame_system::Config> module_asset_registry::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_erc20_behaviour() -> Weight {
+		module_asset_registry::estimated_weights::set_erc20_behaviour::<T::DbWeight>()
+	}
 }
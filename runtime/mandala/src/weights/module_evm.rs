@@ -184,4 +184,19 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	fn archive_contract() -> Weight {
+		module_evm::estimated_weights::archive_contract::<T::DbWeight>()
+	}
+	fn revive_contract(n: u32, ) -> Weight {
+		module_evm::estimated_weights::revive_contract::<T::DbWeight>(n)
+	}
+	fn propose_upgrade(c: u32, ) -> Weight {
+		module_evm::estimated_weights::propose_upgrade::<T::DbWeight>(c)
+	}
+	fn cancel_upgrade() -> Weight {
+		module_evm::estimated_weights::cancel_upgrade::<T::DbWeight>()
+	}
+	fn submit_verification() -> Weight {
+		module_evm::estimated_weights::submit_verification::<T::DbWeight>()
+	}
 }
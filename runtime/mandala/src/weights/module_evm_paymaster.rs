@@ -0,0 +1,82 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimated weights for module_evm_paymaster
+//!
+//! THIS FILE WAS NOT GENERATED BY THE BENCHMARK CLI. The weights are estimated from the storage
+//! accesses of the extrinsics and the benchmarked weights of the similar extrinsics, e.g. the
+//! transfers and the EVM calls. Replace them by running the `module_evm_paymaster` benchmarks.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for module_evm_paymaster.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> module_evm_paymaster::WeightInfo for WeightInfo<T> {
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EvmAccounts EvmAddresses (r:2 w:1)
+	// Storage: EvmPaymaster Paymasters (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:0 w:1)
+	fn register_paymaster() -> Weight {
+		(41_265_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EvmPaymaster Paymasters (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn unregister_paymaster() -> Weight {
+		(52_781_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EvmPaymaster Paymasters (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn deposit() -> Weight {
+		(43_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EvmPaymaster Paymasters (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw() -> Weight {
+		(47_118_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EvmPaymaster Paymasters (r:1 w:0)
+	// Storage: EvmAccounts EvmAddresses (r:2 w:0)
+	// Storage: EVM Accounts (r:2 w:0)
+	// Storage: EVM Codes (r:1 w:0)
+	// Storage: EVM ContractStorageSizes (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: EVM ExtrinsicOrigin (r:1 w:1)
+	fn sponsored_call() -> Weight {
+		(86_437_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
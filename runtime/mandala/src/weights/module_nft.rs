@@ -124,4 +124,13 @@ impl<T: frame_system::Config> module_nft::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn approve() -> Weight {
+		module_nft::estimated_weights::approve::<T::DbWeight>()
+	}
+	fn set_approval_for_all() -> Weight {
+		module_nft::estimated_weights::set_approval_for_all::<T::DbWeight>()
+	}
+	fn transfer_from() -> Weight {
+		module_nft::estimated_weights::transfer_from::<T::DbWeight>()
+	}
 }
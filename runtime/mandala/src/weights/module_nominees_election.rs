@@ -109,4 +109,7 @@ impl<T: frame_system::Config> module_nominees_election::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn on_initialize(n: u32, ) -> Weight {
+		module_nominees_election::estimated_weights::on_initialize::<T::DbWeight>(n)
+	}
 }